@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// 注釈ファイルの接尾辞。ソース (アーカイブ/フォルダ/PDF) の隣に "<ソース名>.annotations.json" として置く
+const ANNOTATION_FILE_SUFFIX: &str = ".annotations.json";
+
+/// 近すぎる点は記録しない (ページ上の正規化座標での距離)
+const MIN_POINT_DISTANCE: f32 = 0.001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationTool {
+    Pen,
+    /// 半透明の太い線 (文字の上に重ねる)
+    Highlighter,
+}
+
+impl AnnotationTool {
+    pub fn label(self) -> &'static str {
+        match self {
+            AnnotationTool::Pen => "ペン",
+            AnnotationTool::Highlighter => "蛍光ペン",
+        }
+    }
+
+    fn default_color(self) -> [f32; 3] {
+        match self {
+            AnnotationTool::Pen => [0.85, 0.1, 0.1],
+            AnnotationTool::Highlighter => [1.0, 0.85, 0.0],
+        }
+    }
+
+    /// 線の太さ (ページの短辺に対する割合)
+    fn default_width(self) -> f32 {
+        match self {
+            AnnotationTool::Pen => 0.003,
+            AnnotationTool::Highlighter => 0.02,
+        }
+    }
+
+    /// 描画時の不透明度
+    pub fn alpha(self) -> f32 {
+        match self {
+            AnnotationTool::Pen => 1.0,
+            AnnotationTool::Highlighter => 0.35,
+        }
+    }
+}
+
+/// 1 本の線
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stroke {
+    pub tool: AnnotationTool,
+    pub color: [f32; 3],
+    /// 線の太さ (ページの短辺に対する割合)
+    pub width: f32,
+    /// 回転・反転前のページ上の正規化座標 (0.0〜1.0, 左上原点)
+    pub points: Vec<[f32; 2]>,
+}
+
+impl Stroke {
+    pub fn new(tool: AnnotationTool, start: [f32; 2]) -> Self {
+        Self { tool, color: tool.default_color(), width: tool.default_width(), points: vec![start] }
+    }
+
+    pub fn push(&mut self, point: [f32; 2]) {
+        if let Some(last) = self.points.last() {
+            if (point[0] - last[0]).hypot(point[1] - last[1]) < MIN_POINT_DISTANCE {
+                return;
+            }
+        }
+        self.points.push(point);
+    }
+}
+
+/// 注釈ファイルの内容 (ページ名 -> 線の一覧)
+/// ページ名はソース内の相対パス ("vol01/005.jpg" など) で、ファイルの増減でページ番号がずれても引き継げる
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct AnnotationFile {
+    pages: BTreeMap<String, Vec<Stroke>>,
+}
+
+/// 表示中ソースの注釈
+#[derive(Debug, Default)]
+pub struct Annotations {
+    path: Option<PathBuf>,
+    file: AnnotationFile,
+}
+
+impl Annotations {
+    /// ソースの隣にある注釈ファイルを読み込む。無い・読めない場合は空
+    pub fn load(source_path: &str) -> Self {
+        let trimmed = source_path.trim_end_matches(['\\', '/']);
+        if trimmed.is_empty() {
+            return Self::default();
+        }
+        let path = PathBuf::from(format!("{}{}", trimmed, ANNOTATION_FILE_SUFFIX));
+        let file = match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<AnnotationFile>(&content) {
+                Ok(file) => {
+                    let count: usize = file.pages.values().map(Vec::len).sum();
+                    println!("[注釈] 読み込み: {} ({} ページ / {} 本)", path.display(), file.pages.len(), count);
+                    file
+                }
+                Err(e) => {
+                    println!("[注釈] 注釈ファイルを読み込めませんでした ({}): {}", path.display(), e);
+                    AnnotationFile::default()
+                }
+            },
+            Err(_) => AnnotationFile::default(),
+        };
+        Self { path: Some(path), file }
+    }
+
+    pub fn strokes(&self, page: &str) -> &[Stroke] {
+        self.file.pages.get(page).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn add(&mut self, page: &str, stroke: Stroke) {
+        self.file.pages.entry(page.to_string()).or_default().push(stroke);
+    }
+
+    /// ページの最後の線を取り除く
+    pub fn remove_last(&mut self, page: &str) -> Option<Stroke> {
+        let strokes = self.file.pages.get_mut(page)?;
+        let stroke = strokes.pop();
+        if strokes.is_empty() {
+            self.file.pages.remove(page);
+        }
+        stroke
+    }
+
+    /// 注釈ファイルに書き出す。注釈が 1 本も無くなった場合はファイルを削除する
+    pub fn save(&self) -> std::io::Result<()> {
+        let Some(path) = self.path.as_ref() else { return Ok(()); };
+        if self.file.pages.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        let content = serde_json::to_string_pretty(&self.file).map_err(std::io::Error::other)?;
+        std::fs::write(path, content)
+    }
+}
@@ -0,0 +1,103 @@
+use crate::image::loader::UserEvent;
+use crate::platform::clipboard;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use winit::event_loop::EventLoopProxy;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// 前回以前のセッションのフォルダを削除するまでの経過時間 (他のプロセスが使用中のものは残す)
+const STALE_SESSION_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+const SESSION_PREFIX: &str = "clipboard-";
+
+/// クリップボードの画像を保存するフォルダの親 (%TEMP%\HayateViewer)
+fn sessions_root() -> PathBuf {
+    std::env::temp_dir().join("HayateViewer")
+}
+
+/// クリップボード監視で作った一時的なソースか (履歴・ソース別設定に残さない)
+pub fn is_transient_source(path: &str) -> bool {
+    Path::new(path)
+        .strip_prefix(sessions_root())
+        .ok()
+        .and_then(|rel| rel.to_str())
+        .is_some_and(|rel| rel.starts_with(SESSION_PREFIX))
+}
+
+/// 以前のセッションで残ったフォルダを削除する
+fn remove_stale_sessions(root: &Path) {
+    let Ok(read_dir) = std::fs::read_dir(root) else { return; };
+    for entry in read_dir.flatten() {
+        let is_session = entry.file_name().to_string_lossy().starts_with(SESSION_PREFIX);
+        let age = entry.metadata().and_then(|m| m.modified()).ok().and_then(|t| t.elapsed().ok());
+        if is_session && age.is_some_and(|age| age > STALE_SESSION_AGE) {
+            if std::fs::remove_dir_all(entry.path()).is_ok() {
+                println!("[クリップボード] 古い一時フォルダを削除しました: {}", entry.path().display());
+            }
+        }
+    }
+}
+
+/// クリップボードを監視し、画像がコピーされるたびにセッション用フォルダへ PNG で保存して
+/// UserEvent::ClipboardImage で通知する (監視開始前からクリップボードにある画像は対象外)
+pub struct ClipboardWatcher {
+    /// 保存先フォルダ (このフォルダをソースとして表示する)
+    pub dir: PathBuf,
+    stop: Arc<AtomicBool>,
+}
+
+impl ClipboardWatcher {
+    pub fn start(proxy: EventLoopProxy<UserEvent>) -> Option<Self> {
+        let root = sessions_root();
+        remove_stale_sessions(&root);
+        let dir = root.join(format!("{}{}", SESSION_PREFIX, std::process::id()));
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            println!("[クリップボード] 保存先フォルダを作成できませんでした ({}): {}", dir.display(), e);
+            return None;
+        }
+        // 同じセッションで再開した場合は続きの番号から保存する
+        let mut count = std::fs::read_dir(&dir).map(|d| d.count()).unwrap_or(0);
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let save_dir = dir.clone();
+        std::thread::spawn(move || {
+            let mut last_sequence = clipboard::sequence_number();
+            while !stop_flag.load(Ordering::Relaxed) {
+                std::thread::sleep(POLL_INTERVAL);
+                let sequence = clipboard::sequence_number();
+                if sequence == last_sequence {
+                    continue;
+                }
+                last_sequence = sequence;
+                // 表示中のページを Ctrl + C でコピーしたものは取り込まない
+                if clipboard::is_own_copy(sequence) {
+                    continue;
+                }
+                // テキストなど画像以外のコピーは無視する
+                let Some(png) = clipboard::get_image_png() else { continue; };
+                count += 1;
+                let path = save_dir.join(format!("clip{:04}.png", count));
+                if let Err(e) = std::fs::write(&path, png) {
+                    println!("[クリップボード] 画像を保存できませんでした ({}): {}", path.display(), e);
+                    continue;
+                }
+                println!("[クリップボード] 画像を追加: {}", path.display());
+                if proxy.send_event(UserEvent::ClipboardImage(path.to_string_lossy().to_string())).is_err() {
+                    break;
+                }
+            }
+        });
+
+        println!("[クリップボード] 監視を開始しました: {}", dir.display());
+        Some(Self { dir, stop })
+    }
+}
+
+impl Drop for ClipboardWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        println!("[クリップボード] 監視を停止しました");
+    }
+}
@@ -1,35 +1,364 @@
+use crate::persist::sibling_path;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime};
+
+/// save() の書き込みを遅らせる時間 (この間の保存要求は 1 回の書き込みにまとめる)
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// 設定ファイルのパス (既定は paths::data_dir() の config.json、環境変数 HAYATE_CONFIG で変更可能)
+pub fn config_path() -> &'static str {
+    static PATH: OnceLock<String> = OnceLock::new();
+    PATH.get_or_init(|| {
+        std::env::var("HAYATE_CONFIG")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| crate::paths::data_dir().dir.join("config.json").to_string_lossy().to_string())
+    })
+}
+
+/// 背景テーマのプリセット (設定値, 表示名, 背景色 RGB)
+pub const BACKGROUND_THEMES: [(&str, &str, [f32; 3]); 4] = [
+    ("dark", "ダーク", [0.1, 0.1, 0.1]),
+    ("black", "ブラック", [0.0, 0.0, 0.0]),
+    ("white", "ホワイト", [0.95, 0.95, 0.95]),
+    ("sepia", "セピア", [0.92, 0.87, 0.76]),
+];
+
+fn parse_env_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "on" | "yes" => Some(true),
+        "0" | "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HistoryItem {
     pub path: String,
     pub page: usize,
-    pub binding: String, // "left", "right", "single"
+    pub binding: String, // "left", "right", "single", "webtoon"
+    /// 手動で指定したトリミング範囲 (Shift + G)
+    #[serde(default)]
+    pub crop: Option<crate::image::postprocess::CropMargins>,
 }
 
+/// しおり (ソース内の特定ページ)
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Bookmark {
+    pub path: String,
+    pub page: usize,
+    pub label: String,
+    /// 登録日時 (UNIX 秒)
+    pub timestamp: u64,
+}
+
+/// ソースに付けたタグとメモ (閲覧履歴の絞り込みで検索できる)
+/// config.json には DPAPI で暗号化した 16 進文字列として保存し、同じ Windows ユーザー以外には読めない
+#[derive(Debug, Clone, Default)]
+pub struct SourceNote {
+    tags: Vec<String>,
+    text: String,
+    /// 保存する暗号文 (内容を変えない限り使い回し、保存のたびに config.json が書き換わらないようにする)
+    sealed: OnceLock<String>,
+    /// 復号できなかった (別のユーザー・PC で保存された)。内容は読めないが sealed をそのまま書き戻す
+    locked: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PlainNote {
+    tags: Vec<String>,
+    text: String,
+}
+
+impl SourceNote {
+    pub fn new(tags: Vec<String>, text: String) -> Self {
+        Self { tags, text, ..Default::default() }
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.locked && self.tags.is_empty() && self.text.trim().is_empty()
+    }
+
+    /// "翻訳済み, お気に入り" のような入力をタグの一覧にする (読点・カンマ区切り、空と重複を除く)
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split([',', '、', '，']).map(str::trim).filter(|t| !t.is_empty()) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    /// タグかメモに query (小文字にしたもの) を含むか
+    pub fn matches(&self, query: &str) -> bool {
+        self.tags.iter().any(|tag| tag.to_lowercase().contains(query)) || self.text.to_lowercase().contains(query)
+    }
+}
+
+impl Serialize for SourceNote {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Some(sealed) = self.sealed.get() {
+            return serializer.serialize_str(sealed);
+        }
+        let plain = serde_json::to_vec(&PlainNote { tags: self.tags.clone(), text: self.text.clone() }).unwrap_or_default();
+        match crate::platform::protect::protect(&plain) {
+            Ok(data) => serializer.serialize_str(self.sealed.get_or_init(|| crate::platform::protect::to_hex(&data))),
+            Err(e) => {
+                // 平文では残さない (このセッションの間はメモリ上の内容が使える)
+                println!("[メモ] 暗号化に失敗したため保存できませんでした: {}", e);
+                serializer.serialize_str("")
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SourceNote {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sealed = String::deserialize(deserializer)?;
+        let plain = crate::platform::protect::from_hex(&sealed)
+            .and_then(|data| crate::platform::protect::unprotect(&data).ok())
+            .and_then(|data| serde_json::from_slice::<PlainNote>(&data).ok());
+        let note = match plain {
+            Some(plain) => Self::new(plain.tags, plain.text),
+            None if sealed.is_empty() => Self::default(),
+            None => {
+                println!("[メモ] 復号できないメモがあります (別のユーザー・PC で保存されたもの)。内容はそのまま残します");
+                Self { locked: true, ..Default::default() }
+            }
+        };
+        let _ = note.sealed.set(sealed);
+        Ok(note)
+    }
+}
+
+/// ソース (フォルダ/アーカイブ) ごとの表示設定
+/// 履歴とは別に保持するため、履歴件数の上限で削除されても失われない
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SourceOverride {
+    pub binding: String, // "left", "right", "single", "webtoon"
+    pub first_page_single: bool,
+    /// 見開きの組み合わせを1ページずらしているか
+    pub spread_offset: bool,
+    /// 最後に表示していたページ (zoom_page) のズーム倍率とパン位置 (地図・ポスターなどの復元用)
+    pub zoom_level: f32,
+    pub pan_offset: (f32, f32),
+    /// ズーム・パンを記録したページのファイル名/エントリ名。空なら 1 枚画像のソースとして記録した古い設定
+    pub zoom_page: String,
+    /// ページの合わせ方 (設定の fit_mode と同じ値)。空なら全体設定に従う
+    pub fit_mode: String,
+    /// 色調整 (暗いスキャン画像の補正用)
+    pub brightness: f32,
+    pub contrast: f32,
+    pub gamma: f32,
+    /// ページ番号 -> 回転・反転 (横向きに取り込まれたページの補正用)
+    pub page_transforms: BTreeMap<usize, crate::render::PageTransform>,
+    /// 最後に記録した日時 (UNIX 秒)。上限を超えたら古いものから削除する
+    pub last_used: u64,
+}
+
+impl Default for SourceOverride {
+    fn default() -> Self {
+        Self {
+            binding: "left".to_string(),
+            first_page_single: true,
+            spread_offset: false,
+            zoom_level: 1.0,
+            pan_offset: (0.0, 0.0),
+            zoom_page: String::new(),
+            fit_mode: String::new(),
+            brightness: 0.0,
+            contrast: 1.0,
+            gamma: 1.0,
+            page_transforms: BTreeMap::new(),
+            last_used: 0,
+        }
+    }
+}
+
+/// 仕分けモードの振り分け先 (数字キー 1〜5 に順に対応する)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TriageTarget {
+    pub path: String,
+    /// true ならコピー、false なら移動する
+    pub copy: bool,
+}
+
+/// ソース別設定の保持上限 (超えた場合は最後に記録したのが古いものから削除する)
+const MAX_SOURCE_OVERRIDES: usize = 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)] // 新しい項目が無い古い config.json も読み込めるようにする
 pub struct Settings {
     pub rendering_backend: String,
     pub is_spread_view: bool,
     pub binding_direction: String,
+    /// 縦スクロール (Webtoon) 表示。有効な間は is_spread_view より優先する
+    pub webtoon_view: bool,
     pub spread_view_first_page_single: bool,
+    /// 見開き時にフォルダ (章) の先頭ページを単独表示する
+    pub chapter_start_single: bool,
+    /// 見開き時に横長のページ (幅 > 高さ) を単独表示する (見開きでスキャンしたページを他のページと並べない)
+    pub wide_page_single: bool,
+    /// 単ページ表示で横長のページを左右の半分に分け、綴じ方向の順に 1 ページずつ表示する
+    pub split_wide_pages: bool,
+    /// フォルダ/アーカイブのサイドバー (Shift + Tab) を開いた状態で起動する
+    pub show_browser_pane: bool,
     pub window_size: (u32, u32),
     pub window_position: (i32, i32),
     pub window_geometry: (i32, i32, u32, u32),
+    /// 終了時に最大化・全画面だったか (window_geometry は通常時の矩形を保持する)
+    pub window_maximized: bool,
+    pub window_fullscreen: bool,
+    /// デコード用スレッド数 (0 = 自動: 論理 CPU 数)
     pub parallel_decoding_workers: usize,
     pub resampling_mode_cpu: String,
     pub resampling_mode_gpu: String,
+    /// YCbCr (JPEG 2000 等) の間引かれた色差プレーンの補間: "Bilinear" / "CatmullRom"
+    /// 輝度の補間 (resampling_mode_gpu) とは別に行い、Nearest でも色の境界がギザギザににじまないようにする
+    pub chroma_upsampling: String,
     pub show_advanced_cache_options: bool,
     pub max_cache_size_mb: u64,
+    /// アーカイブの展開方法: "streaming" (表示するエントリだけ展開) / "slurp" (初回に全エントリをメモリへ展開)
+    pub archive_extraction_mode: String,
+    /// フォルダの画像の並び順 ("name" / "modified" / "size" / "shuffle"。image::sort::SortOrder)
+    pub file_sort_order: String,
+    /// アーカイブのデコード済みページをディスクに保存し、次に開いたときに展開・デコードを省く
+    pub disk_cache_enabled: bool,
+    /// ディスクキャッシュの保存先 (空なら %LOCALAPPDATA%/HayateViewer/cache)
+    pub disk_cache_dir: String,
+    /// ディスクキャッシュの容量の上限 (MB)。超えた分は使われていないものから削除する
+    pub disk_cache_max_mb: u64,
+    /// PDF のページを描画する解像度 (72〜600 dpi)
+    pub pdf_render_dpi: u32,
     pub cpu_max_prefetch_pages: usize,
     pub gpu_max_prefetch_pages: usize,
+    /// GPU テクスチャの合計サイズ上限 (MB、0 = ページ数のみで制限)。表示中のページはこれを超えても保持する
+    pub gpu_max_cache_mb: u64,
     pub show_status_bar_info: bool,
+    /// ページ移動時に画面隅へページ番号を一時表示する
+    pub show_page_osd: bool,
     pub use_cpu_color_conversion: bool,
     pub magnifier_zoom: f32,
+    /// Alt + 1〜9 のズームプリセット (0.0 = 全体表示、それ以外は原寸に対する倍率)
+    pub zoom_presets: Vec<f32>,
+    /// ズーム/パンのアニメーション時間 (ミリ秒、0 で無効)。0 ならドラッグを離した後の慣性スクロールも行わない
+    pub view_animation_ms: u32,
+    /// ページ送りのアニメーション ("none" / "slide" / "curl")。curl は D3D11 / OpenGL で紙をめくるように変形する
+    pub page_turn_animation_type: String,
+    /// ページ送りのアニメーション時間 (ミリ秒)
+    pub page_turn_animation_ms: u32,
+    /// ウィンドウのサイズ変更中は直前のフレームを縮小して余白を付けて表示し、配置の計算と転送は変更が落ち着いてから行う
+    pub freeze_frame_on_resize: bool,
+    /// ページをウィンドウに合わせる方法 ("window" / "width" / "height" / "actual" / "fill")
+    pub fit_mode: String,
+    /// 出力ウィンドウ (F12、セカンドモニター用) のページの合わせ方 (fit_mode と同じ値。メインウィンドウとは別に切り替える)
+    pub mirror_fit_mode: String,
+    /// 高さ / 幅がこの比率以上の縦長ページは幅に合わせて表示し、ホイールで縦にスクロールする (0 = 無効)
+    pub tall_page_fit_width_ratio: f32,
+    /// 縦置きモニター向けに全ページをローダーで事前回転する角度 (0 / 90 / 180 / 270、時計回り)
+    pub pre_rotation: u32,
+    /// 左手モード: シークバーの進行方向 (綴じ方向に対して反転) と OSD の位置を左右反転する
+    pub left_handed_mode: bool,
+    /// 瞬時切り替え: ページ送りは移動先のページが GPU に転送済みになってから行う (読み込み途中のページを表示しない)
+    pub instant_flip: bool,
+    /// タイトルバー・ステータスバーのパスをこの文字数に収まるよう中央を省略する (0 = 省略しない)
+    pub path_display_max_chars: usize,
+    /// タイトルバーの書式 ({archive} / {page} / {zoom} などのプレースホルダーは template.rs を参照)
+    pub title_format: String,
+    /// ステータスバーの書式 (一時メッセージの表示中はそちらを優先する)
+    pub statusbar_format: String,
+    /// マウス操作が無いままこの秒数が経つとカーソルを隠す (0 = 隠さない)
+    pub cursor_hide_seconds: f32,
+    /// ソースを開いている間はディスプレイの自動消灯を抑止する
+    pub keep_display_on: bool,
+    /// 夜間モード (色反転)。smart_invert が有効なら元から暗いページは反転しない
+    pub invert_colors: bool,
+    pub smart_invert: bool,
+    /// ページごとの自動レベル補正 (輝度ヒストグラムの黒点・白点を描画時に引き伸ばす。色あせたスキャン画像向け)
+    pub auto_levels: bool,
+    /// ブルーライト軽減の強さ (0.0〜1.0)。blue_light_schedule が有効なら start〜end の間だけ適用する
+    pub blue_light_strength: f32,
+    pub blue_light_schedule: bool,
+    pub blue_light_start: String, // "HH:MM"
+    pub blue_light_end: String,   // "HH:MM"
+    /// 背景テーマ (BACKGROUND_THEMES の設定値、または "custom" で background_color を使う)
+    pub background_theme: String,
+    pub background_color: [f32; 3],
+    /// 背景色を表示中のページの明るさに寄せる強さ (0.0〜1.0、0 で無効)
+    /// 白いページの周りが真っ黒・暗いページの周りが真っ白にならないよう、ページの平均輝度の灰色へ近づける
+    pub adaptive_background: f32,
+    /// シークバー・選択範囲などの強調色 (RGB 0.0〜1.0)
+    pub accent_color: [f32; 3],
     pub history: Vec<HistoryItem>,
     pub max_history_count: usize,
+    /// 引数なしで起動したとき、閲覧履歴の先頭 (最後に読んだソース) を前回の位置・綴じ方向で開く
+    pub reopen_last_on_startup: bool,
+    /// しおり (新しい順)
+    pub bookmarks: Vec<Bookmark>,
+    /// パス -> ソース別の表示設定
+    pub source_overrides: BTreeMap<String, SourceOverride>,
+    /// ページを書き出したフォルダ (次の保存ダイアログをここから開く)
+    pub export_dir: String,
+    /// パス -> ソースに付けたタグとメモ
+    pub source_notes: BTreeMap<String, SourceNote>,
+    /// キー名 -> 操作名 のキー割り当て (例: "MediaTrackNext": "next_page")
+    pub key_bindings: BTreeMap<String, String>,
+    /// ホットフォルダ (新しく置かれたファイルを自動で開く) の監視対象
+    pub hot_folder_path: String,
+    pub hot_folder_enabled: bool,
+    /// 仕分けモード (K) で数字キー 1〜5 に割り当てる振り分け先フォルダ
+    pub triage_targets: Vec<TriageTarget>,
+    /// 週1回 GitHub Releases で新しいバージョンを確認する (外部に接続するため既定では無効。設定画面で有効にする)
+    pub check_for_updates: bool,
+    pub last_update_check: u64,
+    pub latest_version: String,
+    /// 先読みの一時停止中か (一時的な操作のため保存せず、起動時は常に先読みする)
+    #[serde(skip)]
+    pub prefetch_paused: bool,
+
+    /// 最後に読み書きした時点の config.json の内容と更新日時 (外部編集との差分マージ用)
+    #[serde(skip)]
+    sync_base: Option<serde_json::Value>,
+    #[serde(skip)]
+    sync_mtime: Option<SystemTime>,
+    /// 環境変数で上書きした項目 (キー, 上書き前の値, 上書き後の値)。保存時は上書き前の値を書き戻す
+    #[serde(skip)]
+    env_overrides: Vec<(String, serde_json::Value, serde_json::Value)>,
+    /// まだ書き込んでいない保存要求 (保存先, 最初に要求された時刻)
+    #[serde(skip)]
+    pending_save: Option<(PathBuf, Instant)>,
+}
+
+/// 外部で編集された config.json を取り込んだ結果
+#[derive(Debug, Default)]
+pub struct MergeResult {
+    /// ファイル側の値を取り込んだ項目
+    pub changed: Vec<String>,
+    /// アプリ側でも変更されていたため、アプリ側の値を優先した項目
+    pub conflicts: Vec<String>,
+}
+
+fn file_mtime<P: AsRef<Path>>(path: P) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
 }
 
 impl Default for Settings {
@@ -38,42 +367,289 @@ impl Default for Settings {
             rendering_backend: "direct2d".to_string(), // Rust版のデフォルトは D2D
             is_spread_view: true,
             binding_direction: "left".to_string(),
+            webtoon_view: false,
             spread_view_first_page_single: true,
+            chapter_start_single: true,
+            wide_page_single: true,
+            split_wide_pages: false,
+            show_browser_pane: false,
             window_size: (1280, 768),
             window_position: (100, 100),
             window_geometry: (100, 100, 1280, 768),
-            parallel_decoding_workers: 8,
+            window_maximized: false,
+            window_fullscreen: false,
+            parallel_decoding_workers: 0,
             resampling_mode_cpu: "PIL_LANCZOS".to_string(),
             resampling_mode_gpu: "Lanczos".to_string(),
+            chroma_upsampling: "Bilinear".to_string(),
             show_advanced_cache_options: true,
             max_cache_size_mb: 4096,
+            archive_extraction_mode: "streaming".to_string(),
+            file_sort_order: "name".to_string(),
+            disk_cache_enabled: false,
+            disk_cache_dir: String::new(),
+            disk_cache_max_mb: 2048,
+            pdf_render_dpi: 200,
             cpu_max_prefetch_pages: 10,
             gpu_max_prefetch_pages: 9,
+            gpu_max_cache_mb: 1024,
             show_status_bar_info: true,
+            show_page_osd: true,
             use_cpu_color_conversion: false,
             magnifier_zoom: 2.0,
+            zoom_presets: vec![0.0, 1.0, 2.0],
+            view_animation_ms: 150,
+            page_turn_animation_type: "none".to_string(),
+            page_turn_animation_ms: 350,
+            freeze_frame_on_resize: true,
+            fit_mode: "window".to_string(),
+            mirror_fit_mode: "window".to_string(),
+            tall_page_fit_width_ratio: 2.5,
+            pre_rotation: 0,
+            left_handed_mode: false,
+            instant_flip: false,
+            path_display_max_chars: 60,
+            title_format: crate::template::DEFAULT_TITLE_FORMAT.to_string(),
+            statusbar_format: crate::template::DEFAULT_STATUSBAR_FORMAT.to_string(),
+            cursor_hide_seconds: 3.0,
+            keep_display_on: true,
+            invert_colors: false,
+            smart_invert: true,
+            auto_levels: false,
+            blue_light_strength: 0.0,
+            blue_light_schedule: false,
+            blue_light_start: "19:00".to_string(),
+            blue_light_end: "07:00".to_string(),
+            background_theme: "dark".to_string(),
+            background_color: [0.1, 0.1, 0.1],
+            adaptive_background: 0.0,
+            accent_color: [0.0, 0.47, 0.83],
             history: Vec::new(),
             max_history_count: 50,
+            reopen_last_on_startup: false,
+            bookmarks: Vec::new(),
+            source_overrides: BTreeMap::new(),
+            export_dir: String::new(),
+            source_notes: BTreeMap::new(),
+            key_bindings: crate::keymap::default_bindings(),
+            hot_folder_path: String::new(),
+            hot_folder_enabled: false,
+            triage_targets: Vec::new(),
+            check_for_updates: false,
+            last_update_check: 0,
+            latest_version: String::new(),
+            prefetch_paused: false,
+            sync_base: None,
+            sync_mtime: None,
+            env_overrides: Vec::new(),
+            pending_save: None,
         }
     }
 }
 
 impl Settings {
     pub fn load_or_default<P: AsRef<Path>>(path: P) -> Self {
-        if let Ok(content) = fs::read_to_string(path) {
-            if let Ok(settings) = serde_json::from_str(&content) {
-                return settings;
+        let path = path.as_ref();
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) => return Self::default(),
+        };
+        match serde_json::from_str::<Settings>(&content) {
+            Ok(mut settings) => {
+                settings.sync_base = serde_json::to_value(&settings).ok();
+                settings.sync_mtime = file_mtime(path);
+                settings.prune_missing_source_overrides();
+                settings
+            }
+            Err(e) => {
+                // 壊れたファイルは次の保存で上書きされるため、手で直せるように別名で残す
+                let broken = sibling_path(path, ".broken");
+                println!("[設定] {} を読み込めませんでした ({})。{} に退避します", path.display(), e, broken.display());
+                let _ = fs::copy(path, &broken);
+                let backup = sibling_path(path, ".bak");
+                match fs::read_to_string(&backup).ok().and_then(|c| serde_json::from_str::<Settings>(&c).ok()) {
+                    Some(mut settings) => {
+                        println!("[設定] バックアップから復元しました: {}", backup.display());
+                        // 復元した内容で config.json を書き直す
+                        settings.pending_save = Some((path.to_path_buf(), Instant::now()));
+                        settings
+                    }
+                    None => Self::default(),
+                }
+            }
+        }
+    }
+
+    /// 保存を要求する。トグルの連打などで何度も書き込まないよう、実際の書き込みは
+    /// SAVE_DEBOUNCE 後の flush_if_due (または終了時の flush) でまとめて書き込みスレッドに頼む
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref().to_path_buf();
+        match self.pending_save.as_ref() {
+            Some((pending, _)) if *pending == path => {}
+            Some(_) => {
+                // 別の保存先への要求が残っていれば先に書き込む
+                self.flush();
+                self.pending_save = Some((path, Instant::now()));
             }
+            None => self.pending_save = Some((path, Instant::now())),
         }
-        Self::default()
+        Ok(())
     }
 
-    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
-        let content = serde_json::to_string_pretty(self).unwrap();
-        fs::write(path, content)
+    /// 保存要求から SAVE_DEBOUNCE 経っていれば書き込む (イベントループの待機前に毎回呼ぶ)
+    pub fn flush_if_due(&mut self) {
+        if self.pending_save.as_ref().is_some_and(|(_, at)| at.elapsed() >= SAVE_DEBOUNCE) {
+            self.flush();
+        }
     }
 
-    pub fn add_to_history(&mut self, path: String, page: usize, binding: String) {
+    /// 保存要求が残っていればすぐに書き込みスレッドへ渡す (ディスクへの書き込みは待たない)
+    /// 終了時は続けて crate::persist::wait_idle で書き込みの完了を待つ
+    pub fn flush(&mut self) {
+        if let Some((path, _)) = self.pending_save.take() {
+            let value = self.value_to_save(&path);
+            let content = serde_json::to_string_pretty(&value).unwrap();
+            crate::persist::write_in_background(path, content);
+            self.sync_base = Some(value);
+        }
+    }
+
+    /// その場で書き込む (起動時に config.json を作る場合など)
+    pub fn save_now<P: AsRef<Path>>(&mut self, path: P) -> std::io::Result<()> {
+        self.pending_save = None;
+        let value = self.value_to_save(path.as_ref());
+        let content = serde_json::to_string_pretty(&value).unwrap();
+        crate::persist::write_atomic(path.as_ref(), &content)?;
+        self.sync_base = Some(value);
+        self.sync_mtime = file_mtime(&path);
+        Ok(())
+    }
+
+    /// 書き込む内容。前回の読み書き以降にファイルが外部で編集されていれば、先にその変更を取り込む
+    fn value_to_save(&mut self, path: &Path) -> serde_json::Value {
+        if self.is_file_changed(path) {
+            match self.merge_external(path) {
+                Ok(result) if !result.conflicts.is_empty() => {
+                    println!("[設定] 外部の変更と競合したためアプリ側の値を保存します: {:?}", result.conflicts);
+                }
+                Ok(_) => {}
+                Err(e) => println!("[設定] 外部で編集された config.json を読み込めませんでした: {}", e),
+            }
+        }
+        let mut value = serde_json::to_value(&*self).unwrap();
+        // 環境変数による一時的な上書きは config.json に残さない (アプリ内で変更された場合を除く)
+        if let Some(map) = value.as_object_mut() {
+            for (key, before, after) in &self.env_overrides {
+                if map.get(key) == Some(after) {
+                    map.insert(key.clone(), before.clone());
+                }
+            }
+        }
+        value
+    }
+
+    fn override_field(&mut self, key: &str, apply: impl FnOnce(&mut Self)) {
+        let before = serde_json::to_value(&*self).ok().and_then(|v| v.get(key).cloned());
+        apply(self);
+        let after = serde_json::to_value(&*self).ok().and_then(|v| v.get(key).cloned());
+        if let (Some(before), Some(after)) = (before, after) {
+            println!("[設定] 環境変数で {} を上書きしました: {} -> {}", key, before, after);
+            self.env_overrides.retain(|(k, _, _)| k != key);
+            self.env_overrides.push((key.to_string(), before, after));
+        }
+    }
+
+    /// HAYATE_* 環境変数による上書きを適用する (config.json 読み込み後に呼ぶ)
+    /// HAYATE_BACKEND, HAYATE_THREADS, HAYATE_CACHE_MB, HAYATE_CPU_COLOR, HAYATE_CHECK_UPDATES
+    pub fn apply_env_overrides(&mut self) {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(value) = var("HAYATE_BACKEND") {
+            let backend = match value.to_lowercase().as_str() {
+                "d2d" | "direct2d" => Some("direct2d"),
+                "d3d11" | "direct3d11" => Some("direct3d11"),
+                "gl" | "opengl" => Some("opengl"),
+                _ => None,
+            };
+            match backend {
+                Some(b) => self.override_field("rendering_backend", |s| s.rendering_backend = b.to_string()),
+                None => println!("[設定] HAYATE_BACKEND の値が不正です: {}", value),
+            }
+        }
+        if let Some(value) = var("HAYATE_THREADS") {
+            match value.trim().parse::<usize>() {
+                Ok(n) => self.override_field("parallel_decoding_workers", |s| s.parallel_decoding_workers = n),
+                Err(_) => println!("[設定] HAYATE_THREADS の値が不正です: {}", value),
+            }
+        }
+        if let Some(value) = var("HAYATE_CACHE_MB") {
+            match value.trim().parse::<u64>() {
+                Ok(mb) => self.override_field("max_cache_size_mb", |s| s.max_cache_size_mb = mb),
+                Err(_) => println!("[設定] HAYATE_CACHE_MB の値が不正です: {}", value),
+            }
+        }
+        if let Some(value) = var("HAYATE_CPU_COLOR") {
+            match parse_env_bool(&value) {
+                Some(b) => self.override_field("use_cpu_color_conversion", |s| s.use_cpu_color_conversion = b),
+                None => println!("[設定] HAYATE_CPU_COLOR の値が不正です: {}", value),
+            }
+        }
+        if let Some(value) = var("HAYATE_CHECK_UPDATES") {
+            match parse_env_bool(&value) {
+                Some(b) => self.override_field("check_for_updates", |s| s.check_for_updates = b),
+                None => println!("[設定] HAYATE_CHECK_UPDATES の値が不正です: {}", value),
+            }
+        }
+    }
+
+    /// 最後に読み書きした後に config.json が外部で更新されたか
+    /// 読み込み・外部変更の取り込みの時刻と、書き込みスレッドで保存した時刻の新しい方と比べる
+    pub fn is_file_changed<P: AsRef<Path>>(&self, path: P) -> bool {
+        let known = self.sync_mtime.max(crate::persist::last_written_mtime(path.as_ref()));
+        match (file_mtime(path), known) {
+            (Some(disk), Some(known)) => disk != known,
+            _ => false,
+        }
+    }
+
+    /// 外部で編集された config.json を項目単位で3方向マージする
+    /// ファイル側だけが変わった項目は取り込み、両方で異なる値に変わった項目はアプリ側を優先して競合として返す
+    pub fn merge_external<P: AsRef<Path>>(&mut self, path: P) -> Result<MergeResult, String> {
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let disk: Settings = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        let disk_value = serde_json::to_value(&disk).map_err(|e| e.to_string())?;
+        let ours = serde_json::to_value(&*self).map_err(|e| e.to_string())?;
+        let base = self.sync_base.clone().unwrap_or_else(|| ours.clone());
+
+        let mut result = MergeResult::default();
+        let mut merged = ours.clone();
+        if let (Some(disk_map), Some(merged_map)) = (disk_value.as_object(), merged.as_object_mut()) {
+            for (key, disk_val) in disk_map {
+                let base_val = base.get(key);
+                let our_val = ours.get(key);
+                if base_val == Some(disk_val) || our_val == Some(disk_val) {
+                    continue;
+                }
+                if our_val == base_val {
+                    merged_map.insert(key.clone(), disk_val.clone());
+                    result.changed.push(key.clone());
+                } else {
+                    result.conflicts.push(key.clone());
+                }
+            }
+        }
+
+        let mut next: Settings = serde_json::from_value(merged).map_err(|e| e.to_string())?;
+        next.sync_base = Some(disk_value);
+        next.sync_mtime = file_mtime(&path);
+        next.env_overrides = std::mem::take(&mut self.env_overrides);
+        next.prefetch_paused = self.prefetch_paused;
+        next.pending_save = self.pending_save.take();
+        *self = next;
+        Ok(result)
+    }
+
+    pub fn add_to_history(&mut self, path: String, page: usize, binding: String, crop: Option<crate::image::postprocess::CropMargins>) {
         // すでに存在する場合は一旦削除して先頭に持ってくる
         self.history.retain(|item| item.path != path);
         self.history.insert(
@@ -82,6 +658,7 @@ impl Settings {
                 path,
                 page,
                 binding,
+                crop,
             },
         );
         if self.history.len() > self.max_history_count {
@@ -89,6 +666,115 @@ impl Settings {
         }
     }
 
+    /// 履歴にあるソースの手動トリミングを書き換える (履歴の順序は変えない)。履歴に無ければ false
+    pub fn set_history_crop(&mut self, path: &str, crop: Option<crate::image::postprocess::CropMargins>) -> bool {
+        match self.history.iter_mut().find(|item| item.path == path) {
+            Some(item) => {
+                item.crop = crop;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// ごみ箱へ移動したソースを履歴とソース別設定から取り除く
+    pub fn forget_source(&mut self, path: &str) {
+        self.history.retain(|item| item.path != path);
+        self.source_overrides.remove(path);
+        self.source_notes.remove(path);
+    }
+
+    /// ソースのタグを書き換える (メモはそのまま)。タグもメモも空になれば取り除く
+    /// 復号できないメモは書き換えると暗号化されたままの内容が失われるため、変更せずに false を返す
+    pub fn set_source_tags(&mut self, path: &str, tags: Vec<String>) -> bool {
+        if self.source_notes.get(path).is_some_and(SourceNote::is_locked) {
+            return false;
+        }
+        let text = self.source_notes.get(path).map(|note| note.text().to_string()).unwrap_or_default();
+        self.set_source_note(path, SourceNote::new(tags, text));
+        true
+    }
+
+    /// ソースのメモを書き換える (タグはそのまま)。復号できないメモは変更せずに false を返す
+    pub fn set_source_text(&mut self, path: &str, text: String) -> bool {
+        if self.source_notes.get(path).is_some_and(SourceNote::is_locked) {
+            return false;
+        }
+        let tags = self.source_notes.get(path).map(|note| note.tags().to_vec()).unwrap_or_default();
+        self.set_source_note(path, SourceNote::new(tags, text));
+        true
+    }
+
+    fn set_source_note(&mut self, path: &str, note: SourceNote) {
+        if note.is_empty() {
+            self.source_notes.remove(path);
+        } else {
+            self.source_notes.insert(path.to_string(), note);
+        }
+    }
+
+    /// 閲覧履歴の絞り込み: 空白で区切った語がすべてパス・タグ・メモのどれかに含まれるか (大文字小文字は区別しない)
+    pub fn history_matches(&self, item: &HistoryItem, filter: &str) -> bool {
+        let path = item.path.to_lowercase();
+        let note = self.source_notes.get(&item.path);
+        filter
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| path.contains(word) || note.is_some_and(|note| note.matches(word)))
+    }
+
+    /// 現在の背景テーマの背景色 (未知のテーマ名はダーク扱い)
+    pub fn background_rgb(&self) -> [f32; 3] {
+        if self.background_theme == "custom" {
+            return self.background_color.map(|c| c.clamp(0.0, 1.0));
+        }
+        BACKGROUND_THEMES
+            .iter()
+            .find(|(name, _, _)| *name == self.background_theme)
+            .map_or(BACKGROUND_THEMES[0].2, |(_, _, rgb)| *rgb)
+    }
+
+    /// ページの平均輝度 (0.0〜1.0) に合わせた背景色。輝度が未計測か追従が無効なら背景テーマの色
+    pub fn adaptive_background_rgb(&self, page_luminance: Option<f32>) -> [f32; 3] {
+        let base = self.background_rgb();
+        let strength = self.adaptive_background.clamp(0.0, 1.0);
+        match page_luminance {
+            Some(luminance) if strength > 0.0 => base.map(|c| c + (luminance.clamp(0.0, 1.0) - c) * strength),
+            _ => base,
+        }
+    }
+
+    /// 事前回転の角度を 0 / 90 / 180 / 270 に丸めて返す (手編集された不正値への対策)
+    pub fn effective_pre_rotation(&self) -> u32 {
+        (self.pre_rotation % 360) / 90 * 90
+    }
+
+    pub fn set_source_override(&mut self, path: String, value: SourceOverride) {
+        let last_used = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.source_overrides.insert(path, SourceOverride { last_used, ..value });
+        if self.source_overrides.len() > MAX_SOURCE_OVERRIDES {
+            let mut by_age: Vec<(u64, String)> = self.source_overrides.iter().map(|(k, v)| (v.last_used, k.clone())).collect();
+            by_age.sort_unstable();
+            let excess = self.source_overrides.len() - MAX_SOURCE_OVERRIDES;
+            for (_, key) in by_age.into_iter().take(excess) {
+                self.source_overrides.remove(&key);
+            }
+        }
+    }
+
+    /// 上限を超えていれば、存在しなくなったソースの設定を削除する (起動時に 1 回だけ。ネットワーク上のパスもあるため記録のたびには調べない)
+    fn prune_missing_source_overrides(&mut self) {
+        if self.source_overrides.len() <= MAX_SOURCE_OVERRIDES {
+            return;
+        }
+        let before = self.source_overrides.len();
+        self.source_overrides.retain(|p, _| Path::new(p).exists());
+        println!("[設定] 存在しないソースの設定を {} 件削除しました", before - self.source_overrides.len());
+    }
+
     pub fn remove_from_history(&mut self, index: usize) {
         if index < self.history.len() {
             self.history.remove(index);
@@ -98,4 +784,34 @@ impl Settings {
     pub fn clear_history(&mut self) {
         self.history.clear();
     }
+
+    /// ページのしおりを追加し、すでにあれば削除する (追加した場合は true)
+    pub fn toggle_bookmark(&mut self, path: &str, page: usize, label: String) -> bool {
+        if let Some(pos) = self.bookmarks.iter().position(|b| b.path == path && b.page == page) {
+            self.bookmarks.remove(pos);
+            return false;
+        }
+        self.bookmarks.insert(
+            0,
+            Bookmark {
+                path: path.to_string(),
+                page,
+                label,
+                timestamp: crate::platform::update::now_secs(),
+            },
+        );
+        true
+    }
+
+    pub fn remove_bookmark(&mut self, index: usize) {
+        if index < self.bookmarks.len() {
+            self.bookmarks.remove(index);
+        }
+    }
+
+    pub fn rename_bookmark(&mut self, index: usize, label: String) {
+        if let Some(bookmark) = self.bookmarks.get_mut(index) {
+            bookmark.label = label;
+        }
+    }
 }
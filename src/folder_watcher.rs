@@ -0,0 +1,73 @@
+use crate::image::loader::UserEvent;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+use winit::event_loop::EventLoopProxy;
+
+/// 変更の通知がこの時間途切れてから一覧を読み直す (書き込み中のファイルを何度も読み直さないため)
+const QUIET_PERIOD: Duration = Duration::from_millis(1000);
+
+/// 画像を並べたフォルダのソースか (アーカイブをつなげたフォルダはページ名がアーカイブの下になる)
+pub fn is_image_folder(path_key: &str, image_files: &[String]) -> bool {
+    let dir = Path::new(path_key);
+    dir.is_dir() && image_files.first().is_none_or(|f| Path::new(f).parent() == Some(dir))
+}
+
+/// 表示中のフォルダを監視し、ファイルが増減したら UserEvent::SourceChanged を送る
+/// ダウンロード中のフォルダなどで、開いたまま新しい画像をページ一覧に取り込めるようにする
+pub struct FolderWatcher {
+    pub path: String,
+    // 破棄すると監視が止まり、通知を待つスレッドも終わる
+    _watcher: RecommendedWatcher,
+}
+
+impl FolderWatcher {
+    pub fn start(path: &str, proxy: EventLoopProxy<UserEvent>) -> Option<Self> {
+        let (tx, rx) = mpsc::channel::<()>();
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    let _ = tx.send(());
+                }
+            }
+        });
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                println!("[フォルダ監視] 監視を開始できませんでした: {}: {}", path, e);
+                return None;
+            }
+        };
+        if let Err(e) = watcher.watch(Path::new(path), RecursiveMode::NonRecursive) {
+            println!("[フォルダ監視] 監視を開始できませんでした: {}: {}", path, e);
+            return None;
+        }
+
+        let source = path.to_string();
+        std::thread::spawn(move || {
+            while rx.recv().is_ok() {
+                // 続けて届く通知 (コピー中の書き込みなど) が落ち着くまで待ってから 1 回だけ知らせる
+                loop {
+                    match rx.recv_timeout(QUIET_PERIOD) {
+                        Ok(()) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+                if proxy.send_event(UserEvent::SourceChanged(source.clone())).is_err() {
+                    break;
+                }
+            }
+        });
+
+        println!("[フォルダ監視] 監視を開始しました: {}", path);
+        Some(Self { path: path.to_string(), _watcher: watcher })
+    }
+}
+
+impl Drop for FolderWatcher {
+    fn drop(&mut self) {
+        println!("[フォルダ監視] 監視を停止しました: {}", self.path);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::image::loader::UserEvent;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use winit::event_loop::EventLoopProxy;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// 開ける形式 (画像・アーカイブ・PDF) のファイルか。拡張子は image モジュールの一覧を使う
+fn is_supported(path: &Path) -> bool {
+    use crate::image::{ARCHIVE_EXTENSIONS, IMAGE_EXTENSIONS, PDF_EXTENSION};
+    path.extension()
+        .and_then(|s| s.to_str())
+        .map(|ext| ext.to_lowercase())
+        .is_some_and(|ext| ext == PDF_EXTENSION || IMAGE_EXTENSIONS.contains(&ext.as_str()) || ARCHIVE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// フォルダ内の対応ファイルとその (サイズ, 更新日時) を列挙する
+fn scan(dir: &Path) -> HashMap<PathBuf, (u64, SystemTime)> {
+    let mut entries = HashMap::new();
+    if let Ok(read_dir) = std::fs::read_dir(dir) {
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if !is_supported(&path) {
+                continue;
+            }
+            if let Ok(meta) = entry.metadata() {
+                if meta.is_file() {
+                    let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    entries.insert(path, (meta.len(), modified));
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// 指定フォルダを監視し、新しく置かれたアーカイブ/画像を UserEvent::HotFolderItem で通知する
+/// ダウンロード途中のファイルを開かないよう、サイズが1周期変化しなくなってから通知する
+pub struct HotFolderWatcher {
+    pub path: String,
+    stop: Arc<AtomicBool>,
+}
+
+impl HotFolderWatcher {
+    pub fn start(path: &str, proxy: EventLoopProxy<UserEvent>) -> Option<Self> {
+        let dir = PathBuf::from(path);
+        if !dir.is_dir() {
+            println!("[ホットフォルダ] フォルダが存在しません: {}", path);
+            return None;
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        std::thread::spawn(move || {
+            // 監視開始時点で存在するファイルは対象外
+            let mut known = scan(&dir);
+            // 新規ファイル -> 前回観測したサイズ
+            let mut pending: HashMap<PathBuf, u64> = HashMap::new();
+
+            while !stop_flag.load(Ordering::Relaxed) {
+                std::thread::sleep(POLL_INTERVAL);
+                let current = scan(&dir);
+
+                for (path, (size, _)) in &current {
+                    let is_new_or_updated = match known.get(path) {
+                        Some((old_size, _)) => old_size != size,
+                        None => true,
+                    };
+                    if is_new_or_updated && !pending.contains_key(path) {
+                        pending.insert(path.clone(), u64::MAX);
+                    }
+                }
+
+                // サイズが安定したものから最新の1件を開く
+                let mut ready: Vec<(PathBuf, SystemTime)> = Vec::new();
+                pending.retain(|path, last_size| {
+                    let Some((size, modified)) = current.get(path) else { return false; };
+                    if *size > 0 && *size == *last_size {
+                        ready.push((path.clone(), *modified));
+                        false
+                    } else {
+                        *last_size = *size;
+                        true
+                    }
+                });
+
+                if let Some((newest, _)) = ready.into_iter().max_by_key(|(_, modified)| *modified) {
+                    println!("[ホットフォルダ] 新しいファイルを検出: {}", newest.display());
+                    if proxy.send_event(UserEvent::HotFolderItem(newest.to_string_lossy().to_string())).is_err() {
+                        break;
+                    }
+                }
+                known = current;
+            }
+        });
+
+        println!("[ホットフォルダ] 監視を開始しました: {}", path);
+        Some(Self { path: path.to_string(), stop })
+    }
+}
+
+impl Drop for HotFolderWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        println!("[ホットフォルダ] 監視を停止しました: {}", self.path);
+    }
+}
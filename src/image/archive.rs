@@ -1,123 +1,242 @@
-use std::io::Read;
+use std::io::{Cursor, Read, Seek};
 use zip::ZipArchive;
 use sevenz_rust;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Duration, Instant};
+use rayon::prelude::*;
 use crate::image::decoder::{DecodedImage, _decode_image_from_memory};
 
 pub enum ArchiveInternal {
     Zip(ZipArchive<std::fs::File>),
     SevenZ {
         archive_path: std::path::PathBuf,
+        /// 一覧の取得時に読み込んだヘッダー (ストリーミング展開にも使う)
+        header: Option<sevenz_rust::Archive>,
     },
     Rar {
         archive_path: std::path::PathBuf,
     },
 }
 
+/// 7z のソリッドブロックをキャッシュする上限 (これを超えるブロックは目的のエントリまでしか展開しない)
+const SOLID_BLOCK_CACHE_BYTES: usize = 256 * 1024 * 1024;
+
+/// ZIP の中の ZIP を何段まで開くか
+const MAX_NESTING: usize = 3;
+
+/// ZIP の中の ZIP (メモリへ展開して開いたもの)
+struct NestedZip {
+    /// 外側のアーカイブでのパス (入れ子の場合は「外側/内側」とつなげたもの)。中のエントリは「prefix/エントリ名」で一覧に載せる
+    prefix: String,
+    archive: ZipArchive<Cursor<Vec<u8>>>,
+}
+
+/// 一覧の取得を待つ時間。これを過ぎても終わらない巨大なアーカイブは、取得できた分から表示する
+const LISTING_WAIT: Duration = Duration::from_millis(300);
+/// バックグラウンドでの一覧取得で、続きを送る間隔とエントリ数
+const LISTING_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+const LISTING_FLUSH_ENTRIES: usize = 512;
+
+/// バックグラウンドで取得中の一覧の続き
+pub enum ListingUpdate {
+    /// 新しく見つかった画像エントリ (アーカイブ内の順。一覧の末尾に追加する)
+    Append(Vec<String>),
+    /// 一覧の取得が終わった (名前順に並べた全エントリ)
+    Complete(Vec<String>),
+    Failed(String),
+}
+
+pub type ListingReceiver = mpsc::Receiver<ListingUpdate>;
+
+/// アーカイブの展開方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionMode {
+    /// 初回の読み込みで全エントリをメモリへ展開する (小さなアーカイブではページ送りが速い)
+    Slurp,
+    /// 要求されたエントリだけを展開する (大きなアーカイブでもメモリを使い切らない)
+    Streaming,
+}
+
+impl ExtractionMode {
+    /// 設定値 ("slurp" / "streaming") から変換する。不明な値はストリーミングとみなす
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "slurp" => Self::Slurp,
+            _ => Self::Streaming,
+        }
+    }
+}
+
 pub struct ArchiveLoader {
     internal: ArchiveInternal,
     file_names: Vec<String>,
-    // メモリキャッシュ: パス名 -> ファイルデータ
+    mode: ExtractionMode,
+    // メモリキャッシュ: パス名 -> ファイルデータ (一括展開モード)
     cache: Arc<Mutex<Option<HashMap<String, Vec<u8>>>>>,
+    /// 直前に展開した 7z ソリッドブロックのエントリ: パス名 -> ファイルデータ (ストリーミングモード)
+    block_cache: Option<HashMap<String, Vec<u8>>>,
+    /// 暗号化されたアーカイブの展開に使うパスワード (サイドカーファイルで指定)
+    password: Option<String>,
+    /// バックグラウンドで取得中の一覧の続き (巨大な RAR)
+    listing: Option<ListingReceiver>,
+    /// ZIP の中の ZIP (外側が ZIP の場合のみ)
+    nested: Vec<NestedZip>,
 }
 
 impl ArchiveLoader {
     pub fn open(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let path_buf = std::path::PathBuf::from(path);
         let ext = path_buf.extension().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
-        
-        let mut file_names = Vec::new();
-        let supported = ["jpg", "jpeg", "png", "webp", "bmp", "jp2", "avif"];
 
         if ext == "zip" || ext == "cbz" {
             let file = std::fs::File::open(path)?;
             let mut archive = ZipArchive::new(file)?;
-            for i in 0..archive.len() {
-                let file = archive.by_index(i)?;
-                if file.is_file() {
-                    let name = file.name();
-                    if let Some(ext) = std::path::Path::new(name).extension().and_then(|s| s.to_str()) {
-                        if supported.contains(&ext.to_lowercase().as_str()) {
-                            file_names.push(name.to_string());
-                        }
-                    }
-                }
+            // セントラルディレクトリは ZipArchive::new で読み込み済み
+            let mut names: Vec<String> = archive.file_names().filter(|name| !name.ends_with('/')).map(str::to_string).collect();
+            let mut nested = Vec::new();
+            open_nested_zips(&mut archive, "", 1, &mut nested, &mut names);
+            if !nested.is_empty() {
+                println!("[Archive] アーカイブ内のアーカイブを {} 個開きました", nested.len());
             }
-            file_names.sort_by(|a, b| natord::compare(a, b));
-            Ok(Self {
-                internal: ArchiveInternal::Zip(archive),
-                file_names,
-                cache: Arc::new(Mutex::new(None)),
-            })
+            let file_names = sort_entries(filter_image_entries(names));
+            let mut loader = Self::new(ArchiveInternal::Zip(archive), file_names, None);
+            loader.nested = nested;
+            Ok(loader)
         } else if ext == "7z" {
-            // 7z のファイルリストを取得（高速）
+            // ヘッダーだけを読んで一覧を作る (SevenZReader::for_each_entries は全エントリを展開してしまう)
             println!("[Archive] Listing 7z: {}", path);
-            let mut file_names = Vec::new();
-            let mut reader = sevenz_rust::SevenZReader::open(path_buf.clone(), sevenz_rust::Password::empty())?;
-            reader.for_each_entries(|entry, _| {
-                let name = entry.name().replace("\\", "/");
-                if let Some(ext) = std::path::Path::new(&name).extension().and_then(|s| s.to_str()) {
-                    if supported.contains(&ext.to_lowercase().as_str()) {
-                        file_names.push(name);
-                    }
-                }
-                Ok(true)
-            })?;
-            
-            file_names.sort_by(|a, b| natord::compare(a, b));
-            Ok(Self {
-                internal: ArchiveInternal::SevenZ {
+            let mut file = std::fs::File::open(&path_buf)?;
+            let len = file.metadata()?.len();
+            let header = sevenz_rust::Archive::read(&mut file, len, sevenz_rust::Password::empty().as_slice())?;
+            let names = header
+                .files
+                .iter()
+                .filter(|entry| !entry.is_directory())
+                .map(|entry| entry.name().replace("\\", "/"))
+                .collect();
+            let file_names = sort_entries(filter_image_entries(names));
+            Ok(Self::new(
+                ArchiveInternal::SevenZ {
                     archive_path: path_buf,
+                    header: Some(header),
                 },
                 file_names,
-                cache: Arc::new(Mutex::new(None)),
-            })
+                None,
+            ))
         } else if ext == "rar" || ext == "cbr" {
-            // unrar クレートを使用してファイルリストを取得（高速）
+            // RAR はヘッダーを順に辿るしかないため、一覧の取得を別スレッドで行い、届いた分から表示する
             println!("[Archive] Listing RAR: {}", path);
-            let mut file_names = Vec::new();
-            let mut archive = unrar::Archive::new(path).open_for_listing()?;
-            while let Some(header) = archive.read_header()? {
-                let entry = header.entry();
-                if entry.is_file() {
-                    let name = entry.filename.to_string_lossy().replace("\\", "/");
-                    if let Some(ext) = std::path::Path::new(&name).extension().and_then(|s| s.to_str()) {
-                        if supported.contains(&ext.to_lowercase().as_str()) {
-                            file_names.push(name);
-                        }
-                    }
-                }
-                archive = header.skip()?;
+            let receiver = spawn_rar_listing(path_buf.clone())?;
+            let (file_names, listing) = wait_for_listing(receiver)?;
+            if listing.is_some() {
+                println!("[Archive] 一覧の取得を続けながら表示します (取得済み {} エントリ)", file_names.len());
             }
-
-            file_names.sort_by(|a, b| natord::compare(a, b));
-            Ok(Self {
-                internal: ArchiveInternal::Rar {
-                    archive_path: path_buf,
-                },
-                file_names,
-                cache: Arc::new(Mutex::new(None)),
-            })
+            Ok(Self::new(ArchiveInternal::Rar { archive_path: path_buf }, file_names, listing))
         } else {
             Err("Unsupported archive format".into())
         }
     }
 
+    fn new(internal: ArchiveInternal, file_names: Vec<String>, listing: Option<ListingReceiver>) -> Self {
+        Self {
+            internal,
+            file_names,
+            mode: ExtractionMode::Streaming,
+            cache: Arc::new(Mutex::new(None)),
+            block_cache: None,
+            password: None,
+            listing,
+            nested: Vec::new(),
+        }
+    }
+
+    /// 一覧が揃うまで待って開く (フォルダ内のアーカイブをまとめて開く場合など)
+    pub fn open_complete(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut loader = Self::open(path)?;
+        if let Some(receiver) = loader.take_listing() {
+            for update in receiver {
+                match update {
+                    ListingUpdate::Append(_) => {}
+                    ListingUpdate::Complete(names) => {
+                        loader.file_names = names;
+                        break;
+                    }
+                    ListingUpdate::Failed(e) => return Err(e.into()),
+                }
+            }
+        }
+        Ok(loader)
+    }
+
+    /// バックグラウンドで取得中の一覧の続きを受け取るチャンネルを取り出す (一覧が揃っていれば None)
+    pub fn take_listing(&mut self) -> Option<ListingReceiver> {
+        self.listing.take()
+    }
+
+    /// 一覧を差し替える (取得中の一覧の続きが届いたとき、名前順に並べ直したとき)
+    pub fn set_file_names(&mut self, file_names: Vec<String>) {
+        self.file_names = file_names;
+    }
+
     pub fn get_file_names(&self) -> &[String] {
         &self.file_names
     }
 
-    pub fn load_image(&mut self, index: usize, use_cpu_color_conversion: bool) -> Result<DecodedImage, Box<dyn std::error::Error>> {
-        let name = &self.file_names[index];
-        
+    pub fn set_password(&mut self, password: String) {
+        self.password = Some(password);
+    }
+
+    /// 展開方法を設定する (設定の archive_extraction_mode)
+    pub fn set_extraction_mode(&mut self, mode: ExtractionMode) {
+        self.mode = mode;
+    }
+
+    /// progress には展開の進み具合 (0.0〜1.0) を渡す。一括展開と 7z のソリッドブロックのように時間のかかる展開でだけ呼ぶ
+    pub fn load_image(
+        &mut self,
+        index: usize,
+        use_cpu_color_conversion: bool,
+        progress: &mut dyn FnMut(f32),
+    ) -> Result<DecodedImage, Box<dyn std::error::Error>> {
+        let name = self.file_names.get(index).cloned().ok_or_else(|| format!("エントリ {} はまだ一覧にありません", index))?;
+        let data = match self.mode {
+            // ZIP の中の ZIP は開いたときにメモリへ展開済みなので、どちらのモードでもそこから読む
+            _ if self.nested_entry(&name).is_some() => self.read_nested(&name)?,
+            ExtractionMode::Slurp => self.read_slurped(&name, progress)?,
+            ExtractionMode::Streaming => self.read_entry(&name, progress)?,
+        };
+        let decoded = _decode_image_from_memory(&data, use_cpu_color_conversion)?;
+        Ok(decoded)
+    }
+
+    /// name が ZIP の中の ZIP のエントリなら (nested の番号, 内側のエントリ名)。入れ子は最も内側のものを選ぶ
+    fn nested_entry<'a>(&self, name: &'a str) -> Option<(usize, &'a str)> {
+        self.nested
+            .iter()
+            .enumerate()
+            .filter_map(|(i, nested)| {
+                let rest = name.strip_prefix(nested.prefix.as_str())?.strip_prefix('/')?;
+                Some((i, rest, nested.prefix.len()))
+            })
+            .max_by_key(|&(_, _, len)| len)
+            .map(|(i, rest, _)| (i, rest))
+    }
+
+    fn read_nested(&mut self, name: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let (index, entry) = self.nested_entry(name).ok_or_else(|| format!("File '{}' not found in archive", name))?;
+        read_zip_entry(&mut self.nested[index].archive, entry, self.password.as_deref())
+    }
+
+    /// 一括展開モード: 初回に全エントリをメモリへ展開し、以降はそこから返す
+    fn read_slurped(&mut self, name: &str, progress: &mut dyn FnMut(f32)) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         // 1. キャッシュチェック
         {
             let cache = self.cache.lock().unwrap();
             if let Some(ref map) = *cache {
                 if let Some(data) = map.get(name) {
                     println!("[Archive] Cache hit: {}", name);
-                    return _decode_image_from_memory(data, use_cpu_color_conversion).map_err(|e| e.into());
+                    return Ok(data.clone());
                 }
             }
         }
@@ -128,34 +247,50 @@ impl ArchiveLoader {
         
         match self.internal {
             ArchiveInternal::Zip(ref mut archive) => {
+                let count = archive.len().max(1);
                 for i in 0..archive.len() {
-                    let mut file = archive.by_index(i)?;
+                    // パスワードは暗号化されていないエントリでは無視される
+                    let mut file = match self.password {
+                        Some(ref password) => archive
+                            .by_index_decrypt(i, password.as_bytes())?
+                            .map_err(|_| "アーカイブのパスワードが違います")?,
+                        None => archive.by_index(i)?,
+                    };
                     if file.is_file() {
                         let fname = file.name().to_string();
                         let mut buffer = Vec::new();
                         file.read_to_end(&mut buffer)?;
                         new_cache.insert(fname, buffer);
                     }
+                    progress((i + 1) as f32 / count as f32);
                 }
             }
-            ArchiveInternal::SevenZ { ref archive_path } => {
-                let mut reader = sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())?;
+            ArchiveInternal::SevenZ { ref archive_path, .. } => {
+                let mut reader = sevenz_rust::SevenZReader::open(archive_path, sevenz_password(self.password.as_deref()))?;
+                // 展開後の合計サイズに対する展開済みのバイト数で進み具合を求める
+                let total_bytes: u64 = reader.archive().files.iter().map(|f| f.size()).sum::<u64>().max(1);
+                let mut done_bytes = 0u64;
                 reader.for_each_entries(|entry, entry_reader| {
                     if !entry.is_directory() {
                         let fname = entry.name().replace("\\", "/");
                         let mut buffer = Vec::new();
                         entry_reader.read_to_end(&mut buffer)?;
+                        done_bytes += buffer.len() as u64;
                         new_cache.insert(fname, buffer);
+                        progress(done_bytes as f32 / total_bytes as f32);
                     }
                     Ok(true)
                 })?;
             }
             ArchiveInternal::Rar { ref archive_path } => {
-                let mut archive = unrar::Archive::new(archive_path).open_for_processing()?;
+                // RAR は全体の大きさを先に求められないため、一覧のエントリ数に対する展開済みの数で代用する
+                let count = self.file_names.len().max(1);
+                let mut archive = open_rar(archive_path, self.password.as_deref()).open_for_processing()?;
                 while let Some(header) = archive.read_header()? {
                     let filename = header.entry().filename.to_string_lossy().replace("\\", "/");
                     let (data, next_archive) = header.read()?;
                     new_cache.insert(filename, data);
+                    progress((new_cache.len() as f32 / count as f32).min(1.0));
                     archive = next_archive;
                 }
             }
@@ -169,8 +304,272 @@ impl ArchiveLoader {
         }
 
         println!("[Archive] Slurping complete. Memory items: {}", self.cache.lock().unwrap().as_ref().unwrap().len());
-        let decoded = _decode_image_from_memory(&data, use_cpu_color_conversion)?;
-        Ok(decoded)
+        Ok(data)
+    }
+
+    /// ストリーミングモード: 要求されたエントリだけを展開する
+    fn read_entry(&mut self, name: &str, progress: &mut dyn FnMut(f32)) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        match self.internal {
+            ArchiveInternal::Zip(ref mut archive) => {
+                // ZIP はエントリ単位で圧縮されているため、セントラルディレクトリから直接シークできる
+                read_zip_entry(archive, name, self.password.as_deref())
+            }
+            ArchiveInternal::SevenZ { ref archive_path, ref mut header } => {
+                // 直前に展開したソリッドブロックに含まれていればそこから返す
+                if let Some(data) = self.block_cache.as_ref().and_then(|block| block.get(name)) {
+                    return Ok(data.clone());
+                }
+
+                let password = sevenz_password(self.password.as_deref());
+                let mut file = std::fs::File::open(archive_path)?;
+                if header.is_none() {
+                    let len = file.metadata()?.len();
+                    *header = Some(sevenz_rust::Archive::read(&mut file, len, password.as_slice())?);
+                }
+                let archive = header.as_ref().unwrap();
+                let file_index = archive
+                    .files
+                    .iter()
+                    .position(|entry| entry.name().replace("\\", "/") == name)
+                    .ok_or_else(|| format!("File '{}' not found in archive", name))?;
+                let Some(folder_index) = archive.stream_map.file_folder_index[file_index] else {
+                    // データを持たない (サイズ 0 の) エントリ
+                    return Ok(Vec::new());
+                };
+
+                // ソリッドブロックは先頭から順にしか展開できないため、目的のエントリまで読み進める
+                // 同じブロックの他のエントリも上限までキャッシュし、続くページで再展開しないようにする
+                let solid = archive.folders[folder_index].num_unpack_sub_streams > 1;
+                // 目的のエントリまでに展開するバイト数 (同じブロックでそれより前にあるエントリを含む)
+                let target_bytes: u64 = archive
+                    .files
+                    .iter()
+                    .enumerate()
+                    .take(file_index + 1)
+                    .filter(|&(i, _)| archive.stream_map.file_folder_index[i] == Some(folder_index))
+                    .map(|(_, f)| f.size())
+                    .sum::<u64>()
+                    .max(1);
+                let mut done_bytes = 0u64;
+                let mut block = HashMap::new();
+                let mut block_bytes = 0usize;
+                let mut found = None;
+                sevenz_rust::BlockDecoder::new(folder_index, archive, password.as_slice(), &mut file).for_each_entries(
+                    &mut |entry, entry_reader| {
+                        let entry_name = entry.name().replace("\\", "/");
+                        let mut buffer = Vec::new();
+                        entry_reader.read_to_end(&mut buffer)?;
+                        if found.is_none() {
+                            done_bytes += buffer.len() as u64;
+                            progress((done_bytes as f32 / target_bytes as f32).min(1.0));
+                        }
+                        if entry_name == name {
+                            found = Some(buffer.clone());
+                        }
+                        if solid && block_bytes + buffer.len() <= SOLID_BLOCK_CACHE_BYTES {
+                            block_bytes += buffer.len();
+                            block.insert(entry_name, buffer);
+                        } else if found.is_some() {
+                            // キャッシュに収まらなくなったら残りは展開しない
+                            return Ok(false);
+                        }
+                        Ok(true)
+                    },
+                )?;
+                if solid {
+                    println!("[Archive] 7z ソリッドブロック #{} を展開: {} エントリ ({}MB)", folder_index, block.len(), block_bytes / (1024 * 1024));
+                    self.block_cache = Some(block);
+                }
+                found.ok_or_else(|| format!("File '{}' not found in archive", name).into())
+            }
+            ArchiveInternal::Rar { ref archive_path } => {
+                // RAR はヘッダーを順に辿り、目的のエントリ以外は展開せずに読み飛ばす
+                let mut archive = open_rar(archive_path, self.password.as_deref()).open_for_processing()?;
+                while let Some(header) = archive.read_header()? {
+                    if header.entry().filename.to_string_lossy().replace("\\", "/") == name {
+                        let (data, _) = header.read()?;
+                        return Ok(data);
+                    }
+                    archive = header.skip()?;
+                }
+                Err(format!("File '{}' not found in archive", name).into())
+            }
+        }
+    }
+
+}
+
+/// ZIP のエントリを 1 つ読み出す (パスワードは暗号化されていないエントリでは無視される)
+fn read_zip_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str, password: Option<&str>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut file = match password {
+        Some(password) => archive
+            .by_name_decrypt(name, password.as_bytes())?
+            .map_err(|_| "アーカイブのパスワードが違います")?,
+        None => archive.by_name(name)?,
+    };
+    let mut buffer = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// ZIP 内の .zip / .cbz をメモリへ展開して開き、中のエントリを「内側のパス/エントリ名」として names に加える
+/// 入れ子は MAX_NESTING 段まで。開けないもの (暗号化されているなど) は飛ばす
+fn open_nested_zips<R: Read + Seek>(archive: &mut ZipArchive<R>, prefix: &str, depth: usize, nested: &mut Vec<NestedZip>, names: &mut Vec<String>) {
+    let inner_names: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            std::path::Path::new(name)
+                .extension()
+                .and_then(|s| s.to_str())
+                .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "zip" | "cbz"))
+        })
+        .map(str::to_string)
+        .collect();
+    for inner_name in inner_names {
+        let path = if prefix.is_empty() { inner_name.clone() } else { format!("{}/{}", prefix, inner_name) };
+        let mut inner = match read_zip_entry(archive, &inner_name, None)
+            .and_then(|data| ZipArchive::new(Cursor::new(data)).map_err(Into::into))
+        {
+            Ok(inner) => inner,
+            Err(e) => {
+                println!("[Archive] アーカイブ内のアーカイブを開けないため飛ばします: {}: {}", path, e);
+                continue;
+            }
+        };
+        names.extend(inner.file_names().filter(|name| !name.ends_with('/')).map(|name| format!("{}/{}", path, name)));
+        if depth < MAX_NESTING {
+            open_nested_zips(&mut inner, &path, depth + 1, nested, names);
+        }
+        nested.push(NestedZip { prefix: path, archive: inner });
+    }
+}
+
+/// 画像エントリだけを残す (数万エントリのアーカイブでも待たされないよう並列に判定する)
+fn filter_image_entries(names: Vec<String>) -> Vec<String> {
+    names
+        .into_par_iter()
+        .filter(|name| {
+            std::path::Path::new(name)
+                .extension()
+                .and_then(|s| s.to_str())
+                .is_some_and(|ext| crate::image::IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .collect()
+}
+
+/// エントリを名前順 (自然順) に並べる
+/// フォルダごとにまとめて並べるため、章ごとのフォルダに分かれたアーカイブでも章の途中に別のフォルダのページが混ざらない
+fn sort_entries(mut names: Vec<String>) -> Vec<String> {
+    names.par_sort_by(|a, b| compare_entries(a, b));
+    names
+}
+
+/// エントリのフォルダ部分 ("第1話/001.jpg" なら "第1話"、直下のエントリは "")
+fn entry_dir(name: &str) -> &str {
+    name.rfind(['/', '\\']).map_or("", |pos| &name[..pos])
+}
+
+/// フォルダを階層ごとに自然順で比べてから、ファイル名を自然順で比べる
+/// 直下のエントリはフォルダの中のエントリより前、フォルダ直下のエントリはそのサブフォルダより前に並ぶ
+fn compare_entries(a: &str, b: &str) -> std::cmp::Ordering {
+    let mut parts_a = a.split(['/', '\\']).filter(|c| !c.is_empty()).collect::<Vec<_>>();
+    let mut parts_b = b.split(['/', '\\']).filter(|c| !c.is_empty()).collect::<Vec<_>>();
+    let (file_a, file_b) = (parts_a.pop().unwrap_or(""), parts_b.pop().unwrap_or(""));
+    parts_a
+        .iter()
+        .zip(parts_b.iter())
+        .map(|(x, y)| natord::compare(x, y))
+        .find(|o| o.is_ne())
+        .unwrap_or_else(|| parts_a.len().cmp(&parts_b.len()))
+        .then_with(|| natord::compare(file_a, file_b))
+}
+
+/// エントリのフォルダが切り替わる位置 (章の先頭)
+/// フォルダのソースではフルパスの親フォルダ、アーカイブではエントリのフォルダ (入れ子のアーカイブは 1 つの章) で区切る
+pub fn chapter_start_indices(names: &[String]) -> Vec<usize> {
+    names
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| entry_dir(&pair[0]) != entry_dir(&pair[1]))
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+/// RAR の一覧を別スレッドで取得し、見つかった画像エントリを少しずつ送る
+fn spawn_rar_listing(archive_path: std::path::PathBuf) -> Result<ListingReceiver, Box<dyn std::error::Error>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::Builder::new().name("hayate-archive-listing".to_string()).spawn(move || {
+        let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+            let mut archive = unrar::Archive::new(&archive_path).open_for_listing()?;
+            let mut all = Vec::new();
+            let mut batch = Vec::new();
+            let mut last_flush = Instant::now();
+            while let Some(header) = archive.read_header()? {
+                let entry = header.entry();
+                if entry.is_file() {
+                    batch.push(entry.filename.to_string_lossy().replace("\\", "/"));
+                }
+                archive = header.skip()?;
+                if batch.len() >= LISTING_FLUSH_ENTRIES || last_flush.elapsed() >= LISTING_FLUSH_INTERVAL {
+                    let names = filter_image_entries(std::mem::take(&mut batch));
+                    last_flush = Instant::now();
+                    if names.is_empty() {
+                        continue;
+                    }
+                    all.extend_from_slice(&names);
+                    // 受け取り側 (開いていたソース) が閉じられたら取得をやめる
+                    if tx.send(ListingUpdate::Append(names)).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            // 残りも送ってから名前順の一覧を送る (受け取り側は届いた一覧と比べて並べ直すかを決める)
+            let names = filter_image_entries(batch);
+            if !names.is_empty() {
+                all.extend_from_slice(&names);
+                if tx.send(ListingUpdate::Append(names)).is_err() {
+                    return Ok(());
+                }
+            }
+            let _ = tx.send(ListingUpdate::Complete(sort_entries(all)));
+            Ok(())
+        })();
+        if let Err(e) = result {
+            let _ = tx.send(ListingUpdate::Failed(e.to_string()));
+        }
+    })?;
+    Ok(rx)
+}
+
+/// 一覧が揃うか LISTING_WAIT が過ぎるまで待つ (取得済みの一覧, 続きを受け取るチャンネル)
+/// 画像エントリが 1 つも届いていない間は待ち続ける
+fn wait_for_listing(receiver: ListingReceiver) -> Result<(Vec<String>, Option<ListingReceiver>), Box<dyn std::error::Error>> {
+    let deadline = Instant::now() + LISTING_WAIT;
+    let mut file_names = Vec::new();
+    loop {
+        let update = if file_names.is_empty() {
+            receiver.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected)
+        } else {
+            receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        };
+        match update {
+            Ok(ListingUpdate::Append(names)) => file_names.extend(names),
+            Ok(ListingUpdate::Complete(names)) => return Ok((names, None)),
+            Ok(ListingUpdate::Failed(e)) => return Err(e.into()),
+            Err(mpsc::RecvTimeoutError::Timeout) => return Ok((file_names, Some(receiver))),
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Err("一覧の取得が中断されました".into()),
+        }
+    }
+}
+
+fn sevenz_password(password: Option<&str>) -> sevenz_rust::Password {
+    password.map(sevenz_rust::Password::from).unwrap_or_else(sevenz_rust::Password::empty)
+}
+
+fn open_rar<'a>(archive_path: &'a std::path::Path, password: Option<&'a str>) -> unrar::Archive<'a> {
+    match password {
+        Some(password) => unrar::Archive::with_password(archive_path, password.as_bytes()),
+        None => unrar::Archive::new(archive_path),
     }
 }
 
@@ -0,0 +1,84 @@
+use crate::image::archive::{ArchiveLoader, ExtractionMode};
+use crate::image::decoder::DecodedImage;
+use rayon::prelude::*;
+
+/// フォルダに入ったアーカイブ (巻) をつなげて 1 冊として扱うソース (アーカイブだけが入ったフォルダ)
+/// ページ名は「アーカイブのパス/エントリ名」とし、アーカイブの切り替わりを章の区切り (folder_start_indices) にする
+pub struct ArchiveBundle {
+    archives: Vec<ArchiveLoader>,
+    /// archives と同じ順のアーカイブのパス
+    archive_paths: Vec<String>,
+    file_names: Vec<String>,
+    /// ページ番号 -> (アーカイブの番号, アーカイブ内のページ番号)
+    pages: Vec<(usize, usize)>,
+}
+
+impl ArchiveBundle {
+    /// archive_paths は名前順に並べたもの。開けない・画像の無いアーカイブは飛ばし、1 冊も残らなければ None
+    pub fn open(archive_paths: &[String]) -> Option<Self> {
+        // 巻ごとの一覧の取得は互いに独立しているので並列に行う
+        let opened: Vec<(String, ArchiveLoader)> = archive_paths
+            .par_iter()
+            .filter_map(|path| match ArchiveLoader::open_complete(path) {
+                Ok(loader) if !loader.get_file_names().is_empty() => Some((path.clone(), loader)),
+                Ok(_) => None,
+                Err(e) => {
+                    println!("[Archive] 開けないため飛ばします: {}: {}", path, e);
+                    None
+                }
+            })
+            .collect();
+        if opened.is_empty() {
+            return None;
+        }
+
+        let mut archives = Vec::with_capacity(opened.len());
+        let mut archive_paths = Vec::with_capacity(opened.len());
+        let mut file_names = Vec::new();
+        let mut pages = Vec::new();
+        for (path, loader) in opened {
+            let archive = archives.len();
+            for (page, entry) in loader.get_file_names().iter().enumerate() {
+                file_names.push(std::path::Path::new(&path).join(entry).to_string_lossy().to_string());
+                pages.push((archive, page));
+            }
+            archives.push(loader);
+            archive_paths.push(path);
+        }
+        println!("[Archive] {} 冊をつなげて開きました ({} ページ)", archives.len(), file_names.len());
+        Some(Self { archives, archive_paths, file_names, pages })
+    }
+
+    pub fn get_file_names(&self) -> &[String] {
+        &self.file_names
+    }
+
+    /// ページを含むアーカイブのパス
+    pub fn archive_path(&self, index: usize) -> Option<&str> {
+        let &(archive, _) = self.pages.get(index)?;
+        self.archive_paths.get(archive).map(String::as_str)
+    }
+
+    pub fn set_password(&mut self, password: String) {
+        for archive in &mut self.archives {
+            archive.set_password(password.clone());
+        }
+    }
+
+    /// 展開方法を設定する (一括展開は巻ごとに、その巻のページを初めて読むときに行う)
+    pub fn set_extraction_mode(&mut self, mode: ExtractionMode) {
+        for archive in &mut self.archives {
+            archive.set_extraction_mode(mode);
+        }
+    }
+
+    pub fn load_image(
+        &mut self,
+        index: usize,
+        use_cpu_color_conversion: bool,
+        progress: &mut dyn FnMut(f32),
+    ) -> Result<DecodedImage, Box<dyn std::error::Error>> {
+        let &(archive, page) = self.pages.get(index).ok_or_else(|| format!("ページ {} はありません", index))?;
+        self.archives[archive].load_image(page, use_cpu_color_conversion, progress)
+    }
+}
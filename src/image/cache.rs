@@ -26,16 +26,209 @@ impl PixelData {
     }
 }
 
+/// 画像ファイルの情報 (情報パネル用。デコード時に集める)
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ImageMetadata {
+    /// ファイル形式 (JPEG / PNG など)
+    pub format: String,
+    /// ファイルサイズ (バイト。アーカイブ内のページは展開後のサイズ)
+    pub file_size: u64,
+    /// 1 チャンネルあたりのビット数
+    pub bit_depth: u8,
+    /// 色空間 (RGB / グレースケール / YCbCr など。EXIF の色空間と ICC プロファイルの有無を含む)
+    pub color_space: String,
+    /// カメラ (メーカー + 機種)
+    pub camera: Option<String>,
+    /// 撮影日時
+    pub date_taken: Option<String>,
+    /// 撮影条件 (シャッター速度・絞り・ISO 感度・焦点距離)
+    pub exposure: Option<String>,
+    /// EXIF の Orientation (2〜8 はデコード時に適用済み。無ければ 1)
+    pub orientation: u16,
+}
+
+#[derive(Debug)]
 pub struct DecodedImage {
     pub width: u32,
     pub height: u32,
     pub pixel_data: PixelData,
+    /// ローダーで事前回転した角度 (0 / 90 / 180 / 270、時計回り)
+    pub rotation: u32,
+    pub metadata: ImageMetadata,
 }
 
 impl DecodedImage {
     pub fn memory_size(&self) -> usize {
         self.pixel_data.len()
     }
+
+    /// 画素 (x, y) の RGBA 値。YCbCr は CPU 変換と同じ式 (ICT) で RGB に変換する
+    pub fn pixel_rgba(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let (x, y, width) = (x as usize, y as usize, self.width as usize);
+        match &self.pixel_data {
+            PixelData::Rgba8(data) => {
+                let i = (y * width + x) * 4;
+                data.get(i..i + 4).map(|px| [px[0], px[1], px[2], px[3]])
+            }
+            PixelData::Ycbcr { planes, subsampling, precision, y_is_signed, c_is_signed } => {
+                let (Some(y_plane), Some(cb_plane), Some(cr_plane)) = (planes.first(), planes.get(1), planes.get(2)) else {
+                    return None;
+                };
+                let scale = 1.0 / (((1u64 << *precision) - 1) as f32).max(1.0);
+                let (dx, dy) = (subsampling.0.max(1) as usize, subsampling.1.max(1) as usize);
+                let c_idx = (y / dy) * width.div_ceil(dx) + x / dx;
+
+                let y_val = *y_plane.get(y * width + x)? as f32 * scale;
+                let y_norm = if *y_is_signed { y_val + 0.5 } else { y_val };
+                let cb_val = *cb_plane.get(c_idx)? as f32 * scale;
+                let cr_val = *cr_plane.get(c_idx)? as f32 * scale;
+                let cb_norm = if *c_is_signed { cb_val } else { cb_val - 0.5 };
+                let cr_norm = if *c_is_signed { cr_val } else { cr_val - 0.5 };
+
+                let r = y_norm + 1.402 * cr_norm;
+                let g = y_norm - 0.34413 * cb_norm - 0.71414 * cr_norm;
+                let b = y_norm + 1.772 * cb_norm;
+                let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0) as u8;
+                Some([to_u8(r), to_u8(g), to_u8(b), 255])
+            }
+        }
+    }
+
+    /// 全画素を RGBA の画像にする (書き出し用。YCbCr は pixel_rgba と同じ式で変換する)
+    pub fn to_rgba_image(&self) -> Option<::image::RgbaImage> {
+        let data = match &self.pixel_data {
+            PixelData::Rgba8(data) => data.clone(),
+            PixelData::Ycbcr { .. } => (0..self.height)
+                .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+                .flat_map(|(x, y)| self.pixel_rgba(x, y).unwrap_or([0, 0, 0, 255]))
+                .collect(),
+        };
+        ::image::RgbaImage::from_raw(self.width, self.height, data)
+    }
+
+    /// 情報パネルに表示する行 (解像度は事前回転前の大きさ)
+    pub fn info_lines(&self) -> Vec<String> {
+        let meta = &self.metadata;
+        let (width, height) = if self.rotation % 180 == 90 { (self.height, self.width) } else { (self.width, self.height) };
+        let mut lines = Vec::new();
+        if !meta.format.is_empty() {
+            lines.push(format!("形式: {}", meta.format));
+        }
+        lines.push(format!("解像度: {} × {} ({:.1} MP)", width, height, width as f64 * height as f64 / 1_000_000.0));
+        if meta.bit_depth > 0 {
+            lines.push(format!("ビット深度: {} bit", meta.bit_depth));
+        }
+        if !meta.color_space.is_empty() {
+            lines.push(format!("色空間: {}", meta.color_space));
+        }
+        if meta.file_size > 0 {
+            let size = meta.file_size as f64;
+            let text = if size >= 1024.0 * 1024.0 {
+                format!("{:.2} MB", size / 1024.0 / 1024.0)
+            } else {
+                format!("{:.1} KB", size / 1024.0)
+            };
+            lines.push(format!("ファイルサイズ: {} ({} バイト)", text, meta.file_size));
+        }
+        if let Some(camera) = &meta.camera {
+            lines.push(format!("カメラ: {}", camera));
+        }
+        if let Some(date) = &meta.date_taken {
+            lines.push(format!("撮影日時: {}", date));
+        }
+        if let Some(exposure) = &meta.exposure {
+            lines.push(format!("撮影条件: {}", exposure));
+        }
+        if meta.orientation > 1 {
+            lines.push(format!("EXIF 回転: {} (適用済み)", orientation_name(meta.orientation)));
+        }
+        lines
+    }
+
+    /// 事前回転した画像上の座標を、回転前の元画像での座標に戻す
+    pub fn source_position(&self, x: u32, y: u32) -> (u32, u32) {
+        match self.rotation {
+            90 => (y, self.width.saturating_sub(1 + x)),
+            180 => (self.width.saturating_sub(1 + x), self.height.saturating_sub(1 + y)),
+            270 => (self.height.saturating_sub(1 + y), x),
+            _ => (x, y),
+        }
+    }
+
+    /// 画像全体の平均輝度 (0.0 ~ 1.0)。間引きサンプリングによる概算
+    pub fn mean_luminance(&self) -> f32 {
+        const MAX_SAMPLES: usize = 65536;
+        let pixel_count = (self.width as usize * self.height as usize).max(1);
+        let step = (pixel_count / MAX_SAMPLES).max(1);
+
+        match &self.pixel_data {
+            PixelData::Rgba8(data) => {
+                let (sum, count) = data
+                    .chunks_exact(4)
+                    .step_by(step)
+                    .fold((0.0f64, 0usize), |(sum, count), px| {
+                        let luma = 0.299 * px[0] as f64 + 0.587 * px[1] as f64 + 0.114 * px[2] as f64;
+                        (sum + luma, count + 1)
+                    });
+                if count == 0 { 0.0 } else { (sum / count as f64 / 255.0) as f32 }
+            }
+            PixelData::Ycbcr { planes, precision, y_is_signed, .. } => {
+                let Some(y_plane) = planes.first() else { return 0.0; };
+                let max_val = (((1u64 << *precision) - 1) as f64).max(1.0);
+                let bias = if *y_is_signed { (1u64 << precision.saturating_sub(1)) as f64 } else { 0.0 };
+                let (sum, count) = y_plane
+                    .iter()
+                    .step_by(step)
+                    .fold((0.0f64, 0usize), |(sum, count), &y| (sum + y as f64 + bias, count + 1));
+                if count == 0 { 0.0 } else { (sum / count as f64 / max_val).clamp(0.0, 1.0) as f32 }
+            }
+        }
+    }
+
+    /// 輝度ヒストグラム (256 ビン)。GPU で集計できない場合の CPU 版 (間引きサンプリング)
+    pub fn luminance_histogram(&self) -> [u32; 256] {
+        const MAX_SAMPLES: usize = 1 << 20;
+        let pixel_count = (self.width as usize * self.height as usize).max(1);
+        let step = (pixel_count / MAX_SAMPLES).max(1);
+        let mut histogram = [0u32; 256];
+
+        match &self.pixel_data {
+            PixelData::Rgba8(data) => {
+                for px in data.chunks_exact(4).step_by(step) {
+                    let luma = 0.299 * px[0] as f32 + 0.587 * px[1] as f32 + 0.114 * px[2] as f32;
+                    histogram[(luma + 0.5).min(255.0) as usize] += 1;
+                }
+            }
+            PixelData::Ycbcr { planes, precision, y_is_signed, .. } => {
+                let Some(y_plane) = planes.first() else { return histogram; };
+                let max_val = (((1u64 << *precision) - 1) as f32).max(1.0);
+                let bias = if *y_is_signed { (1u64 << precision.saturating_sub(1)) as f32 } else { 0.0 };
+                for &y in y_plane.iter().step_by(step) {
+                    let luma = ((y as f32 + bias) / max_val).clamp(0.0, 1.0);
+                    histogram[(luma * 255.0 + 0.5) as usize] += 1;
+                }
+            }
+        }
+        histogram
+    }
+}
+
+/// EXIF の Orientation の説明
+fn orientation_name(orientation: u16) -> &'static str {
+    match orientation {
+        2 => "左右反転",
+        3 => "180°",
+        4 => "上下反転",
+        5 => "左右反転 + 時計回りに 270°",
+        6 => "時計回りに 90°",
+        7 => "左右反転 + 時計回りに 90°",
+        8 => "反時計回りに 90°",
+        _ => "なし",
+    }
 }
 
 pub struct ImageCache {
@@ -1,28 +1,195 @@
-use image::{DynamicImage, GenericImageView};
+use exif::{In, Tag, Value};
+use image::metadata::Orientation;
+use image::{DynamicImage, GenericImageView, ImageDecoder, ImageReader};
+use rayon::prelude::*;
+use std::io::{BufRead, Cursor, Seek};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
-pub use crate::image::cache::{DecodedImage, PixelData};
+pub use crate::image::cache::{DecodedImage, ImageMetadata, PixelData};
+
+/// デコード処理 (色変換などの並列部分) に使うスレッドプール。実行中に作り直せるよう Rayon のグローバルプールとは分ける
+static DECODE_POOL: Mutex<Option<Arc<rayon::ThreadPool>>> = Mutex::new(None);
+
+/// 設定値からスレッド数を決める (0 = 自動: 論理 CPU 数)
+pub fn effective_workers(setting: usize) -> usize {
+    if setting > 0 {
+        setting
+    } else {
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+    }
+}
+
+/// デコード用スレッドプールを作り直す。実行中のデコードは古いプールで最後まで処理される
+pub fn set_decode_workers(setting: usize) {
+    let workers = effective_workers(setting);
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(workers)
+        .thread_name(|i| format!("hayate-decode-{}", i))
+        .build()
+    {
+        Ok(pool) => {
+            *DECODE_POOL.lock().unwrap() = Some(Arc::new(pool));
+            let mode = if setting == 0 { "自動" } else { "手動" };
+            println!("[設定] デコードスレッド数を {} ({}) に設定しました", workers, mode);
+        }
+        Err(e) => println!("[設定] デコード用スレッドプールの作成に失敗しました: {}", e),
+    }
+}
+
+/// デコード用プールの中で処理を実行する (プール未作成時はそのまま実行)
+fn with_decode_pool<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    let pool = DECODE_POOL.lock().unwrap().clone();
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+fn decode_jp2_in_pool(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImage, Box<dyn std::error::Error>> {
+    with_decode_pool(|| decode_jp2(data, use_cpu_color_conversion).map_err(|e| e.to_string())).map_err(Into::into)
+}
 
 pub fn decode_image<P: AsRef<Path>>(path: P, use_cpu_color_conversion: bool) -> Result<DecodedImage, Box<dyn std::error::Error>> {
     let path_ref = path.as_ref();
     let ext = path_ref.extension().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
     
-    if ext == "jp2" || ext == "j2k" {
+    let mut decoded = if ext == "jp2" || ext == "j2k" {
         let data = std::fs::read(path_ref)?;
-        return decode_jp2(&data, use_cpu_color_conversion);
-    }
- 
-    let img = image::open(path_ref)?;
-    Ok(process_dynamic_image(img))
+        decode_jp2_in_pool(&data, use_cpu_color_conversion)?
+    } else if ext == "heic" || ext == "heif" {
+        // HEIC/HEIF は image クレートが未対応のため WIC でデコードする (AVIF は image クレートで扱う)
+        let data = std::fs::read(path_ref)?;
+        decode_heif_with_exif(&data)?
+    } else {
+        decode_with_image_crate(ImageReader::open(path_ref)?)?
+    };
+    decoded.metadata.file_size = std::fs::metadata(path_ref).map(|m| m.len()).unwrap_or(0);
+    Ok(decoded)
 }
 
 pub fn _decode_image_from_memory(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImage, Box<dyn std::error::Error>> {
-    if data.len() > 8 && &data[0..8] == &[0x00, 0x00, 0x00, 0x0C, 0x6A, 0x50, 0x20, 0x20] {
-        return decode_jp2(data, use_cpu_color_conversion);
+    let mut decoded = if data.len() > 8 && &data[0..8] == &[0x00, 0x00, 0x00, 0x0C, 0x6A, 0x50, 0x20, 0x20] {
+        decode_jp2_in_pool(data, use_cpu_color_conversion)?
+    } else if crate::platform::heif::is_heif(data) {
+        decode_heif_with_exif(data)?
+    } else {
+        decode_with_image_crate(ImageReader::new(Cursor::new(data)).with_guessed_format()?)?
+    };
+    decoded.metadata.file_size = data.len() as u64;
+    Ok(decoded)
+}
+
+/// image クレートでデコードする。EXIF の Orientation は転送前にここで適用し、情報パネル用のメタデータを集める
+fn decode_with_image_crate<R: BufRead + Seek>(reader: ImageReader<R>) -> Result<DecodedImage, Box<dyn std::error::Error>> {
+    let format = reader.format();
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let exif = decoder.exif_metadata().ok().flatten().and_then(|raw| exif::Reader::new().read_raw(raw).ok());
+    let has_icc = decoder.icc_profile().ok().flatten().is_some();
+    let mut img = DynamicImage::from_decoder(decoder)?;
+    img.apply_orientation(orientation);
+
+    let color = img.color();
+    let mut metadata = ImageMetadata {
+        format: format.map(|f| format!("{:?}", f).to_uppercase()).unwrap_or_default(),
+        bit_depth: (color.bits_per_pixel() / color.channel_count().max(1) as u16) as u8,
+        orientation: orientation.to_exif() as u16,
+        ..Default::default()
+    };
+    let model = match (color.has_color(), color.has_alpha()) {
+        (true, true) => "RGBA",
+        (true, false) => "RGB",
+        (false, true) => "グレースケール + アルファ",
+        (false, false) => "グレースケール",
+    };
+    let exif_color_space = exif.as_ref().and_then(|exif| read_exif(exif, &mut metadata));
+    metadata.color_space = std::iter::once(model)
+        .chain(exif_color_space)
+        .chain(has_icc.then_some("ICC プロファイル付き"))
+        .collect::<Vec<_>>()
+        .join(" / ");
+
+    let (width, height) = img.dimensions();
+    let rgba = img.to_rgba8();
+    Ok(DecodedImage {
+        width,
+        height,
+        pixel_data: PixelData::Rgba8(rgba.into_raw()),
+        rotation: 0,
+        metadata,
+    })
+}
+
+/// HEIF を WIC でデコードし、EXIF からカメラ・撮影条件を読み取る
+/// HEIF の回転は irot ボックスで表すため、EXIF の Orientation は適用しない
+fn decode_heif_with_exif(data: &[u8]) -> Result<DecodedImage, Box<dyn std::error::Error>> {
+    let mut decoded = crate::platform::heif::decode_heif(data)?;
+    decoded.metadata.format = "HEIF".to_string();
+    if let Ok(exif) = exif::Reader::new().read_from_container(&mut Cursor::new(data)) {
+        if let Some(color_space) = read_exif(&exif, &mut decoded.metadata) {
+            decoded.metadata.color_space = color_space.to_string();
+        }
+    }
+    Ok(decoded)
+}
+
+fn exif_text(exif: &exif::Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(values) => values
+            .first()
+            .map(|v| String::from_utf8_lossy(v).trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string())
+            .filter(|s| !s.is_empty()),
+        _ => None,
+    }
+}
+
+fn exif_rational(exif: &exif::Exif, tag: Tag) -> Option<f64> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Rational(values) => values.first().map(|r| r.to_f64()).filter(|v| v.is_finite() && *v > 0.0),
+        _ => None,
+    }
+}
+
+/// EXIF からカメラ・撮影日時・撮影条件を読み取る。戻り値は EXIF に記録された色空間
+fn read_exif(exif: &exif::Exif, metadata: &mut ImageMetadata) -> Option<&'static str> {
+    let make = exif_text(exif, Tag::Make);
+    let model = exif_text(exif, Tag::Model);
+    metadata.camera = match (make, model) {
+        // 機種名にメーカー名が含まれていることが多い (NIKON CORPORATION / NIKON D850 など)
+        (Some(make), Some(model)) => {
+            let brand = make.split_whitespace().next().unwrap_or(&make).to_lowercase();
+            if model.to_lowercase().contains(&brand) { Some(model) } else { Some(format!("{} {}", make, model)) }
+        }
+        (make, model) => make.or(model),
+    };
+    // "2024:05:01 12:34:56" の日付部分を "2024/05/01" にする
+    metadata.date_taken = exif_text(exif, Tag::DateTimeOriginal)
+        .or_else(|| exif_text(exif, Tag::DateTime))
+        .map(|date| date.replacen(':', "/", 2));
+
+    let mut exposure = Vec::new();
+    if let Some(time) = exif_rational(exif, Tag::ExposureTime) {
+        exposure.push(if time < 1.0 { format!("1/{:.0} 秒", 1.0 / time) } else { format!("{} 秒", time) });
+    }
+    if let Some(f_number) = exif_rational(exif, Tag::FNumber) {
+        exposure.push(format!("F{:.1}", f_number));
+    }
+    if let Some(iso) = exif.get_field(Tag::PhotographicSensitivity, In::PRIMARY).and_then(|f| f.value.get_uint(0)) {
+        exposure.push(format!("ISO {}", iso));
+    }
+    if let Some(focal) = exif_rational(exif, Tag::FocalLength) {
+        exposure.push(format!("{:.0} mm", focal));
+    }
+    if !exposure.is_empty() {
+        metadata.exposure = Some(exposure.join(" / "));
+    }
+
+    match exif.get_field(Tag::ColorSpace, In::PRIMARY).and_then(|f| f.value.get_uint(0)) {
+        Some(1) => Some("sRGB"),
+        Some(0xFFFF) => Some("未調整 (Adobe RGB など)"),
+        _ => None,
     }
-    
-    let img = image::load_from_memory(data)?;
-    Ok(process_dynamic_image(img))
 }
 
 fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImage, Box<dyn std::error::Error>> {
@@ -37,6 +204,12 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
     let color_space = image.color_space();
     
     println!("[Decoder] Decoded. ColorSpace: {:?}", color_space);
+    let metadata = ImageMetadata {
+        format: "JPEG 2000".to_string(),
+        bit_depth: components.first().map_or(0, |c| c.precision() as u8),
+        color_space: format!("{:?} ({} コンポーネント)", color_space, components.len()),
+        ..Default::default()
+    };
  
     // OpenJPEG が既に RGB と判定している場合、または components が 3 未満の場合は通常パス（RGB）へ
     // SRGB, Gray, YUV などがある。RGB 変換済みなら YCbCr シェーダーを通さない
@@ -54,7 +227,7 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
         
         if dx_y == 1 && dy_y == 1 {
             println!("[Decoder] GPU YCbCr path. Subsampling: ({},{})", dx_c, dy_c);
-            let planes: Vec<Vec<i32>> = components.iter().map(|c| c.data().to_vec()).collect();
+            let planes: Vec<Vec<i32>> = components.par_iter().map(|c| c.data().to_vec()).collect();
             return Ok(DecodedImage {
                 width: c_y.width(),
                 height: c_y.height(),
@@ -65,6 +238,8 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
                     y_is_signed: c_y.is_signed(),
                     c_is_signed: c_cb.is_signed(),
                 },
+                rotation: 0,
+                metadata,
             });
         }
     } else if components.len() == 3 && !is_rgb_already && use_cpu_color_conversion {
@@ -82,7 +257,7 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
         let dx_c = (orig_width as f32 / c_cb.width() as f32).round() as u32;
         let dy_c = (orig_height as f32 / c_cb.height() as f32).round() as u32;
  
-        let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+        let mut rgba = vec![0u8; (width * height * 4) as usize];
         let y_data = c_y.data();
         let cb_data = c_cb.data();
         let cr_data = c_cr.data();
@@ -91,8 +266,10 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
         let y_is_signed = c_y.is_signed();
         let c_is_signed = c_cb.is_signed();
  
-        for y in 0..height {
-            for x in 0..width {
+        // 行単位で Rayon のワーカーに分配する (parallel_decoding_workers のデコード用プールを使用)
+        rgba.par_chunks_mut((width * 4) as usize).enumerate().for_each(|(y, row)| {
+            let y = y as u32;
+            for (x, pixel) in (0..width).zip(row.chunks_exact_mut(4)) {
                 let y_val = y_data[(y * width + x) as usize] as f32 * scale;
                 // DC offset for signed Y
                 let y_norm = if y_is_signed { y_val + 0.5 } else { y_val };
@@ -112,13 +289,13 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
                 let g = y_norm - 0.34413 * cb_norm - 0.71414 * cr_norm;
                 let b = y_norm + 1.772 * cb_norm;
  
-                rgba.push((r.clamp(0.0, 1.0) * 255.0) as u8);
-                rgba.push((g.clamp(0.0, 1.0) * 255.0) as u8);
-                rgba.push((b.clamp(0.0, 1.0) * 255.0) as u8);
-                rgba.push(255);
+                pixel[0] = (r.clamp(0.0, 1.0) * 255.0) as u8;
+                pixel[1] = (g.clamp(0.0, 1.0) * 255.0) as u8;
+                pixel[2] = (b.clamp(0.0, 1.0) * 255.0) as u8;
+                pixel[3] = 255;
             }
-        }
-        return Ok(DecodedImage { width, height, pixel_data: PixelData::Rgba8(rgba) });
+        });
+        return Ok(DecodedImage { width, height, pixel_data: PixelData::Rgba8(rgba), rotation: 0, metadata });
     }
 
     // Fallback: use get_pixels (RGB) if not standard 3-component YCbCr
@@ -129,17 +306,17 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
     // Note: get_pixels can return various formats, we need to handle them
     match rgb_image.data {
         jpeg2k::ImagePixelData::Rgb8(data) => {
-            let mut rgba = Vec::with_capacity(data.len() / 3 * 4);
-            for chunk in data.chunks_exact(3) {
-                rgba.push(chunk[0]);
-                rgba.push(chunk[1]);
-                rgba.push(chunk[2]);
-                rgba.push(255);
-            }
+            let mut rgba = vec![0u8; data.len() / 3 * 4];
+            rgba.par_chunks_mut(4).zip(data.par_chunks_exact(3)).for_each(|(dst, src)| {
+                dst[..3].copy_from_slice(src);
+                dst[3] = 255;
+            });
             Ok(DecodedImage {
                 width: rgb_image.width,
                 height: rgb_image.height,
                 pixel_data: PixelData::Rgba8(rgba),
+                rotation: 0,
+                metadata,
             })
         },
         jpeg2k::ImagePixelData::Rgba8(data) => {
@@ -147,18 +324,59 @@ fn decode_jp2(data: &[u8], use_cpu_color_conversion: bool) -> Result<DecodedImag
                 width: rgb_image.width,
                 height: rgb_image.height,
                 pixel_data: PixelData::Rgba8(data),
+                rotation: 0,
+                metadata,
             })
         },
         _ => Err(format!("Unsupported color format from OpenJPEG: {:?}", rgb_image.format).into()),
     }
 }
 
-fn process_dynamic_image(img: DynamicImage) -> DecodedImage {
-    let (width, height) = img.dimensions();
-    let rgba = img.to_rgba8();
-    DecodedImage {
-        width,
-        height,
-        pixel_data: PixelData::Rgba8(rgba.into_raw()),
+/// 幅 w・高さ h・1画素 channels 要素の平面を時計回りに rotation 度回転する
+fn rotate_plane<T: Copy + Default + Send + Sync>(src: &[T], w: usize, h: usize, channels: usize, rotation: u32) -> Vec<T> {
+    let (new_w, new_h) = if rotation % 180 == 90 { (h, w) } else { (w, h) };
+    let mut dst = vec![T::default(); new_w * new_h * channels];
+    if dst.is_empty() || src.len() < dst.len() {
+        return src.to_vec();
     }
+    dst.par_chunks_mut(new_w * channels).enumerate().for_each(|(ny, row)| {
+        for nx in 0..new_w {
+            // 回転後の (nx, ny) に対応する回転前の座標
+            let (x, y) = match rotation {
+                90 => (ny, h - 1 - nx),
+                180 => (w - 1 - nx, h - 1 - ny),
+                270 => (w - 1 - ny, nx),
+                _ => (nx, ny),
+            };
+            let src_idx = (y * w + x) * channels;
+            row[nx * channels..(nx + 1) * channels].copy_from_slice(&src[src_idx..src_idx + channels]);
+        }
+    });
+    dst
+}
+
+/// デコード済みページを事前回転する (縦置きモニター向け。描画側は回転なしの矩形描画のまま)
+/// YCbCr は各プレーンを回転し、90°/270° では色差の間引き方向を入れ替える
+pub fn rotate_decoded(image: DecodedImage, rotation: u32) -> DecodedImage {
+    let rotation = rotation % 360;
+    if rotation == 0 || rotation % 90 != 0 {
+        return image;
+    }
+    let (w, h) = (image.width as usize, image.height as usize);
+    let (width, height) = if rotation % 180 == 90 { (image.height, image.width) } else { (image.width, image.height) };
+    let pixel_data = with_decode_pool(|| match image.pixel_data {
+        PixelData::Rgba8(data) => PixelData::Rgba8(rotate_plane(&data, w, h, 4, rotation)),
+        PixelData::Ycbcr { planes, subsampling, precision, y_is_signed, c_is_signed } => {
+            let (dx, dy) = (subsampling.0.max(1) as usize, subsampling.1.max(1) as usize);
+            let (cw, ch) = (w.div_ceil(dx), h.div_ceil(dy));
+            let planes = planes
+                .par_iter()
+                .enumerate()
+                .map(|(i, plane)| if i == 0 { rotate_plane(plane, w, h, 1, rotation) } else { rotate_plane(plane, cw, ch, 1, rotation) })
+                .collect();
+            let subsampling = if rotation % 180 == 90 { (subsampling.1, subsampling.0) } else { subsampling };
+            PixelData::Ycbcr { planes, subsampling, precision, y_is_signed, c_is_signed }
+        }
+    });
+    DecodedImage { width, height, pixel_data, rotation, metadata: image.metadata }
 }
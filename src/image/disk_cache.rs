@@ -0,0 +1,167 @@
+use crate::image::cache::{DecodedImage, ImageMetadata, PixelData};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// キャッシュファイルの先頭の識別子 (形式を変えたら番号を上げて古いファイルを読まないようにする)
+const MAGIC: &[u8; 4] = b"HVC1";
+
+/// この回数書き込むごとに容量の上限を超えた分を削除する
+const TRIM_INTERVAL: usize = 16;
+
+/// キャッシュファイルのヘッダー (ハッシュの衝突に備えてキーも保存する)
+#[derive(Serialize, Deserialize)]
+struct EntryHeader {
+    key: String,
+    width: u32,
+    height: u32,
+    rotation: u32,
+    metadata: ImageMetadata,
+}
+
+/// デコード済みのページをセッションを跨いで保存するディスクキャッシュ
+/// 巨大な 7z などを開き直したときに、展開とデコードをやり直さずに済むようにする
+/// ページは QOI (可逆・高速) で保存し、YCbCr のまま GPU へ送るページは保存しない
+pub struct DiskCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    writes: AtomicUsize,
+}
+
+impl DiskCache {
+    /// 既定の保存先 (%LOCALAPPDATA%/HayateViewer/cache)
+    pub fn default_dir() -> PathBuf {
+        let base = std::env::var_os("LOCALAPPDATA").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+        base.join("HayateViewer").join("cache")
+    }
+
+    pub fn new(dir: PathBuf, max_mb: u64) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir, max_bytes: max_mb * 1024 * 1024, writes: AtomicUsize::new(0) })
+    }
+
+    /// キャッシュのキー (ソースのパス, ページのエントリ名, ソースの更新日時, 事前回転)
+    /// 一覧の取得中はページ番号がずれるため、ページはエントリ名で指す
+    pub fn key(path_key: &str, entry: &str, mtime: u64, rotation: u32) -> String {
+        format!("{}::{}::{}::{}", path_key, entry, mtime, rotation)
+    }
+
+    /// ソースの更新日時 (UNIX 時間の秒)。取得できなければ None (キャッシュを使わない)
+    pub fn source_mtime(path_key: &str) -> Option<u64> {
+        let modified = std::fs::metadata(path_key).and_then(|m| m.modified()).ok()?;
+        modified.duration_since(std::time::UNIX_EPOCH).ok().map(|d| d.as_secs())
+    }
+
+    fn file_path(&self, key: &str) -> PathBuf {
+        let hash = crc::Crc::<u64>::new(&crc::CRC_64_ECMA_182).checksum(key.as_bytes());
+        self.dir.join(format!("{:016x}.hvc", hash))
+    }
+
+    pub fn load(&self, key: &str) -> Option<DecodedImage> {
+        let path = self.file_path(key);
+        let bytes = std::fs::read(&path).ok()?;
+        let image = Self::parse(key, &bytes);
+        match image {
+            Some(_) => {
+                // 最近使ったものとして残すため、更新日時を今にする
+                if let Ok(file) = std::fs::File::options().append(true).open(&path) {
+                    let _ = file.set_modified(std::time::SystemTime::now());
+                }
+            }
+            None => {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+        image
+    }
+
+    fn parse(key: &str, bytes: &[u8]) -> Option<DecodedImage> {
+        let rest = bytes.strip_prefix(MAGIC)?;
+        let header_len = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+        let header: EntryHeader = serde_json::from_slice(rest.get(4..4 + header_len)?).ok()?;
+        if header.key != key {
+            return None;
+        }
+        let pixels = ::image::load_from_memory_with_format(rest.get(4 + header_len..)?, ::image::ImageFormat::Qoi).ok()?.into_rgba8();
+        if pixels.width() != header.width || pixels.height() != header.height {
+            return None;
+        }
+        Some(DecodedImage {
+            width: header.width,
+            height: header.height,
+            pixel_data: PixelData::Rgba8(pixels.into_raw()),
+            rotation: header.rotation,
+            metadata: header.metadata,
+        })
+    }
+
+    /// ページを保存する (書き込み途中のファイルを読まないよう、一時ファイルに書いてから置き換える)
+    pub fn store(&self, key: &str, image: &DecodedImage) -> Result<(), Box<dyn std::error::Error>> {
+        let PixelData::Rgba8(data) = &image.pixel_data else {
+            return Ok(());
+        };
+        let header = EntryHeader {
+            key: key.to_string(),
+            width: image.width,
+            height: image.height,
+            rotation: image.rotation,
+            metadata: image.metadata.clone(),
+        };
+        let header = serde_json::to_vec(&header)?;
+        let path = self.file_path(key);
+        let temp = path.with_extension("tmp");
+        {
+            let mut file = std::io::BufWriter::new(std::fs::File::create(&temp)?);
+            file.write_all(MAGIC)?;
+            file.write_all(&(header.len() as u32).to_le_bytes())?;
+            file.write_all(&header)?;
+            ::image::ImageEncoder::write_image(
+                ::image::codecs::qoi::QoiEncoder::new(&mut file),
+                data,
+                image.width,
+                image.height,
+                ::image::ExtendedColorType::Rgba8,
+            )?;
+            file.flush()?;
+        }
+        std::fs::rename(&temp, &path)?;
+
+        if self.writes.fetch_add(1, Ordering::Relaxed) % TRIM_INTERVAL == 0 {
+            self.trim();
+        }
+        Ok(())
+    }
+
+    /// 容量の上限を超えた分を、使われていない (更新日時が古い) ものから削除する
+    pub fn trim(&self) {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else { return; };
+        let mut files: Vec<(PathBuf, u64, std::time::SystemTime)> = dir
+            .flatten()
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok()?;
+                meta.is_file().then(|| (entry.path(), meta.len(), meta.modified().unwrap_or(std::time::UNIX_EPOCH)))
+            })
+            .collect();
+        let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+        if total <= self.max_bytes {
+            return;
+        }
+        files.sort_by_key(|(_, _, modified)| *modified);
+        let mut removed = 0;
+        for (path, len, _) in files {
+            if total <= self.max_bytes {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total -= len;
+                removed += 1;
+            }
+        }
+        println!("[ディスクキャッシュ] 上限を超えたため {} 件を削除しました (残り {}MB)", removed, total / (1024 * 1024));
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
@@ -0,0 +1,109 @@
+use crate::image::cache::DecodedImage;
+use crate::image::postprocess::CropMargins;
+use crate::render::PageTransform;
+use ::image::imageops::{self, FilterType};
+use ::image::{Rgba, RgbaImage};
+use std::path::Path;
+use std::sync::Arc;
+
+/// JPEG で書き出すときの品質
+const JPEG_QUALITY: u8 = 95;
+
+/// 書き出すページ (CPU キャッシュの画像と、表示時の回転・反転・トリミング)
+pub struct ExportPage {
+    pub image: Arc<DecodedImage>,
+    pub transform: PageTransform,
+    /// 画面に表示している範囲 (AppState::effective_crop。分割表示中は表示している半分)
+    pub crop: Option<CropMargins>,
+}
+
+/// トリミングの余白を削る (余白は事前回転後の画像、つまり CPU キャッシュの画像の向きで指定する)
+fn apply_crop(image: RgbaImage, crop: CropMargins) -> RgbaImage {
+    let crop = crop.clamped();
+    let (width, height) = (image.width(), image.height());
+    let left = (width as f32 * crop.left).round() as u32;
+    let top = (height as f32 * crop.top).round() as u32;
+    let right = width.saturating_sub((width as f32 * crop.right).round() as u32).max(left + 1).min(width);
+    let bottom = height.saturating_sub((height as f32 * crop.bottom).round() as u32).max(top + 1).min(height);
+    if left == 0 && top == 0 && right == width && bottom == height {
+        return image;
+    }
+    imageops::crop_imm(&image, left, top, right - left, bottom - top).to_image()
+}
+
+/// ページの向きを画面の見た目に合わせる (時計回りに回転してから反転)
+fn apply_transform(image: RgbaImage, transform: PageTransform) -> RgbaImage {
+    let mut image = rotate_clockwise(image, transform.rotation);
+    if transform.flip_h {
+        imageops::flip_horizontal_in_place(&mut image);
+    }
+    if transform.flip_v {
+        imageops::flip_vertical_in_place(&mut image);
+    }
+    image
+}
+
+fn rotate_clockwise(image: RgbaImage, degrees: u32) -> RgbaImage {
+    match degrees % 360 {
+        90 => imageops::rotate90(&image),
+        180 => imageops::rotate180(&image),
+        270 => imageops::rotate270(&image),
+        _ => image,
+    }
+}
+
+/// 表示中のページ (見開きなら画面の左から順) を 1 枚の画像にする
+/// トリミング・分割表示中のページは画面に表示している範囲だけを使う
+/// 縮小はせず、見開きで高さが違うページは高い方に合わせて拡大して横に並べる
+/// 縦置きモニター向けの事前回転 (DecodedImage.rotation) は、並べた後で元の向きに戻す
+pub fn compose(pages: &[ExportPage], background: [u8; 3]) -> Result<RgbaImage, String> {
+    let pre_rotation = pages.first().map_or(0, |page| page.image.rotation);
+    let mut images = Vec::with_capacity(pages.len());
+    for page in pages {
+        let mut image = page.image.to_rgba_image().ok_or("画像の変換に失敗しました")?;
+        if let Some(crop) = page.crop {
+            image = apply_crop(image, crop);
+        }
+        images.push(apply_transform(image, page.transform));
+    }
+    let composed = match images.len() {
+        0 => return Err("書き出すページがありません".to_string()),
+        1 => images.pop().unwrap(),
+        _ => {
+            let height = images.iter().map(|image| image.height()).max().unwrap_or(1);
+            let images: Vec<RgbaImage> = images
+                .into_iter()
+                .map(|image| {
+                    if image.height() == height {
+                        return image;
+                    }
+                    let width = (image.width() as u64 * height as u64 / image.height().max(1) as u64).max(1) as u32;
+                    imageops::resize(&image, width, height, FilterType::Lanczos3)
+                })
+                .collect();
+            let width = images.iter().map(|image| image.width()).sum();
+            let [r, g, b] = background;
+            let mut canvas = RgbaImage::from_pixel(width, height, Rgba([r, g, b, 255]));
+            let mut left = 0;
+            for image in &images {
+                imageops::replace(&mut canvas, image, left as i64, 0);
+                left += image.width();
+            }
+            canvas
+        }
+    };
+    Ok(rotate_clockwise(composed, (360 - pre_rotation % 360) % 360))
+}
+
+/// 拡張子に合わせて PNG / JPEG で保存する (JPEG は透過を捨てる)
+pub fn save(image: &RgbaImage, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+    if matches!(extension.as_str(), "jpg" | "jpeg") {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let rgb = ::image::DynamicImage::ImageRgba8(image.clone()).into_rgb8();
+        ::image::codecs::jpeg::JpegEncoder::new_with_quality(file, JPEG_QUALITY).encode_image(&rgb)?;
+    } else {
+        image.save_with_format(path, ::image::ImageFormat::Png)?;
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::image::ImageSource;
 use crate::image::cache::SharedImageCache;
+use crate::image::disk_cache::DiskCache;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
@@ -8,13 +10,26 @@ pub enum LoaderRequest {
         index: usize,
         priority: i32,
         use_cpu_color_conversion: bool,
+        /// 事前回転の角度 (0 / 90 / 180 / 270)
+        rotation: u32,
     },
     SetSource {
         source: ImageSource,
         path_key: String,
     },
+    /// 取得中だったアーカイブの一覧を差し替える (path_key が現在のソースと異なる場合は無視する)
+    SetFileNames {
+        path_key: String,
+        file_names: Vec<String>,
+    },
+    /// ディスクキャッシュの切り替え (None で使わない)
+    SetDiskCache(Option<Arc<DiskCache>>),
     Clear,
     ClearPrefetch,
+    /// 先読み (Priority 1 以降) の処理を一時停止する。表示中ページの読み込みは続ける
+    Pause,
+    /// 一時停止中に溜まった先読みを再開する
+    Resume,
 }
 
 #[derive(Debug)]
@@ -28,42 +43,142 @@ pub enum LoaderResponse {
 #[allow(dead_code)]
 pub enum UserEvent {
     PageLoaded(usize),
+    /// ページの展開の進み具合 (ページ番号, 0〜100%)。アーカイブの一括展開など時間のかかる展開の間だけ届く
+    PageLoadProgress(usize, u8),
     ToggleSpreadView,
     ToggleBindingDirection,
     ToggleFirstPageSingle,
+    ToggleChapterStartSingle,
+    /// 見開き時に横長のページを単独表示するかの切り替え
+    ToggleWidePageSingle,
+    /// 単ページ表示で横長のページを左右に分けて表示するかの切り替え (Y)
+    ToggleSplitWidePages,
+    /// 起動時に最後に読んだソースを開くかの切り替え
+    ToggleReopenLastOnStartup,
     ToggleCpuColorConversion,
+    /// ページごとの自動レベル補正の切り替え
+    ToggleAutoLevels,
     RotateResamplingCpu(isize),
     RotateResamplingGpu(isize),
     ToggleStatusBar,
     RotateRenderingBackend(isize),
     RotateDisplayMode(isize),
     SetMagnifierZoom(f32),
+    /// 表示中ソースの色調整を1段階変更する (+1 / -1)
+    StepBrightness(isize),
+    StepContrast(isize),
+    StepGamma(isize),
+    ResetColorAdjustment,
+    /// 夜間モード (色反転) の切り替え: オフ / オン (暗いページは除外) / オン (全ページ)
+    RotateNightMode(isize),
+    /// ブルーライト軽減の強さを 10% 単位で変更する (+1 / -1)
+    StepBlueLight(isize),
+    ToggleBlueLightSchedule,
+    /// 背景テーマを切り替える (ダーク -> ブラック -> ホワイト -> セピア)
+    RotateBackgroundTheme(isize),
+    /// 背景色をページの明るさに寄せる強さを 10% 単位で変更する (+1 / -1)
+    StepAdaptiveBackground(isize),
+    /// 表示中のページを 90° 回転する (+1 = 時計回り / -1 = 反時計回り)
+    RotatePage(isize),
+    /// 表示中のページを反転する (true = 左右 / false = 上下)
+    FlipPage(bool),
     LoadPath(String),
+    /// ホットフォルダに新しいファイルが置かれた
+    HotFolderItem(String),
+    /// クリップボード監視でコピーされた画像を保存した (保存先のパス)
+    ClipboardImage(String),
+    /// 表示中のフォルダでファイルが増減した (監視しているフォルダのパス)
+    SourceChanged(String),
     LoadHistory(usize),
     ClearHistory,
     DeleteHistoryItem(usize),
+    /// 表示中のページ (見開きなら 2 ページを並べたもの) を保存ダイアログで選んだファイルへ書き出す
+    ExportPage,
+    /// 書き出しの完了通知 (保存したパス / エラー)
+    PageExported(Result<String, String>),
+    /// 表示中のページをクリップボードへコピーした結果 (画像の幅, 高さ / エラー)
+    PageCopied(Result<(u32, u32), String>),
+    /// 共有する画像を一時ファイルに書き出した結果 ((一時ファイル, 共有シートの名前) / エラー)。共有 UI は UI スレッドで開く
+    ShareReady(Result<(std::path::PathBuf, String), String>),
+    /// ソースのタグ / メモを書き換える (パス, 新しい値)
+    SetSourceTags(String, Vec<String>),
+    SetSourceText(String, String),
+    /// しおりのページを開く / 削除する / 名前を変える (インデックスは settings.bookmarks)
+    LoadBookmark(usize),
+    DeleteBookmark(usize),
+    RenameBookmark(usize, String),
     SetMaxHistoryCount(usize),
     SetMaxCacheSize(u64),
     SetCpuPrefetchPages(usize),
     SetGpuPrefetchPages(usize),
+    /// GPU テクスチャの合計サイズ上限の変更 (MB、0 = ページ数のみで制限)
+    SetGpuCacheSize(u64),
+    /// デコード用スレッド数の変更 (0 = 自動)
+    SetDecodeWorkers(usize),
+    /// ズーム/パンのアニメーション時間の変更 (ミリ秒、0 = 無効)
+    SetViewAnimation(u32),
+    /// ページ送りのアニメーションの変更 ("none" / "slide" / "curl")
+    SetPageTurnAnimation(String),
+    /// サイズ変更中に直前のフレームを使い回すかの切り替え
+    ToggleFreezeFrameOnResize,
+    /// 事前回転の角度の変更 (0 / 90 / 180 / 270)
+    SetPreRotation(u32),
+    ToggleLeftHandedMode,
+    /// 瞬時切り替えモードの切り替え
+    ToggleInstantFlip,
+    /// YCbCr の色差の補間 (バイリニア / Catmull-Rom) の切り替え
+    ToggleChromaUpsampling,
+    /// 先読みの一時停止/再開を切り替える
+    TogglePrefetchPaused,
+    /// パス表示の最大文字数の変更 (0 = 省略しない)
+    SetPathDisplayLimit(usize),
+    /// ページの合わせ方の変更 (Q / 設定画面)
+    SetFitMode(crate::state::FitMode),
+    /// フォルダの画像の並び順の変更 (J / config.json)
+    SetSortOrder(crate::image::sort::SortOrder),
+    /// タイトルバーの書式の変更 (空なら既定に戻す)
+    SetTitleFormat(String),
+    /// ステータスバーの書式の変更 (空なら既定に戻す)
+    SetStatusbarFormat(String),
+    ToggleUpdateCheck,
+    /// config.json の更新日時が変わった (外部エディタでの編集検出)
+    ConfigFileChanged,
+    /// 更新確認の結果 (最新バージョン番号)
+    UpdateCheckCompleted(Result<String, String>),
+    /// OCR 完了通知 (path_key が現在のソースと異なる場合は破棄する)
+    OcrCompleted {
+        path_key: String,
+        index: usize,
+        result: Result<crate::platform::ocr::OcrPageResult, String>,
+    },
+    /// ルーペ用の縮小済み画像の作成完了 (generation が最新の要求と異なる場合は破棄する)
+    LoupePrescaled {
+        generation: u64,
+        image: Arc<crate::image::cache::DecodedImage>,
+    },
 }
 
 pub struct AsyncLoader {
     request_tx: mpsc::Sender<LoaderRequest>,
     response_rx: Mutex<mpsc::Receiver<LoaderResponse>>,
+    /// キャッシュの世代。invalidate で進め、デコード中に世代が変わった結果は破棄する
+    generation: Arc<AtomicU64>,
 }
 
 impl AsyncLoader {
     pub fn new(
         cache: SharedImageCache,
         proxy: winit::event_loop::EventLoopProxy<UserEvent>,
+        disk_cache: Option<Arc<DiskCache>>,
     ) -> Arc<Self> {
         let (req_tx, mut req_rx) = mpsc::channel(500);
         let (res_tx, res_rx) = mpsc::channel(500);
 
+        let generation = Arc::new(AtomicU64::new(0));
         let loader = Arc::new(Self {
             request_tx: req_tx,
             response_rx: Mutex::new(res_rx),
+            generation: Arc::clone(&generation),
         });
 
         let cache_clone = cache.clone();
@@ -72,7 +187,10 @@ impl AsyncLoader {
         tokio::spawn(async move {
             let mut current_source: Option<ImageSource> = None;
             let mut current_path_key: String = String::new();
+            let mut disk_cache = disk_cache;
             let mut queue = std::collections::VecDeque::new();
+            // 一時停止中も先読みリクエストはキューに保持し、再開時にまとめて処理する
+            let mut paused = false;
 
             loop {
                 // 1. 新しいリクエストを全てキューに取り込む
@@ -90,14 +208,34 @@ impl AsyncLoader {
                             current_path_key = path_key;
                             queue.clear();
                         }
+                        LoaderRequest::SetDiskCache(cache) => {
+                            disk_cache = cache;
+                        }
+                        LoaderRequest::SetFileNames { path_key, file_names } => {
+                            if let Some(source) = current_source.as_mut().filter(|_| path_key == current_path_key) {
+                                source.set_file_names(file_names);
+                            }
+                        }
+                        LoaderRequest::Pause => {
+                            println!("[先読み] 一時停止しました");
+                            paused = true;
+                        }
+                        LoaderRequest::Resume => {
+                            println!("[先読み] 再開しました (待機中 {} 件)", queue.len());
+                            paused = false;
+                        }
                         _ => {
                             queue.push_back(req);
                         }
                     }
                 }
 
-                // 2. キューが空なら次のメッセージを待機
-                if queue.is_empty() {
+                // 2. 処理できるリクエストが無ければ次のメッセージを待機
+                // (一時停止中は表示要求 Priority 0 のみ処理する)
+                let has_display_request = queue
+                    .iter()
+                    .any(|r| matches!(r, LoaderRequest::Load { priority: 0, .. }));
+                if queue.is_empty() || (paused && !has_display_request) {
                     match req_rx.recv().await {
                         Some(req) => match req {
                             LoaderRequest::Clear => {
@@ -114,7 +252,31 @@ impl AsyncLoader {
                                 queue.clear();
                                 continue;
                             }
-                            _ => queue.push_back(req),
+                            LoaderRequest::SetDiskCache(cache) => {
+                                disk_cache = cache;
+                                continue;
+                            }
+                            LoaderRequest::SetFileNames { path_key, file_names } => {
+                                if let Some(source) = current_source.as_mut().filter(|_| path_key == current_path_key) {
+                                    source.set_file_names(file_names);
+                                }
+                                continue;
+                            }
+                            LoaderRequest::Pause => {
+                                println!("[先読み] 一時停止しました");
+                                paused = true;
+                                continue;
+                            }
+                            LoaderRequest::Resume => {
+                                println!("[先読み] 再開しました (待機中 {} 件)", queue.len());
+                                paused = false;
+                                continue;
+                            }
+                            _ => {
+                                // 一時停止中の判定をやり直すため、先頭に戻って取り込む
+                                queue.push_back(req);
+                                continue;
+                            }
                         },
                         None => break, // チャンネルクローズ
                     }
@@ -136,13 +298,15 @@ impl AsyncLoader {
                         index,
                         priority,
                         use_cpu_color_conversion,
+                        rotation,
                     } => {
                         if let Some(ref mut _source) = current_source {
                             let key = format!("{}::{}", current_path_key, index);
 
                             let already_cached = {
                                 let mut c = cache_clone.lock().unwrap();
-                                c.get(&key).is_some()
+                                // 回転設定が変わった後の古い画像は読み直す
+                                c.get(&key).is_some_and(|img| img.rotation == rotation)
                             };
 
                             if !already_cached {
@@ -150,14 +314,40 @@ impl AsyncLoader {
                                     "[読み込み] デコード中: インデックス {} (優先度 {})...",
                                     index, priority
                                 );
+                                let started_generation = generation.load(Ordering::Acquire);
                                 // 重い処理（特に7z一括展開）をスレッドプールに逃がす
                                 let mut source_for_task = current_source.take().unwrap();
-                                let (res, returned_source) =
+                                let progress_proxy = event_proxy.clone();
+                                let disk = disk_cache.clone().and_then(|disk| {
+                                    let mtime = disk_cache_mtime(&source_for_task, &current_path_key, index)?;
+                                    let entry = source_for_task.file_names().get(index)?;
+                                    Some((disk, DiskCache::key(&current_path_key, entry, mtime, rotation)))
+                                });
+                                let disk_for_task = disk.clone();
+                                let (res, from_disk, returned_source) =
                                     tokio::task::spawn_blocking(move || {
-                                        let r = source_for_task
-                                            .load_image(index, use_cpu_color_conversion)
-                                            .map_err(|e| e.to_string());
-                                        (r, source_for_task)
+                                        // 前のセッションで保存したページがあれば展開・デコードを省く
+                                        if let Some(image) = disk_for_task.as_ref().and_then(|(disk, key)| disk.load(key)) {
+                                            return (Ok(image), true, source_for_task);
+                                        }
+                                        // 展開の進み具合は 1% 進むごとに通知する (プレースホルダーの進捗表示用)
+                                        let mut last_pct = 0u8;
+                                        let mut report = |fraction: f32| {
+                                            let pct = (fraction.clamp(0.0, 1.0) * 100.0) as u8;
+                                            if pct > last_pct {
+                                                last_pct = pct;
+                                                let _ = progress_proxy.send_event(UserEvent::PageLoadProgress(index, pct));
+                                            }
+                                        };
+                                        // 壊れた画像でデコーダーがパニックしても、そのページの読み込みエラーとして扱う
+                                        let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                                            source_for_task
+                                                .load_image(index, use_cpu_color_conversion, &mut report)
+                                                .map(|img| crate::image::decoder::rotate_decoded(img, rotation))
+                                                .map_err(|e| e.to_string())
+                                        }))
+                                        .unwrap_or_else(|_| Err("デコード中にパニックが発生しました".to_string()));
+                                        (r, false, source_for_task)
                                     })
                                     .await
                                     .unwrap();
@@ -165,10 +355,26 @@ impl AsyncLoader {
                                 current_source = Some(returned_source);
 
                                 match res {
+                                    // 再読み込みなどでキャッシュが無効化された後に終わったデコードは、
+                                    // 置き換え前のファイルの内容かもしれないので同じキーで登録しない
+                                    Ok(_) if generation.load(Ordering::Acquire) != started_generation => {
+                                        println!("[読み込み] 無効化されたため破棄: インデックス {}", index);
+                                    }
                                     Ok(decoded) => {
+                                        let decoded = Arc::new(decoded);
                                         {
                                             let mut c = cache_clone.lock().unwrap();
-                                            c.insert(key.clone(), Arc::new(decoded));
+                                            c.insert(key.clone(), decoded.clone());
+                                        }
+                                        if from_disk {
+                                            println!("[ディスクキャッシュ] 読み込み: インデックス {}", index);
+                                        } else if let Some((disk, disk_key)) = disk {
+                                            // 書き込みは次のページの読み込みを待たせないよう別のスレッドで行う
+                                            tokio::task::spawn_blocking(move || {
+                                                if let Err(e) = disk.store(&disk_key, &decoded) {
+                                                    println!("[ディスクキャッシュ] 保存に失敗しました (インデックス {}): {}", index, e);
+                                                }
+                                            });
                                         }
                                         let _ = res_tx.send(LoaderResponse::Loaded { index }).await;
                                         let _ =
@@ -196,6 +402,12 @@ impl AsyncLoader {
         loader
     }
 
+    /// デコード中の結果をキャッシュに登録させないようにする (ディスク上のファイルの再読み込み用)
+    /// 新しいソースの SetSource より前に呼ぶ
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
     pub async fn send_request(&self, req: LoaderRequest) {
         let _ = self.request_tx.send(req).await;
     }
@@ -209,3 +421,14 @@ impl AsyncLoader {
         self.request_tx.clone()
     }
 }
+
+/// ディスクキャッシュに保存するソースの更新日時 (展開に時間のかかるアーカイブのみ)
+/// フォルダの画像は直接デコードした方が速く、PDF は描画の解像度が設定で変わるため保存しない
+/// つなげたアーカイブ (フォルダ) は巻を上書きしてもフォルダの更新日時が変わらないため、ページを含む巻の更新日時を使う
+fn disk_cache_mtime(source: &ImageSource, path_key: &str, index: usize) -> Option<u64> {
+    match source {
+        ImageSource::Archive(_) => DiskCache::source_mtime(path_key),
+        ImageSource::Bundle(bundle) => DiskCache::source_mtime(bundle.archive_path(index)?),
+        _ => None,
+    }
+}
@@ -1,15 +1,25 @@
 pub mod decoder;
 pub mod archive;
+pub mod bundle;
 pub mod cache;
+pub mod disk_cache;
+pub mod export;
 pub mod loader;
+pub mod postprocess;
+pub mod sort;
 
 use crate::image::archive::ArchiveLoader;
+use crate::image::bundle::ArchiveBundle;
 use crate::image::decoder::DecodedImage;
+use crate::platform::pdf::PdfSource;
 use walkdir::WalkDir;
 
 pub enum ImageSource {
     Files(Vec<String>),
     Archive(ArchiveLoader),
+    /// アーカイブだけが入ったフォルダ (巻をつなげて 1 冊として扱う)
+    Bundle(ArchiveBundle),
+    Pdf(PdfSource),
 }
 
 impl ImageSource {
@@ -17,42 +27,108 @@ impl ImageSource {
         match self {
             Self::Files(f) => f.len(),
             Self::Archive(a) => a.get_file_names().len(),
+            Self::Bundle(b) => b.get_file_names().len(),
+            Self::Pdf(p) => p.get_file_names().len(),
         }
     }
 
-    pub fn load_image(&mut self, index: usize, use_cpu_color_conversion: bool) -> Result<DecodedImage, Box<dyn std::error::Error>> {
+    /// ファイルパス (アーカイブの場合はエントリ名、PDF の場合はページ名) の一覧
+    pub fn file_names(&self) -> &[String] {
+        match self {
+            Self::Files(f) => f,
+            Self::Archive(a) => a.get_file_names(),
+            Self::Bundle(b) => b.get_file_names(),
+            Self::Pdf(p) => p.get_file_names(),
+        }
+    }
+
+    /// バックグラウンドで取得中の一覧の続きを受け取るチャンネルを取り出す (巨大なアーカイブのみ)
+    pub fn take_listing(&mut self) -> Option<archive::ListingReceiver> {
+        match self {
+            Self::Archive(a) => a.take_listing(),
+            _ => None,
+        }
+    }
+
+    /// 取得中の一覧の続き・並べ直した一覧を反映する (アーカイブのみ)
+    pub fn set_file_names(&mut self, file_names: Vec<String>) {
+        if let Self::Archive(a) = self {
+            a.set_file_names(file_names);
+        }
+    }
+
+    /// progress には展開の進み具合 (0.0〜1.0) を渡す (アーカイブの展開でだけ呼ばれる)
+    pub fn load_image(
+        &mut self,
+        index: usize,
+        use_cpu_color_conversion: bool,
+        progress: &mut dyn FnMut(f32),
+    ) -> Result<DecodedImage, Box<dyn std::error::Error>> {
         match self {
             Self::Files(f) => {
                 let decoded = decoder::decode_image(&f[index], use_cpu_color_conversion)?;
                 Ok(decoded)
             }
             Self::Archive(a) => {
-                a.load_image(index, use_cpu_color_conversion)
+                a.load_image(index, use_cpu_color_conversion, progress)
+            }
+            Self::Bundle(b) => {
+                b.load_image(index, use_cpu_color_conversion, progress)
+            }
+            Self::Pdf(p) => {
+                p.load_image(index, use_cpu_color_conversion)
             }
         }
     }
 }
 
+/// フォルダを開いたときに画像として読み込む拡張子
+pub const IMAGE_EXTENSIONS: [&str; 9] = ["jpg", "jpeg", "png", "webp", "bmp", "jp2", "avif", "heic", "heif"];
+
+/// アーカイブとして開く拡張子
+pub const ARCHIVE_EXTENSIONS: [&str; 5] = ["zip", "7z", "cbz", "rar", "cbr"];
+
+/// PDF として開く拡張子
+pub const PDF_EXTENSION: &str = "pdf";
+
+fn has_extension(path: &std::path::Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+}
+
 pub fn get_image_source(path: &str) -> Option<ImageSource> {
     let path_buf = std::path::Path::new(path);
     if path_buf.is_dir() {
         let mut files: Vec<String> = Vec::new();
-        let supported = ["jpg", "jpeg", "png", "webp", "bmp", "jp2", "avif"];
+        let mut archives: Vec<String> = Vec::new();
         for entry in WalkDir::new(path).max_depth(1).into_iter().filter_map(|e| e.ok()) {
-            if let Some(ext) = entry.path().extension().and_then(|s| s.to_str()) {
-                if supported.contains(&ext.to_lowercase().as_str()) {
-                    files.push(entry.path().to_string_lossy().to_string());
-                }
+            if has_extension(entry.path(), &IMAGE_EXTENSIONS) {
+                files.push(entry.path().to_string_lossy().to_string());
+            } else if entry.file_type().is_file() && has_extension(entry.path(), &ARCHIVE_EXTENSIONS) {
+                archives.push(entry.path().to_string_lossy().to_string());
             }
         }
-        files.sort_by(|a, b| natord::compare(a, b));
+        // 画像が無くアーカイブだけが入ったフォルダは、アーカイブを名前順につなげて 1 冊として開く
+        if files.is_empty() && !archives.is_empty() {
+            archives.sort_by(|a, b| natord::compare(a, b));
+            if let Some(bundle) = ArchiveBundle::open(&archives) {
+                return Some(ImageSource::Bundle(bundle));
+            }
+        }
+        sort::sort_files(&mut files);
         return Some(ImageSource::Files(files));
     } else if let Some(ext) = path_buf.extension().and_then(|s| s.to_str()) {
         let ext_lower = ext.to_lowercase();
-        if ext_lower == "zip" || ext_lower == "7z" || ext_lower == "cbz" || ext_lower == "rar" || ext_lower == "cbr" {
+        if ARCHIVE_EXTENSIONS.contains(&ext_lower.as_str()) {
             if let Ok(loader) = ArchiveLoader::open(path) {
                 return Some(ImageSource::Archive(loader));
             }
+        } else if ext_lower == PDF_EXTENSION {
+            match PdfSource::open(path) {
+                Ok(pdf) => return Some(ImageSource::Pdf(pdf)),
+                Err(e) => println!("[PDF] 開けませんでした: {}: {}", path, e),
+            }
         } else {
             // 単一ファイル
             return Some(ImageSource::Files(vec![path.to_string()]));
@@ -60,3 +136,25 @@ pub fn get_image_source(path: &str) -> Option<ImageSource> {
     }
     None
 }
+
+/// 起動引数・ドロップなどで指定されたパスを開くソースに変換する (ソースのパス, ソース, 開くページ)
+/// 単体の画像ファイルは親フォルダ全体をソースにして、その画像のページから開く (前後の画像へページ送りできるように)
+pub fn resolve_open_path(path: &str) -> Option<(String, ImageSource, Option<usize>)> {
+    let path_buf = std::path::Path::new(path);
+    let is_image = path_buf.is_file()
+        && path_buf
+            .extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+    if is_image {
+        if let Some(parent) = path_buf.parent().filter(|p| !p.as_os_str().is_empty()) {
+            let folder = parent.to_string_lossy().to_string();
+            if let Some(source) = get_image_source(&folder) {
+                if let Some(index) = source.file_names().iter().position(|f| std::path::Path::new(f) == path_buf) {
+                    return Some((folder, source, Some(index)));
+                }
+            }
+        }
+    }
+    get_image_source(path).map(|source| (path.to_string(), source, None))
+}
@@ -0,0 +1,143 @@
+use crate::image::decoder::DecodedImage;
+use crate::render::PageTransform;
+use serde::{Deserialize, Serialize};
+use windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F;
+
+/// 余白判定で調べる格子の細かさ (縦横それぞれ最大この数の画素を調べる)
+const GRID: u32 = 256;
+/// 余白の色との差 (輝度 0.0〜1.0) がこれを超える画素を「絵がある」とみなす
+const INK_THRESHOLD: f32 = 0.15;
+/// 行・列のうち絵がある画素がこの割合未満なら余白とみなす (スキャンのごみ・ノイズを無視する)
+const INK_RATIO: f32 = 0.005;
+/// 1 辺から削る上限
+const MAX_MARGIN: f32 = 0.3;
+/// 削った後に残る幅・高さの下限 (ほぼ白紙のページを潰さないように)
+const MIN_VISIBLE: f32 = 0.4;
+
+/// トリミング量 (テクスチャ = 事前回転後の画像の各辺から削る割合 0.0〜0.5)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CropMargins {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl CropMargins {
+    pub fn is_empty(&self) -> bool {
+        self.left <= 0.0 && self.top <= 0.0 && self.right <= 0.0 && self.bottom <= 0.0
+    }
+
+    /// 各辺を 0.0〜0.5 に収め、残る範囲が無くならないようにする
+    pub fn clamped(self) -> Self {
+        let clamp = |v: f32| if v.is_finite() { v.clamp(0.0, 0.5) } else { 0.0 };
+        let (mut left, mut right) = (clamp(self.left), clamp(self.right));
+        let (mut top, mut bottom) = (clamp(self.top), clamp(self.bottom));
+        if left + right > 0.95 {
+            let scale = 0.95 / (left + right);
+            left *= scale;
+            right *= scale;
+        }
+        if top + bottom > 0.95 {
+            let scale = 0.95 / (top + bottom);
+            top *= scale;
+            bottom *= scale;
+        }
+        Self { left, top, right, bottom }
+    }
+
+    /// 表示矩形 (0.0〜1.0) 上で残す範囲。回転・反転したページでは削る辺が入れ替わる
+    pub fn visible_rect(&self, transform: PageTransform) -> D2D_RECT_F {
+        let unit = D2D_RECT_F { left: 0.0, top: 0.0, right: 1.0, bottom: 1.0 };
+        transform.map_rect(self.left, self.top, 1.0 - self.right, 1.0 - self.bottom, &unit)
+    }
+
+    /// 表示矩形上で残す範囲 (visible_rect の逆変換) からトリミング量を作る
+    pub fn from_visible_rect(rect: &D2D_RECT_F, transform: PageTransform) -> Self {
+        Self::unclamped_from_visible_rect(rect, transform).clamped()
+    }
+
+    fn unclamped_from_visible_rect(rect: &D2D_RECT_F, transform: PageTransform) -> Self {
+        let [u0, v0] = transform.source_uv(rect.left, rect.top);
+        let [u1, v1] = transform.source_uv(rect.right, rect.bottom);
+        Self {
+            left: u0.min(u1),
+            top: v0.min(v1),
+            right: 1.0 - u0.max(u1),
+            bottom: 1.0 - v0.max(v1),
+        }
+    }
+
+    /// 表示矩形上で残す範囲の左半分 / 右半分だけを残すトリミング量 (横長ページの分割表示)
+    /// 片側を 0.5 以上削るため clamped は通さない
+    pub fn half(&self, transform: PageTransform, left: bool) -> Self {
+        let visible = self.visible_rect(transform);
+        let middle = (visible.left + visible.right) / 2.0;
+        let rect = if left { D2D_RECT_F { right: middle, ..visible } } else { D2D_RECT_F { left: middle, ..visible } };
+        Self::unclamped_from_visible_rect(&rect, transform)
+    }
+
+    /// ステータス表示用の文字列
+    pub fn describe(&self) -> String {
+        format!(
+            "左 {:.0}% / 上 {:.0}% / 右 {:.0}% / 下 {:.0}%",
+            self.left * 100.0,
+            self.top * 100.0,
+            self.right * 100.0,
+            self.bottom * 100.0
+        )
+    }
+}
+
+/// スキャン画像の外周の白/黒の余白を検出する (転送前に CPU で 1 回だけ行う)
+/// 外周の色が白にも黒にも近くない (カラーの絵が端まである) ページや、削ると大半が消えるページは削らない
+pub fn detect_margins(image: &DecodedImage) -> CropMargins {
+    if image.width < 16 || image.height < 16 {
+        return CropMargins::default();
+    }
+    let (cols, rows) = (image.width.min(GRID), image.height.min(GRID));
+    let luminance: Vec<f32> = (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| (row, col)))
+        .map(|(row, col)| {
+            let x = (col as u64 * (image.width - 1) as u64 / (cols - 1) as u64) as u32;
+            let y = (row as u64 * (image.height - 1) as u64 / (rows - 1) as u64) as u32;
+            image
+                .pixel_rgba(x, y)
+                .map(|px| (0.299 * px[0] as f32 + 0.587 * px[1] as f32 + 0.114 * px[2] as f32) / 255.0)
+                .unwrap_or(0.0)
+        })
+        .collect();
+    let at = |row: u32, col: u32| luminance[(row * cols + col) as usize];
+
+    // 外周 1 周の平均から余白の色を決める
+    let ring: Vec<f32> = (0..cols)
+        .flat_map(|col| [at(0, col), at(rows - 1, col)])
+        .chain((0..rows).flat_map(|row| [at(row, 0), at(row, cols - 1)]))
+        .collect();
+    let border = ring.iter().sum::<f32>() / ring.len() as f32;
+    if (0.2..=0.8).contains(&border) {
+        return CropMargins::default();
+    }
+
+    let is_ink = |v: f32| (v - border).abs() > INK_THRESHOLD;
+    let blank_row = |row: u32| ((0..cols).filter(|&col| is_ink(at(row, col))).count() as f32) < cols as f32 * INK_RATIO;
+    let blank_col = |col: u32| ((0..rows).filter(|&row| is_ink(at(row, col))).count() as f32) < rows as f32 * INK_RATIO;
+
+    // 外側から絵に当たるまでの余白の行/列数 (1 格子分は残して絵の端を削らないようにする)
+    let margin = |blank: usize, total: u32| (blank.saturating_sub(1) as f32 / total as f32).min(MAX_MARGIN);
+    let blank_top = (0..rows).take_while(|&row| blank_row(row)).count();
+    if blank_top == rows as usize {
+        // 白紙 (真っ黒) のページ
+        return CropMargins::default();
+    }
+    let top = margin(blank_top, rows);
+    let bottom = margin((0..rows).rev().take_while(|&row| blank_row(row)).count(), rows);
+    let left = margin((0..cols).take_while(|&col| blank_col(col)).count(), cols);
+    let right = margin((0..cols).rev().take_while(|&col| blank_col(col)).count(), cols);
+
+    if 1.0 - left - right < MIN_VISIBLE || 1.0 - top - bottom < MIN_VISIBLE {
+        return CropMargins::default();
+    }
+    CropMargins { left, top, right, bottom }
+}
@@ -0,0 +1,108 @@
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// フォルダの画像の並び順 (アーカイブの中のページは常に名前順)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// 名前の自然順 (数字は数値として比べる)
+    Name,
+    /// 更新日時の古い順
+    Modified,
+    /// ファイルサイズの小さい順
+    Size,
+    /// シャッフル (起動中は同じフォルダを何度開いても同じ順になる)
+    Shuffle,
+}
+
+impl SortOrder {
+    const ALL: [SortOrder; 4] = [Self::Name, Self::Modified, Self::Size, Self::Shuffle];
+
+    /// 設定値 ("name" / "modified" / "size" / "shuffle") から変換する。不明な値は名前順とみなす
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "modified" => Self::Modified,
+            "size" => Self::Size,
+            "shuffle" => Self::Shuffle,
+            _ => Self::Name,
+        }
+    }
+
+    pub fn setting_name(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Modified => "modified",
+            Self::Size => "size",
+            Self::Shuffle => "shuffle",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "名前順",
+            Self::Modified => "更新日時順",
+            Self::Size => "サイズ順",
+            Self::Shuffle => "シャッフル",
+        }
+    }
+
+    /// 次 (direction < 0 なら前) の並び順
+    pub fn cycle(self, direction: isize) -> Self {
+        let current = Self::ALL.iter().position(|&o| o == self).unwrap_or(0) as isize;
+        Self::ALL[(current + direction).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
+/// get_image_source が使う並び順 (設定の file_sort_order から set_sort_order で反映する)
+static SORT_ORDER: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_sort_order(order: SortOrder) {
+    let index = SortOrder::ALL.iter().position(|&o| o == order).unwrap_or(0);
+    SORT_ORDER.store(index as u8, Ordering::Relaxed);
+}
+
+pub fn sort_order() -> SortOrder {
+    SortOrder::ALL.get(SORT_ORDER.load(Ordering::Relaxed) as usize).copied().unwrap_or(SortOrder::Name)
+}
+
+/// シャッフルの種 (起動ごとに 1 回だけ決める)
+fn session_seed() -> u64 {
+    static SEED: OnceLock<u64> = OnceLock::new();
+    *SEED.get_or_init(|| {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        std::time::SystemTime::now().hash(&mut hasher);
+        std::process::id().hash(&mut hasher);
+        hasher.finish()
+    })
+}
+
+/// フォルダの画像を現在の並び順で並べる (同じ値のものは名前順)
+/// シャッフルはパスと起動ごとの種から順序を決めるため、起動中は一覧を作り直しても (ファイルが増減しても) 他の画像の前後は変わらず、
+/// 先読みや履歴のページ番号がずれない
+pub fn sort_files(files: &mut Vec<String>) {
+    let order = sort_order();
+    if order == SortOrder::Name {
+        files.sort_by(|a, b| natord::compare(a, b));
+        return;
+    }
+    let seed = session_seed();
+    let key = |path: &str| -> u64 {
+        match order {
+            SortOrder::Modified => std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_millis() as u64),
+            SortOrder::Size => std::fs::metadata(path).map_or(0, |m| m.len()),
+            _ => {
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                seed.hash(&mut hasher);
+                path.hash(&mut hasher);
+                hasher.finish()
+            }
+        }
+    };
+    let mut keyed: Vec<(u64, String)> = files.drain(..).map(|f| (key(&f), f)).collect();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| natord::compare(&a.1, &b.1)));
+    files.extend(keyed.into_iter().map(|(_, f)| f));
+}
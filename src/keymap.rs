@@ -0,0 +1,77 @@
+use std::collections::{BTreeMap, HashMap};
+use winit::keyboard::Key;
+
+/// キー割り当てから呼び出せる操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    NextPage,
+    PrevPage,
+    FirstPage,
+    LastPage,
+    /// 表示中のページをファイルに書き出す
+    ExportPage,
+}
+
+impl KeyAction {
+    /// config.json に記述する操作名から変換する
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "next_page" => Some(Self::NextPage),
+            "prev_page" => Some(Self::PrevPage),
+            "first_page" => Some(Self::FirstPage),
+            "last_page" => Some(Self::LastPage),
+            "export_page" => Some(Self::ExportPage),
+            _ => None,
+        }
+    }
+}
+
+/// winit のキーを config.json 上のキー名に変換する
+/// 名前付きキーは winit の列挙子名 ("MediaTrackNext", "PageDown" など)、文字キーは小文字の文字そのもの
+pub fn key_to_name(key: &Key) -> Option<String> {
+    match key {
+        Key::Named(named) => Some(format!("{:?}", named)),
+        Key::Character(s) => Some(s.to_lowercase()),
+        _ => None,
+    }
+}
+
+/// 既定のキー割り当て（メディアキー・ブラウザキー・プレゼンターリモコン向け）
+/// PageUp / PageDown は履歴ナビゲーションに使用しているため既定では割り当てない
+pub fn default_bindings() -> BTreeMap<String, String> {
+    let mut map = BTreeMap::new();
+    map.insert("MediaTrackNext".to_string(), "next_page".to_string());
+    map.insert("MediaTrackPrevious".to_string(), "prev_page".to_string());
+    map.insert("MediaFastForward".to_string(), "next_page".to_string());
+    map.insert("MediaRewind".to_string(), "prev_page".to_string());
+    map.insert("BrowserForward".to_string(), "next_page".to_string());
+    map.insert("BrowserBack".to_string(), "prev_page".to_string());
+    map
+}
+
+/// 設定から構築したキー割り当て表
+pub struct Keymap {
+    bindings: HashMap<String, KeyAction>,
+}
+
+impl Keymap {
+    pub fn from_settings(bindings: &BTreeMap<String, String>) -> Self {
+        let mut map = HashMap::new();
+        for (key, action_name) in bindings {
+            match KeyAction::from_name(action_name) {
+                Some(action) => {
+                    map.insert(key.to_lowercase(), action);
+                }
+                None => {
+                    println!("[設定] 不明なキー割り当てを無視しました: {} -> {}", key, action_name);
+                }
+            }
+        }
+        Self { bindings: map }
+    }
+
+    pub fn action_for(&self, key: &Key) -> Option<KeyAction> {
+        let name = key_to_name(key)?;
+        self.bindings.get(&name.to_lowercase()).copied()
+    }
+}
@@ -1,26 +1,43 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+mod annotations;
+mod clipboard_watcher;
 mod config;
+mod folder_watcher;
+mod hot_folder;
+mod keymap;
+mod paths;
+mod perf_hud;
+mod persist;
+mod platform;
 mod render;
 mod image;
+mod sidecar;
 mod state;
+mod template;
+mod triage;
 mod ui;
+mod undo;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-use crate::config::Settings;
-use crate::render::{Renderer, PageDrawInfo, TextureHandle};
+use crate::config::{Settings, TriageTarget};
+use crate::keymap::{KeyAction, Keymap};
+use crate::render::{ColorAdjustment, Renderer, PageCurl, PageDrawInfo, PageTransform, TextureHandle};
+use std::collections::HashMap;
 use crate::render::d2d::D2DRenderer;
-use crate::image::{get_image_source, ImageSource};
-use crate::image::cache::{create_shared_cache, SharedImageCache};
+use crate::render::gpu_cache::{GpuCache, GpuCacheLimits};
+use crate::image::{get_image_source, resolve_open_path, ImageSource};
+use crate::image::cache::{create_shared_cache, PixelData, SharedImageCache};
+use crate::image::postprocess::CropMargins;
 use crate::image::loader::{AsyncLoader, LoaderRequest, UserEvent};
-use crate::state::{AppState, BindingDirection};
+use crate::state::{AppState, BindingDirection, CropEdit, FitMode, FrozenFrame, PageTurnAnimation, SeriesOverlay, TurnedPage};
 use std::sync::Arc;
 use windows::Win32::Graphics::Direct2D::Common::{D2D_RECT_F, D2D1_COLOR_F, D2D_SIZE_F};
 use windows::Win32::Graphics::DirectWrite::{
     DWRITE_TEXT_ALIGNMENT_CENTER, DWRITE_TEXT_ALIGNMENT_LEADING,
 };
 use winit::{
-    event::{Event, WindowEvent, ElementState, MouseButton, MouseScrollDelta, KeyEvent},
+    event::{Event, WindowEvent, ElementState, MouseButton, MouseScrollDelta, KeyEvent, TouchPhase},
     event_loop::{ControlFlow, EventLoopBuilder},
     window::WindowBuilder,
     keyboard::{PhysicalKey, KeyCode, ModifiersState, Key, NamedKey},
@@ -29,19 +46,69 @@ use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 use winit::platform::windows::WindowBuilderExtWindows;
 use tokio::runtime::Runtime;
 
-use windows::Win32::Foundation::{HWND, WPARAM, LPARAM};
+use windows::Win32::Foundation::{HWND, WPARAM, LPARAM, RECT};
 use windows::Win32::UI::Controls::{
     InitCommonControlsEx, INITCOMMONCONTROLSEX, ICC_BAR_CLASSES, STATUSCLASSNAMEW,
     SB_SETTEXTW, SB_SETPARTS,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, SendMessageW, WS_CHILD, WS_VISIBLE,
+    CreateWindowExW, GetWindowRect, IsWindowVisible, SendMessageW, ShowWindow, SW_HIDE, SW_SHOW, WS_CHILD, WS_VISIBLE,
     WINDOW_EX_STYLE, WINDOW_STYLE, WM_SIZE,
 };
 use windows::core::w;
 
 
-fn update_window_title(window: &winit::window::Window, path_key: &str, app_state: &AppState) {
+/// ページの表示名。アーカイブ内やフォルダ内のサブフォルダは "vol01/ch003/005.jpg" のように階層付きで返す
+fn page_display_name(entry: &str, path_key: &str) -> String {
+    let path = std::path::Path::new(entry);
+    let relative = if !path_key.is_empty() {
+        path.strip_prefix(path_key).ok()
+    } else {
+        None
+    };
+    let name = match relative {
+        Some(rel) => rel.to_string_lossy().to_string(),
+        // フォルダ外の絶対パスはファイル名のみ、アーカイブのエントリ名はそのまま (元から相対パス)
+        None if path.is_absolute() => path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| entry.to_string()),
+        None => entry.to_string(),
+    };
+    name.replace('\\', "/")
+}
+
+/// 長いパスを `max_chars` 文字に収まるよう中央を "…" で省略する (0 = 省略しない)
+/// 末尾のファイル名はできるだけ残し、先頭側のフォルダ名を削る
+fn ellipsize_middle(text: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if max_chars == 0 || chars.len() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars <= 1 {
+        return "…".to_string();
+    }
+    // 最後の区切り以降 (区切り文字を含む) を末尾として残す
+    let tail_start = chars
+        .iter()
+        .rposition(|&c| c == '/' || c == '\\')
+        .unwrap_or(chars.len());
+    let tail_len = chars.len() - tail_start;
+    // ファイル名だけで上限の半分を超える場合は前後を均等に残す
+    let keep_tail = if tail_len > 0 && tail_len < max_chars / 2 {
+        tail_len
+    } else {
+        (max_chars - 1) / 2
+    };
+    let keep_head = max_chars - 1 - keep_tail;
+    let head: String = chars[..keep_head].iter().collect();
+    let tail: String = chars[chars.len() - keep_tail..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+/// タイトルバー・ステータスバーの書式に埋め込む値 (キャッシュ詳細 {cache} はステータスバー側で追加する)
+fn format_values(path_key: &str, app_state: &AppState, settings: &Settings, zoom: f32) -> Vec<(&'static str, String)> {
+    let max_chars = settings.path_display_max_chars;
     let archive_name = if !path_key.is_empty() {
         std::path::Path::new(path_key)
             .file_name()
@@ -51,41 +118,158 @@ fn update_window_title(window: &winit::window::Window, path_key: &str, app_state
         String::new()
     };
 
-    let display_indices = app_state.get_page_indices_to_display();
-    let mut image_names = Vec::new();
-    
     // 見開き順（表示順）にソートしてファイル名を取得
-    let mut sorted_indices = display_indices.clone();
+    let mut sorted_indices = app_state.get_page_indices_to_display();
     sorted_indices.sort();
-    
-    for idx in sorted_indices {
-        if let Some(path_str) = app_state.image_files.get(idx) {
-            let fname = std::path::Path::new(path_str)
-                .file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_else(|| path_str.clone());
-            image_names.push(fname);
-        }
-    }
-    
-    let images_str = image_names.join(" - ");
+    let image_names: Vec<String> = sorted_indices
+        .iter()
+        .filter_map(|&idx| app_state.image_files.get(idx))
+        .map(|path_str| ellipsize_middle(&page_display_name(path_str, path_key), max_chars))
+        .collect();
 
-    let title_text = if !archive_name.is_empty() {
-        if !images_str.is_empty() {
-            format!("{} / {}", archive_name, images_str)
-        } else {
-            archive_name
-        }
+    let total_pages = app_state.image_files.len();
+    let current_page_str = if sorted_indices.len() > 1 {
+        format!("{}-{}", sorted_indices[0] + 1, sorted_indices.last().unwrap() + 1)
+    } else {
+        format!("{}", app_state.current_page_index + 1)
+    };
+    // 複数フォルダにまたがるソースでは、全体のページ数と並べて章内の位置も表示する
+    let page_info = match app_state.chapter_page_position() {
+        Some((chapter, page, pages)) => format!("{} / {} (ch.{} — page {}/{})", current_page_str, total_pages, chapter, page, pages),
+        None => format!("{} / {}", current_page_str, total_pages),
+    };
+
+    // ソースのパスと表示中ページ (アーカイブ内の階層付き) を上限文字数に収めて表示する
+    let page_name = app_state
+        .image_files
+        .get(app_state.current_page_index)
+        .map(|entry| page_display_name(entry, path_key))
+        .unwrap_or_default();
+    let path_preview = if page_name.is_empty() {
+        ellipsize_middle(path_key, max_chars)
     } else {
-        images_str
+        format!("{} :: {}", ellipsize_middle(path_key, max_chars), ellipsize_middle(&page_name, max_chars))
     };
 
-    let title = if !title_text.is_empty() {
-        format!("HayateViewer v{} - {}", VERSION, title_text)
+    let mut spread_info = if app_state.is_webtoon_view {
+        "[縦スクロール]".to_string()
+    } else if app_state.is_spread_view {
+        let binding = if app_state.binding_direction == BindingDirection::Right { "右" } else { "左" };
+        format!("[見開き:{}]", binding)
+    } else if let Some(half) = app_state.page_half(app_state.current_page_index) {
+        let side = if app_state.is_left_half(half) { "左" } else { "右" };
+        format!("[単ページ:{}半分]", side)
+    } else {
+        "[単ページ]".to_string()
+    };
+    if !app_state.folder_start_indices.is_empty() {
+        // 章の区切りで見開きの組み合わせが変わるため、現在の章と扱いを表示する
+        let (chapter, chapters) = app_state.chapter_position();
+        let boundary = if app_state.chapter_start_single { "章頭単独" } else { "章頭連続" };
+        spread_info.push_str(&format!("[章 {}/{} {}]", chapter, chapters, boundary));
+    }
+    if settings.prefetch_paused {
+        spread_info.push_str("[先読み停止]");
+    }
+    if app_state.triage_mode {
+        spread_info.push_str("[仕分け]");
+    }
+
+    vec![
+        ("version", VERSION.to_string()),
+        ("archive", archive_name),
+        ("path", path_preview),
+        ("file", image_names.join(" - ")),
+        ("page", current_page_str),
+        ("total", total_pages.to_string()),
+        ("position", page_info),
+        ("mode", spread_info),
+        ("zoom", format!("{:.0}%", zoom * 100.0)),
+        ("backend", get_backend_display_name(&settings.rendering_backend).to_string()),
+    ]
+}
+
+fn render_format(template: &str, values: &[(&'static str, String)]) -> String {
+    let values: Vec<(&str, &str)> = values.iter().map(|(name, value)| (*name, value.as_str())).collect();
+    crate::template::render(template, &values)
+}
+
+fn update_window_title(window: &winit::window::Window, path_key: &str, app_state: &AppState, settings: &Settings, zoom: f32) {
+    let title = if path_key.is_empty() && app_state.image_files.is_empty() {
+        String::new()
+    } else {
+        render_format(&settings.title_format, &format_values(path_key, app_state, settings, zoom))
+    };
+    if title.is_empty() {
+        window.set_title(&format!("HayateViewer v{}", VERSION));
     } else {
-        format!("HayateViewer v{}", VERSION)
+        window.set_title(&title);
+    }
+}
+
+/// 保存されたウィンドウ矩形 (物理座標) を仮想スクリーン内に収める
+/// モニター構成が変わってもウィンドウが画面外に出ないようにする
+fn clamp_to_virtual_screen(x: i32, y: i32, w: u32, h: u32) -> (i32, i32, u32, u32) {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
     };
-    window.set_title(&title);
+    let (vx, vy, vw, vh) = unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    };
+    if vw <= 0 || vh <= 0 {
+        return (x, y, w, h);
+    }
+    // 仮想画面が最小サイズより小さいと clamp の下限が上限を超えるため、max / min で画面の大きさを優先する
+    let w = (w as i32).max(320).min(vw);
+    let h = (h as i32).max(240).min(vh);
+    let x = x.clamp(vx, vx + vw - w);
+    let y = y.clamp(vy, vy + vh - h);
+    (x, y, w as u32, h as u32)
+}
+
+/// 通常状態 (最大化・最小化以外) のウィンドウ位置とサイズを設定に記録する
+fn record_window_geometry(window: &winit::window::Window, settings: &mut Settings) {
+    // 最大化・全画面中は通常時の矩形を保持したままにする
+    if window.is_maximized() || window.is_minimized() == Some(true) || window.fullscreen().is_some() {
+        return;
+    }
+    let Ok(pos) = window.outer_position() else { return; };
+    let size = window.inner_size();
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+    let logical: winit::dpi::LogicalSize<u32> = size.to_logical(window.scale_factor());
+    settings.window_position = (pos.x, pos.y);
+    settings.window_size = (logical.width, logical.height);
+    settings.window_geometry = (pos.x, pos.y, size.width, size.height);
+}
+
+/// ボーダーレス全画面と通常ウィンドウを切り替える
+/// 全画面中はステータスバーを隠し、ページを画面全体に配置する (通常時の矩形は winit が復元する)
+fn toggle_fullscreen(window: &winit::window::Window, status_hwnd: Option<HWND>, settings: &mut Settings) {
+    let fullscreen = window.fullscreen().is_none();
+    if fullscreen {
+        record_window_geometry(window, settings);
+        window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)));
+    } else {
+        window.set_fullscreen(None);
+    }
+    set_status_bar_visible(status_hwnd, !fullscreen);
+    settings.window_fullscreen = fullscreen;
+    window.request_redraw();
+}
+
+fn set_status_bar_visible(status_hwnd: Option<HWND>, visible: bool) {
+    if let Some(hwnd) = status_hwnd {
+        unsafe {
+            let _ = ShowWindow(hwnd, if visible { SW_SHOW } else { SW_HIDE });
+        }
+    }
 }
 
 /// Windows システムステータスバーを作成する
@@ -130,6 +314,34 @@ fn create_status_bar(parent_hwnd: HWND) -> Option<HWND> {
     }
 }
 
+/// ステータスバーのフォントをウィンドウの DPI に合わせる (別の拡大率のモニターへ移動したときにも呼ぶ)
+/// コモンコントロールのフォントは自動では追従しないため、システムのステータスバー用フォントを DPI 指定で作り直す
+fn update_status_bar_font(status_hwnd: HWND, scale: f32) {
+    use windows::Win32::Graphics::Gdi::{CreateFontIndirectW, DeleteObject, HGDIOBJ};
+    use windows::Win32::UI::HiDpi::SystemParametersInfoForDpi;
+    use windows::Win32::UI::WindowsAndMessaging::{NONCLIENTMETRICSW, SPI_GETNONCLIENTMETRICS, WM_GETFONT, WM_SETFONT};
+    unsafe {
+        let dpi = (96.0 * scale).round() as u32;
+        let mut metrics = NONCLIENTMETRICSW { cbSize: std::mem::size_of::<NONCLIENTMETRICSW>() as u32, ..Default::default() };
+        let pv = &mut metrics as *mut NONCLIENTMETRICSW as *mut std::ffi::c_void;
+        if SystemParametersInfoForDpi(SPI_GETNONCLIENTMETRICS.0, metrics.cbSize, Some(pv), 0, dpi).is_err() {
+            return;
+        }
+        let font = CreateFontIndirectW(&metrics.lfStatusFont);
+        if font.is_invalid() {
+            return;
+        }
+        let old = SendMessageW(status_hwnd, WM_GETFONT, None, None);
+        SendMessageW(status_hwnd, WM_SETFONT, Some(WPARAM(font.0 as usize)), Some(LPARAM(1)));
+        // 前回設定したフォントを破棄する (既定のフォントのままなら 0 が返る)
+        if old.0 != 0 {
+            let _ = DeleteObject(HGDIOBJ(old.0 as _));
+        }
+        // フォントに合わせて高さを計算し直させる
+        SendMessageW(status_hwnd, WM_SIZE, Some(WPARAM(0)), Some(LPARAM(0)));
+    }
+}
+
 /// ステータスバーのテキストを更新する
 fn update_status_bar_text(status_hwnd: HWND, text: &str) {
     unsafe {
@@ -144,22 +356,199 @@ fn update_status_bar_text(status_hwnd: HWND, text: &str) {
     }
 }
 
-fn sync_current_state_to_history(settings: &mut Settings, app_state: &AppState, current_path_key: &str) {
-    if current_path_key.is_empty() { return; }
-    let binding_str = if !app_state.is_spread_view {
+fn binding_str(app_state: &AppState) -> &'static str {
+    if app_state.is_webtoon_view {
+        "webtoon"
+    } else if !app_state.is_spread_view {
         "single"
     } else if app_state.binding_direction == BindingDirection::Left {
         "left"
     } else {
         "right"
+    }
+}
+
+/// 設定の表示モード (単一 / 見開き / 縦スクロール) を表示状態に反映する
+fn apply_display_mode(app_state: &mut AppState, settings: &Settings) {
+    app_state.is_webtoon_view = settings.webtoon_view;
+    // 縦スクロール中は見開きを使わない (設定の見開きは縦スクロールを抜けたときに戻す)
+    app_state.is_spread_view = settings.is_spread_view && !settings.webtoon_view;
+    app_state.binding_direction = if settings.binding_direction == "right" { BindingDirection::Right } else { BindingDirection::Left };
+}
+
+/// 現在のソースの表示設定をソース別設定テーブルに記録する
+fn sync_current_state_to_overrides(settings: &mut Settings, app_state: &AppState, view_state: &ViewState, current_path_key: &str) {
+    if current_path_key.is_empty() || app_state.image_files.is_empty() { return; }
+    if clipboard_watcher::is_transient_source(current_path_key) { return; }
+    let spread_offset = app_state.is_spread_view
+        && app_state.snap_to_spread(app_state.current_page_index) != app_state.current_page_index;
+    // ズーム・パンはページ送りでリセットされるため、表示中のページのファイルと一緒に記録し、同じページで開いたときだけ復元する
+    // (画像ファイルはフォルダとして開くため、1 枚画像のソースに限らず地図・ポスターなどの表示を戻せる)
+    let zoom_page = app_state.image_files.get(app_state.current_page_index).cloned().unwrap_or_default();
+    let (zoom_level, pan_offset) = if app_state.is_webtoon_view {
+        (1.0, (0.0, 0.0))
+    } else {
+        (view_state.zoom_level, view_state.pan_offset)
+    };
+    let adjustment = app_state.color_adjustment;
+    settings.set_source_override(current_path_key.to_string(), crate::config::SourceOverride {
+        binding: binding_str(app_state).to_string(),
+        first_page_single: app_state.spread_view_first_page_single,
+        spread_offset,
+        zoom_level,
+        pan_offset,
+        zoom_page,
+        fit_mode: view_state.fit_mode.setting_name().to_string(),
+        brightness: adjustment.brightness,
+        contrast: adjustment.contrast,
+        gamma: adjustment.gamma,
+        page_transforms: app_state.page_transforms.iter().map(|(&index, &transform)| (index, transform)).collect(),
+    });
+}
+
+/// 表示中のページの平均輝度 (夜間モードで反転して表示するページは反転後の明るさ)。どのページも未計測なら None
+fn displayed_luminance(app_state: &AppState, settings: &Settings) -> Option<f32> {
+    let values: Vec<f32> = app_state
+        .get_page_indices_to_display()
+        .into_iter()
+        .filter_map(|index| {
+            let luminance = *app_state.page_luminance.get(&index)?;
+            let inverted = settings.invert_colors && !(settings.smart_invert && app_state.is_dark_page(index));
+            Some(if inverted { 1.0 - luminance } else { luminance })
+        })
+        .collect();
+    (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+}
+
+/// 色調整を変更し、ステータスバーに現在値を表示する
+fn update_color_adjustment(app_state: &mut AppState, adjustment: ColorAdjustment) {
+    app_state.color_adjustment = adjustment;
+    let message = if adjustment.is_identity() {
+        "色調整: リセット".to_string()
+    } else {
+        format!("色調整: {}", adjustment.describe())
+    };
+    app_state.status_message = Some((message, std::time::Instant::now()));
+}
+
+/// ページの回転・反転を変更し、ステータスバーに現在の状態を表示する
+fn update_page_transform(app_state: &mut AppState, current_path_key: &str, index: usize, transform: PageTransform) {
+    app_state.record_undo(current_path_key, "回転・反転");
+    if transform.is_identity() {
+        app_state.page_transforms.remove(&index);
+    } else {
+        app_state.page_transforms.insert(index, transform);
+    }
+    let message = if transform.is_identity() {
+        format!("{}ページ: 回転・反転なし", index + 1)
+    } else {
+        format!("{}ページ: {}", index + 1, transform.describe())
     };
-    settings.add_to_history(current_path_key.to_string(), app_state.current_page_index, binding_str.to_string());
+    app_state.status_message = Some((message, std::time::Instant::now()));
+}
+
+/// 回転・反転の対象ページ (カーソル下のページ、ページ外なら先頭の表示ページ)
+fn transform_target_page(app_state: &AppState, view_state: &ViewState) -> usize {
+    let (x, y) = view_state.cursor_pos;
+    view_state
+        .page_rects
+        .iter()
+        .find(|(_, r)| x >= r.left && x <= r.right && y >= r.top && y <= r.bottom)
+        .map(|(index, _)| *index)
+        .unwrap_or(app_state.current_page_index)
+}
+
+/// 表示するページを切り替える。瞬時切り替えモードでは移動先のページがすべて GPU に転送済みの場合だけ切り替え、
+/// 揃っていなければ保留して、揃った時点で再描画時に切り替える (読み込み途中のページを表示しない)
+fn flip_to(app_state: &mut AppState, gpu_cache: &GpuCache, settings: &Settings, page_index: usize) {
+    app_state.pending_flip = None;
+    let current = app_state.current_page_index;
+    app_state.current_page_index = page_index;
+    if !settings.instant_flip || app_state.is_webtoon_view || page_index == current {
+        return;
+    }
+    let pages = app_state.get_page_indices_to_display();
+    if !pages.iter().all(|&i| gpu_cache.contains(i)) {
+        app_state.current_page_index = current;
+        app_state.pending_flip = Some(crate::state::PendingFlip { page_index, pages });
+    }
+}
+
+/// ページ送り (瞬時切り替えモードでは flip_to と同じく移動先が揃うまで待つ)
+fn navigate_pages(app_state: &mut AppState, gpu_cache: &GpuCache, settings: &Settings, direction: i32) {
+    if !settings.instant_flip || app_state.is_webtoon_view {
+        app_state.pending_flip = None;
+        app_state.navigate(direction);
+        return;
+    }
+    // 保留中のページ送りがあればその移動先から数える (待っている間に押した分も進む)
+    let current = app_state.current_page_index;
+    if let Some(pending) = app_state.pending_flip.as_ref() {
+        app_state.current_page_index = pending.page_index;
+    }
+    app_state.navigate(direction);
+    let target = app_state.current_page_index;
+    app_state.current_page_index = current;
+    flip_to(app_state, gpu_cache, settings, target);
+}
+
+/// 瞬時切り替えで待っているページの読み込み具合 (0.0〜1.0。デコード済みで半分、GPU 転送済みで 1 ページ分)
+fn pending_flip_progress(pending: &crate::state::PendingFlip, gpu_cache: &GpuCache, cpu_cache: &SharedImageCache, path_key: &str) -> f32 {
+    if pending.pages.is_empty() {
+        return 1.0;
+    }
+    let mut cache = cpu_cache.lock().unwrap();
+    let done: f32 = pending
+        .pages
+        .iter()
+        .map(|&i| {
+            if gpu_cache.contains(i) {
+                1.0
+            } else if cache.get(&format!("{}::{}", path_key, i)).is_some() {
+                0.5
+            } else {
+                0.0
+            }
+        })
+        .sum();
+    done / pending.pages.len() as f32
+}
+
+/// 瞬時切り替えの待機中に画面下部中央へ進捗バーを描画する (window は論理座標)
+fn draw_flip_progress(renderer: &dyn Renderer, settings: &Settings, progress: f32, window: (f32, f32), ui_scale: f32) {
+    let (bar_w, bar_h) = (240.0, 6.0);
+    let left = (window.0 - bar_w) / 2.0;
+    let top = window.1 - 64.0;
+    let track = D2D_RECT_F { left, top, right: left + bar_w, bottom: top + bar_h };
+    renderer.fill_rectangle(&scale_rect(&track, ui_scale), &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.6 });
+    let fill = D2D_RECT_F { right: left + bar_w * progress.clamp(0.0, 1.0), ..track };
+    renderer.fill_rectangle(&scale_rect(&fill, ui_scale), &accent_color(settings, 0.0, 0.9));
+}
+
+fn sync_current_state_to_history(settings: &mut Settings, app_state: &AppState, current_path_key: &str) {
+    if current_path_key.is_empty() || clipboard_watcher::is_transient_source(current_path_key) { return; }
+    settings.add_to_history(
+        current_path_key.to_string(),
+        app_state.current_page_index,
+        binding_str(app_state).to_string(),
+        app_state.manual_crop,
+    );
+}
+
+/// 現在のソースの読書位置を記録する (履歴から押し出されたソースも次に開いたときに再開できるように)
+fn sync_current_state_to_positions(app_state: &mut AppState, current_path_key: &str) {
+    if current_path_key.is_empty() || app_state.image_files.is_empty() { return; }
+    if clipboard_watcher::is_transient_source(current_path_key) { return; }
+    let (page, total) = (app_state.current_page_index, app_state.image_files.len());
+    let binding = binding_str(app_state);
+    let file = app_state.image_files[page.min(total - 1)].clone();
+    app_state.positions.record(current_path_key, page, binding, total, &file);
 }
 
 fn load_new_source(
-    new_source: ImageSource,
+    mut new_source: ImageSource,
     path_str: String,
-    initial_page: usize,
+    initial_page: Option<usize>, // None なら前回読んだ位置 (記録が無ければ先頭) から開く
     initial_binding: Option<String>,
     app_state: &mut AppState,
     current_path_key: &mut String,
@@ -168,42 +557,170 @@ fn load_new_source(
     loader: &Arc<AsyncLoader>,
     rt: &Runtime,
     settings: &mut Settings,
-    current_bitmaps: &mut Vec<(usize, crate::render::TextureHandle)>,
+    gpu_cache: &mut GpuCache,
+    view_state: &mut ViewState,
     skip_history_update: bool,  // 履歴ナビゲーション時は true にして履歴の順序を保持
 ) {
     println!("ソースを読み込み: {} ({} 個のファイル/エントリ)", path_str, new_source.len());
     
     // 切り替え前に現在のファイルの状態（ページ・綴じ方向）を履歴に保存
+    sync_current_state_to_overrides(settings, app_state, view_state, current_path_key);
+    sync_current_state_to_positions(app_state, current_path_key);
     if !skip_history_update {
         sync_current_state_to_history(settings, app_state, current_path_key);
     }
 
-    if let ImageSource::Files(ref files) = new_source {
-        app_state.image_files = files.clone();
-    } else if let ImageSource::Archive(ref loader) = new_source {
-        app_state.image_files = loader.get_file_names().to_vec();
+    // ファイルと一緒に置かれたサイドカー (.hayate.json) の設定。この PC の設定が無い項目の既定値として使う
+    let sidecar = crate::sidecar::load_for(&path_str);
+    if let ImageSource::Archive(ref mut archive) = new_source {
+        archive.set_extraction_mode(crate::image::archive::ExtractionMode::from_setting(&settings.archive_extraction_mode));
+        if let Some(password) = sidecar.password.clone() {
+            archive.set_password(password);
+        }
+    }
+    if let ImageSource::Bundle(ref mut bundle) = new_source {
+        bundle.set_extraction_mode(crate::image::archive::ExtractionMode::from_setting(&settings.archive_extraction_mode));
+        if let Some(password) = sidecar.password.clone() {
+            bundle.set_password(password);
+        }
     }
+    if let ImageSource::Pdf(ref mut pdf) = new_source {
+        pdf.set_dpi(settings.pdf_render_dpi);
+    }
+    let listing = new_source.take_listing();
+    app_state.image_files = new_source.file_names().to_vec();
+    app_state.folder_start_indices = crate::image::archive::chapter_start_indices(&app_state.image_files);
+
+    // ソース別設定があれば優先し、無ければ全体設定に戻す
+    let source_override = settings.source_overrides.get(&path_str).cloned();
+    // 読書位置はソース別設定より長く残るため、ソース別設定が消えていても表示モードを復元できる
+    let saved_position = app_state.positions.get(&path_str).cloned();
+    let initial_binding = initial_binding
+        .or_else(|| source_override.as_ref().map(|o| o.binding.clone()))
+        .or_else(|| saved_position.as_ref().map(|p| p.binding.clone()))
+        .or_else(|| sidecar.binding.clone());
+    app_state.spread_view_first_page_single = source_override
+        .as_ref()
+        .map(|o| o.first_page_single)
+        .or(sidecar.first_page_single)
+        .unwrap_or(settings.spread_view_first_page_single);
 
     // 読み込み先の設定を反映（履歴からの復元用）
     if let Some(binding) = initial_binding {
         match binding.as_str() {
-            "single" => app_state.is_spread_view = false,
+            "single" => {
+                app_state.is_spread_view = false;
+                app_state.is_webtoon_view = false;
+            }
             "left" => {
                 app_state.is_spread_view = true;
+                app_state.is_webtoon_view = false;
                 app_state.binding_direction = BindingDirection::Left;
             }
             "right" => {
                 app_state.is_spread_view = true;
+                app_state.is_webtoon_view = false;
                 app_state.binding_direction = BindingDirection::Right;
             }
+            "webtoon" => {
+                app_state.is_spread_view = false;
+                app_state.is_webtoon_view = true;
+            }
             _ => {}
         }
     }
 
+    let resumed_page = if initial_page.is_none() { saved_position.as_ref().map(|p| p.page).filter(|&p| p > 0) } else { None };
+    let initial_page = initial_page.or(resumed_page).unwrap_or(0);
+    // 記録した位置から再開するとき (履歴から開くときも同じページ番号を渡される) は、並び順が変わっていても同じ画像を開けるようファイル名で引き直す
+    let initial_page = saved_position
+        .as_ref()
+        .filter(|p| p.page == initial_page && !p.file.is_empty())
+        .and_then(|p| app_state.image_files.iter().position(|f| *f == p.file))
+        .unwrap_or(initial_page);
     app_state.current_page_index = initial_page.min(app_state.image_files.len().saturating_sub(1));
+    // 見開きのずらしを復元 (揃った位置で開かれた場合のみ1ページずらす)
+    let spread_offset = source_override
+        .as_ref()
+        .map(|o| o.spread_offset)
+        .unwrap_or(sidecar.page_offset.is_some_and(|n| n % 2 == 1));
+    if spread_offset && app_state.is_spread_view {
+        let idx = app_state.current_page_index;
+        if app_state.snap_to_spread(idx) == idx && app_state.get_page_indices_to_display().len() == 2 {
+            app_state.current_page_index = idx + 1;
+        }
+    }
+    app_state.archive_listing = listing.map(|receiver| crate::state::PendingListing {
+        receiver,
+        deferred_page: (initial_page > 0).then_some((initial_page, app_state.current_page_index)),
+    });
+
+    // 色調整はソースごとに保持し、設定が無ければ無補正で表示する
+    app_state.color_adjustment = source_override
+        .as_ref()
+        .map(|o| ColorAdjustment { brightness: o.brightness, contrast: o.contrast, gamma: o.gamma, ..Default::default() }.clamped())
+        .unwrap_or_default();
+    // ページごとの回転・反転もソース別に復元する
+    // 一覧を取得中のアーカイブは、まだ届いていないページの指定も残しておく
+    let listing_pending = app_state.archive_listing.is_some();
+    app_state.page_transforms = source_override
+        .as_ref()
+        .map(|o| {
+            o.page_transforms
+                .iter()
+                .map(|(&index, transform)| (index, transform.normalized()))
+                .filter(|(index, transform)| (listing_pending || *index < app_state.image_files.len()) && !transform.is_identity())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // 合わせ方はソースごとに復元し、記録が無ければ全体設定に戻す
+    view_state.fit_mode = source_override
+        .as_ref()
+        .map(|o| o.fit_mode.as_str())
+        .filter(|mode| !mode.is_empty())
+        .map_or_else(|| FitMode::from_setting(&settings.fit_mode), FitMode::from_setting);
+    // 前回ズーム・パンしていたページで開いたときはそれを復元し、それ以外は等倍から表示する
+    view_state.reset();
+    if let Some(o) = source_override.as_ref().filter(|_| !app_state.is_webtoon_view) {
+        let current_file = app_state.image_files.get(app_state.current_page_index);
+        let same_page = if o.zoom_page.is_empty() { app_state.image_files.len() == 1 } else { current_file == Some(&o.zoom_page) };
+        if same_page {
+            view_state.zoom_level = o.zoom_level.clamp(0.1, 50.0);
+            view_state.pan_offset = o.pan_offset;
+        }
+    }
     // 新しいソースを読み込む際は履歴インデックスをリセット（履歴からの読み込み時は呼び出し元で設定）
     app_state.current_history_index = None;
-    current_bitmaps.clear();
+    app_state.clear_ocr();
+    app_state.page_luminance.clear();
+    app_state.page_histograms.clear();
+    app_state.page_crops.clear();
+    app_state.page_sizes.clear();
+    app_state.page_progress.clear();
+    app_state.crop_edit = None;
+    // 前のソースのページはめくらない
+    app_state.page_turn = None;
+    app_state.last_drawn_pages.clear();
+    app_state.frozen_frame.pages.clear();
+    // 手動トリミングは履歴に保存したものを優先し、無ければサイドカーの指定を使う
+    app_state.manual_crop = settings
+        .history
+        .iter()
+        .find(|item| item.path == path_str)
+        .and_then(|item| item.crop)
+        .or(sidecar.crop)
+        .map(|crop| crop.clamped())
+        .filter(|crop| !crop.is_empty());
+    if let Some(crop) = app_state.manual_crop {
+        println!("[トリミング] 手動トリミングを復元: {}", crop.describe());
+    }
+    app_state.page_osd = None;
+    app_state.annotations = crate::annotations::Annotations::load(&path_str);
+    app_state.annotation_stroke = None;
+    app_state.pending_flip = None;
+    app_state.undo.retain_source(&path_str);
+    gpu_cache.clear();
     
     // CPU キャッシュもクリア
     if let Ok(mut cache) = cpu_cache.lock() {
@@ -211,7 +728,7 @@ fn load_new_source(
     }
     
     *current_path_key = path_str.clone();
-    update_window_title(window, current_path_key, app_state);
+    update_window_title(window, current_path_key, app_state, settings, view_state.zoom_level);
     
     rt.block_on(loader.send_request(LoaderRequest::Clear));
     let l_prefetch = Arc::clone(loader);
@@ -221,23 +738,301 @@ fn load_new_source(
         path_key: path_str.clone() 
     }));
 
+    if resumed_page.is_some() {
+        let message = format!("前回の続きから表示: {} / {} ページ", app_state.current_page_index + 1, app_state.image_files.len());
+        println!("[位置] {}", message);
+        app_state.status_message = Some((message, std::time::Instant::now()));
+    }
+
     // 新しいファイルを履歴の先頭に追加（履歴ナビゲーション時はスキップ）
     if !skip_history_update {
         sync_current_state_to_history(settings, app_state, &path_str);
-        let _ = settings.save("config.json");
+        let _ = settings.save(crate::config::config_path());
+        if let Err(e) = app_state.positions.save() {
+            println!("[位置] 読書位置を保存できませんでした: {}", e);
+        }
+        // 前のソースの閲覧を締めくくる (履歴から開いた場合は新しいソースのページを表示したときに締めくくる)
+        app_state.stats.end_session();
+        if let Err(e) = app_state.stats.save() {
+            println!("[統計] 読書の統計を保存できませんでした: {}", e);
+        }
+    }
+    request_pages_with_prefetch(app_state, loader, rt, cpu_cache, settings, current_path_key);
+}
+
+/// フォルダ内のファイルの増減 (ごみ箱・仕分け) を反映するため、表示中のフォルダを読み直す
+/// `select` で読み直した一覧から表示するページを選ぶ。読み直せない・画像が無くなった場合は false
+fn reload_current_folder(
+    select: impl FnOnce(&[String]) -> usize,
+    app_state: &mut AppState,
+    current_path_key: &mut String,
+    window: &winit::window::Window,
+    cpu_cache: &SharedImageCache,
+    loader: &Arc<AsyncLoader>,
+    rt: &Runtime,
+    settings: &mut Settings,
+    gpu_cache: &mut GpuCache,
+    view_state: &mut ViewState,
+    staged_uploads: &mut Vec<StagedUpload>,
+) -> bool {
+    let Some(new_source) = get_image_source(current_path_key) else { return false; };
+    if new_source.len() == 0 {
+        return false;
+    }
+    // ページ番号がずれるため、回転・反転の指定はファイル名で引き継ぐ
+    let transforms: HashMap<String, PageTransform> = app_state
+        .page_transforms
+        .iter()
+        .filter_map(|(&index, &transform)| app_state.image_files.get(index).map(|f| (f.clone(), transform)))
+        .collect();
+    let page = select(new_source.file_names()).min(new_source.len() - 1);
+    let binding = binding_str(app_state).to_string();
+    // 同じパスのキャッシュキーを使い回すため、デコード中の古い結果と段階転送中のテクスチャも捨てる
+    loader.invalidate();
+    staged_uploads.clear();
+    let path = current_path_key.clone();
+    load_new_source(
+        new_source,
+        path,
+        Some(page),
+        Some(binding),
+        app_state,
+        current_path_key,
+        window,
+        cpu_cache,
+        loader,
+        rt,
+        settings,
+        gpu_cache,
+        view_state,
+        true, // 履歴の順序は変えない
+    );
+    app_state.page_transforms = app_state
+        .image_files
+        .iter()
+        .enumerate()
+        .filter_map(|(index, f)| transforms.get(f).map(|&transform| (index, transform)))
+        .collect();
+    true
+}
+
+/// バックグラウンドで取得中のアーカイブの一覧の続きを反映する
+/// 続きは末尾に追加するのでページ番号は変わらない。取得が終わって名前順に並べ直すと順序が変わる場合は、
+/// 表示中のページと回転・反転をファイル名で引き継ぎ、ページ番号で引いているキャッシュを捨てる
+fn poll_archive_listing(
+    app_state: &mut AppState,
+    current_path_key: &str,
+    window: &winit::window::Window,
+    cpu_cache: &SharedImageCache,
+    loader: &Arc<AsyncLoader>,
+    rt: &Runtime,
+    settings: &Settings,
+    gpu_cache: &mut GpuCache,
+    staged_uploads: &mut Vec<StagedUpload>,
+    zoom: f32,
+) {
+    use crate::image::archive::ListingUpdate;
+    use std::sync::mpsc::TryRecvError;
+
+    let Some(listing) = app_state.archive_listing.as_mut() else { return; };
+    let mut appended = Vec::new();
+    let mut sorted = None;
+    let (mut finished, mut failed) = (false, false);
+    loop {
+        match listing.receiver.try_recv() {
+            Ok(ListingUpdate::Append(names)) => appended.extend(names),
+            Ok(ListingUpdate::Complete(names)) => {
+                sorted = Some(names);
+                finished = true;
+                break;
+            }
+            Ok(ListingUpdate::Failed(e)) => {
+                println!("[Archive] 一覧の取得に失敗しました: {}", e);
+                app_state.status_message = Some((format!("一覧の取得に失敗しました: {}", e), std::time::Instant::now()));
+                (finished, failed) = (true, true);
+                break;
+            }
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => {
+                (finished, failed) = (true, true);
+                break;
+            }
+        }
+    }
+    if appended.is_empty() && !finished {
+        return;
+    }
+    // ページを移動していなければ、開くときに指定されたページへ移動する
+    let requested_page = listing
+        .deferred_page
+        .filter(|&(_, shown)| shown == app_state.current_page_index)
+        .map(|(requested, _)| requested);
+    if finished {
+        app_state.archive_listing = None;
+    }
+
+    app_state.image_files.extend(appended);
+    match sorted {
+        Some(sorted) if sorted != app_state.image_files => {
+            let current_name = app_state.image_files.get(app_state.current_page_index).cloned();
+            let transforms: HashMap<String, PageTransform> = app_state
+                .page_transforms
+                .iter()
+                .filter_map(|(&index, &transform)| app_state.image_files.get(index).map(|f| (f.clone(), transform)))
+                .collect();
+            // 同じページ番号で別のエントリを指すようになるため、デコード中の結果・転送中のテクスチャも捨てる
+            loader.invalidate();
+            staged_uploads.clear();
+            gpu_cache.clear();
+            if let Ok(mut cache) = cpu_cache.lock() {
+                cache.clear();
+            }
+            app_state.clear_ocr();
+            app_state.page_luminance.clear();
+            app_state.page_histograms.clear();
+            app_state.page_crops.clear();
+            app_state.page_sizes.clear();
+            app_state.page_progress.clear();
+            app_state.page_turn = None;
+            app_state.last_drawn_pages.clear();
+            app_state.frozen_frame.pages.clear();
+            app_state.pending_flip = None;
+            app_state.current_page_index = requested_page
+                .or_else(|| current_name.and_then(|name| sorted.iter().position(|f| *f == name)))
+                .unwrap_or(0)
+                .min(sorted.len().saturating_sub(1));
+            app_state.page_transforms = sorted
+                .iter()
+                .enumerate()
+                .filter_map(|(index, f)| transforms.get(f).map(|&transform| (index, transform)))
+                .collect();
+            app_state.image_files = sorted;
+            println!("[Archive] 一覧を名前順に並べ直しました");
+        }
+        Some(_) => {
+            if let Some(page) = requested_page {
+                app_state.current_page_index = page.min(app_state.image_files.len().saturating_sub(1));
+            }
+        }
+        None => {
+            if let Some(page) = requested_page.filter(|&page| page < app_state.image_files.len()) {
+                app_state.current_page_index = page;
+                if let Some(listing) = app_state.archive_listing.as_mut() {
+                    listing.deferred_page = Some((page, page));
+                }
+            }
+        }
+    }
+    app_state.folder_start_indices = crate::image::archive::chapter_start_indices(&app_state.image_files);
+    rt.block_on(loader.send_request(LoaderRequest::SetFileNames {
+        path_key: current_path_key.to_string(),
+        file_names: app_state.image_files.clone(),
+    }));
+    update_window_title(window, current_path_key, app_state, settings, zoom);
+    if finished && !failed {
+        println!("[Archive] 一覧の取得が完了しました: {} エントリ", app_state.image_files.len());
     }
     request_pages_with_prefetch(app_state, loader, rt, cpu_cache, settings, current_path_key);
 }
 
+/// 設定に従ってディスクキャッシュを開く (無効、または保存先を作れない場合は None)
+fn open_disk_cache(settings: &Settings) -> Option<Arc<crate::image::disk_cache::DiskCache>> {
+    use crate::image::disk_cache::DiskCache;
+    if !settings.disk_cache_enabled {
+        return None;
+    }
+    let dir = if settings.disk_cache_dir.is_empty() {
+        DiskCache::default_dir()
+    } else {
+        std::path::PathBuf::from(&settings.disk_cache_dir)
+    };
+    match DiskCache::new(dir, settings.disk_cache_max_mb) {
+        Ok(cache) => {
+            println!("[ディスクキャッシュ] 保存先: {} (上限 {}MB)", cache.dir().display(), settings.disk_cache_max_mb);
+            Some(Arc::new(cache))
+        }
+        Err(e) => {
+            println!("[ディスクキャッシュ] 保存先を作成できませんでした: {}", e);
+            None
+        }
+    }
+}
+
+/// 1フレームで段階転送するバイト数の上限
+const STAGED_UPLOAD_BYTES_PER_FRAME: usize = 4 * 1024 * 1024;
+
+/// 別スレッドで転送できないバックエンドで、1フレームに先読みページを一括転送してよい時間
+/// 表示中のページはこの時間を超えても転送する
+const SYNC_UPLOAD_BUDGET: std::time::Duration = std::time::Duration::from_millis(4);
+
+/// 複数フレームに分けて転送中の先読みページ (D2D でビットマップ作成によるページ送りの引っかかりを避ける)
+struct StagedUpload {
+    path_key: String,
+    index: usize,
+    texture: TextureHandle,
+    image: Arc<crate::image::cache::DecodedImage>,
+    next_row: u32,
+}
+
 struct ViewState {
     zoom_level: f32,
     pan_offset: (f32, f32),
     is_panning: bool,
+    /// ルーペ表示中 (右ボタン押下中)。ズーム・パンは変えず、カーソル周辺だけを拡大描画する
     is_loupe: bool,
-    loupe_base_zoom: f32,
-    loupe_base_pan: (f32, f32),
     last_mouse_pos: (f32, f32),
     cursor_pos: (f32, f32),
+    /// 直近の描画で使用したページごとの描画矩形 (ヒットテスト用)
+    page_rects: Vec<(usize, D2D_RECT_F)>,
+    /// 直近の描画でのズーム後のコンテンツサイズ (パネルスクロール用)
+    content_size: (f32, f32),
+    /// Z キーを押している間は左ドラッグで範囲ズーム
+    zoom_select_armed: bool,
+    zoom_select_start: Option<(f32, f32)>,
+    /// ズーム/パンのアニメーション (zoom_level / pan_offset は到達先の値)
+    animation: Option<ViewAnimation>,
+    /// 単一/見開きの切り替え後、次の描画で同じ内容位置へ合わせるための基準点
+    pending_anchor: Option<ViewAnchor>,
+    /// 縦スクロール表示: 現在のページの上端が画面上端からどれだけ上にあるか (物理ピクセル)
+    webtoon_offset: f32,
+    /// 縦スクロール表示: webtoon_offset を計算したときのズーム倍率 (ズーム時にオフセットを合わせる)
+    webtoon_zoom: f32,
+    /// 直近の描画でページが画面からはみ出していたか (縦長ページの幅合わせ・原寸など。ホイールでページ内をスクロールする)
+    page_scrollable: bool,
+    /// 次にはみ出すページを表示したとき、読み始める端 (戻ってきた場合は読み終わりの端) に合わせる
+    align_page_start: bool,
+    /// ページをウィンドウに合わせる方法 (reset では戻さない)
+    fit_mode: FitMode,
+    /// 画面に触れている指・ペン (タッチ ID -> 物理座標)。2 本指でパン・ピンチズームする
+    touches: HashMap<u64, (f32, f32)>,
+    /// ドラッグ中のパンの速さ (物理ピクセル/秒) と最後に動かした時刻 (離したときの慣性に使う)
+    pan_velocity: (f32, f32),
+    pan_moved_at: Option<std::time::Instant>,
+    /// ドラッグを離した後の慣性スクロール
+    inertia: Option<PanInertia>,
+}
+
+/// ドラッグを離した後も、離す直前の速さで滑らせて徐々に止める
+struct PanInertia {
+    velocity: (f32, f32),
+    last_tick: std::time::Instant,
+}
+
+/// 表示中ページ上の基準点 (ウィンドウ中央にあった位置をページ内の比率で記録する)
+struct ViewAnchor {
+    index: usize,
+    u: f32,
+    v: f32,
+    /// 切り替え前のページの表示上の高さ (拡大率の維持に使う)
+    page_height: f32,
+}
+
+/// ズーム/パンのアニメーション開始時の表示状態
+struct ViewAnimation {
+    from_zoom: f32,
+    from_pan: (f32, f32),
+    start: std::time::Instant,
+    duration: std::time::Duration,
 }
 
 impl ViewState {
@@ -247,10 +1042,172 @@ impl ViewState {
             pan_offset: (0.0, 0.0),
             is_panning: false,
             is_loupe: false,
-            loupe_base_zoom: 1.0,
-            loupe_base_pan: (0.0, 0.0),
             last_mouse_pos: (0.0, 0.0),
             cursor_pos: (0.0, 0.0),
+            page_rects: Vec::new(),
+            content_size: (0.0, 0.0),
+            zoom_select_armed: false,
+            zoom_select_start: None,
+            animation: None,
+            pending_anchor: None,
+            webtoon_offset: 0.0,
+            webtoon_zoom: 1.0,
+            page_scrollable: false,
+            align_page_start: true,
+            fit_mode: FitMode::FitWindow,
+            touches: HashMap::new(),
+            pan_velocity: (0.0, 0.0),
+            pan_moved_at: None,
+            inertia: None,
+        }
+    }
+
+    /// ドラッグでパンを始める (滑っている途中なら止める)
+    fn begin_pan(&mut self) {
+        self.is_panning = true;
+        self.inertia = None;
+        self.pan_velocity = (0.0, 0.0);
+        self.pan_moved_at = Some(std::time::Instant::now());
+    }
+
+    /// ドラッグ中の移動量をパンに加え、直近の速さを平滑化して記録する
+    fn drag_pan(&mut self, delta: (f32, f32)) {
+        // ドラッグは直接操作なので、途中のアニメーションは打ち切る
+        self.animation = None;
+        self.pan_offset.0 += delta.0;
+        self.pan_offset.1 += delta.1;
+        let now = std::time::Instant::now();
+        let dt = self.pan_moved_at.map_or(0.0, |t| now.duration_since(t).as_secs_f32());
+        if dt > 0.0 {
+            let instant = (delta.0 / dt, delta.1 / dt);
+            self.pan_velocity = (
+                self.pan_velocity.0 * 0.6 + instant.0 * 0.4,
+                self.pan_velocity.1 * 0.6 + instant.1 * 0.4,
+            );
+        }
+        self.pan_moved_at = Some(now);
+    }
+
+    /// ドラッグを離す。動かしながら離した場合は慣性で滑らせる (enabled = false なら即座に止める)
+    fn end_pan(&mut self, enabled: bool) {
+        /// 離す直前にこれ以上止まっていたら滑らせない
+        const HOLD_LIMIT: std::time::Duration = std::time::Duration::from_millis(60);
+        /// 滑らせる最低の速さ (物理ピクセル/秒)
+        const MIN_SPEED: f32 = 150.0;
+        self.is_panning = false;
+        let moving = self.pan_moved_at.is_some_and(|t| t.elapsed() < HOLD_LIMIT);
+        let speed = self.pan_velocity.0.hypot(self.pan_velocity.1);
+        if enabled && moving && speed >= MIN_SPEED {
+            self.inertia = Some(PanInertia { velocity: self.pan_velocity, last_tick: std::time::Instant::now() });
+        }
+        self.pan_velocity = (0.0, 0.0);
+        self.pan_moved_at = None;
+    }
+
+    /// 描画ごとに呼び、慣性スクロールを経過時間ぶん進める (速さは指数的に減衰させる)
+    fn tick_inertia(&mut self) {
+        /// 速さが 1/e になるまでの時間 (秒)
+        const DECAY: f32 = 0.25;
+        /// これより遅くなったら止める (物理ピクセル/秒)
+        const STOP_SPEED: f32 = 20.0;
+        let Some(ref mut inertia) = self.inertia else { return; };
+        let now = std::time::Instant::now();
+        let dt = now.duration_since(inertia.last_tick).as_secs_f32().min(0.1);
+        inertia.last_tick = now;
+        // 減衰しながら dt 秒進んだ距離 (v * τ * (1 - e^(-dt/τ)))
+        let decay = (-dt / DECAY).exp();
+        let travel = DECAY * (1.0 - decay);
+        self.pan_offset.0 += inertia.velocity.0 * travel;
+        self.pan_offset.1 += inertia.velocity.1 * travel;
+        inertia.velocity = (inertia.velocity.0 * decay, inertia.velocity.1 * decay);
+        if inertia.velocity.0.hypot(inertia.velocity.1) < STOP_SPEED {
+            self.inertia = None;
+        }
+    }
+
+    /// 拡大中なら、ウィンドウ中央にあるページ上の位置を記録してからリセットする
+    /// (表示モードの切り替え用。次の描画で apply_anchor により同じ位置・同じ拡大率に戻す)
+    fn reset_keeping_anchor(&mut self, window_size: (f32, f32)) {
+        let center = (window_size.0 / 2.0, window_size.1 / 2.0);
+        let anchor = (self.zoom_level > 1.0)
+            .then(|| {
+                let (index, rect) = self
+                    .page_rects
+                    .iter()
+                    .find(|(_, r)| center.0 >= r.left && center.0 <= r.right)
+                    .or(self.page_rects.first())?;
+                let (w, h) = (rect.right - rect.left, rect.bottom - rect.top);
+                (w > 0.0 && h > 0.0).then(|| ViewAnchor {
+                    index: *index,
+                    u: (center.0 - rect.left) / w,
+                    v: (center.1 - rect.top) / h,
+                    page_height: h,
+                })
+            })
+            .flatten();
+        self.reset();
+        self.pending_anchor = anchor;
+    }
+
+    /// 切り替え後のページ矩形 rect に対して、記録した基準点がウィンドウ中央に来るようズーム/パンを合わせる
+    fn apply_anchor(&mut self, anchor: &ViewAnchor, rect: D2D_RECT_F, window_size: (f32, f32)) {
+        let (w, h) = (rect.right - rect.left, rect.bottom - rect.top);
+        if w <= 0.0 || h <= 0.0 {
+            return;
+        }
+        let new_zoom = self.zoom_level * anchor.page_height / h;
+        if new_zoom <= 1.0 {
+            return; // 全体表示に収まるならリセットしたままにする
+        }
+        let center = (window_size.0 / 2.0, window_size.1 / 2.0);
+        let old_zoom = self.zoom_level;
+        self.set_zoom(new_zoom, center, window_size);
+        // 中央基準のズームでは各点が中央から factor 倍の位置へ移る
+        let factor = self.zoom_level / old_zoom;
+        let point = (rect.left + anchor.u * w, rect.top + anchor.v * h);
+        self.pan_offset.0 -= (point.0 - center.0) * factor;
+        self.pan_offset.1 -= (point.1 - center.1) * factor;
+    }
+
+    /// 現在表示すべき (ズーム, パン)。アニメーション中は開始値から到達先へ補間する
+    fn displayed_transform(&self) -> (f32, (f32, f32)) {
+        let Some(ref anim) = self.animation else {
+            return (self.zoom_level, self.pan_offset);
+        };
+        let t = (anim.start.elapsed().as_secs_f32() / anim.duration.as_secs_f32()).min(1.0);
+        let e = 1.0 - (1.0 - t).powi(3); // ease-out cubic
+        // ズームは倍率の比で補間すると拡大/縮小の速さが揃う
+        let zoom = anim.from_zoom * (self.zoom_level / anim.from_zoom).powf(e);
+        let pan = (
+            anim.from_pan.0 + (self.pan_offset.0 - anim.from_pan.0) * e,
+            anim.from_pan.1 + (self.pan_offset.1 - anim.from_pan.1) * e,
+        );
+        (zoom, pan)
+    }
+
+    /// `f` でズーム/パンを変更し、変更前の表示状態からアニメーションさせる (duration_ms = 0 なら即時)
+    /// アニメーション中に呼ばれた場合は途中の表示状態から次の到達先へ繋ぐ
+    fn animate<R>(&mut self, duration_ms: u32, f: impl FnOnce(&mut Self) -> R) -> R {
+        let (from_zoom, from_pan) = self.displayed_transform();
+        self.inertia = None;
+        let result = f(self);
+        self.animation = if duration_ms > 0 && (from_zoom != self.zoom_level || from_pan != self.pan_offset) {
+            Some(ViewAnimation {
+                from_zoom,
+                from_pan,
+                start: std::time::Instant::now(),
+                duration: std::time::Duration::from_millis(duration_ms as u64),
+            })
+        } else {
+            None
+        };
+        result
+    }
+
+    /// 描画ごとに呼び、終了したアニメーションを破棄する
+    fn tick_animation(&mut self) {
+        if self.animation.as_ref().is_some_and(|a| a.start.elapsed() >= a.duration) {
+            self.animation = None;
         }
     }
 
@@ -269,10 +1226,6 @@ impl ViewState {
     }
 
     fn clamp_pan_offset(&mut self, window_size: (f32, f32), content_size: (f32, f32)) {
-        if self.is_loupe {
-            // ルーペ中はマウス位置を保持するために制限を緩める
-            return;
-        }
         let max_pan_x = (content_size.0 - window_size.0).max(0.0) / 2.0;
         let max_pan_y = (content_size.1 - window_size.1).max(0.0) / 2.0;
 
@@ -280,49 +1233,714 @@ impl ViewState {
         self.pan_offset.1 = self.pan_offset.1.clamp(-max_pan_y, max_pan_y);
     }
 
+    /// 拡大表示中の見開きを画面単位で読み進める (右綴じなら右ページの上から下、次に左ページ)
+    /// 端まで到達していて移動できない場合は false を返す
+    fn panel_scroll(&mut self, window_size: (f32, f32), right_to_left: bool, direction: isize) -> bool {
+        // 画面の 15% を重ねて送る
+        const OVERLAP: f32 = 0.15;
+        fn stops(content: f32, view: f32, from_positive: bool) -> Vec<f32> {
+            let max = (content - view).max(0.0) / 2.0;
+            if max <= 0.0 {
+                return vec![0.0];
+            }
+            let step = view * (1.0 - OVERLAP);
+            let count = ((2.0 * max) / step).ceil() as usize;
+            (0..=count)
+                .map(|i| {
+                    let d = (i as f32 * step).min(2.0 * max);
+                    if from_positive { max - d } else { -max + d }
+                })
+                .collect()
+        }
+        fn nearest(stops: &[f32], value: f32) -> usize {
+            stops
+                .iter()
+                .enumerate()
+                .min_by(|a, b| (a.1 - value).abs().total_cmp(&(b.1 - value).abs()))
+                .map_or(0, |(i, _)| i)
+        }
+
+        // パンが正のときコンテンツは右/下へずれる (= 左端/上端が見える)
+        let columns = stops(self.content_size.0, window_size.0, !right_to_left);
+        let rows = stops(self.content_size.1, window_size.1, true);
+        let (mut col, mut row) = (nearest(&columns, self.pan_offset.0), nearest(&rows, self.pan_offset.1));
+
+        if direction > 0 {
+            if row + 1 < rows.len() {
+                row += 1;
+            } else if col + 1 < columns.len() {
+                col += 1;
+                row = 0;
+            } else {
+                return false;
+            }
+        } else if row > 0 {
+            row -= 1;
+        } else if col > 0 {
+            col -= 1;
+            row = rows.len() - 1;
+        } else {
+            return false;
+        }
+        self.pan_offset = (columns[col], rows[row]);
+        true
+    }
+
+    /// 指定した矩形 (ウィンドウ座標) がウィンドウいっぱいに収まるよう拡大し、中央に寄せる
+    fn zoom_to_rect(&mut self, rect: D2D_RECT_F, window_size: (f32, f32)) {
+        let w = rect.right - rect.left;
+        let h = rect.bottom - rect.top;
+        if w < 8.0 || h < 8.0 {
+            return; // クリックとほぼ同じなら何もしない
+        }
+        let factor = (window_size.0 / w).min(window_size.1 / h);
+        let center = ((rect.left + rect.right) / 2.0, (rect.top + rect.bottom) / 2.0);
+        // set_zoom は center の位置を保つため、その後で center をウィンドウ中央へ移動する
+        self.set_zoom(self.zoom_level * factor, center, window_size);
+        self.pan_offset.0 += window_size.0 / 2.0 - center.0;
+        self.pan_offset.1 += window_size.1 / 2.0 - center.1;
+    }
+
     fn reset(&mut self) {
         self.zoom_level = 1.0;
         self.pan_offset = (0.0, 0.0);
         self.is_panning = false;
+        self.inertia = None;
         self.is_loupe = false;
+        self.zoom_select_start = None;
+        self.animation = None;
+        self.pending_anchor = None;
+        self.webtoon_offset = 0.0;
+        self.webtoon_zoom = 1.0;
+        self.align_page_start = true;
+    }
+
+    /// 縦方向にスクロールする (縦長ページ用)。パンの範囲の端に着いていて動かせなければ false
+    fn scroll_vertically(&mut self, delta: f32, window_height: f32) -> bool {
+        let limit = (self.content_size.1 - window_height).max(0.0) / 2.0;
+        let next = (self.pan_offset.1 + delta).clamp(-limit, limit);
+        if (next - self.pan_offset.1).abs() < 0.5 {
+            return false;
+        }
+        self.pan_offset.1 = next;
+        self.animation = None;
+        self.inertia = None;
+        true
     }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let config_path = "config.json";
-    let mut settings = Settings::load_or_default(config_path);
-    if !std::path::Path::new(config_path).exists() { let _ = settings.save(config_path); }
+/// 読書中にマウスカーソルを自動で隠す
+struct CursorAutoHide {
+    last_activity: std::time::Instant,
+    hidden: bool,
+}
 
-    // コマンドライン引数のパース
-    let args: Vec<String> = std::env::args().collect();
-    if let Some(pos) = args.iter().position(|a| a == "--threads") {
-        if let Some(val) = args.get(pos + 1) {
-            if let Ok(n) = val.parse::<usize>() {
-                settings.parallel_decoding_workers = n;
-                println!("[設定] スレッド数を引数から {} に設定しました", n);
-            }
+impl CursorAutoHide {
+    fn new() -> Self {
+        Self { last_activity: std::time::Instant::now(), hidden: false }
+    }
+
+    /// マウス操作があった: タイマーをリセットしてカーソルを表示する
+    fn on_activity(&mut self, window: &winit::window::Window) {
+        self.last_activity = std::time::Instant::now();
+        if self.hidden {
+            window.set_cursor_visible(true);
+            self.hidden = false;
         }
     }
 
-    // Rayon Global Thread Pool の初期化
-    let num_threads = settings.parallel_decoding_workers;
-    if num_threads > 0 {
-        let _ = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build_global();
-        println!("[設定] Rayon スレッドプールを {} スレッドで初期化しました", num_threads);
+    fn hide(&mut self, window: &winit::window::Window) {
+        if !self.hidden {
+            window.set_cursor_visible(false);
+            self.hidden = true;
+        }
     }
+}
 
-    // Tokio Runtime
-    let rt = Runtime::new()?;
-    let _guard = rt.enter();
+// メインウィンドウのオーバーレイ (OSD・シークバー・各種パネル) は論理座標 (96 DPI 基準) で配置し、
+// 描画時に scale_rect で物理ピクセルへ変換する。ヒットテストもカーソル位置を論理座標に直して同じ配置関数で行う
 
-    let event_loop = EventLoopBuilder::<UserEvent>::with_user_event().build()?;
-    let proxy = event_loop.create_proxy();
+/// 設定の強調色。lighten (0.0〜1.0) だけ白に寄せる (枠線やドラッグ中の表示用)
+fn accent_color(settings: &Settings, lighten: f32, alpha: f32) -> D2D1_COLOR_F {
+    let [r, g, b] = settings.accent_color.map(|c| c.clamp(0.0, 1.0) + (1.0 - c.clamp(0.0, 1.0)) * lighten);
+    D2D1_COLOR_F { r, g, b, a: alpha }
+}
 
-    let window = Arc::new(WindowBuilder::new()
+/// 論理座標の矩形を物理ピクセルに変換する
+fn scale_rect(rect: &D2D_RECT_F, scale: f32) -> D2D_RECT_F {
+    D2D_RECT_F {
+        left: rect.left * scale,
+        top: rect.top * scale,
+        right: rect.right * scale,
+        bottom: rect.bottom * scale,
+    }
+}
+
+/// ネイティブのステータスバーの高さ (論理座標)。取得できない場合は標準的な高さとみなす
+fn status_bar_logical_height(status_hwnd: Option<HWND>, scale: f32) -> f32 {
+    let Some(hwnd) = status_hwnd else { return 0.0; };
+    // 全画面中は非表示
+    if !unsafe { IsWindowVisible(hwnd) }.as_bool() {
+        return 0.0;
+    }
+    let mut rect = RECT::default();
+    if unsafe { GetWindowRect(hwnd, &mut rect) }.is_ok() && rect.bottom > rect.top {
+        (rect.bottom - rect.top) as f32 / scale
+    } else {
+        22.0
+    }
+}
+
+/// ページを配置する領域の大きさ (物理ピクセル)。表示中のステータスバーの分を除く
+fn page_area_size(window: &winit::window::Window, status_hwnd: Option<HWND>) -> (f32, f32) {
+    let size = window.inner_size();
+    let scale = window.scale_factor() as f32;
+    let status_bar_h = status_bar_logical_height(status_hwnd, scale) * scale;
+    (size.width as f32, (size.height as f32 - status_bar_h).max(1.0))
+}
+
+/// シークバーの矩形 (論理座標、ステータスバーの直上)
+fn seekbar_rect(logical_w: f32, logical_h: f32, status_bar_h: f32, dragging: bool) -> D2D_RECT_F {
+    let bar_height = if dragging { 12.0 } else { 8.0 };
+    let top = logical_h - status_bar_h - bar_height;
+    D2D_RECT_F { left: 0.0, top, right: logical_w, bottom: top + bar_height }
+}
+
+/// ページジャンプ入力パネルの矩形 (論理座標、中央)
+fn jump_panel_rect(logical_w: f32, logical_h: f32) -> D2D_RECT_F {
+    let (jump_w, jump_h) = (340.0, 160.0);
+    D2D_RECT_F {
+        left: (logical_w - jump_w) / 2.0,
+        top: (logical_h - jump_h) / 2.0,
+        right: (logical_w + jump_w) / 2.0,
+        bottom: (logical_h + jump_h) / 2.0,
+    }
+}
+
+const OSD_HEIGHT: f32 = 36.0;
+
+/// OSD の不透明度 (約1秒表示したあと、フェードアウトして 0 になる)
+fn osd_alpha(start: std::time::Instant) -> f32 {
+    const OSD_HOLD_SECS: f32 = 1.0;
+    const OSD_FADE_SECS: f32 = 0.4;
+    let elapsed = start.elapsed().as_secs_f32();
+    if elapsed < OSD_HOLD_SECS {
+        1.0
+    } else {
+        (1.0 - (elapsed - OSD_HOLD_SECS) / OSD_FADE_SECS).max(0.0)
+    }
+}
+
+/// シークバーが右端から進むか (右綴じなら右から。左手モードではさらに反転する)
+fn seekbar_from_right(app_state: &AppState, settings: &Settings) -> bool {
+    (app_state.binding_direction == BindingDirection::Right) != settings.left_handed_mode
+}
+
+/// OSD の枠の幅の目安 (全角文字は半角の約2倍で見積もる)
+fn osd_text_width(text: &str) -> f32 {
+    40.0 + text.chars().map(|c| if c.is_ascii() { 11.0 } else { 18.0 }).sum::<f32>()
+}
+
+/// 半透明の背景付きで OSD テキストを中央揃えで描画する
+fn draw_osd_box(renderer: &dyn Renderer, text: &str, rect: &D2D_RECT_F, alpha: f32) {
+    renderer.fill_rectangle(rect, &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.6 * alpha });
+    renderer.set_text_alignment(DWRITE_TEXT_ALIGNMENT_CENTER);
+    renderer.draw_text(text, rect, &D2D1_COLOR_F { r: 1.0, g: 1.0, b: 1.0, a: alpha }, false);
+    renderer.set_text_alignment(DWRITE_TEXT_ALIGNMENT_LEADING);
+}
+
+const SERIES_ROW_HEIGHT: f32 = 26.0;
+const SERIES_HEADER_HEIGHT: f32 = 50.0;
+
+/// シリーズ一覧オーバーレイの配置 (パネル矩形, 先頭に表示する項目, 表示行数)
+fn series_overlay_layout(overlay: &SeriesOverlay, win_w: f32, win_h: f32) -> (D2D_RECT_F, usize, usize) {
+    let max_rows = (((win_h - 160.0) / SERIES_ROW_HEIGHT).floor() as usize).clamp(1, 20);
+    let rows = overlay.entries.len().min(max_rows);
+    let first = overlay.selected.saturating_sub(rows / 2).min(overlay.entries.len() - rows);
+    let panel_w = (win_w - 80.0).min(640.0);
+    let panel_h = SERIES_HEADER_HEIGHT + rows as f32 * SERIES_ROW_HEIGHT + 16.0;
+    let panel = D2D_RECT_F {
+        left: (win_w - panel_w) / 2.0,
+        top: (win_h - panel_h) / 2.0,
+        right: (win_w + panel_w) / 2.0,
+        bottom: (win_h + panel_h) / 2.0,
+    };
+    (panel, first, rows)
+}
+
+/// フォルダ/アーカイブのサイドバーを左端に描画する (height・cursor は論理座標)
+fn draw_browser_pane(
+    renderer: &dyn Renderer,
+    settings: &Settings,
+    pane: &crate::ui::browser::BrowserPane,
+    height: f32,
+    cursor: (f32, f32),
+    ui_scale: f32,
+) {
+    use crate::ui::browser::{BrowserPane, BrowserRow, HEADER_HEIGHT, PANE_WIDTH};
+    let panel = D2D_RECT_F { left: 0.0, top: 0.0, right: PANE_WIDTH, bottom: height };
+    renderer.fill_rectangle(&scale_rect(&panel, ui_scale), &D2D1_COLOR_F { r: 0.05, g: 0.05, b: 0.05, a: 0.92 });
+    renderer.draw_rectangle(&scale_rect(&panel, ui_scale), &D2D1_COLOR_F { r: 0.3, g: 0.3, b: 0.3, a: 1.0 }, ui_scale);
+
+    let title = match pane.dir.as_deref() {
+        Some(dir) => dir.file_name().unwrap_or(dir.as_os_str()).to_string_lossy().to_string(),
+        None => "ソースを開いていません".to_string(),
+    };
+    let title_rect = D2D_RECT_F { left: 12.0, top: 8.0, right: PANE_WIDTH - 8.0, bottom: HEADER_HEIGHT - 4.0 };
+    renderer.draw_text(&title, &scale_rect(&title_rect, ui_scale), &D2D1_COLOR_F { r: 0.6, g: 0.6, b: 0.6, a: 1.0 }, false);
+
+    let hovered = pane.row_at(cursor.0, cursor.1, height);
+    let rows = BrowserPane::visible_rows(height).min(pane.row_count().saturating_sub(pane.scroll));
+    for visible in 0..rows {
+        let Some(row) = pane.row(pane.scroll + visible) else { break; };
+        let row_rect = BrowserPane::row_rect(visible);
+        let is_current = matches!(row, BrowserRow::Entry(i) if Some(i) == pane.current);
+        if is_current {
+            renderer.fill_rectangle(&scale_rect(&row_rect, ui_scale), &accent_color(settings, 0.0, 0.8));
+        } else if hovered == Some(row) {
+            renderer.fill_rectangle(&scale_rect(&row_rect, ui_scale), &D2D1_COLOR_F { r: 1.0, g: 1.0, b: 1.0, a: 0.08 });
+        }
+        let (text, color) = match row {
+            BrowserRow::Parent => ("..".to_string(), D2D1_COLOR_F { r: 0.6, g: 0.6, b: 0.6, a: 1.0 }),
+            BrowserRow::Entry(i) => {
+                let entry = &pane.entries[i];
+                let marker = if entry.is_dir { "▸ " } else { "   " };
+                let color = if is_current {
+                    D2D1_COLOR_F { r: 1.0, g: 0.8, b: 0.0, a: 1.0 }
+                } else {
+                    D2D1_COLOR_F { r: 0.9, g: 0.9, b: 0.9, a: 1.0 }
+                };
+                (format!("{}{}", marker, entry.name()), color)
+            }
+        };
+        let text_rect = D2D_RECT_F { left: row_rect.left + 12.0, right: row_rect.right - 8.0, ..row_rect };
+        renderer.draw_text(&text, &scale_rect(&text_rect, ui_scale), &color, false);
+    }
+}
+
+fn series_row_rect(panel: &D2D_RECT_F, row: usize) -> D2D_RECT_F {
+    let top = panel.top + SERIES_HEADER_HEIGHT + row as f32 * SERIES_ROW_HEIGHT;
+    D2D_RECT_F { left: panel.left + 8.0, top, right: panel.right - 8.0, bottom: top + SERIES_ROW_HEIGHT }
+}
+
+/// 表示中の先頭ページの、原寸に対する現在の表示倍率
+fn current_pixel_scale(
+    app_state: &AppState,
+    view_state: &ViewState,
+    gpu_cache: &GpuCache,
+    renderer: &dyn Renderer,
+) -> Option<f32> {
+    let (index, rect) = view_state.page_rects.first()?;
+    let texture = gpu_cache.get(*index)?;
+    let (tex_w, tex_h) = renderer.get_texture_size(texture);
+    let (display_w, _) = app_state.page_transform(*index).display_size(tex_w, tex_h);
+    (display_w > 0.0).then(|| (rect.right - rect.left) / display_w)
+}
+
+/// ページの輝度ヒストグラム (ヒストグラム, GPU で集計したか)
+/// GPU のテクスチャがあればコンピュートシェーダで集計し、使えなければ CPU キャッシュのデコード結果から集計する
+fn page_histogram(
+    renderer: &dyn Renderer,
+    gpu_cache: &GpuCache,
+    cpu_cache: &SharedImageCache,
+    path_key: &str,
+    index: usize,
+) -> Option<([u32; 256], bool)> {
+    if let Some(texture) = gpu_cache.get(index) {
+        if let Some(histogram) = renderer.compute_histogram(texture) {
+            return Some((histogram, true));
+        }
+    }
+    let decoded = cpu_cache.lock().unwrap().get(&format!("{}::{}", path_key, index))?;
+    Some((decoded.luminance_histogram(), false))
+}
+
+/// スポイトで取得した画素 (元画像上の座標と RGBA 値)
+struct PixelSample {
+    x: u32,
+    y: u32,
+    rgba: [u8; 4],
+}
+
+impl PixelSample {
+    fn hex(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.rgba[0], self.rgba[1], self.rgba[2])
+    }
+}
+
+/// カーソル下の画素を CPU キャッシュのデコード結果から取得する (拡大縮小・色調整前の値)
+fn sample_pixel(
+    app_state: &AppState,
+    page_rects: &[(usize, D2D_RECT_F)],
+    cpu_cache: &SharedImageCache,
+    path_key: &str,
+    pos: (f32, f32),
+) -> Option<PixelSample> {
+    let &(index, rect) = page_rects.iter().find(|(_, r)| point_in_rect(pos, r))?;
+    let (w, h) = (rect.right - rect.left, rect.bottom - rect.top);
+    if w <= 0.0 || h <= 0.0 {
+        return None;
+    }
+    let [u, v] = app_state.page_transform(index).source_uv((pos.0 - rect.left) / w, (pos.1 - rect.top) / h);
+    let decoded = cpu_cache.lock().unwrap().get(&format!("{}::{}", path_key, index))?;
+    let px = ((u * decoded.width as f32) as u32).min(decoded.width.saturating_sub(1));
+    let py = ((v * decoded.height as f32) as u32).min(decoded.height.saturating_sub(1));
+    let rgba = decoded.pixel_rgba(px, py)?;
+    let (x, y) = decoded.source_position(px, py);
+    Some(PixelSample { x, y, rgba })
+}
+
+const PIXEL_PANEL_SIZE: (f32, f32) = (190.0, 66.0);
+
+/// スポイトの値をカーソルの右下 (画面端では反対側) に表示する (座標は論理座標)
+fn draw_pixel_panel(renderer: &dyn Renderer, sample: Option<&PixelSample>, cursor: (f32, f32), window: (f32, f32), ui_scale: f32) {
+    let (panel_w, panel_h) = PIXEL_PANEL_SIZE;
+    let left = if cursor.0 + 20.0 + panel_w > window.0 { cursor.0 - 20.0 - panel_w } else { cursor.0 + 20.0 };
+    let top = if cursor.1 + 20.0 + panel_h > window.1 { cursor.1 - 20.0 - panel_h } else { cursor.1 + 20.0 };
+    let panel = D2D_RECT_F { left, top, right: left + panel_w, bottom: top + panel_h };
+    renderer.fill_rectangle(&scale_rect(&panel, ui_scale), &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.75 });
+
+    let text_color = D2D1_COLOR_F { r: 0.9, g: 0.9, b: 0.9, a: 1.0 };
+    let Some(sample) = sample else {
+        let rect = D2D_RECT_F { left: left + 10.0, top: top + 8.0, right: panel.right - 10.0, bottom: top + 28.0 };
+        renderer.draw_text("ページ外 / 読み込み中", &scale_rect(&rect, ui_scale), &text_color, false);
+        return;
+    };
+    let swatch = D2D_RECT_F { left: left + 10.0, top: top + 10.0, right: left + 56.0, bottom: top + 56.0 };
+    let [r, g, b, a] = sample.rgba;
+    let color = D2D1_COLOR_F { r: r as f32 / 255.0, g: g as f32 / 255.0, b: b as f32 / 255.0, a: 1.0 };
+    renderer.fill_rectangle(&scale_rect(&swatch, ui_scale), &color);
+    renderer.draw_rectangle(&scale_rect(&swatch, ui_scale), &D2D1_COLOR_F { r: 0.6, g: 0.6, b: 0.6, a: 1.0 }, ui_scale);
+
+    let lines = [
+        sample.hex(),
+        format!("RGBA {}, {}, {}, {}", r, g, b, a),
+        format!("座標 ({}, {})", sample.x, sample.y),
+    ];
+    for (i, line) in lines.iter().enumerate() {
+        let line_top = top + 6.0 + i as f32 * 18.0;
+        let rect = D2D_RECT_F { left: left + 66.0, top: line_top, right: panel.right - 6.0, bottom: line_top + 18.0 };
+        renderer.draw_text(line, &scale_rect(&rect, ui_scale), &text_color, false);
+    }
+}
+
+const IMAGE_INFO_PANEL_WIDTH: f32 = 380.0;
+const IMAGE_INFO_LINE_HEIGHT: f32 = 20.0;
+
+/// 表示中のページの画像情報 ((見出し, 行)。見開きではページ番号順に並べる)
+fn image_info_sections(app_state: &AppState, cpu_cache: &SharedImageCache, path_key: &str) -> Vec<(String, Vec<String>)> {
+    let mut indices = app_state.get_page_indices_to_display();
+    indices.sort();
+    indices
+        .into_iter()
+        .map(|index| {
+            let name = app_state
+                .image_files
+                .get(index)
+                .and_then(|f| std::path::Path::new(f).file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_default();
+            let title = format!("ページ {}: {}", index + 1, name);
+            let lines = match cpu_cache.lock().unwrap().get(&format!("{}::{}", path_key, index)) {
+                Some(decoded) => decoded.info_lines(),
+                None => vec!["読み込み中...".to_string()],
+            };
+            (title, lines)
+        })
+        .collect()
+}
+
+/// 画像情報パネル (I) を描く (座標は論理座標)
+fn draw_image_info_panel(renderer: &dyn Renderer, sections: &[(String, Vec<String>)], left: f32, top: f32, ui_scale: f32) {
+    let rows: usize = sections.iter().map(|(_, lines)| 1 + lines.len()).sum();
+    let gaps = sections.len().saturating_sub(1) as f32 * 8.0;
+    let panel = D2D_RECT_F {
+        left,
+        top,
+        right: left + IMAGE_INFO_PANEL_WIDTH,
+        bottom: top + 12.0 + rows as f32 * IMAGE_INFO_LINE_HEIGHT + gaps,
+    };
+    renderer.fill_rectangle(&scale_rect(&panel, ui_scale), &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.75 });
+
+    let title_color = D2D1_COLOR_F { r: 1.0, g: 1.0, b: 1.0, a: 1.0 };
+    let text_color = D2D1_COLOR_F { r: 0.8, g: 0.8, b: 0.8, a: 1.0 };
+    let mut y = top + 6.0;
+    for (title, lines) in sections {
+        for (i, line) in std::iter::once(title).chain(lines.iter()).enumerate() {
+            let indent = if i == 0 { 10.0 } else { 22.0 };
+            let rect = D2D_RECT_F { left: left + indent, top: y, right: panel.right - 10.0, bottom: y + IMAGE_INFO_LINE_HEIGHT };
+            renderer.draw_text(line, &scale_rect(&rect, ui_scale), if i == 0 { &title_color } else { &text_color }, false);
+            y += IMAGE_INFO_LINE_HEIGHT;
+        }
+        y += 8.0;
+    }
+}
+
+/// 自動レベル補正で黒点・白点の外側として切り捨てる画素の割合
+const AUTO_LEVELS_CLIP: f32 = 0.005;
+
+const HISTOGRAM_PANEL_SIZE: (f32, f32) = (276.0, 130.0);
+
+/// 輝度ヒストグラムのオーバーレイを描画する (panel は論理座標)
+fn draw_histogram_overlay(renderer: &dyn Renderer, histogram: &[u32; 256], on_gpu: bool, panel: &D2D_RECT_F, ui_scale: f32) {
+    renderer.fill_rectangle(&scale_rect(panel, ui_scale), &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.7 });
+    let label_rect = D2D_RECT_F { left: panel.left + 10.0, top: panel.top + 4.0, right: panel.right - 10.0, bottom: panel.top + 24.0 };
+    let label = format!("輝度ヒストグラム ({})", if on_gpu { "GPU" } else { "CPU" });
+    renderer.draw_text(&label, &scale_rect(&label_rect, ui_scale), &D2D1_COLOR_F { r: 0.8, g: 0.8, b: 0.8, a: 1.0 }, false);
+
+    // 256 ビンを 1 論理ピクセルずつ並べ、最大ビンで正規化する
+    let (graph_left, graph_bottom, graph_h) = (panel.left + 10.0, panel.bottom - 10.0, panel.bottom - panel.top - 44.0);
+    let max = histogram.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let bar_color = D2D1_COLOR_F { r: 0.85, g: 0.85, b: 0.85, a: 0.9 };
+    for (bin, &count) in histogram.iter().enumerate() {
+        if count == 0 { continue; }
+        let x = graph_left + bin as f32;
+        let bar = D2D_RECT_F { left: x, top: graph_bottom - graph_h * count as f32 / max, right: x + 1.0, bottom: graph_bottom };
+        renderer.fill_rectangle(&scale_rect(&bar, ui_scale), &bar_color);
+    }
+
+    // 自動レベル補正で使う黒点・白点
+    let (black, white) = crate::render::histogram_levels(histogram, AUTO_LEVELS_CLIP);
+    let marker_color = D2D1_COLOR_F { r: 0.3, g: 0.7, b: 1.0, a: 1.0 };
+    for level in [black, white] {
+        let x = graph_left + (level * 255.0).round();
+        let marker = D2D_RECT_F { left: x, top: graph_bottom - graph_h, right: x + 1.0, bottom: graph_bottom };
+        renderer.fill_rectangle(&scale_rect(&marker, ui_scale), &marker_color);
+    }
+}
+
+/// ルーペの一辺の長さ (論理座標)
+const LOUPE_SIZE: f32 = 320.0;
+
+/// 縦スクロール表示・縦長ページでのホイール 1 ノッチあたりのスクロール量 (論理ピクセル)
+const WHEEL_SCROLL_STEP: f32 = 120.0;
+
+/// ルーペ用の縮小済み画像の一辺の最大ピクセル数 (2 倍のスーパーサンプリング込み)
+const LOUPE_PRESCALE_SIZE: f32 = 2048.0;
+
+/// ルーペ用にカーソル周辺だけを CPU で縮小しておいた画像
+/// 高倍率では巨大なテクスチャを毎フレーム Lanczos で補間するのが重いため、
+/// 表示の 2 倍の解像度で作っておき、カーソルがこの範囲にある間は差し替えて描画する
+struct LoupePrescale {
+    generation: u64,
+    path_key: String,
+    index: usize,
+    /// 要求時のページの表示矩形・倍率・回転 (変わったら作り直す)
+    page_rect: D2D_RECT_F,
+    zoom: f32,
+    transform: PageTransform,
+    /// 縮小した範囲 (ページ上の画面座標)
+    region: D2D_RECT_F,
+    /// 作成中は None
+    texture: Option<TextureHandle>,
+}
+
+impl LoupePrescale {
+    fn matches(&self, path_key: &str, info: &PageDrawInfo, zoom: f32) -> bool {
+        self.path_key == path_key
+            && self.index == info.index
+            && self.transform == info.transform
+            && self.zoom == zoom
+            && rect_eq(&self.page_rect, &info.dest_rect)
+    }
+
+    /// ルーペに映るページ上の範囲 (visible) が縮小済みの範囲に収まっているか
+    fn covers(&self, visible: &D2D_RECT_F) -> bool {
+        let v = D2D_RECT_F {
+            left: visible.left.max(self.page_rect.left),
+            top: visible.top.max(self.page_rect.top),
+            right: visible.right.min(self.page_rect.right),
+            bottom: visible.bottom.min(self.page_rect.bottom),
+        };
+        v.left >= self.region.left && v.top >= self.region.top && v.right <= self.region.right && v.bottom <= self.region.bottom
+    }
+}
+
+fn rect_eq(a: &D2D_RECT_F, b: &D2D_RECT_F) -> bool {
+    a.left == b.left && a.top == b.top && a.right == b.right && a.bottom == b.bottom
+}
+
+fn point_in_rect(p: (f32, f32), r: &D2D_RECT_F) -> bool {
+    p.0 >= r.left && p.0 <= r.right && p.1 >= r.top && p.1 <= r.bottom
+}
+
+/// カーソル周辺のルーペ用縮小画像の作成を要求する (完了すると UserEvent::LoupePrescaled が届く)
+/// 元画像がルーペの表示より小さい (拡大になる) 場合や YCbCr のページは GPU の補間で十分なので作らない
+fn request_loupe_prescale(
+    generation: u64,
+    path_key: &str,
+    info: &PageDrawInfo,
+    cursor: (f32, f32),
+    zoom: f32,
+    rotation: u32,
+    cpu_cache: &SharedImageCache,
+    rt: &Runtime,
+    proxy: &winit::event_loop::EventLoopProxy<UserEvent>,
+) -> Option<LoupePrescale> {
+    let page = info.dest_rect;
+    let (page_w, page_h) = (page.right - page.left, page.bottom - page.top);
+    if page_w <= 0.0 || page_h <= 0.0 || zoom <= 1.0 {
+        return None;
+    }
+    let key = format!("{}::{}", path_key, info.index);
+    let decoded = cpu_cache.lock().unwrap().get(&key).filter(|d| d.rotation == rotation)?;
+    let PixelData::Rgba8(_) = decoded.pixel_data else { return None; };
+
+    // カーソルを中心に、拡大・2 倍スーパーサンプリング後に LOUPE_PRESCALE_SIZE になる範囲
+    let half = LOUPE_PRESCALE_SIZE / (2.0 * zoom) / 2.0;
+    let (nx0, ny0) = (((cursor.0 - half - page.left) / page_w).max(0.0), ((cursor.1 - half - page.top) / page_h).max(0.0));
+    let (nx1, ny1) = (((cursor.0 + half - page.left) / page_w).min(1.0), ((cursor.1 + half - page.top) / page_h).min(1.0));
+    if nx0 >= nx1 || ny0 >= ny1 {
+        return None;
+    }
+    // 表示上の範囲をテクスチャ上の画素範囲に直す (回転・反転は軸に沿うので対角の 2 点で足りる)
+    let [u0, v0] = info.transform.source_uv(nx0, ny0);
+    let [u1, v1] = info.transform.source_uv(nx1, ny1);
+    let (w, h) = (decoded.width as f32, decoded.height as f32);
+    let x0 = (u0.min(u1) * w).floor() as u32;
+    let y0 = (v0.min(v1) * h).floor() as u32;
+    let x1 = ((u0.max(u1) * w).ceil() as u32).min(decoded.width);
+    let y1 = ((v0.max(v1) * h).ceil() as u32).min(decoded.height);
+    if x0 >= x1 || y0 >= y1 {
+        return None;
+    }
+    let region = info.transform.map_rect(x0 as f32 / w, y0 as f32 / h, x1 as f32 / w, y1 as f32 / h, &page);
+    let (region_w, region_h) = info.transform.display_size(region.right - region.left, region.bottom - region.top);
+    let out_w = (region_w * zoom * 2.0).round().max(1.0) as u32;
+    let out_h = (region_h * zoom * 2.0).round().max(1.0) as u32;
+    if out_w >= x1 - x0 || out_h >= y1 - y0 {
+        return None;
+    }
+
+    let proxy = proxy.clone();
+    rt.spawn_blocking(move || {
+        let PixelData::Rgba8(ref data) = decoded.pixel_data else { return; };
+        let stride = decoded.width as usize * 4;
+        let mut cropped = Vec::with_capacity((x1 - x0) as usize * (y1 - y0) as usize * 4);
+        for y in y0..y1 {
+            let start = y as usize * stride + x0 as usize * 4;
+            cropped.extend_from_slice(&data[start..start + (x1 - x0) as usize * 4]);
+        }
+        let Some(buffer) = ::image::RgbaImage::from_raw(x1 - x0, y1 - y0, cropped) else { return; };
+        let scaled = ::image::imageops::resize(&buffer, out_w, out_h, ::image::imageops::FilterType::Lanczos3);
+        let image = crate::image::cache::DecodedImage {
+            width: out_w,
+            height: out_h,
+            pixel_data: PixelData::Rgba8(scaled.into_raw()),
+            rotation: decoded.rotation,
+            metadata: decoded.metadata.clone(),
+        };
+        let _ = proxy.send_event(UserEvent::LoupePrescaled { generation, image: Arc::new(image) });
+    });
+
+    Some(LoupePrescale {
+        generation,
+        path_key: path_key.to_string(),
+        index: info.index,
+        page_rect: page,
+        zoom,
+        transform: info.transform,
+        region,
+        texture: None,
+    })
+}
+
+/// rect を center を中心に zoom 倍に拡大した矩形
+fn magnify_rect(rect: &D2D_RECT_F, center: (f32, f32), zoom: f32) -> D2D_RECT_F {
+    D2D_RECT_F {
+        left: center.0 + (rect.left - center.0) * zoom,
+        top: center.1 + (rect.top - center.1) * zoom,
+        right: center.0 + (rect.right - center.0) * zoom,
+        bottom: center.1 + (rect.bottom - center.1) * zoom,
+    }
+}
+
+/// カーソルがいずれかのページの描画範囲上にあるか
+fn is_over_page(view_state: &ViewState) -> bool {
+    let (x, y) = view_state.cursor_pos;
+    view_state
+        .page_rects
+        .iter()
+        .any(|(_, r)| x >= r.left && x <= r.right && y >= r.top && y <= r.bottom)
+}
+
+/// カーソルを隠してはいけない操作中か (ドラッグ・ジャンプ入力・OCR 選択など)
+fn is_cursor_in_use(app_state: &AppState, view_state: &ViewState) -> bool {
+    app_state.is_jump_open
+        || app_state.series_overlay.is_some()
+        || app_state.is_dragging_seekbar
+        || app_state.is_ocr_mode
+        || app_state.annotation_tool.is_some()
+        || app_state.crop_edit.is_some()
+        || app_state.is_color_picker
+        || view_state.is_panning
+        || view_state.is_loupe
+        || view_state.zoom_select_start.is_some()
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // --register / --unregister: エクスプローラーとの関連付けを登録・解除して終了する (ウィンドウは開かない)
+    // データフォルダを作成・移行しないよう、クラッシュハンドラや設定の読み込みより先に処理する
+    if let Some(mode) = std::env::args().find(|a| a == "--register" || a == "--unregister") {
+        let (result, done) = if mode == "--register" {
+            (crate::platform::association::register(), "関連付けを登録しました")
+        } else {
+            (crate::platform::association::unregister(), "関連付けを解除しました")
+        };
+        match result {
+            Ok(()) => ui::dialogs::show_message_dialog("HayateViewer - 関連付け", done, false),
+            Err(e) => {
+                eprintln!("[関連付け] {}", e);
+                ui::dialogs::show_message_dialog(
+                    "HayateViewer - 関連付け",
+                    &format!("関連付けに失敗しました:\n{}", e),
+                    true,
+                );
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    // パニック・未処理例外時にミニダンプと読書位置を保存する
+    crate::platform::crash::install();
+
+    let config_path = crate::config::config_path();
+    let mut settings = Settings::load_or_default(config_path);
+    if !std::path::Path::new(config_path).exists() { let _ = settings.save_now(config_path); }
+    settings.apply_env_overrides();
+
+    // コマンドライン引数のパース
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|a| a == "--threads") {
+        if let Some(val) = args.get(pos + 1) {
+            if let Ok(n) = val.parse::<usize>() {
+                settings.parallel_decoding_workers = n;
+                println!("[設定] スレッド数を引数から {} に設定しました", n);
+            }
+        }
+    }
+
+    // デコード用スレッドプールの初期化 (0 = 論理 CPU 数に合わせる)
+    crate::image::decoder::set_decode_workers(settings.parallel_decoding_workers);
+    // フォルダの画像の並び順 (起動時に開くソースより先に反映する)
+    crate::image::sort::set_sort_order(crate::image::sort::SortOrder::from_setting(&settings.file_sort_order));
+
+    // Tokio Runtime
+    let rt = Runtime::new()?;
+    let _guard = rt.enter();
+
+    let event_loop = EventLoopBuilder::<UserEvent>::with_user_event().build()?;
+    let proxy = event_loop.create_proxy();
+
+    // 前回のウィンドウ位置・サイズを復元 (画面外に出ないよう仮想スクリーン内に収める)
+    let (geo_x, geo_y, geo_w, geo_h) = settings.window_geometry;
+    let (win_x, win_y, win_w, win_h) = clamp_to_virtual_screen(geo_x, geo_y, geo_w, geo_h);
+
+    let window = Arc::new(WindowBuilder::new()
         .with_title(format!("HayateViewer Rust v{}", VERSION))
-        .with_inner_size(winit::dpi::LogicalSize::new(settings.window_size.0, settings.window_size.1))
+        .with_inner_size(winit::dpi::PhysicalSize::new(win_w, win_h))
+        .with_position(winit::dpi::PhysicalPosition::new(win_x, win_y))
+        .with_maximized(settings.window_maximized)
+        .with_fullscreen(settings.window_fullscreen.then(|| winit::window::Fullscreen::Borderless(None)))
         .with_drag_and_drop(true)
         .build(&event_loop)?);
 
@@ -333,9 +1951,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Windows システムステータスバーを作成
     let status_bar_hwnd = create_status_bar(hwnd);
-    if status_bar_hwnd.is_some() {
+    if let Some(sb_hwnd) = status_bar_hwnd {
         println!("[UI] Windows システムステータスバーを作成しました");
+        update_status_bar_font(sb_hwnd, window.scale_factor() as f32);
     }
+    // 全画面で起動した場合はステータスバーを隠す
+    set_status_bar_visible(status_bar_hwnd, !settings.window_fullscreen);
 
 
 
@@ -370,17 +1991,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("[情報] レンダリングエンジン: {}", settings.rendering_backend);
     let mut view_state = ViewState::new();
+    view_state.fit_mode = FitMode::from_setting(&settings.fit_mode);
     let mut app_state = AppState::new();
+    app_state.positions = crate::state::positions::PositionStore::load(config_path);
+    app_state.stats = crate::state::stats::ReadingStats::load(config_path);
     let mut current_path_key = String::new();
 
-    app_state.is_spread_view = settings.is_spread_view;
-    app_state.binding_direction = if settings.binding_direction == "right" { BindingDirection::Right } else { BindingDirection::Left };
+    apply_display_mode(&mut app_state, &settings);
     app_state.spread_view_first_page_single = settings.spread_view_first_page_single;
+    app_state.chapter_start_single = settings.chapter_start_single;
+    app_state.wide_page_single = settings.wide_page_single;
+    app_state.split_wide_pages = settings.split_wide_pages;
+    if settings.show_browser_pane {
+        app_state.browser_pane = Some(crate::ui::browser::BrowserPane::new());
+    }
 
     // Cache & Loader
     let max_bytes = (settings.max_cache_size_mb as usize) * 1024 * 1024;
     let cpu_cache = create_shared_cache(100, max_bytes);
-    let loader = AsyncLoader::new(cpu_cache.clone(), proxy.clone());
+    let loader = AsyncLoader::new(cpu_cache.clone(), proxy.clone(), open_disk_cache(&settings));
 
     let gpu_mode = match settings.resampling_mode_gpu.as_str() {
         "Nearest" => crate::render::InterpolationMode::NearestNeighbor,
@@ -390,17 +2019,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         _ => crate::render::InterpolationMode::Linear,
     };
     renderer.set_interpolation_mode(gpu_mode);
+    renderer.set_chroma_upsampling(crate::render::ChromaUpsampling::from_setting(&settings.chroma_upsampling));
 
-    let mut current_bitmaps: Vec<(usize, crate::render::TextureHandle)> = Vec::new();
+    let mut gpu_cache = GpuCache::new();
+    let mut staged_uploads: Vec<StagedUpload> = Vec::new();
+    // 先読みページを別スレッドで転送するアップローダー (対応するバックエンドのみ)
+    let mut async_uploader = renderer.texture_uploader().map(crate::render::uploader::AsyncUploader::start);
+    // ルーペ用の縮小済み画像 (ルーペを閉じると破棄する)
+    let mut loupe_prescale: Option<LoupePrescale> = None;
+    let mut loupe_generation: u64 = 0;
+    // パフォーマンス HUD (F3) のフレーム時間統計
+    let mut frame_stats = perf_hud::FrameStats::default();
 
-    // 初期パスの読み込み
+    // 初期パスの読み込み (引数で開く場合はクラッシュ復元情報を破棄する)
+    let recovery = crate::platform::crash::take_recovery();
     let args: Vec<String> = std::env::args().collect();
-    if args.len() > 1 {
-        if let Some(src) = get_image_source(&args[1]) {
+    if let Some(open_arg) = open_path_arg(&args) {
+        if let Some((path, src, page)) = resolve_open_path(open_arg) {
             load_new_source(
                 src,
-                args[1].clone(),
-                0,
+                path,
+                page,
                 None,
                 &mut app_state,
                 &mut current_path_key,
@@ -409,18 +2048,98 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 &loader,
                 &rt,
                 &mut settings,
-                &mut current_bitmaps,
+                &mut gpu_cache,
+                &mut view_state,
                 false, // 通常のファイル読み込み
             );
         }
+    } else if let Some(recovery) = recovery {
+        // 前回クラッシュした場合は読んでいた位置からの再開を提案する
+        println!("[クラッシュ] 前回の異常終了を検出: {}", recovery.message);
+        let message = format!(
+            "前回は異常終了しました。\n読んでいた位置から再開しますか？\n\n{} ({}ページ)",
+            recovery.path,
+            recovery.page + 1
+        );
+        if ui::dialogs::show_confirm_dialog(hwnd, "HayateViewer - 復元", &message) {
+            if let Some(src) = get_image_source(&recovery.path) {
+                load_new_source(
+                    src,
+                    recovery.path,
+                    Some(recovery.page),
+                    Some(recovery.binding),
+                    &mut app_state,
+                    &mut current_path_key,
+                    &window,
+                    &cpu_cache,
+                    &loader,
+                    &rt,
+                    &mut settings,
+                    &mut gpu_cache,
+                    &mut view_state,
+                    false,
+                );
+            }
+        }
+    } else if settings.reopen_last_on_startup {
+        // 空のウィンドウの代わりに最後に読んだソースを開く (ズーム・色調整はソース別設定から復元される)
+        if let Some(item) = settings.history.first().cloned() {
+            match get_image_source(&item.path) {
+                Some(src) => {
+                    println!("[起動] 前回のソースを開きます: {} ({}ページ)", item.path, item.page + 1);
+                    load_new_source(
+                        src,
+                        item.path,
+                        Some(item.page),
+                        Some(item.binding),
+                        &mut app_state,
+                        &mut current_path_key,
+                        &window,
+                        &cpu_cache,
+                        &loader,
+                        &rt,
+                        &mut settings,
+                        &mut gpu_cache,
+                        &mut view_state,
+                        true, // 履歴の順序はそのまま
+                    );
+                    app_state.current_history_index = Some(0);
+                }
+                None => println!("[起動] 前回のソースを開けませんでした: {}", item.path),
+            }
+        }
     }
 
     let mut modifiers = ModifiersState::default();
+    let mut keymap = Keymap::from_settings(&settings.key_bindings);
+    spawn_config_watcher(config_path, proxy.clone());
+    // 更新確認 (週1回、設定で無効化可能)
+    if settings.check_for_updates
+        && crate::platform::update::now_secs().saturating_sub(settings.last_update_check) >= crate::platform::update::CHECK_INTERVAL_SECS
+    {
+        spawn_update_check(&rt, &proxy);
+    }
+    let mut hot_folder_watcher = if settings.hot_folder_enabled {
+        hot_folder::HotFolderWatcher::start(&settings.hot_folder_path, proxy.clone())
+    } else {
+        None
+    };
+    // クリップボード監視 (Ctrl + V)。セッション限りで設定には保存しない
+    let mut clipboard_watch: Option<clipboard_watcher::ClipboardWatcher> = None;
+    // 表示中の画像フォルダの監視 (watched_source はフォルダかどうかを調べ終えたソース)
+    let mut folder_watcher: Option<folder_watcher::FolderWatcher> = None;
+    let mut watched_source = String::new();
+    // ドロップされたパス (DroppedFile はファイルごとに届くため、AboutToWait でまとめて開く)
+    let mut dropped_paths: Vec<String> = Vec::new();
     
     let mut last_dialog_close = std::time::Instant::now();
+    let mut cursor_auto_hide = CursorAutoHide::new();
     let mut modern_settings: Option<ui::modern_settings::ModernSettingsWindow> = None;
     let mut modern_history: Option<ui::history::HistoryWindow> = None;
+    let mut modern_bookmarks: Option<ui::bookmarks::BookmarksWindow> = None;
     let mut modern_help: Option<ui::help::HelpWindow> = None;
+    // 出力ウィンドウ (F12)。ページはメインウィンドウのレンダラーで描く
+    let mut mirror_window: Option<ui::mirror::MirrorWindow> = None;
 
     event_loop.run(move |event: Event<UserEvent>, elwt: &winit::event_loop::EventLoopWindowTarget<UserEvent>| {
         elwt.set_control_flow(ControlFlow::Wait);
@@ -451,6 +2170,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
 
+                if let Some(ref mut mb) = modern_bookmarks {
+                    if mb.window.id() == window_id {
+                        if mb.handle_event(&event, &settings) {
+                            modern_bookmarks = None;
+                            last_dialog_close = std::time::Instant::now();
+                        } else if matches!(event, WindowEvent::RedrawRequested) {
+                            mb.draw(&settings);
+                        }
+                        return;
+                    }
+                }
+
                 if let Some(ref mut mhelp) = modern_help {
                     if mhelp.window.id() == window_id {
                         if mhelp.handle_event(&event) {
@@ -463,6 +2194,31 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
 
+                if let Some(ref mut mirror) = mirror_window {
+                    if mirror.window.id() == window_id {
+                        match mirror.handle_event(&event) {
+                            ui::mirror::MirrorAction::Close => {
+                                renderer.set_mirror_target(None);
+                                mirror_window = None;
+                            }
+                            ui::mirror::MirrorAction::Resized(width, height) => {
+                                renderer.resize_mirror(width, height);
+                                window.request_redraw();
+                            }
+                            ui::mirror::MirrorAction::FitModeChanged => {
+                                settings.mirror_fit_mode = mirror.fit_mode.setting_name().to_string();
+                                let _ = settings.save(config_path);
+                                app_state.status_message = Some((format!("出力ウィンドウ: {}", mirror.fit_mode.label()), std::time::Instant::now()));
+                                window.request_redraw();
+                            }
+                            // 出力ウィンドウはメインウィンドウの描画と一緒に描く
+                            ui::mirror::MirrorAction::Redraw => window.request_redraw(),
+                            ui::mirror::MirrorAction::None => {}
+                        }
+                        return;
+                    }
+                }
+
                 if window_id != window.id() { return; }
                 
                 match event {
@@ -470,13 +2226,46 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     println!("終了リクエストを受信しました。終了します...");
                     // 終了前に現在の状態を保存
                     sync_current_state_to_history(&mut settings, &app_state, &current_path_key);
-                    let _ = settings.save("config.json");
+                    sync_current_state_to_overrides(&mut settings, &app_state, &view_state, &current_path_key);
+                    sync_current_state_to_positions(&mut app_state, &current_path_key);
+                    if let Err(e) = app_state.positions.save() {
+                        println!("[位置] 読書位置を保存できませんでした: {}", e);
+                    }
+                    app_state.stats.end_session();
+                    if let Err(e) = app_state.stats.save() {
+                        println!("[統計] 読書の統計を保存できませんでした: {}", e);
+                    }
+                    record_window_geometry(&window, &mut settings);
+                    settings.window_maximized = window.is_maximized();
+                    settings.window_fullscreen = window.fullscreen().is_some();
+                    let _ = settings.save(config_path);
+                    settings.flush();
+                    // 書き込みスレッドに頼んだ設定・読書位置の保存が終わるまで待つ
+                    crate::persist::wait_idle();
                     elwt.exit();
                     // 非同期タスクがブロッキングしている場合に備え、プロセスを強制終了
                     std::process::exit(0);
                 }
+                WindowEvent::Moved(_) => {
+                    record_window_geometry(&window, &mut settings);
+                }
+                WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                    // 別の拡大率のモニターへ移動した (続けて Resized が届き、バッファはそこで作り直す)
+                    // オーバーレイ・シークバー・ヒットテストは描画のたびに scale_factor から計算し直す
+                    println!("[UI] 表示スケールが {:.0}% に変わりました", scale_factor * 100.0);
+                    if let Some(sb_hwnd) = status_bar_hwnd {
+                        update_status_bar_font(sb_hwnd, scale_factor as f32);
+                    }
+                    renderer.set_ui_scale(scale_factor as f32);
+                    window.request_redraw();
+                }
                 WindowEvent::Resized(physical_size) => {
                     let _ = renderer.resize(physical_size.width, physical_size.height);
+                    // 枠をドラッグしている間 (左ボタンが押されたまま) だけ直前のフレームを使い回す
+                    // 最大化・全画面の切り替えのような 1 回きりの変更はすぐに配置し直す
+                    let dragging = unsafe { windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState(windows::Win32::UI::Input::KeyboardAndMouse::VK_LBUTTON.0 as i32) } < 0;
+                    app_state.frozen_frame.resized_at = (settings.freeze_frame_on_resize && dragging).then(std::time::Instant::now);
+                    record_window_geometry(&window, &mut settings);
                     if let Some(sb_hwnd) = status_bar_hwnd {
                         unsafe {
                             SendMessageW(sb_hwnd, WM_SIZE, Some(WPARAM(0)), Some(LPARAM(0)));
@@ -494,28 +2283,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 WindowEvent::DroppedFile(path) => {
                     let path_str = path.to_string_lossy().to_string();
                     println!("ファイルをドロップ: {}", path_str);
-                    if let Some(new_source) = get_image_source(&path_str) {
-                        load_new_source(
-                            new_source,
-                            path_str,
-                            0,
-                            None,
-                            &mut app_state,
-                            &mut current_path_key,
-                            &window,
-                            &cpu_cache,
-                            &loader,
-                            &rt,
-                            &mut settings,
-                            &mut current_bitmaps,
-                            false, // 通常のファイル読み込み
-                        );
-                        window.request_redraw();
-                    }
+                    dropped_paths.push(path_str);
                 }
                 WindowEvent::ModifiersChanged(new_modifiers) => {
                     modifiers = new_modifiers.state();
                 }
+                WindowEvent::KeyboardInput {
+                    event: KeyEvent { logical_key: Key::Character(ref s), state: ElementState::Released, .. }, ..
+                } if s.to_lowercase() == "z" => {
+                    view_state.zoom_select_armed = false;
+                }
                 WindowEvent::KeyboardInput { 
                     event: KeyEvent { 
                         logical_key, 
@@ -524,12 +2301,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .. 
                     }, .. 
                 } => {
+                    // 全画面ではキー操作で読み進めている間はすぐにカーソルを隠す
+                    if window.fullscreen().is_some() && settings.cursor_hide_seconds > 0.0 && !is_cursor_in_use(&app_state, &view_state) {
+                        cursor_auto_hide.hide(&window);
+                    }
                     if app_state.is_jump_open {
                         match logical_key {
                             Key::Character(ref s) if s.chars().all(|c| c.is_ascii_digit()) => {
                                 if app_state.jump_input_buffer.len() < 5 {
                                     app_state.jump_input_buffer.push_str(s.as_str());
                                 }
+                                if app_state.jump_typed_at.is_some() {
+                                    app_state.jump_typed_at = Some(std::time::Instant::now());
+                                }
                             }
                             Key::Named(NamedKey::Backspace) => {
                                 app_state.jump_input_buffer.pop();
@@ -537,6 +2321,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             Key::Named(NamedKey::Enter) => {
                                 if let Ok(page_num) = app_state.jump_input_buffer.parse::<usize>() {
                                     if page_num > 0 && page_num <= app_state.image_files.len() {
+                                        app_state.record_undo(&current_path_key, "ページジャンプ");
                                         app_state.current_page_index = page_num - 1;
                                         view_state.reset();
                                         let l = loader.clone();
@@ -557,53 +2342,405 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         return;
                     }
 
-                    match logical_key {
-                        Key::Character(ref s) if s.to_lowercase() == "o" => {
-                            if last_dialog_close.elapsed() < std::time::Duration::from_millis(500) {
-                                return;
-                            }
-                            
-                            if modern_settings.is_none() {
-                                match ui::modern_settings::ModernSettingsWindow::new(elwt, hwnd, &settings, proxy.clone()) {
-                                    Ok(mw) => {
-                                        modern_settings = Some(mw);
-                                    }
-                                    Err(e) => {
-                                        println!("Failed to open Modern UI: {:?}", e);
-                                    }
+                    if let Some(ref mut overlay) = app_state.series_overlay {
+                        match logical_key {
+                            Key::Named(NamedKey::ArrowUp) => overlay.move_selection(-1),
+                            Key::Named(NamedKey::ArrowDown) => overlay.move_selection(1),
+                            Key::Named(NamedKey::PageUp) => overlay.move_selection(-10),
+                            Key::Named(NamedKey::PageDown) => overlay.move_selection(10),
+                            Key::Named(NamedKey::Home) => overlay.selected = 0,
+                            Key::Named(NamedKey::End) => overlay.move_selection(isize::MAX / 2),
+                            Key::Named(NamedKey::Enter) => {
+                                let path = overlay.entries[overlay.selected].clone();
+                                if Some(overlay.selected) != overlay.current {
+                                    let _ = proxy.send_event(UserEvent::LoadPath(path));
                                 }
+                                app_state.series_overlay = None;
                             }
-                            last_dialog_close = std::time::Instant::now();
-                        }
-                        Key::Character(ref s) if s.to_lowercase() == "h" => {
-                            // H: ヘルプ画面を開く
-                            if last_dialog_close.elapsed() < std::time::Duration::from_millis(500) {
-                                return;
-                            }
-                            
-                            if modern_help.is_none() { // ヘルプウィンドウが開いていない場合のみ開く
-                                match ui::help::HelpWindow::new(elwt, hwnd) {
-                                    Ok(mw) => {
-                                        modern_help = Some(mw);
-                                    }
-                                    Err(e) => {
-                                        println!("Failed to open Help Window: {:?}", e);
-                                    }
-                                }
+                            Key::Named(NamedKey::Escape) | Key::Named(NamedKey::Tab) => {
+                                app_state.series_overlay = None;
                             }
-                            last_dialog_close = std::time::Instant::now();
+                            _ => (),
                         }
-                        Key::Character(ref s) if s.to_lowercase() == "s" => {
-                            if modifiers.shift_key() {
-                                // Shift + S: ページジャンプを開く
-                                app_state.is_jump_open = true;
-                                app_state.jump_input_buffer.clear();
+                        window.request_redraw();
+                        return;
+                    }
 
-                            } else {
-                                // S: シークバー切り替え
-                                app_state.show_seekbar = !app_state.show_seekbar;
+                    // トリミング範囲の編集中: Enter で確定 / Esc で取り消し / Delete で解除
+                    if app_state.crop_edit.is_some() {
+                        match logical_key {
+                            Key::Named(NamedKey::Enter) => {
+                                if let Some(edit) = app_state.crop_edit.take() {
+                                    let transform = app_state.page_transform(edit.page_index);
+                                    let crop = CropMargins::from_visible_rect(&edit.rect, transform);
+                                    set_manual_crop(&mut app_state, &mut settings, &current_path_key, Some(crop));
+                                }
                             }
-                        }
+                            Key::Named(NamedKey::Escape) => {
+                                app_state.crop_edit = None;
+                                app_state.osd_toast = Some(("トリミング: 編集を取り消しました".to_string(), std::time::Instant::now()));
+                            }
+                            Key::Named(NamedKey::Delete) => {
+                                app_state.crop_edit = None;
+                                set_manual_crop(&mut app_state, &mut settings, &current_path_key, None);
+                            }
+                            _ => (),
+                        }
+                        window.request_redraw();
+                        return;
+                    }
+
+                    // キー割り当て（メディアキー・リモコン・フットペダル等）を優先して処理
+                    if let Some(action) = keymap.action_for(&logical_key) {
+                        if action == KeyAction::ExportPage {
+                            let _ = proxy.send_event(UserEvent::ExportPage);
+                            return;
+                        }
+                        if matches!(action, KeyAction::FirstPage | KeyAction::LastPage) {
+                            app_state.record_undo(&current_path_key, "ページジャンプ");
+                        }
+                        match action {
+                            KeyAction::NextPage => navigate_pages(&mut app_state, &gpu_cache, &settings, 1),
+                            KeyAction::PrevPage => navigate_pages(&mut app_state, &gpu_cache, &settings, -1),
+                            KeyAction::FirstPage => flip_to(&mut app_state, &gpu_cache, &settings, 0),
+                            KeyAction::LastPage => {
+                                let last = app_state.snap_to_spread(app_state.image_files.len().saturating_sub(1));
+                                flip_to(&mut app_state, &gpu_cache, &settings, last);
+                            }
+                            KeyAction::ExportPage => {}
+                        }
+                        view_state.reset();
+                        let l = loader.clone();
+                        rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                        request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                        window.request_redraw();
+                        return;
+                    }
+
+                    match logical_key {
+                        Key::Character(ref s)
+                            if app_state.triage_mode
+                                && !modifiers.alt_key()
+                                && matches!(s.as_str(), "1" | "2" | "3" | "4" | "5") =>
+                        {
+                            let slot = s.parse::<usize>().unwrap_or(1) - 1;
+                            if modifiers.control_key() {
+                                // 仕分けモードで Ctrl + 1〜5: 振り分け先フォルダを選ぶ
+                                if let Some(folder) = ui::dialogs::select_folder(hwnd) {
+                                    if settings.triage_targets.len() <= slot {
+                                        settings.triage_targets.resize(slot + 1, TriageTarget::default());
+                                    }
+                                    settings.triage_targets[slot].path = folder.to_string_lossy().to_string();
+                                    let _ = settings.save(config_path);
+                                    app_state.osd_toast = Some((format!("振り分け先 {}: {}", slot + 1, folder.display()), std::time::Instant::now()));
+                                }
+                            } else if let Some(target) = settings.triage_targets.get(slot).filter(|t| !t.path.is_empty()).cloned() {
+                                // 仕分けモードで 1〜5: 表示中の画像を振り分け先へ移動/コピーして次へ進む
+                                let on_disk = app_state
+                                    .image_files
+                                    .get(app_state.current_page_index)
+                                    .is_some_and(|f| std::path::Path::new(f).is_file());
+                                if !std::path::Path::new(&current_path_key).is_dir() || !on_disk {
+                                    app_state.status_message = Some(("仕分けはフォルダで開いた画像のみ対応しています".to_string(), std::time::Instant::now()));
+                                } else {
+                                    let index = app_state.current_page_index.min(app_state.image_files.len() - 1);
+                                    let file = std::path::PathBuf::from(&app_state.image_files[index]);
+                                    match crate::triage::apply(hwnd, &target, &file, &mut app_state.undo) {
+                                        Ok(destination) => {
+                                            if target.copy {
+                                                app_state.navigate(1);
+                                                view_state.reset();
+                                                let l = loader.clone();
+                                                rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                                                request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                                            } else if !reload_current_folder(
+                                                |_| index,
+                                                &mut app_state,
+                                                &mut current_path_key,
+                                                &window,
+                                                &cpu_cache,
+                                                &loader,
+                                                &rt,
+                                                &mut settings,
+                                                &mut gpu_cache,
+                                                &mut view_state,
+                                                &mut staged_uploads,
+                                            ) {
+                                                // 最後の 1 枚を移動した: 取り消しで戻せるようフォルダは開いたままにする
+                                                let path = current_path_key.clone();
+                                                load_new_source(
+                                                    ImageSource::Files(Vec::new()),
+                                                    path,
+                                                    Some(0),
+                                                    None,
+                                                    &mut app_state,
+                                                    &mut current_path_key,
+                                                    &window,
+                                                    &cpu_cache,
+                                                    &loader,
+                                                    &rt,
+                                                    &mut settings,
+                                                    &mut gpu_cache,
+                                                    &mut view_state,
+                                                    true,
+                                                );
+                                            }
+                                            let verb = if target.copy { "コピー" } else { "移動" };
+                                            let folder = destination
+                                                .parent()
+                                                .and_then(|p| p.file_name())
+                                                .map(|n| n.to_string_lossy().to_string())
+                                                .unwrap_or_default();
+                                            app_state.osd_toast = Some((format!("{} → {} ({})", slot + 1, folder, verb), std::time::Instant::now()));
+                                        }
+                                        Err(e) => {
+                                            println!("[仕分け] {} を振り分けできませんでした: {}", file.display(), e);
+                                            app_state.status_message = Some((format!("振り分けできませんでした: {}", e), std::time::Instant::now()));
+                                        }
+                                    }
+                                }
+                            } else {
+                                let message = format!("振り分け先 {} が未設定です (Ctrl+{} で設定)", slot + 1, slot + 1);
+                                app_state.status_message = Some((message, std::time::Instant::now()));
+                            }
+                        }
+                        Key::Character(ref s)
+                            if modifiers.control_key() && !modifiers.alt_key() && s.to_lowercase() == "z" =>
+                        {
+                            // Ctrl + Z: 直前の操作 (ページジャンプ・表示モード・回転・仕分け) を取り消す
+                            match app_state.undo.pop() {
+                                Some(crate::undo::UndoEntry::View { label, snapshot }) => {
+                                    let mode_changed = snapshot.is_spread_view != app_state.is_spread_view
+                                        || snapshot.is_webtoon_view != app_state.is_webtoon_view
+                                        || snapshot.binding_direction != app_state.binding_direction;
+                                    let crop_changed = snapshot.manual_crop != app_state.manual_crop;
+                                    app_state.restore_view(snapshot);
+                                    if crop_changed {
+                                        // 手動トリミングは履歴に保存しているため合わせて戻す
+                                        settings.set_history_crop(&current_path_key, app_state.manual_crop);
+                                        let _ = settings.save(config_path);
+                                    }
+                                    if mode_changed {
+                                        // 表示モードは全体設定にも保存しているため合わせて戻す
+                                        settings.webtoon_view = app_state.is_webtoon_view;
+                                        if !app_state.is_webtoon_view {
+                                            settings.is_spread_view = app_state.is_spread_view;
+                                        }
+                                        settings.binding_direction = if app_state.binding_direction == BindingDirection::Right {
+                                            "right".to_string()
+                                        } else {
+                                            "left".to_string()
+                                        };
+                                        let _ = settings.save(config_path);
+                                        if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                                    }
+                                    view_state.reset();
+                                    let l = loader.clone();
+                                    rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                                    request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                                    app_state.osd_toast = Some((format!("取り消しました: {}", label), std::time::Instant::now()));
+                                }
+                                Some(crate::undo::UndoEntry::Triage(record)) => match crate::triage::undo(hwnd, &record) {
+                                    Ok(restored) => {
+                                        let in_current = restored.parent().is_some_and(|dir| dir == std::path::Path::new(&current_path_key));
+                                        if in_current && !record.copied {
+                                            reload_current_folder(
+                                                |files| files.iter().position(|f| std::path::Path::new(f) == restored).unwrap_or(0),
+                                                &mut app_state,
+                                                &mut current_path_key,
+                                                &window,
+                                                &cpu_cache,
+                                                &loader,
+                                                &rt,
+                                                &mut settings,
+                                                &mut gpu_cache,
+                                                &mut view_state,
+                                                &mut staged_uploads,
+                                            );
+                                        } else if let Some(page) = app_state.image_files.iter().position(|f| std::path::Path::new(f) == restored) {
+                                            // コピーの取り消し: 元の画像へ戻る
+                                            app_state.current_page_index = app_state.snap_to_spread(page);
+                                            view_state.reset();
+                                            request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                                        }
+                                        let name = restored.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                                        app_state.osd_toast = Some((format!("仕分けを取り消しました: {}", name), std::time::Instant::now()));
+                                    }
+                                    Err(e) => {
+                                        println!("[仕分け] 取り消しに失敗しました: {}", e);
+                                        app_state.status_message = Some((format!("仕分けを取り消せませんでした: {}", e), std::time::Instant::now()));
+                                        app_state.undo.push(crate::undo::UndoEntry::Triage(record));
+                                    }
+                                },
+                                None => {
+                                    app_state.status_message = Some(("取り消す操作がありません".to_string(), std::time::Instant::now()));
+                                }
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "d" && !modifiers.control_key() => {
+                            use crate::annotations::AnnotationTool;
+                            let message = if modifiers.shift_key() {
+                                // Shift + D: 注釈の表示/非表示
+                                app_state.show_annotations = !app_state.show_annotations;
+                                if app_state.show_annotations { "注釈: 表示" } else { "注釈: 非表示" }.to_string()
+                            } else {
+                                // D: 注釈モード (オフ -> ペン -> 蛍光ペン -> オフ)
+                                app_state.annotation_tool = match app_state.annotation_tool {
+                                    None => Some(AnnotationTool::Pen),
+                                    Some(AnnotationTool::Pen) => Some(AnnotationTool::Highlighter),
+                                    Some(AnnotationTool::Highlighter) => None,
+                                };
+                                app_state.annotation_stroke = None;
+                                match app_state.annotation_tool {
+                                    Some(tool) => {
+                                        app_state.show_annotations = true;
+                                        format!("注釈モード: {} (ドラッグで描画 / Backspace で 1 本消す / Esc で終了)", tool.label())
+                                    }
+                                    None => "注釈モード: オフ".to_string(),
+                                }
+                            };
+                            app_state.osd_toast = Some((message, std::time::Instant::now()));
+                        }
+                        Key::Named(NamedKey::Backspace) if app_state.annotation_tool.is_some() => {
+                            // Backspace: 表示中のページで最後に描いた線を消す
+                            let pages: Vec<String> = app_state
+                                .get_page_indices_to_display()
+                                .iter()
+                                .filter_map(|&index| app_state.image_files.get(index))
+                                .map(|entry| page_display_name(entry, &current_path_key))
+                                .collect();
+                            if pages.iter().any(|page| app_state.annotations.remove_last(page).is_some()) {
+                                if let Err(e) = app_state.annotations.save() {
+                                    println!("[注釈] 保存に失敗しました: {}", e);
+                                }
+                            } else {
+                                app_state.status_message = Some(("このページには注釈がありません".to_string(), std::time::Instant::now()));
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "k" && !modifiers.control_key() => {
+                            // K: 仕分けモード (数字キーで振り分け先フォルダへ移動/コピー)
+                            app_state.triage_mode = !app_state.triage_mode;
+                            let message = if app_state.triage_mode {
+                                let configured = settings.triage_targets.iter().take(5).filter(|t| !t.path.is_empty()).count();
+                                format!("仕分けモード: オン (振り分け先 {}/5 件、Ctrl+1〜5 で設定)", configured)
+                            } else {
+                                "仕分けモード: オフ".to_string()
+                            };
+                            app_state.osd_toast = Some((message, std::time::Instant::now()));
+                        }
+                        Key::Character(ref s)
+                            if !modifiers.control_key()
+                                && !modifiers.alt_key()
+                                && !app_state.triage_mode
+                                && !app_state.image_files.is_empty()
+                                && s.chars().all(|c| c.is_ascii_digit()) =>
+                        {
+                            // 数字キー: ダイアログを開かずにそのままページ番号の入力を始める (Enter で確定)
+                            app_state.is_jump_open = true;
+                            app_state.jump_input_buffer = s.to_string();
+                            app_state.jump_typed_at = Some(std::time::Instant::now());
+                            window.request_redraw();
+                        }
+                        Key::Character(ref s)
+                            if modifiers.alt_key()
+                                && !modifiers.control_key()
+                                && matches!(s.as_str(), "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9") =>
+                        {
+                            // Alt + 1〜9: ズームプリセット (数字のみはページ番号入力に使う)
+                            let slot = s.parse::<usize>().unwrap_or(1) - 1;
+                            if let Some(&preset) = settings.zoom_presets.get(slot) {
+                                let win_size = page_area_size(&window, status_bar_hwnd);
+                                let center = (win_size.0 / 2.0, win_size.1 / 2.0);
+                                let label = if preset <= 0.0 {
+                                    view_state.animate(settings.view_animation_ms, |v| v.reset());
+                                    "全体表示".to_string()
+                                } else if let Some(scale) = current_pixel_scale(&app_state, &view_state, &gpu_cache, renderer.as_ref()) {
+                                    view_state.animate(settings.view_animation_ms, |v| v.set_zoom(v.zoom_level * preset / scale, center, win_size));
+                                    format!("{:.0}%", preset * 100.0)
+                                } else {
+                                    "ページ読み込み中".to_string()
+                                };
+                                app_state.osd_toast = Some((format!("ズーム: {}", label), std::time::Instant::now()));
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "z" && !modifiers.control_key() => {
+                            // Z を押しながら左ドラッグ: 範囲ズーム
+                            view_state.zoom_select_armed = true;
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "o" => {
+                            if last_dialog_close.elapsed() < std::time::Duration::from_millis(500) {
+                                return;
+                            }
+                            
+                            if modern_settings.is_none() {
+                                match ui::modern_settings::ModernSettingsWindow::new(elwt, hwnd, &settings, proxy.clone()) {
+                                    Ok(mut mw) => {
+                                        mw.color_adjustment = app_state.color_adjustment;
+                                        modern_settings = Some(mw);
+                                    }
+                                    Err(e) => {
+                                        println!("Failed to open Modern UI: {:?}", e);
+                                    }
+                                }
+                            }
+                            last_dialog_close = std::time::Instant::now();
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "h" => {
+                            // H: ヘルプ画面を開く
+                            if last_dialog_close.elapsed() < std::time::Duration::from_millis(500) {
+                                return;
+                            }
+                            
+                            if modern_help.is_none() { // ヘルプウィンドウが開いていない場合のみ開く
+                                match ui::help::HelpWindow::new(elwt, hwnd) {
+                                    Ok(mw) => {
+                                        modern_help = Some(mw);
+                                    }
+                                    Err(e) => {
+                                        println!("Failed to open Help Window: {:?}", e);
+                                    }
+                                }
+                            }
+                            last_dialog_close = std::time::Instant::now();
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "s" => {
+                            if modifiers.control_key() && modifiers.shift_key() {
+                                // Ctrl + Shift + S: 表示中のページ (見開き) をファイルに書き出す
+                                let _ = proxy.send_event(UserEvent::ExportPage);
+                            } else if modifiers.control_key() {
+                                // Ctrl + S: 表示中のページ (見開きは並べた 1 枚) を Windows の共有 UI で送る
+                                // 合成と PNG のエンコードは別スレッドで行い、書き出せたら ShareReady で共有 UI を開く
+                                let (indices, pages) = displayed_export_pages(&app_state, &cpu_cache, &current_path_key);
+                                if let Some(pages) = pages {
+                                    let name = app_state.image_files.get(app_state.current_page_index).cloned().unwrap_or_default();
+                                    let file_name = std::path::Path::new(&name).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                                    let mut page_numbers: Vec<usize> = indices.iter().map(|i| i + 1).collect();
+                                    page_numbers.sort();
+                                    let pages_label = page_numbers.iter().map(|n| n.to_string()).collect::<Vec<_>>().join("-");
+                                    let title = format!("{} ({}ページ)", file_name, pages_label);
+                                    let background = settings.background_rgb().map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                                    let proxy = proxy.clone();
+                                    app_state.status_message = Some(("共有の準備中...".to_string(), std::time::Instant::now()));
+                                    rt.spawn_blocking(move || {
+                                        let result = crate::image::export::compose(&pages, background).and_then(|image| {
+                                            crate::platform::share::write_temp_png(&image, &name).map_err(|e| e.to_string())
+                                        });
+                                        let _ = proxy.send_event(UserEvent::ShareReady(result.map(|path| (path, title))));
+                                    });
+                                } else {
+                                    app_state.status_message = Some(("ページを読み込み中のため共有できません".to_string(), std::time::Instant::now()));
+                                }
+                            } else if modifiers.shift_key() {
+                                // Shift + S: ページジャンプを開く
+                                app_state.is_jump_open = true;
+                                app_state.jump_input_buffer.clear();
+                                app_state.jump_typed_at = None;
+
+                            } else {
+                                // S: シークバー切り替え
+                                app_state.show_seekbar = !app_state.show_seekbar;
+                            }
+                        }
                         Key::Character(ref s) if s.to_lowercase() == "r" => {
                             // R: 履歴ウィンドウを開く
                             if last_dialog_close.elapsed() < std::time::Duration::from_millis(500) {
@@ -622,25 +2759,443 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                             last_dialog_close = std::time::Instant::now();
                         }
+                        Key::Named(NamedKey::F11) | Key::Named(NamedKey::Enter) => {
+                            // F11 / Enter: ボーダーレス全画面の切り替え
+                            toggle_fullscreen(&window, status_bar_hwnd, &mut settings);
+                        }
+                        Key::Named(NamedKey::F5) if modifiers.control_key() => {
+                            // Ctrl + F5: ディスク上で差し替えたファイルを反映するため、現在のソースを読み直す
+                            if current_path_key.is_empty() {
+                                return;
+                            }
+                            let Some(new_source) = get_image_source(&current_path_key) else {
+                                app_state.status_message = Some(("再読み込み: ソースを開けませんでした".to_string(), std::time::Instant::now()));
+                                return;
+                            };
+                            // 同じファイル名があればそのページへ、無ければ同じ番号のページへ戻る
+                            let current_name = app_state.image_files.get(app_state.current_page_index).cloned();
+                            let page = current_name
+                                .and_then(|name| new_source.file_names().iter().position(|f| *f == name))
+                                .unwrap_or(app_state.current_page_index);
+                            let binding = binding_str(&app_state).to_string();
+                            // 同じパスのキャッシュキーを使い回すため、デコード中の古い結果と段階転送中のテクスチャも捨てる
+                            loader.invalidate();
+                            staged_uploads.clear();
+                            let path = current_path_key.clone();
+                            load_new_source(
+                                new_source,
+                                path,
+                                Some(page),
+                                Some(binding),
+                                &mut app_state,
+                                &mut current_path_key,
+                                &window,
+                                &cpu_cache,
+                                &loader,
+                                &rt,
+                                &mut settings,
+                                &mut gpu_cache,
+                                &mut view_state,
+                                true, // 履歴の順序は変えない
+                            );
+                            let message = format!("再読み込みしました ({} 個のファイル)", app_state.image_files.len());
+                            app_state.status_message = Some((message, std::time::Instant::now()));
+                            window.request_redraw();
+                        }
+                        Key::Named(NamedKey::Delete) => {
+                            // Delete: 表示中の画像 (アーカイブ・単一ファイルはそのもの) を確認のうえごみ箱へ移動し、次へ進む
+                            if current_path_key.is_empty() || app_state.image_files.is_empty() {
+                                return;
+                            }
+                            let source_path = std::path::PathBuf::from(&current_path_key);
+                            let is_folder = source_path.is_dir();
+                            let index = app_state.current_page_index.min(app_state.image_files.len() - 1);
+                            // アーカイブをつなげて開いたフォルダでは、ページを含むアーカイブ (巻) ごと移動する
+                            let target = if is_folder { containing_file(&app_state.image_files[index]) } else { source_path.clone() };
+                            let target_pages = if is_folder {
+                                app_state.image_files.iter().filter(|f| std::path::Path::new(f).starts_with(&target)).count()
+                            } else {
+                                app_state.image_files.len()
+                            };
+                            let name = target
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                                .unwrap_or_else(|| target.to_string_lossy().to_string());
+                            let message = if is_book_file(&target) {
+                                format!("「{}」({} ページ) 全体をごみ箱へ移動しますか？", name, target_pages)
+                            } else {
+                                format!("「{}」をごみ箱へ移動しますか？", name)
+                            };
+                            if !ui::dialogs::show_confirm_dialog(hwnd, "ごみ箱へ移動", &message) {
+                                return;
+                            }
+
+                            // ソースごと無くなる場合の移動先は、削除するとパスを解決できなくなるため先に探す
+                            let removes_source = !is_folder || target_pages == app_state.image_files.len();
+                            let neighbor = if removes_source {
+                                find_neighboring_source(&current_path_key, 1).or_else(|| find_neighboring_source(&current_path_key, -1))
+                            } else {
+                                None
+                            };
+                            if let Err(e) = crate::platform::file_ops::move_to_recycle_bin(hwnd, &target) {
+                                println!("[削除] ごみ箱へ移動できませんでした: {}: {}", target.display(), e);
+                                app_state.status_message = Some((format!("ごみ箱へ移動できませんでした: {}", e), std::time::Instant::now()));
+                                return;
+                            }
+                            println!("[削除] ごみ箱へ移動しました: {}", target.display());
+
+                            // 同じフォルダを読み直し、削除したページの位置 (= 次の画像) から表示する
+                            let old_path = current_path_key.clone();
+                            let reloaded = !removes_source
+                                && reload_current_folder(
+                                    |_| index,
+                                    &mut app_state,
+                                    &mut current_path_key,
+                                    &window,
+                                    &cpu_cache,
+                                    &loader,
+                                    &rt,
+                                    &mut settings,
+                                    &mut gpu_cache,
+                                    &mut view_state,
+                                    &mut staged_uploads,
+                                );
+                            if !reloaded {
+                                // ソースが無くなったので隣のフォルダ/アーカイブへ進む (無ければ何も表示しない)
+                                let (new_source, new_path, skip_history) = match neighbor {
+                                    Some((path, source)) => (source, path, false),
+                                    None => (ImageSource::Files(Vec::new()), String::new(), true),
+                                };
+                                load_new_source(
+                                    new_source,
+                                    new_path,
+                                    None,
+                                    None,
+                                    &mut app_state,
+                                    &mut current_path_key,
+                                    &window,
+                                    &cpu_cache,
+                                    &loader,
+                                    &rt,
+                                    &mut settings,
+                                    &mut gpu_cache,
+                                    &mut view_state,
+                                    skip_history,
+                                );
+                                // 切り替え時に記録された削除済みソースの履歴・設定を取り除く
+                                settings.forget_source(&old_path);
+                                app_state.positions.remove(&old_path);
+                                let _ = app_state.positions.save();
+                                let _ = settings.save(config_path);
+                            }
+                            app_state.osd_toast = Some((format!("ごみ箱へ移動: {}", name), std::time::Instant::now()));
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "i" => {
+                            // I: 画像情報 (カメラ・解像度・ビット深度・色空間・ファイルサイズ)
+                            app_state.show_image_info = !app_state.show_image_info;
+                            let message = if app_state.show_image_info { "画像情報: 表示" } else { "画像情報: 非表示" };
+                            app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
+                            window.request_redraw();
+                        }
+                        Key::Named(NamedKey::F3) => {
+                            // F3: パフォーマンス HUD (フレーム時間・Present 間隔・転送時間)
+                            app_state.show_perf_hud = !app_state.show_perf_hud;
+                            frame_stats.reset();
+                            window.request_redraw();
+                        }
+                        Key::Named(NamedKey::F12) => {
+                            // F12: 出力ウィンドウ (表示中のページを別のモニターに映す) の開閉
+                            if mirror_window.is_some() {
+                                // スワップチェーンを先に破棄してからウィンドウを閉じる
+                                renderer.set_mirror_target(None);
+                                mirror_window = None;
+                                app_state.status_message = Some(("出力ウィンドウを閉じました".to_string(), std::time::Instant::now()));
+                            } else {
+                                match ui::mirror::MirrorWindow::new(elwt, &window, FitMode::from_setting(&settings.mirror_fit_mode)) {
+                                    Ok(mirror) if renderer.set_mirror_target(Some(mirror.hwnd)) => {
+                                        app_state.status_message = Some((format!("出力ウィンドウ: {}", mirror.fit_mode.label()), std::time::Instant::now()));
+                                        mirror_window = Some(mirror);
+                                    }
+                                    Ok(_) => {
+                                        let message = format!("出力ウィンドウは {} では使えません", get_backend_display_name(&settings.rendering_backend));
+                                        app_state.status_message = Some((message, std::time::Instant::now()));
+                                    }
+                                    Err(e) => println!("[出力] 出力ウィンドウを開けませんでした: {}", e),
+                                }
+                            }
+                            window.request_redraw();
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "m" => {
+                            if modifiers.shift_key() {
+                                // Shift + M: しおり一覧を開く
+                                if last_dialog_close.elapsed() < std::time::Duration::from_millis(500) {
+                                    return;
+                                }
+                                if modern_bookmarks.is_none() {
+                                    match ui::bookmarks::BookmarksWindow::new(elwt, hwnd, proxy.clone()) {
+                                        Ok(bw) => {
+                                            modern_bookmarks = Some(bw);
+                                        }
+                                        Err(e) => {
+                                            println!("Failed to open Bookmarks Window: {:?}", e);
+                                        }
+                                    }
+                                }
+                                last_dialog_close = std::time::Instant::now();
+                            } else if !current_path_key.is_empty() {
+                                // M: 現在のページのしおりを登録/解除
+                                let page = app_state.current_page_index;
+                                let entry = app_state.image_files.get(page).cloned().unwrap_or_default();
+                                let label = page_display_name(&entry, &current_path_key);
+                                let added = settings.toggle_bookmark(&current_path_key, page, label);
+                                let _ = settings.save(config_path);
+                                let message = if added {
+                                    format!("しおりを登録しました ({}ページ)", page + 1)
+                                } else {
+                                    format!("しおりを解除しました ({}ページ)", page + 1)
+                                };
+                                app_state.osd_toast = Some((message, std::time::Instant::now()));
+                                if let Some(ref mut mb) = modern_bookmarks { mb.window.request_redraw(); }
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "e" && !modifiers.control_key() => {
+                            // E / Shift + E: カーソル下のページを時計回り / 反時計回りに 90° 回転
+                            if !app_state.image_files.is_empty() {
+                                let index = transform_target_page(&app_state, &view_state);
+                                let step = if modifiers.shift_key() { -1 } else { 1 };
+                                let transform = app_state.page_transform(index).rotated(step);
+                                update_page_transform(&mut app_state, &current_path_key, index, transform);
+                                if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                                window.request_redraw();
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "x" && !modifiers.control_key() => {
+                            // X / Shift + X: カーソル下のページを左右 / 上下に反転
+                            if !app_state.image_files.is_empty() {
+                                let index = transform_target_page(&app_state, &view_state);
+                                let horizontal = !modifiers.shift_key();
+                                let transform = app_state.page_transform(index).flipped(horizontal);
+                                update_page_transform(&mut app_state, &current_path_key, index, transform);
+                                if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                                window.request_redraw();
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "w" => {
+                            // W: ホットフォルダ監視の切り替え (Shift + W で監視フォルダを選び直す)
+                            if hot_folder_watcher.is_some() && !modifiers.shift_key() {
+                                hot_folder_watcher = None;
+                                settings.hot_folder_enabled = false;
+                                app_state.status_message = Some(("ホットフォルダ: 監視を停止しました".to_string(), std::time::Instant::now()));
+                            } else {
+                                if settings.hot_folder_path.is_empty() || modifiers.shift_key() {
+                                    match ui::dialogs::select_folder(hwnd) {
+                                        Some(dir) => settings.hot_folder_path = dir.to_string_lossy().to_string(),
+                                        None => return,
+                                    }
+                                }
+                                hot_folder_watcher = hot_folder::HotFolderWatcher::start(&settings.hot_folder_path, proxy.clone());
+                                settings.hot_folder_enabled = hot_folder_watcher.is_some();
+                                let message = if settings.hot_folder_enabled {
+                                    format!("ホットフォルダ: 監視中 {}", settings.hot_folder_path)
+                                } else {
+                                    "ホットフォルダ: フォルダを開けませんでした".to_string()
+                                };
+                                app_state.status_message = Some((message, std::time::Instant::now()));
+                            }
+                            let _ = settings.save(config_path);
+                            last_dialog_close = std::time::Instant::now();
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "n" => {
+                            // N: 夜間モード (色反転) 切り替え
+                            settings.invert_colors = !settings.invert_colors;
+                            let _ = settings.save(config_path);
+                            let message = if settings.invert_colors { "夜間モード: オン (色反転)" } else { "夜間モード: オフ" };
+                            app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
+                            if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "q" => {
+                            // Q / Shift + Q: ページの合わせ方 (全体 / 幅 / 高さ / 原寸 / 全面) を順に切り替える
+                            let direction = if modifiers.shift_key() { -1 } else { 1 };
+                            let _ = proxy.send_event(UserEvent::SetFitMode(view_state.fit_mode.cycle(direction)));
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "j" => {
+                            // J / Shift + J: フォルダの画像の並び順 (名前 / 更新日時 / サイズ / シャッフル) を順に切り替える
+                            let direction = if modifiers.shift_key() { -1 } else { 1 };
+                            let _ = proxy.send_event(UserEvent::SetSortOrder(crate::image::sort::sort_order().cycle(direction)));
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "y" => {
+                            // Y: 単ページ表示で横長のページを左右に分けて表示する
+                            let _ = proxy.send_event(UserEvent::ToggleSplitWidePages);
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "p" => {
+                            // P: 先読みの一時停止/再開 (バックグラウンドで重い処理をしている間など)
+                            let _ = proxy.send_event(UserEvent::TogglePrefetchPaused);
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "l" => {
+                            if modifiers.shift_key() {
+                                // Shift + L: 輝度ヒストグラムの表示切り替え
+                                app_state.show_histogram = !app_state.show_histogram;
+                                let message = if app_state.show_histogram { "ヒストグラム: 表示" } else { "ヒストグラム: 非表示" };
+                                app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
+                            } else {
+                                // L: 自動レベル補正 (色あせたスキャン画像の黒点・白点を引き伸ばす)
+                                let index = app_state.current_page_index;
+                                let histogram = app_state.page_histograms.get(&index).copied()
+                                    .or_else(|| page_histogram(renderer.as_ref(), &gpu_cache, &cpu_cache, &current_path_key, index));
+                                if let Some((histogram, on_gpu)) = histogram {
+                                    app_state.page_histograms.insert(index, (histogram, on_gpu));
+                                    let (black, white) = crate::render::histogram_levels(&histogram, AUTO_LEVELS_CLIP);
+                                    let adjustment = app_state.color_adjustment.auto_levels(black, white);
+                                    update_color_adjustment(&mut app_state, adjustment);
+                                    let message = format!(
+                                        "自動レベル補正: 黒 {:.0}% / 白 {:.0}% ({})",
+                                        black * 100.0,
+                                        white * 100.0,
+                                        app_state.color_adjustment.describe()
+                                    );
+                                    app_state.status_message = Some((message, std::time::Instant::now()));
+                                } else {
+                                    app_state.status_message = Some(("自動レベル補正: ページの読み込みが終わっていません".to_string(), std::time::Instant::now()));
+                                }
+                            }
+                            window.request_redraw();
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "g" && !modifiers.control_key() => {
+                            if modifiers.shift_key() {
+                                // Shift + G: 手動トリミングの範囲を編集する (カーソル下のページ、無ければ先頭のページで)
+                                let (x, y) = view_state.cursor_pos;
+                                let page = view_state
+                                    .page_rects
+                                    .iter()
+                                    .find(|(_, r)| point_in_rect((x, y), r))
+                                    .or(view_state.page_rects.first())
+                                    .map(|(index, _)| *index);
+                                match page {
+                                    _ if app_state.is_webtoon_view => {
+                                        app_state.status_message = Some(("トリミング: 縦スクロール表示では使えません".to_string(), std::time::Instant::now()));
+                                    }
+                                    Some(page_index) => {
+                                        let transform = app_state.page_transform(page_index);
+                                        // 手動の指定が無ければ検出した余白 (自動トリミング中のみ) から始める
+                                        let start = app_state
+                                            .manual_crop
+                                            .or_else(|| app_state.page_crops.get(&page_index).copied().filter(|_| app_state.auto_crop))
+                                            .unwrap_or_default();
+                                        app_state.crop_edit = Some(crate::state::CropEdit {
+                                            page_index,
+                                            rect: start.visible_rect(transform),
+                                            dragging: None,
+                                        });
+                                        app_state.status_message = Some((
+                                            "トリミング: 枠の辺をドラッグ / Enter で確定 / Esc で取り消し / Delete で解除".to_string(),
+                                            std::time::Instant::now(),
+                                        ));
+                                    }
+                                    None => {
+                                        app_state.status_message = Some(("トリミング: ページの読み込みが終わっていません".to_string(), std::time::Instant::now()));
+                                    }
+                                }
+                            } else {
+                                // G: 自動トリミング (スキャンの白/黒の余白を削る) の切り替え。このセッションのみ
+                                app_state.auto_crop = !app_state.auto_crop;
+                                let message = match (app_state.auto_crop, app_state.manual_crop.is_some()) {
+                                    (true, true) => "自動トリミング: オン (このソースは手動トリミングを優先)",
+                                    (true, false) => "自動トリミング: オン",
+                                    (false, _) => "自動トリミング: オフ",
+                                };
+                                app_state.osd_toast = Some((message.to_string(), std::time::Instant::now()));
+                            }
+                            window.request_redraw();
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "t" => {
+                            // T: OCR テキスト選択モード切り替え
+                            app_state.is_ocr_mode = !app_state.is_ocr_mode;
+                            app_state.ocr_selected.clear();
+                            app_state.ocr_drag_start = None;
+                            if app_state.is_ocr_mode {
+                                app_state.status_message = Some(("OCR モード: ドラッグで選択 / Ctrl+C でコピー / Esc で終了".to_string(), std::time::Instant::now()));
+                                request_ocr_for_display(&mut app_state, &cpu_cache, &rt, &proxy, &current_path_key);
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "c" && !modifiers.control_key() => {
+                            // C: スポイト (拡大縮小前の画素の値を表示し、クリックでカラーコードをコピー)
+                            app_state.is_color_picker = !app_state.is_color_picker;
+                            let message = if app_state.is_color_picker {
+                                "スポイト: オン (クリックでカラーコードをコピー / Esc で終了)"
+                            } else {
+                                "スポイト: オフ"
+                            };
+                            app_state.osd_toast = Some((message.to_string(), std::time::Instant::now()));
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "c" && modifiers.control_key() => {
+                            // Ctrl + C: OCR モードでは選択したテキスト、それ以外は表示中のページ (見開きは並べた 1 枚) をコピー
+                            if !app_state.is_ocr_mode {
+                                let (_, pages) = displayed_export_pages(&app_state, &cpu_cache, &current_path_key);
+                                match pages {
+                                    Some(pages) => {
+                                        app_state.status_message = Some(("コピー中...".to_string(), std::time::Instant::now()));
+                                        let background = settings.background_rgb().map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                                        // HWND はスレッド間で受け渡せないため値で渡す
+                                        let owner = hwnd.0 as isize;
+                                        let proxy = proxy.clone();
+                                        rt.spawn_blocking(move || {
+                                            let result = crate::image::export::compose(&pages, background).and_then(|image| {
+                                                crate::platform::clipboard::set_image(HWND(owner as _), &image)
+                                                    .map(|_| image.dimensions())
+                                                    .map_err(|e| e.to_string())
+                                            });
+                                            let _ = proxy.send_event(UserEvent::PageCopied(result));
+                                        });
+                                    }
+                                    None => {
+                                        app_state.status_message = Some(("読み込みが終わっていないためコピーできません".to_string(), std::time::Instant::now()));
+                                    }
+                                }
+                            } else {
+                                let text = collect_selected_ocr_text(&app_state);
+                                if !text.is_empty() {
+                                    match crate::platform::clipboard::set_text(hwnd, &text) {
+                                        Ok(_) => {
+                                            app_state.status_message = Some((format!("テキストをコピーしました ({} 文字)", text.chars().count()), std::time::Instant::now()));
+                                        }
+                                        Err(e) => println!("[OCR] クリップボードへのコピーに失敗: {:?}", e),
+                                    }
+                                }
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "a" && modifiers.control_key() => {
+                            // Ctrl + A: 表示中ページの OCR 結果を全選択
+                            if app_state.is_ocr_mode {
+                                for idx in app_state.get_page_indices_to_display() {
+                                    if let Some(result) = app_state.ocr_results.get(&idx) {
+                                        let count = result.words.len();
+                                        app_state.ocr_selected.extend((0..count).map(|w| (idx, w)));
+                                    }
+                                }
+                            }
+                        }
                         Key::Named(NamedKey::ArrowRight) | Key::Named(NamedKey::ArrowLeft) | Key::Named(NamedKey::Home) | Key::Named(NamedKey::End) => {
-                            // ページ移動
+                            // ページ移動 (先頭・末尾と 10 ページ単位の移動は Ctrl + Z で戻せるようにする)
+                            if matches!(logical_key, Key::Named(NamedKey::Home) | Key::Named(NamedKey::End)) || modifiers.shift_key() {
+                                app_state.record_undo(&current_path_key, "ページジャンプ");
+                            }
                             match logical_key {
                                 Key::Named(NamedKey::Home) => {
-                                    app_state.current_page_index = 0;
+                                    flip_to(&mut app_state, &gpu_cache, &settings, 0);
                                 }
                                 Key::Named(NamedKey::End) => {
-                                    app_state.current_page_index = app_state.image_files.len().saturating_sub(1);
-                                    app_state.current_page_index = app_state.snap_to_spread(app_state.current_page_index);
+                                    let last = app_state.snap_to_spread(app_state.image_files.len().saturating_sub(1));
+                                    flip_to(&mut app_state, &gpu_cache, &settings, last);
                                 }
                                 _ => {
                                     let direction = if logical_key == Key::Named(NamedKey::ArrowRight) { 1 } else { -1 };
                                     if modifiers.shift_key() {
-                                        app_state.navigate(direction * 10);
+                                        navigate_pages(&mut app_state, &gpu_cache, &settings, direction * 10);
                                     } else if modifiers.control_key() {
                                         let new_idx = (app_state.current_page_index as isize + direction as isize).clamp(0, (app_state.image_files.len() as isize - 1).max(0)) as usize;
-                                        app_state.current_page_index = new_idx;
+                                        flip_to(&mut app_state, &gpu_cache, &settings, new_idx);
                                     } else {
-                                        app_state.navigate(direction);
+                                        navigate_pages(&mut app_state, &gpu_cache, &settings, direction);
                                     }
                                 }
                             }
@@ -649,7 +3204,57 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
                             request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
                         }
+                        Key::Named(NamedKey::Tab) if modifiers.shift_key() => {
+                            // Shift + Tab: フォルダ/アーカイブのサイドバーを開閉する
+                            app_state.browser_pane = match app_state.browser_pane {
+                                Some(_) => None,
+                                None => Some(crate::ui::browser::BrowserPane::new()),
+                            };
+                            settings.show_browser_pane = app_state.browser_pane.is_some();
+                            let _ = settings.save(config_path);
+                        }
+                        Key::Named(NamedKey::Tab) => {
+                            // Tab: 同じフォルダのアーカイブ/フォルダ一覧を開く
+                            match list_sibling_sources(&current_path_key) {
+                                Some((entries, current)) if !entries.is_empty() => {
+                                    app_state.series_overlay = Some(SeriesOverlay {
+                                        entries: entries.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+                                        current,
+                                        selected: current.unwrap_or(0),
+                                    });
+                                }
+                                _ => {
+                                    app_state.osd_toast = Some(("同じフォルダに他のアーカイブ/フォルダがありません".to_string(), std::time::Instant::now()));
+                                }
+                            }
+                        }
+                        Key::Named(NamedKey::Space) => {
+                            // Space / Shift+Space: 拡大中は画面単位で読み順に送り、端まで来たらページを移動する
+                            let direction = if modifiers.shift_key() { -1 } else { 1 };
+                            let win_size = page_area_size(&window, status_bar_hwnd);
+                            let right_to_left = app_state.binding_direction == BindingDirection::Right;
+                            if app_state.is_webtoon_view {
+                                // 縦スクロール表示: 少し重なりを残して 1 画面分スクロールする
+                                view_state.webtoon_offset += direction as f32 * win_size.1 * 0.9;
+                                app_state.scroll_direction = direction;
+                            } else if (view_state.zoom_level <= 1.0 && !view_state.page_scrollable)
+                                || !view_state.animate(settings.view_animation_ms, |v| v.panel_scroll(win_size, right_to_left, direction))
+                            {
+                                navigate_pages(&mut app_state, &gpu_cache, &settings, direction);
+                                view_state.reset();
+                                let l = loader.clone();
+                                rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                                request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                            }
+                        }
                         Key::Character(ref s) if s.to_lowercase() == "b" => {
+                                app_state.record_undo(&current_path_key, "表示モードの切り替え");
+                                // 縦スクロール表示中は抜けてから見開きの切り替えに入る
+                                if app_state.is_webtoon_view {
+                                    app_state.is_webtoon_view = false;
+                                    settings.webtoon_view = false;
+                                    app_state.is_spread_view = false;
+                                }
                                 if !app_state.is_spread_view {
                                     app_state.is_spread_view = true;
                                     app_state.binding_direction = BindingDirection::Right;
@@ -666,16 +3271,45 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 } else { 
                                     "left".to_string() 
                                 };
-                                let _ = settings.save("config.json");
+                                let _ = settings.save(config_path);
 
                                 // オプション画面が開いていれば再描画をリクエスト
                                 if let Some(ref mut ms) = modern_settings {
                                     ms.window.request_redraw();
                                 }
                                 
-                                view_state.reset();
+                                // 拡大中なら切り替え後も同じ内容位置を表示する
+                                view_state.reset_keeping_anchor(page_area_size(&window, status_bar_hwnd));
                                 request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
                         }
+                        Key::Character(ref s) if s.to_lowercase() == "v" && modifiers.control_key() => {
+                            // Ctrl + V: クリップボード監視 (コピーされた画像を順に追加して表示する)
+                            if clipboard_watch.take().is_some() {
+                                app_state.osd_toast = Some(("クリップボード監視: オフ".to_string(), std::time::Instant::now()));
+                            } else {
+                                clipboard_watch = clipboard_watcher::ClipboardWatcher::start(proxy.clone());
+                                let message = if clipboard_watch.is_some() {
+                                    "クリップボード監視: オン (コピーした画像を表示します)"
+                                } else {
+                                    "クリップボード監視: 開始できませんでした"
+                                };
+                                app_state.osd_toast = Some((message.to_string(), std::time::Instant::now()));
+                            }
+                        }
+                        Key::Character(ref s) if s.to_lowercase() == "v" => {
+                            // V: 縦スクロール (Webtoon) 表示の切り替え
+                            app_state.record_undo(&current_path_key, "表示モードの切り替え");
+                            settings.webtoon_view = !app_state.is_webtoon_view;
+                            apply_display_mode(&mut app_state, &settings);
+                            let _ = settings.save(config_path);
+                            let label = if app_state.is_webtoon_view { "オン" } else { "オフ" };
+                            app_state.osd_toast = Some((format!("縦スクロール表示: {}", label), std::time::Instant::now()));
+                            if let Some(ref mut ms) = modern_settings {
+                                ms.window.request_redraw();
+                            }
+                            view_state.reset();
+                            request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                        }
                         Key::Named(NamedKey::PageUp) | Key::Named(NamedKey::PageDown) => {
                             // PageUp/PageDown: 履歴を順にナビゲート
                             let history_len = settings.history.len();
@@ -711,7 +3345,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                 load_new_source(
                                                     new_source,
                                                     item.path,
-                                                    item.page,
+                                                    Some(item.page),
                                                     Some(item.binding),
                                                     &mut app_state,
                                                     &mut current_path_key,
@@ -720,7 +3354,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                                     &loader,
                                                     &rt,
                                                     &mut settings,
-                                                    &mut current_bitmaps,
+                                                    &mut gpu_cache,
+                                                    &mut view_state,
                                                     true, // 履歴ナビゲーション: 履歴への再追加をスキップ
                                                 );
                                                 app_state.status_message = Some((
@@ -744,18 +3379,107 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             if app_state.is_jump_open {
                                 app_state.is_jump_open = false;
                                 app_state.jump_input_buffer.clear();
+                            } else if app_state.is_ocr_mode {
+                                app_state.is_ocr_mode = false;
+                                app_state.ocr_selected.clear();
+                                app_state.ocr_drag_start = None;
+                            } else if app_state.is_color_picker {
+                                app_state.is_color_picker = false;
+                            } else if app_state.annotation_tool.is_some() {
+                                app_state.annotation_tool = None;
+                                app_state.annotation_stroke = None;
+                                app_state.osd_toast = Some(("注釈モード: オフ".to_string(), std::time::Instant::now()));
+                            } else if window.fullscreen().is_some() {
+                                // Esc: 全画面を抜ける
+                                toggle_fullscreen(&window, status_bar_hwnd, &mut settings);
+                            }
+                        }
+                        Key::Character(ref s) if (s == "[" || s == "]") && modifiers.control_key() => {
+                            // Ctrl + [ / ]: 前/次の章 (フォルダやアーカイブ内のサブフォルダ) の先頭へ
+                            let direction = if s == "]" { 1 } else { -1 };
+                            if app_state.folder_start_indices.is_empty() {
+                                app_state.osd_toast = Some(("章に分かれていません".to_string(), std::time::Instant::now()));
+                            } else if let Some(start) = app_state.chapter_start(direction) {
+                                app_state.record_undo(&current_path_key, "章ジャンプ");
+                                let target = app_state.snap_to_spread(start);
+                                flip_to(&mut app_state, &gpu_cache, &settings, target);
+                                let (_, chapters) = app_state.chapter_position();
+                                let chapter = app_state.folder_start_indices.iter().filter(|&&idx| idx <= start).count() + 1;
+                                app_state.osd_toast = Some((format!("章 {}/{}", chapter, chapters), std::time::Instant::now()));
+                                view_state.reset();
+                                let l = loader.clone();
+                                rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                                request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                            } else {
+                                let edge = if direction > 0 { "最後" } else { "最初" };
+                                app_state.osd_toast = Some((format!("{}の章です", edge), std::time::Instant::now()));
                             }
                         }
                         Key::Character(ref s) if s == "[" || s == "]" => {
-                            if !app_state.is_jump_open {
+                            // プレイリストから開いたソースならプレイリスト内の前後へ (端ではそこで止まる)
+                            let playlist_step = app_state
+                                .playlist
+                                .as_ref()
+                                .filter(|p| p.position(&current_path_key).is_some())
+                                .map(|p| {
+                                    let direction = if s == "]" { 1 } else { -1 };
+                                    p.neighbor(&current_path_key, direction).map(|(i, e)| (i, p.entries().len(), e.to_string()))
+                                });
+                            if let Some(step) = playlist_step.filter(|_| !app_state.is_jump_open) {
+                                let arrow = if s == "]" { "▶" } else { "◀" };
+                                match step {
+                                    Some((index, total, new_path)) => {
+                                        let name = std::path::Path::new(&new_path)
+                                            .file_name()
+                                            .map(|n| n.to_string_lossy().to_string())
+                                            .unwrap_or_else(|| new_path.clone());
+                                        if let Some(new_source) = get_image_source(&new_path) {
+                                            println!("[プレイリスト] {}/{}: {}", index + 1, total, new_path);
+                                            app_state.osd_toast = Some((format!("{} [{}/{}] {}", arrow, index + 1, total, name), std::time::Instant::now()));
+                                            load_new_source(
+                                                new_source,
+                                                new_path,
+                                                None,
+                                                None,
+                                                &mut app_state,
+                                                &mut current_path_key,
+                                                &window,
+                                                &cpu_cache,
+                                                &loader,
+                                                &rt,
+                                                &mut settings,
+                                                &mut gpu_cache,
+                                                &mut view_state,
+                                                false, // 通常のファイル読み込み
+                                            );
+                                        } else {
+                                            // 移動・削除されたエントリは取り除き、もう一度押せば次へ進めるようにする
+                                            if let Some(playlist) = app_state.playlist.as_mut() {
+                                                playlist.remove(&new_path);
+                                            }
+                                            app_state.osd_toast = Some((format!("開けませんでした: {}", name), std::time::Instant::now()));
+                                        }
+                                    }
+                                    None => {
+                                        let message = if s == "]" { "プレイリストの最後です" } else { "プレイリストの最初です" };
+                                        app_state.osd_toast = Some((message.to_string(), std::time::Instant::now()));
+                                    }
+                                }
+                            } else if !app_state.is_jump_open {
                                 let direction = if s == "]" { 1 } else { -1 };
-                                if let Some(new_path) = get_neighboring_source(&current_path_key, direction) {
-                                    println!("フォルダ/アーカイブ移動: {}", new_path);
-                                    if let Some(new_source) = get_image_source(&new_path) {
+                                match find_neighboring_source(&current_path_key, direction) {
+                                    Some((new_path, new_source)) => {
+                                        println!("フォルダ/アーカイブ移動: {}", new_path);
+                                        let name = std::path::Path::new(&new_path)
+                                            .file_name()
+                                            .map(|n| n.to_string_lossy().to_string())
+                                            .unwrap_or_else(|| new_path.clone());
+                                        let arrow = if direction > 0 { "▶" } else { "◀" };
+                                        app_state.osd_toast = Some((format!("{} {}", arrow, name), std::time::Instant::now()));
                                         load_new_source(
                                             new_source,
                                             new_path,
-                                            0,
+                                            None,
                                             None,
                                             &mut app_state,
                                             &mut current_path_key,
@@ -764,10 +3488,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             &loader,
                                             &rt,
                                             &mut settings,
-                                            &mut current_bitmaps,
+                                            &mut gpu_cache,
+                                            &mut view_state,
                                             false, // 通常のファイル読み込み
                                         );
                                     }
+                                    None => {
+                                        let message = if direction > 0 { "最後です" } else { "最初です" };
+                                        app_state.osd_toast = Some((message.to_string(), std::time::Instant::now()));
+                                    }
                                 }
                             }
                         }
@@ -784,7 +3513,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     load_new_source(
                                         new_source,
                                         new_path,
-                                        0,
+                                        None,
                                         None,
                                         &mut app_state,
                                         &mut current_path_key,
@@ -793,41 +3522,53 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         &loader,
                                         &rt,
                                         &mut settings,
-                                        &mut current_bitmaps,
+                                        &mut gpu_cache,
+                                        &mut view_state,
                                         false, // 通常のファイル読み込み
                                     );
                                 }
                             }
                         }
+                        Key::Character(ref s) if (s == "+" || s == ";" || s == "-") && (modifiers.control_key() || modifiers.alt_key()) => {
+                            // Ctrl: 明るさ / Alt: コントラスト / Ctrl + Alt: ガンマ
+                            let step = if s == "-" { -1.0 } else { 1.0 };
+                            let adjustment = app_state.color_adjustment;
+                            let adjustment = match (modifiers.control_key(), modifiers.alt_key()) {
+                                (true, true) => adjustment.step_gamma(step),
+                                (true, false) => adjustment.step_brightness(step),
+                                _ => adjustment.step_contrast(step),
+                            };
+                            update_color_adjustment(&mut app_state, adjustment);
+                        }
+                        Key::Character(ref s) if s == "0" && modifiers.control_key() => {
+                            // Ctrl + 0: 色調整をリセット
+                            update_color_adjustment(&mut app_state, ColorAdjustment::default());
+                        }
                         Key::Character(ref s) if s == "+" || s == ";" => { // ";" は JP キーボードの "+"
-                            let window_size = window.inner_size();
-                            let win_size = (window_size.width as f32, window_size.height as f32);
+                            let win_size = page_area_size(&window, status_bar_hwnd);
                             let center = (win_size.0 / 2.0, win_size.1 / 2.0);
-                            view_state.set_zoom(view_state.zoom_level * 1.15, center, win_size);
+                            view_state.animate(settings.view_animation_ms, |v| v.set_zoom(v.zoom_level * 1.15, center, win_size));
                         }
                         Key::Character(ref s) if s == "-" => {
-                            let window_size = window.inner_size();
-                            let win_size = (window_size.width as f32, window_size.height as f32);
+                            let win_size = page_area_size(&window, status_bar_hwnd);
                             let center = (win_size.0 / 2.0, win_size.1 / 2.0);
-                            view_state.set_zoom(view_state.zoom_level / 1.15, center, win_size);
+                            view_state.animate(settings.view_animation_ms, |v| v.set_zoom(v.zoom_level / 1.15, center, win_size));
                         }
                         _ => {
                             if let PhysicalKey::Code(code) = physical_key {
                                 match code {
                                     KeyCode::NumpadAdd => {
-                                        let window_size = window.inner_size();
-                                        let win_size = (window_size.width as f32, window_size.height as f32);
+                                        let win_size = page_area_size(&window, status_bar_hwnd);
                                         let center = (win_size.0 / 2.0, win_size.1 / 2.0);
-                                        view_state.set_zoom(view_state.zoom_level * 1.15, center, win_size);
+                                        view_state.animate(settings.view_animation_ms, |v| v.set_zoom(v.zoom_level * 1.15, center, win_size));
                                     }
                                     KeyCode::NumpadSubtract => {
-                                        let window_size = window.inner_size();
-                                        let win_size = (window_size.width as f32, window_size.height as f32);
+                                        let win_size = page_area_size(&window, status_bar_hwnd);
                                         let center = (win_size.0 / 2.0, win_size.1 / 2.0);
-                                        view_state.set_zoom(view_state.zoom_level / 1.15, center, win_size);
+                                        view_state.animate(settings.view_animation_ms, |v| v.set_zoom(v.zoom_level / 1.15, center, win_size));
                                     }
                                     KeyCode::NumpadMultiply => {
-                                        view_state.reset();
+                                        view_state.animate(settings.view_animation_ms, |v| v.reset());
                                     }
                                     _ => (),
                                 }
@@ -838,6 +3579,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 WindowEvent::CursorMoved { position, .. } => {
                     let pos = (position.x as f32, position.y as f32);
+                    // 再描画などで座標が変わらないまま通知されることがあるため、実際に動いたときだけ表示に戻す
+                    if pos != view_state.cursor_pos {
+                        cursor_auto_hide.on_activity(&window);
+                    }
                     let window_size = window.inner_size();
                     let win_w = window_size.width as f32;
 
@@ -845,7 +3590,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     if app_state.is_dragging_seekbar && !app_state.image_files.is_empty() {
                         let progress = (pos.0 / win_w).clamp(0.0, 1.0);
                         let total_pages = app_state.image_files.len();
-                        let target_progress = if app_state.binding_direction == BindingDirection::Right {
+                        let target_progress = if seekbar_from_right(&app_state, &settings) {
                             1.0 - progress
                         } else {
                             progress
@@ -861,34 +3606,75 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
 
-                    if view_state.is_panning || view_state.is_loupe {
-                        view_state.pan_offset.0 += pos.0 - view_state.last_mouse_pos.0;
-                        view_state.pan_offset.1 += pos.1 - view_state.last_mouse_pos.1;
+                    if view_state.is_panning {
+                        view_state.drag_pan((pos.0 - view_state.last_mouse_pos.0, pos.1 - view_state.last_mouse_pos.1));
+                    }
+                    if app_state.annotation_stroke.is_some() && view_state.touches.is_empty() {
+                        extend_annotation_stroke(&mut app_state, &view_state.page_rects, pos);
+                    }
+                    if let Some(ref mut edit) = app_state.crop_edit {
+                        if let Some(page) = view_state.page_rects.iter().find(|(i, _)| *i == edit.page_index).map(|(_, r)| *r) {
+                            drag_crop_edit(edit, &page, pos);
+                        }
                     }
                     view_state.last_mouse_pos = pos;
                     view_state.cursor_pos = pos;
                     window.request_redraw();
                 }
                 WindowEvent::MouseInput { state, button, .. } => {
+                    cursor_auto_hide.on_activity(&window);
                     match button {
                         MouseButton::Left => {
+                            if let Some(ref overlay) = app_state.series_overlay {
+                                if state == ElementState::Pressed {
+                                    let window_size = window.inner_size();
+                                    let scale = window.scale_factor() as f32;
+                                    let (panel, first, rows) = series_overlay_layout(overlay, window_size.width as f32 / scale, window_size.height as f32 / scale);
+                                    let (x, y) = (view_state.cursor_pos.0 / scale, view_state.cursor_pos.1 / scale);
+                                    let hit = (0..rows).find(|&row| {
+                                        let r = series_row_rect(&panel, row);
+                                        x >= r.left && x <= r.right && y >= r.top && y <= r.bottom
+                                    });
+                                    if let Some(row) = hit {
+                                        let index = first + row;
+                                        if Some(index) != overlay.current {
+                                            let _ = proxy.send_event(UserEvent::LoadPath(overlay.entries[index].clone()));
+                                        }
+                                        app_state.series_overlay = None;
+                                    } else if x < panel.left || x > panel.right || y < panel.top || y > panel.bottom {
+                                        app_state.series_overlay = None;
+                                    }
+                                    window.request_redraw();
+                                }
+                                return;
+                            }
+                            // サイドバー上のクリック: 「..」で親フォルダの一覧へ、項目は開く
+                            if let Some(ref mut pane) = app_state.browser_pane {
+                                let scale = window.scale_factor() as f32;
+                                let pane_h = page_area_size(&window, status_bar_hwnd).1 / scale;
+                                let (x, y) = (view_state.cursor_pos.0 / scale, view_state.cursor_pos.1 / scale);
+                                if state == ElementState::Pressed && crate::ui::browser::BrowserPane::contains(x, y, pane_h) {
+                                    match pane.row_at(x, y, pane_h) {
+                                        Some(crate::ui::browser::BrowserRow::Parent) => pane.go_up(is_book_file),
+                                        Some(crate::ui::browser::BrowserRow::Entry(index)) if Some(index) != pane.current => {
+                                            let path = pane.entries[index].path.to_string_lossy().to_string();
+                                            let _ = proxy.send_event(UserEvent::LoadPath(path));
+                                        }
+                                        _ => {}
+                                    }
+                                    window.request_redraw();
+                                    return;
+                                }
+                            }
                             if app_state.is_jump_open {
                                 if state == ElementState::Pressed {
                                     let window_size = window.inner_size();
-                                    let win_w = window_size.width as f32;
-                                    let win_h = window_size.height as f32;
-                                    let jump_w = 340.0;
-                                    let jump_h = 160.0;
-                                    let jump_rect = D2D_RECT_F {
-                                        left: (win_w - jump_w) / 2.0,
-                                        top: (win_h - jump_h) / 2.0,
-                                        right: (win_w + jump_w) / 2.0,
-                                        bottom: (win_h + jump_h) / 2.0,
-                                    };
+                                    let scale = window.scale_factor() as f32;
+                                    let jump_rect = jump_panel_rect(window_size.width as f32 / scale, window_size.height as f32 / scale);
+                                    let (x, y) = (view_state.cursor_pos.0 / scale, view_state.cursor_pos.1 / scale);
                                     
                                     // クリック位置がUI外なら閉じる
-                                    if view_state.cursor_pos.0 < jump_rect.left || view_state.cursor_pos.0 > jump_rect.right ||
-                                       view_state.cursor_pos.1 < jump_rect.top || view_state.cursor_pos.1 > jump_rect.bottom {
+                                    if x < jump_rect.left || x > jump_rect.right || y < jump_rect.top || y > jump_rect.bottom {
                                         app_state.is_jump_open = false;
                                         app_state.jump_input_buffer.clear();
                                         window.request_redraw();
@@ -897,24 +3683,102 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 return;
                             }
 
+                            // スポイト中のクリックはカーソル下の色のカラーコードをコピーする
+                            if app_state.is_color_picker {
+                                if state == ElementState::Pressed {
+                                    match sample_pixel(&app_state, &view_state.page_rects, &cpu_cache, &current_path_key, view_state.cursor_pos) {
+                                        Some(sample) => match crate::platform::clipboard::set_text(hwnd, &sample.hex()) {
+                                            Ok(_) => {
+                                                app_state.osd_toast = Some((format!("コピーしました: {}", sample.hex()), std::time::Instant::now()));
+                                            }
+                                            Err(e) => println!("[スポイト] クリップボードへのコピーに失敗: {:?}", e),
+                                        },
+                                        None => {
+                                            app_state.status_message = Some(("スポイト: ページの読み込みが終わっていません".to_string(), std::time::Instant::now()));
+                                        }
+                                    }
+                                }
+                                window.request_redraw();
+                                return;
+                            }
+
+                            // トリミング範囲の編集中は左ドラッグで枠の辺を動かす
+                            if let Some(ref mut edit) = app_state.crop_edit {
+                                edit.dragging = None;
+                                if state == ElementState::Pressed {
+                                    let page = view_state.page_rects.iter().find(|(i, _)| *i == edit.page_index).map(|(_, r)| *r);
+                                    if let Some(page) = page {
+                                        let tolerance = CROP_HANDLE_TOLERANCE * window.scale_factor() as f32;
+                                        edit.dragging = crop_edit_grab(edit, &page, view_state.cursor_pos, tolerance);
+                                    }
+                                }
+                                window.request_redraw();
+                                return;
+                            }
+
+                            // OCR モード中は左ドラッグをテキスト範囲選択に使用する
+                            if app_state.is_ocr_mode {
+                                if state == ElementState::Pressed {
+                                    app_state.ocr_drag_start = Some(view_state.cursor_pos);
+                                } else if let Some(start) = app_state.ocr_drag_start.take() {
+                                    select_ocr_words(&mut app_state, &view_state.page_rects, start, view_state.cursor_pos, modifiers.control_key());
+                                }
+                                window.request_redraw();
+                                return;
+                            }
+
+                            // 注釈モード中は左ドラッグで線を描く (タッチ・ペンは Touch で処理する)
+                            if app_state.annotation_tool.is_some() {
+                                if state == ElementState::Pressed {
+                                    if view_state.touches.is_empty() {
+                                        begin_annotation_stroke(&mut app_state, &view_state.page_rects, view_state.cursor_pos);
+                                    }
+                                } else {
+                                    finish_annotation_stroke(&mut app_state, &current_path_key);
+                                }
+                                window.request_redraw();
+                                return;
+                            }
+
+                            if state == ElementState::Pressed && view_state.zoom_select_armed {
+                                view_state.zoom_select_start = Some(view_state.cursor_pos);
+                                window.request_redraw();
+                                return;
+                            }
+                            if let Some(start) = view_state.zoom_select_start.take() {
+                                if state == ElementState::Released {
+                                    let win_size = page_area_size(&window, status_bar_hwnd);
+                                    let rect = normalize_rect(start, view_state.cursor_pos);
+                                    view_state.animate(settings.view_animation_ms, |v| v.zoom_to_rect(rect, win_size));
+                                }
+                                window.request_redraw();
+                                return;
+                            }
+
                             if state == ElementState::Pressed {
                                 let window_size = window.inner_size();
-                                let win_h = window_size.height as f32;
-                                let status_bar_h = 22.0;
-                                let seek_bar_h = 8.0;
-                                // 描画ロジックと一致させる (win_h - 22.0 - 8.0)
-                                let bar_y = win_h - status_bar_h - seek_bar_h;
+                                let scale = window.scale_factor() as f32;
+                                // 描画と同じ配置関数を論理座標で使う
+                                let bar = seekbar_rect(
+                                    window_size.width as f32 / scale,
+                                    window_size.height as f32 / scale,
+                                    status_bar_logical_height(status_bar_hwnd, scale),
+                                    false,
+                                );
+                                let cursor_y = view_state.cursor_pos.1 / scale;
 
                                 // シークバークリック判定 (少し判定を広げる: 上下 4px)
                                 let hit_margin = 4.0;
-                                if app_state.show_seekbar && view_state.cursor_pos.1 >= bar_y - hit_margin && view_state.cursor_pos.1 <= bar_y + seek_bar_h + hit_margin {
+                                if app_state.show_seekbar && cursor_y >= bar.top - hit_margin && cursor_y <= bar.bottom + hit_margin {
+                                    // ドラッグ中の移動はまとめて 1 回の操作として取り消す
+                                    app_state.record_undo(&current_path_key, "シークバーでの移動");
                                     app_state.is_dragging_seekbar = true;
                                     // 即座に位置を反映させるために CursorMoved と同じロジックを実行
                                     let win_w = window_size.width as f32;
                                     let progress = (view_state.cursor_pos.0 / win_w).clamp(0.0, 1.0);
                                     let total_pages = app_state.image_files.len();
                                     if total_pages > 0 {
-                                        let target_progress = if app_state.binding_direction == BindingDirection::Right {
+                                        let target_progress = if seekbar_from_right(&app_state, &settings) {
                                             1.0 - progress
                                         } else {
                                             progress
@@ -926,33 +3790,84 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
                                         request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
                                     }
-                                } else if view_state.zoom_level > 1.0 {
-                                    view_state.is_panning = true;
+                                } else if view_state.zoom_level > 1.0 || view_state.page_scrollable || app_state.is_webtoon_view {
+                                    view_state.begin_pan();
+                                } else if window.fullscreen().is_none() && !window.is_maximized() && is_over_page(&view_state) {
+                                    // 拡大していないときは画像のドラッグでウィンドウを移動する (WM_NCLBUTTONDOWN / HTCAPTION 相当)
+                                    // 移動中は OS のモーダルループに入るため、ボタンを離したイベントは届かない
+                                    if let Err(e) = window.drag_window() {
+                                        eprintln!("[ウィンドウ] ドラッグ移動を開始できませんでした: {}", e);
+                                    }
                                 }
                             } else {
-                                view_state.is_panning = false;
+                                if view_state.is_panning {
+                                    // ズーム/パンのアニメーションを無効にしている場合は慣性でも滑らせない
+                                    view_state.end_pan(settings.view_animation_ms > 0);
+                                }
                                 app_state.is_dragging_seekbar = false;
                             }
                         }
                         MouseButton::Right => {
                             if app_state.is_jump_open { return; }
-                            if state == ElementState::Pressed {
-                                view_state.is_loupe = true;
-                                view_state.loupe_base_zoom = view_state.zoom_level;
-                                view_state.loupe_base_pan = view_state.pan_offset;
-
-                                let window_size = window.inner_size();
-                                let win_size = (window_size.width as f32, window_size.height as f32);
-                                view_state.set_zoom(view_state.zoom_level * settings.magnifier_zoom, view_state.cursor_pos, win_size);
+                            // 押している間だけルーペを表示する (描画時にカーソル周辺だけを拡大する)
+                            view_state.is_loupe = state == ElementState::Pressed;
+                        }
+                        _ => (),
+                    }
+                    window.request_redraw();
+                }
+                WindowEvent::Touch(touch) => {
+                    // タッチ・ペンは WM_POINTER から届く (マウスのイベントには変換されない)
+                    cursor_auto_hide.on_activity(&window);
+                    let pos = (touch.location.x as f32, touch.location.y as f32);
+                    match touch.phase {
+                        TouchPhase::Started => {
+                            view_state.inertia = None;
+                            view_state.touches.insert(touch.id, pos);
+                            if view_state.touches.len() == 1 {
+                                begin_annotation_stroke(&mut app_state, &view_state.page_rects, pos);
                             } else {
-                                if view_state.is_loupe {
-                                    view_state.zoom_level = view_state.loupe_base_zoom;
-                                    view_state.pan_offset = view_state.loupe_base_pan;
-                                    view_state.is_loupe = false;
+                                // 2 本目の指が触れたら描きかけの線は捨てて、パン・ズームに切り替える
+                                app_state.annotation_stroke = None;
+                            }
+                        }
+                        TouchPhase::Moved => {
+                            let Some(previous) = view_state.touches.insert(touch.id, pos) else { return; };
+                            let other = view_state.touches.iter().find(|(&id, _)| id != touch.id).map(|(_, &p)| p);
+                            if let Some(other) = other {
+                                // 2 本指: 中点の移動でパン、指の間隔の変化で中点を基準にズームする
+                                view_state.animation = None;
+                                let before = ((previous.0 + other.0) / 2.0, (previous.1 + other.1) / 2.0);
+                                let after = ((pos.0 + other.0) / 2.0, (pos.1 + other.1) / 2.0);
+                                view_state.pan_offset.0 += after.0 - before.0;
+                                view_state.pan_offset.1 += after.1 - before.1;
+                                let distance_before = (previous.0 - other.0).hypot(previous.1 - other.1);
+                                let distance_after = (pos.0 - other.0).hypot(pos.1 - other.1);
+                                if distance_before > 1.0 {
+                                    let win_size = page_area_size(&window, status_bar_hwnd);
+                                    let zoom = view_state.zoom_level * distance_after / distance_before;
+                                    view_state.set_zoom(zoom, after, win_size);
                                 }
+                            } else if app_state.annotation_stroke.is_some() {
+                                extend_annotation_stroke(&mut app_state, &view_state.page_rects, pos);
+                            } else if app_state.annotation_tool.is_none()
+                                && (view_state.zoom_level > 1.0 || view_state.page_scrollable || app_state.is_webtoon_view)
+                            {
+                                view_state.animation = None;
+                                view_state.pan_offset.0 += pos.0 - previous.0;
+                                view_state.pan_offset.1 += pos.1 - previous.1;
                             }
                         }
-                        _ => (),
+                        TouchPhase::Ended => {
+                            view_state.touches.remove(&touch.id);
+                            if view_state.touches.is_empty() {
+                                finish_annotation_stroke(&mut app_state, &current_path_key);
+                            }
+                        }
+                        TouchPhase::Cancelled => {
+                            view_state.touches.remove(&touch.id);
+                            app_state.annotation_stroke = None;
+                        }
                     }
                     window.request_redraw();
                 }
@@ -962,18 +3877,47 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         MouseScrollDelta::LineDelta(_, y) => y,
                         MouseScrollDelta::PixelDelta(pos) => (pos.y / 120.0) as f32,
                     };
+                    if let Some(ref mut overlay) = app_state.series_overlay {
+                        if scroll.abs() > 0.01 {
+                            overlay.move_selection(if scroll > 0.0 { -1 } else { 1 });
+                            window.request_redraw();
+                        }
+                        return;
+                    }
+                    if let Some(ref mut pane) = app_state.browser_pane {
+                        let scale = window.scale_factor() as f32;
+                        let pane_h = page_area_size(&window, status_bar_hwnd).1 / scale;
+                        if crate::ui::browser::BrowserPane::contains(view_state.cursor_pos.0 / scale, view_state.cursor_pos.1 / scale, pane_h) {
+                            if scroll.abs() > 0.01 {
+                                pane.scroll_by(if scroll > 0.0 { -3 } else { 3 }, pane_h);
+                                window.request_redraw();
+                            }
+                            return;
+                        }
+                    }
                     
                     if scroll.abs() > 0.01 {
                         if modifiers.control_key() {
                             // Ctrl + Wheel: ズーム
                             let factor = if scroll > 0.0 { 1.15 } else { 1.0 / 1.15 };
-                            let window_size = window.inner_size();
-                            let win_size = (window_size.width as f32, window_size.height as f32);
-                            view_state.set_zoom(view_state.zoom_level * factor, view_state.cursor_pos, win_size);
+                            let win_size = page_area_size(&window, status_bar_hwnd);
+                            let anchor = view_state.cursor_pos;
+                            view_state.animate(settings.view_animation_ms, |v| v.set_zoom(v.zoom_level * factor, anchor, win_size));
+                        } else if app_state.is_webtoon_view {
+                            // 縦スクロール表示: ページ移動ではなく連続してスクロールする
+                            view_state.webtoon_offset -= scroll * WHEEL_SCROLL_STEP * window.scale_factor() as f32;
+                            app_state.scroll_direction = if scroll > 0.0 { -1 } else { 1 };
+                        } else if view_state.page_scrollable
+                            && view_state.scroll_vertically(
+                                scroll * WHEEL_SCROLL_STEP * window.scale_factor() as f32,
+                                page_area_size(&window, status_bar_hwnd).1,
+                            )
+                        {
+                            // 縦長ページ: 端に着くまではページ内をスクロールし、端からさらに回すとページを移動する
                         } else {
                             // 通常の Wheel: ページ移動
                             let direction = if scroll > 0.0 { -1 } else { 1 };
-                            app_state.navigate(direction);
+                            navigate_pages(&mut app_state, &gpu_cache, &settings, direction);
                             let l = loader.clone();
                             rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
                             view_state.reset();
@@ -991,98 +3935,529 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let window_size = window.inner_size();
                     let win_w = window_size.width as f32;
                     let win_h = window_size.height as f32;
+                    // オーバーレイ用の論理座標系 (ページ画像は物理ピクセルのまま配置する)
+                    let ui_scale = window.scale_factor() as f32;
+                    let (logical_w, logical_h) = (win_w / ui_scale, win_h / ui_scale);
+                    renderer.set_ui_scale(ui_scale);
+                    // ページはステータスバーに隠れない範囲に配置する (全画面中はウィンドウ全体)
+                    let (_, page_h) = page_area_size(&window, status_bar_hwnd);
+
+                    // サイズ変更中: 配置の計算・転送・解放は行わず、直前のフレームを縮小して余白付きで表示する
+                    // 変更が落ち着くと (AboutToWait の再描画で) 通常の描画に戻り、新しい大きさで配置し直す
+                    if app_state.frozen_frame.is_active() {
+                        draw_frozen_frame(renderer.as_mut(), &app_state.frozen_frame, &gpu_cache, win_w, page_h);
+                        return;
+                    }
+
+                    view_state.tick_inertia();
+                    // 縦スクロール表示: スクロール位置から現在のページを決め直す
+                    if app_state.is_webtoon_view
+                        && update_webtoon_scroll(&mut app_state, &mut view_state, renderer.as_ref(), &gpu_cache, win_w, page_h)
+                    {
+                        let l = loader.clone();
+                        rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                        request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                    }
 
                     let indices = app_state.get_page_indices_to_display();
+                    // 瞬時切り替えで待っている移動先のページ (表示中のページと同じく先読みを待たずに転送する)
+                    let flip_pages: Vec<usize> = app_state.pending_flip.as_ref().map(|p| p.pages.clone()).unwrap_or_default();
+                    crate::platform::crash::set_reading_position(&current_path_key, app_state.current_page_index, binding_str(&app_state));
+                    if !clipboard_watcher::is_transient_source(&current_path_key) {
+                        app_state.stats.observe(&current_path_key, &indices);
+                    }
                     
                     // GPU キャッシュの更新と不要なビットマップの解放
-                    {
+                    // ルーペ中はカーソル移動のたびに再描画されるため、転送と解放は行わず描画だけを更新する
+                    let upload_start = std::time::Instant::now();
+                    if !view_state.is_loupe {
                         let mut cache = cpu_cache.lock().unwrap();
-                        cache.set_current_context(app_state.current_page_index, indices.clone());
+                        cache.set_current_context(app_state.current_page_index, indices.iter().chain(&flip_pages).copied().collect());
                         
                         // 1. 不要なビットマップの解放
-                        let max_gpu_bitmaps = settings.gpu_max_prefetch_pages + indices.len();
-                        let current_idx = app_state.current_page_index as isize;
-                        let max_idx = app_state.image_files.len() as isize - 1;
+                        // GPU キャッシュ保持対象 (表示中 + 前後 settings.gpu_max_prefetch_pages ページ分)
+                        // 見開きでは次/前に表示する組の単位で数え、次の見開きの 2 ページが揃って残るようにする
+                        // 縦スクロール表示ではスクロールしている向きに多く確保する
+                        let (before, after) = app_state.prefetch_window(settings.gpu_max_prefetch_pages);
+                        let prefetch_pages = app_state.prefetch_pages(before, after);
+                        let display_count = indices.len() + flip_pages.len();
+                        let gpu_limits = GpuCacheLimits {
+                            max_pages: settings.gpu_max_prefetch_pages.max(prefetch_pages.len()) + display_count,
+                            max_bytes: settings.gpu_max_cache_mb * 1024 * 1024,
+                        };
 
-                        // GPU キャッシュ保持対象範囲の計算 (前後 settings.gpu_max_prefetch_pages)
-                        let mut gpu_targets = indices.clone();
-                        let prefetch_dist = settings.gpu_max_prefetch_pages as isize;
-                        for i in 1..=prefetch_dist {
-                            if current_idx - i >= 0 { gpu_targets.push((current_idx - i) as usize); }
-                            if current_idx + i <= max_idx { gpu_targets.push((current_idx + i) as usize); }
+                        // 2. の転送順 (表示中 -> 近い組から順)
+                        let mut upload_candidates: Vec<usize> = Vec::with_capacity(display_count + prefetch_pages.len());
+                        for &idx in indices.iter().chain(&flip_pages).chain(&prefetch_pages) {
+                            if !upload_candidates.contains(&idx) {
+                                upload_candidates.push(idx);
+                            }
                         }
+                        let mut gpu_targets = upload_candidates.clone();
                         gpu_targets.sort();
-                        gpu_targets.dedup();
-                        
-                        // 強制解放距離 (先読み設定の2倍強、最低20ページ)
-                        let force_evict_dist = (settings.gpu_max_prefetch_pages * 2 + 2).max(20) as isize;
 
-                        if current_bitmaps.len() > max_gpu_bitmaps || current_bitmaps.iter().any(|(idx, _)| (*idx as isize - current_idx).abs() > force_evict_dist) {
-                            // 保持対象（先読み範囲内）または距離が近いページを保護し、それ以外を candidates とする
-                            let (mut to_keep, mut candidates): (Vec<_>, Vec<_>) = current_bitmaps.drain(..).partition(|(idx, _)| {
-                                gpu_targets.contains(idx) || (*idx as isize - current_idx).abs() <= force_evict_dist
-                            });
+                        // 表示中のページを最近使ったものとし、保持対象以外を古い順に上限まで解放する
+                        gpu_cache.touch(&indices);
+                        gpu_cache.evict(&gpu_targets, gpu_limits);
 
-                            // 距離が近い順にソート
-                            candidates.sort_by_cached_key(|(idx, _)| (*idx as isize - current_idx).abs());
-                            
-                            // 枚数上限（または距離制限内の全件）になるまで to_keep に戻す
-                            while to_keep.len() < max_gpu_bitmaps && !candidates.is_empty() {
-                                to_keep.push(candidates.remove(0));
+                        // 別スレッドで転送が終わったページを受け取る
+                        // キャッシュの画像が差し替わったもの (回転の変更・再読み込み) や範囲外になったものは捨てる
+                        if let Some(uploader) = async_uploader.as_mut() {
+                            for result in uploader.poll() {
+                                let key = format!("{}::{}", result.path_key, result.index);
+                                let current = result.path_key == current_path_key
+                                    && gpu_targets.contains(&result.index)
+                                    && !gpu_cache.contains(result.index)
+                                    && cache.get(&key).is_some_and(|d| Arc::ptr_eq(&d, &result.image));
+                                match result.texture {
+                                    Ok(texture) if current => gpu_cache.insert(result.index, texture),
+                                    Ok(_) => {}
+                                    Err(e) => println!("[先読み] GPU への転送に失敗しました (インデックス {}): {}", result.index, e),
+                                }
                             }
-
-                            // 残った candidates は解放対象
-                            current_bitmaps = to_keep;
                         }
 
                         // 2. 新しいビットマップーの生成 (表示中 + 先読み範囲)
-                        let mut upload_candidates = gpu_targets.clone();
-                        upload_candidates.sort_by_key(|&idx| (idx as isize - current_idx).abs());
-
                         for &idx in &upload_candidates {
-                            if !current_bitmaps.iter().any(|(i, _)| *i == idx) {
+                            if !gpu_cache.contains(idx) {
                                 let key = format!("{}::{}", current_path_key, idx);
-                                if let Some(decoded) = cache.get(&key) {
+                                if let Some(decoded) = cache.get(&key).filter(|d| d.rotation == settings.effective_pre_rotation()) {
+                                    app_state.page_luminance.entry(idx).or_insert_with(|| decoded.mean_luminance());
+                                    app_state.page_crops.entry(idx).or_insert_with(|| crate::image::postprocess::detect_margins(&decoded));
+                                    let is_display = indices.contains(&idx) || flip_pages.contains(&idx);
+                                    if let Some(pos) = staged_uploads.iter().position(|s| s.index == idx) {
+                                        if is_display {
+                                            // 転送途中のページが表示対象になったら残りを一括で転送する
+                                            let staged = staged_uploads.remove(pos);
+                                            if renderer.upload_rows(&staged.texture, &staged.image, staged.next_row, u32::MAX).is_ok() {
+                                                gpu_cache.insert(idx, staged.texture);
+                                            }
+                                        }
+                                        continue;
+                                    }
+                                    if !is_display && settings.prefetch_paused {
+                                        // 先読みの一時停止中は表示中のページだけを転送する
+                                        continue;
+                                    }
+                                    if !is_display && !gpu_cache.has_room_for(decoded.width as u64 * decoded.height as u64 * 4, gpu_limits) {
+                                        // 容量の上限に達したら遠いページの先読み転送は行わない
+                                        continue;
+                                    }
+                                    if !is_display {
+                                        if let Some(uploader) = async_uploader.as_mut() {
+                                            // 依頼済みのページは結果を待ち、依頼できなかったものは次のフレームで頼み直す
+                                            uploader.request(&current_path_key, idx, decoded.clone());
+                                            continue;
+                                        }
+                                        if let Some(texture) = renderer.create_staged_texture(&decoded) {
+                                            staged_uploads.push(StagedUpload {
+                                                path_key: current_path_key.clone(),
+                                                index: idx,
+                                                texture,
+                                                image: decoded.clone(),
+                                                next_row: 0,
+                                            });
+                                            continue;
+                                        }
+                                        if upload_start.elapsed() >= SYNC_UPLOAD_BUDGET {
+                                            // 一括転送しかできないバックエンドでは、時間枠を超えた分を次のフレームに回す
+                                            continue;
+                                        }
+                                    }
                                     if let Ok(texture) = renderer.upload_image(&decoded) {
-                                        current_bitmaps.push((idx, texture));
+                                        gpu_cache.insert(idx, texture);
                                     }
                                 }
                             }
                         }
+
+                        // 3. 段階転送を1フレームあたりの上限まで進める (ソース・回転の変更や範囲外になったものは破棄)
+                        let rotation = settings.effective_pre_rotation();
+                        staged_uploads.retain(|s| {
+                            s.path_key == current_path_key && s.image.rotation == rotation && gpu_targets.contains(&s.index)
+                        });
+                        if let Some(staged) = staged_uploads.first_mut().filter(|_| !settings.prefetch_paused) {
+                            let rows = (STAGED_UPLOAD_BYTES_PER_FRAME / (staged.image.width as usize * 4).max(1)).max(1) as u32;
+                            let result = renderer
+                                .upload_rows(&staged.texture, &staged.image, staged.next_row, rows)
+                                .map(|next| {
+                                    staged.next_row = next;
+                                    next >= staged.image.height
+                                });
+                            match result {
+                                Ok(true) => {
+                                    let done = staged_uploads.remove(0);
+                                    gpu_cache.insert(done.index, done.texture);
+                                }
+                                Ok(false) => {}
+                                Err(e) => {
+                                    println!("[先読み] GPU への段階転送に失敗しました (インデックス {}): {}", staged_uploads[0].index, e);
+                                    staged_uploads.remove(0);
+                                }
+                            }
+                        }
+                    }
+
+                    let upload_time = upload_start.elapsed();
+
+                    // 瞬時切り替え: 待っていた移動先のページがすべて GPU に揃ったらこのフレームから切り替える
+                    if app_state.pending_flip.as_ref().is_some_and(|p| p.pages.iter().all(|&i| gpu_cache.contains(i))) {
+                        if let Some(pending) = app_state.pending_flip.take() {
+                            app_state.current_page_index = pending.page_index;
+                            view_state.reset();
+                            let l = loader.clone();
+                            rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                            request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                        }
                     }
 
                     // 描画
+                    let draw_start = std::time::Instant::now();
+                    let [bg_r, bg_g, bg_b] = settings.adaptive_background_rgb(displayed_luminance(&app_state, &settings));
+                    renderer.set_background_color(D2D1_COLOR_F { r: bg_r, g: bg_g, b: bg_b, a: 1.0 });
                     renderer.begin_draw();
                     
                     // 通常描画
                     let display_indices = app_state.get_page_indices_to_display();
-                    let (mut layout_info, (content_w, content_h)) = calculate_page_layout(
-                        renderer.as_ref(),
-                        &display_indices,
-                        &current_bitmaps,
-                        win_w,
-                        win_h,
-                        view_state.zoom_level,
-                        view_state.pan_offset
-                    );
+                    // 手動/自動トリミングで残す範囲 (トリミングしないページは含めない)
+                    let page_crops: HashMap<usize, CropMargins> = display_indices
+                        .iter()
+                        .filter_map(|&i| app_state.effective_crop(i).map(|crop| (i, crop)))
+                        .collect();
+                    // 全体表示でも、極端に縦長の単ページは画面に押し込まず、幅に合わせて縦にスクロールして読む
+                    let fit = if view_state.fit_mode == FitMode::FitWindow
+                        && is_tall_page(
+                            renderer.as_ref(),
+                            &display_indices,
+                            &gpu_cache,
+                            &app_state.page_transforms,
+                            &page_crops,
+                            settings.tall_page_fit_width_ratio,
+                        ) {
+                        FitMode::FitWidth
+                    } else {
+                        view_state.fit_mode
+                    };
+                    let (mut layout_info, mut placeholders, (mut content_w, mut content_h)) = if app_state.is_webtoon_view {
+                        calculate_webtoon_layout(
+                            renderer.as_ref(),
+                            app_state.current_page_index,
+                            app_state.image_files.len(),
+                            &gpu_cache,
+                            &app_state.page_transforms,
+                            win_w,
+                            page_h,
+                            view_state.zoom_level,
+                            view_state.pan_offset.0,
+                            view_state.webtoon_offset,
+                        )
+                    } else {
+                        calculate_page_layout(
+                            renderer.as_ref(),
+                            &display_indices,
+                            &gpu_cache,
+                            &app_state.page_transforms,
+                            &page_crops,
+                            win_w,
+                            page_h,
+                            view_state.zoom_level,
+                            view_state.pan_offset,
+                            fit,
+                        )
+                    };
+                    // 幅合わせ・原寸などでページが画面からはみ出すときは、ホイールとドラッグでページ内をスクロールする
+                    let page_scrollable = !app_state.is_webtoon_view
+                        && fit != FitMode::FitWindow
+                        && (content_w > win_w + 1.0 || content_h > page_h + 1.0);
+                    // はみ出すページを開いた直後は、読み始める端 (戻ってきた場合は読み終わりの端) に合わせる
+                    if view_state.align_page_start && !layout_info.is_empty() {
+                        view_state.align_page_start = false;
+                        if page_scrollable {
+                            let forward = app_state.scroll_direction >= 0;
+                            let limit_y = (content_h - page_h).max(0.0) / 2.0;
+                            view_state.pan_offset.1 = if forward { limit_y } else { -limit_y };
+                            // 右綴じは右端から読み始める (パンが正のときは左端が見える)
+                            let limit_x = (content_w - win_w).max(0.0) / 2.0;
+                            let from_right = app_state.binding_direction == BindingDirection::Right;
+                            view_state.pan_offset.0 = if from_right == forward { -limit_x } else { limit_x };
+                            (layout_info, placeholders, (content_w, content_h)) = calculate_page_layout(
+                                renderer.as_ref(),
+                                &display_indices,
+                                &gpu_cache,
+                                &app_state.page_transforms,
+                                &page_crops,
+                                win_w,
+                                page_h,
+                                view_state.zoom_level,
+                                view_state.pan_offset,
+                                fit,
+                            );
+                        }
+                    }
+                    view_state.page_scrollable = page_scrollable;
+                    if app_state.is_webtoon_view {
+                        // 画面に入るページ数が増えたら、次の描画でそのページも転送する
+                        let visible = (layout_info.len() + placeholders.len()).max(1);
+                        if visible > app_state.webtoon_visible_pages {
+                            window.request_redraw();
+                        }
+                        app_state.webtoon_visible_pages = visible;
+                    }
+
+                    // 表示モード切り替え直後: 基準ページが読み込まれていれば同じ内容位置へ合わせる
+                    if let Some(anchor) = view_state.pending_anchor.take() {
+                        if let Some(rect) = layout_info.iter().find(|info| info.index == anchor.index).map(|info| info.dest_rect) {
+                            view_state.apply_anchor(&anchor, rect, (win_w, page_h));
+                            (layout_info, placeholders, (content_w, content_h)) = calculate_page_layout(
+                                renderer.as_ref(),
+                                &display_indices,
+                                &gpu_cache,
+                                &app_state.page_transforms,
+                                &page_crops,
+                                win_w,
+                                page_h,
+                                view_state.zoom_level,
+                                view_state.pan_offset,
+                                fit,
+                            );
+                        } else if placeholders.iter().any(|(i, _)| *i == anchor.index) {
+                            view_state.pending_anchor = Some(anchor); // 読み込み待ち
+                        }
+                    }
                     
                     // パン制限と位置修正
                     let old_pan = view_state.pan_offset;
-                    view_state.clamp_pan_offset((win_w, win_h), (content_w, content_h));
+                    view_state.clamp_pan_offset((win_w, page_h), (content_w, content_h));
                     let new_pan = view_state.pan_offset;
-                     if old_pan != new_pan {
+                    // 慣性で端に着いた向きはそこで止める
+                    if let Some(ref mut inertia) = view_state.inertia {
+                        if old_pan.0 != new_pan.0 {
+                            inertia.velocity.0 = 0.0;
+                        }
+                        if old_pan.1 != new_pan.1 {
+                            inertia.velocity.1 = 0.0;
+                        }
+                    }
+                    view_state.tick_animation();
+                    if view_state.animation.is_some() {
+                        // アニメーション中は制限後の到達先へ向けて補間した値で配置し直す
+                        let (zoom, pan) = view_state.displayed_transform();
+                        (layout_info, placeholders, _) = calculate_page_layout(
+                            renderer.as_ref(),
+                            &display_indices,
+                            &gpu_cache,
+                            &app_state.page_transforms,
+                            &page_crops,
+                            win_w,
+                            page_h,
+                            zoom,
+                            pan,
+                            fit,
+                        );
+                    } else if old_pan != new_pan {
                          let dx = new_pan.0 - old_pan.0;
                          let dy = new_pan.1 - old_pan.1;
-                         for info in &mut layout_info {
-                             info.dest_rect.left += dx;
-                             info.dest_rect.right += dx;
-                             info.dest_rect.top += dy;
-                             info.dest_rect.bottom += dy;
+                         let rects = layout_info
+                             .iter_mut()
+                             .flat_map(|info| [&mut info.dest_rect, &mut info.clip_rect])
+                             .chain(placeholders.iter_mut().map(|(_, rect)| rect));
+                         for rect in rects {
+                             rect.left += dx;
+                             rect.right += dx;
+                             rect.top += dy;
+                             rect.bottom += dy;
                          }
                      }
 
-                    for info in layout_info {
-                        renderer.draw_image(info.texture, &info.dest_rect);
+                    view_state.page_rects = layout_info.iter().map(|info| (info.index, info.dest_rect)).collect();
+                    view_state.content_size = (content_w, content_h);
+                    update_page_turn(&mut app_state, &settings, &display_indices, &layout_info, placeholders.is_empty());
+                    if let Some(ref mut ms) = modern_settings {
+                        if ms.color_adjustment != app_state.color_adjustment {
+                            ms.color_adjustment = app_state.color_adjustment;
+                            ms.window.request_redraw();
+                        }
+                        let page_transform = app_state.page_transform(app_state.current_page_index);
+                        if ms.page_transform != page_transform {
+                            ms.page_transform = page_transform;
+                            ms.window.request_redraw();
+                        }
+                        if ms.stats_revision != app_state.stats.revision() {
+                            ms.stats_revision = app_state.stats.revision();
+                            ms.stats_summary = app_state.stats.summary();
+                            ms.window.request_redraw();
+                        }
+                    }
+                    // ブルーライト軽減 (時間指定が有効なら範囲外は無効)
+                    // AboutToWait で毎フレーム再描画されるため、開始/終了時刻になると自動で切り替わる
+                    let blue_light = if !settings.blue_light_schedule
+                        || crate::platform::night_light::is_active_now(&settings.blue_light_start, &settings.blue_light_end)
+                    {
+                        settings.blue_light_strength
+                    } else {
+                        0.0
+                    };
+                    // 自動レベル補正: 表示するページの輝度ヒストグラムを (まだ無ければ) 集計しておく
+                    if settings.auto_levels {
+                        for info in &layout_info {
+                            if !app_state.page_histograms.contains_key(&info.index) {
+                                if let Some(result) = page_histogram(renderer.as_ref(), &gpu_cache, &cpu_cache, &current_path_key, info.index) {
+                                    app_state.page_histograms.insert(info.index, result);
+                                }
+                            }
+                        }
+                    }
+                    let page_adjustment = |index: usize| {
+                        // 夜間モード: 元から暗いページ (イラストの黒ベタなど) は二重反転しない
+                        let mut adjustment = app_state.color_adjustment;
+                        adjustment.invert = settings.invert_colors
+                            && !(settings.smart_invert && app_state.is_dark_page(index));
+                        adjustment.warmth = blue_light;
+                        if let Some((histogram, _)) = app_state.page_histograms.get(&index).filter(|_| settings.auto_levels) {
+                            let (black, white) = crate::render::histogram_levels(histogram, AUTO_LEVELS_CLIP);
+                            adjustment = adjustment.with_levels(black, white);
+                        }
+                        adjustment
+                    };
+                    for info in &layout_info {
+                        renderer.set_color_adjustment(page_adjustment(info.index));
+                        renderer.set_page_transform(info.transform);
+                        if rect_eq(&info.clip_rect, &info.dest_rect) {
+                            renderer.draw_image(info.texture, &info.dest_rect);
+                        } else {
+                            renderer.draw_image_clipped(info.texture, &info.dest_rect, &info.clip_rect);
+                        }
+                    }
+                    // サイズ変更が始まったときに使い回すため、このフレームの配置を覚えておく
+                    let frozen_pages: Option<Vec<_>> = settings.freeze_frame_on_resize.then(|| {
+                        layout_info
+                            .iter()
+                            .map(|info| {
+                                let page = TurnedPage { index: info.index, dest_rect: info.dest_rect, clip_rect: info.clip_rect, transform: info.transform };
+                                (page, page_adjustment(info.index))
+                            })
+                            .collect()
+                    });
+                    // 出力ウィンドウのページにも同じ色調整を適用する
+                    let mirror_adjustments: HashMap<usize, ColorAdjustment> = if mirror_window.is_some() {
+                        display_indices.iter().map(|&i| (i, page_adjustment(i))).collect()
+                    } else {
+                        HashMap::new()
+                    };
+                    // PageLoaded が届くまでは確保した枠にプレースホルダーを表示する
+                    for (index, rect) in &placeholders {
+                        draw_loading_placeholder(renderer.as_ref(), rect, *index, app_state.page_progress.get(index).copied(), ui_scale);
+                    }
+                    draw_annotations(renderer.as_ref(), &app_state, &layout_info, &current_path_key);
+                    // ページ送りのアニメーション: 送る前のページを新しいページの上でめくる (ずらす)
+                    if let Some(turn) = app_state.page_turn.as_ref() {
+                        let curl = PageCurl { area: turn.area, progress: turn.progress(), from_right: turn.from_right };
+                        // めくり始めの辺から遠いページを先に描き、めくれて重なる部分を手前にする
+                        let mut pages: Vec<&TurnedPage> = turn.pages.iter().collect();
+                        pages.sort_by(|a, b| a.clip_rect.left.total_cmp(&b.clip_rect.left));
+                        if !turn.from_right {
+                            pages.reverse();
+                        }
+                        for page in pages {
+                            let Some(texture) = gpu_cache.get(page.index) else { continue; };
+                            renderer.set_color_adjustment(page_adjustment(page.index));
+                            renderer.set_page_transform(page.transform);
+                            if turn.curl {
+                                renderer.draw_page_turn(texture, &page.dest_rect, &page.clip_rect, &curl);
+                            } else {
+                                renderer.draw_image_clipped(texture, &curl.slide_rect(&page.dest_rect), &curl.slide_clip(&page.clip_rect));
+                            }
+                        }
+                    }
+                    if let Some(ref edit) = app_state.crop_edit {
+                        draw_crop_edit(renderer.as_ref(), &settings, edit, &view_state.page_rects, ui_scale);
+                    }
+
+                    // ルーペ: 配置はそのままに、カーソル周辺の範囲だけを既存のテクスチャから拡大して描画する
+                    if view_state.is_loupe {
+                        let (cx, cy) = view_state.cursor_pos;
+                        let half = LOUPE_SIZE * ui_scale / 2.0;
+                        let lens = D2D_RECT_F { left: cx - half, top: cy - half, right: cx + half, bottom: cy + half };
+                        renderer.fill_rectangle(&lens, &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 1.0 });
+                        let zoom = settings.magnifier_zoom;
+                        // ルーペに映るページ上の範囲
+                        let visible = D2D_RECT_F { left: cx - half / zoom, top: cy - half / zoom, right: cx + half / zoom, bottom: cy + half / zoom };
+
+                        // カーソル下のページの縮小済み画像が無いか範囲外なら作り直す (作成中は完了を待つ)
+                        if let Some(info) = layout_info.iter().find(|info| point_in_rect(view_state.cursor_pos, &info.dest_rect)) {
+                            let up_to_date = loupe_prescale.as_ref().map_or(false, |p| {
+                                p.matches(&current_path_key, info, zoom) && (p.texture.is_none() || p.covers(&visible))
+                            });
+                            if !up_to_date {
+                                loupe_generation += 1;
+                                loupe_prescale = request_loupe_prescale(
+                                    loupe_generation,
+                                    &current_path_key,
+                                    info,
+                                    view_state.cursor_pos,
+                                    zoom,
+                                    settings.effective_pre_rotation(),
+                                    &cpu_cache,
+                                    &rt,
+                                    &proxy,
+                                );
+                            }
+                        }
+
+                        for info in &layout_info {
+                            let magnified = magnify_rect(&info.dest_rect, view_state.cursor_pos, zoom);
+                            // トリミングで削った部分はルーペにも映さない
+                            let lens = intersect_rect(&lens, &magnify_rect(&info.clip_rect, view_state.cursor_pos, zoom));
+                            if rects_intersect(&magnified, &lens) {
+                                renderer.set_color_adjustment(page_adjustment(info.index));
+                                renderer.set_page_transform(info.transform);
+                                let prescaled = loupe_prescale
+                                    .as_ref()
+                                    .filter(|p| p.matches(&current_path_key, info, zoom) && p.covers(&visible))
+                                    .and_then(|p| p.texture.as_ref().map(|t| (t, p.region)));
+                                match prescaled {
+                                    Some((texture, region)) => {
+                                        let magnified = magnify_rect(&region, view_state.cursor_pos, zoom);
+                                        renderer.draw_image_clipped(texture, &magnified, &lens);
+                                    }
+                                    None => renderer.draw_image_clipped(info.texture, &magnified, &lens),
+                                }
+                            }
+                        }
+                        renderer.draw_rectangle(&lens, &D2D1_COLOR_F { r: 0.8, g: 0.8, b: 0.8, a: 0.9 }, ui_scale);
+                    } else {
+                        loupe_prescale = None;
+                    }
+
+                    // OCR オーバーレイ (認識済みの単語枠と選択範囲)
+                    if app_state.is_ocr_mode {
+                        request_ocr_for_display(&mut app_state, &cpu_cache, &rt, &proxy, &current_path_key);
+                        let selection_rect = app_state.ocr_drag_start.map(|start| normalize_rect(start, view_state.cursor_pos));
+                        for (idx, dest) in &view_state.page_rects {
+                            if let Some(result) = app_state.ocr_results.get(idx) {
+                                for (w_idx, word) in result.words.iter().enumerate() {
+                                    let r = ocr_word_screen_rect(word, result, dest, app_state.page_transform(*idx));
+                                    let selected = app_state.ocr_selected.contains(&(*idx, w_idx))
+                                        || selection_rect.map_or(false, |s| rects_intersect(&s, &r));
+                                    let color = if selected {
+                                        accent_color(&settings, 0.0, 0.4)
+                                    } else {
+                                        D2D1_COLOR_F { r: 1.0, g: 0.85, b: 0.0, a: 0.12 }
+                                    };
+                                    renderer.fill_rectangle(&r, &color);
+                                }
+                            }
+                        }
+                        if let Some(sel) = selection_rect {
+                            renderer.draw_rectangle(&sel, &accent_color(&settings, 0.3, 0.9), 1.0);
+                        }
+                    }
+
+                    // 範囲ズームの選択枠
+                    if let Some(start) = view_state.zoom_select_start {
+                        let sel = normalize_rect(start, view_state.cursor_pos);
+                        renderer.fill_rectangle(&sel, &accent_color(&settings, 0.0, 0.15));
+                        renderer.draw_rectangle(&sel, &accent_color(&settings, 0.3, 0.9), 1.0);
                     }
 
                     // ステータスバーの更新（Windows システムステータスバーを使用）
@@ -1096,45 +4471,34 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         format!("{}", app_state.current_page_index + 1)
                     };
                     
-                    let cpu_indices: Vec<usize> = {
-                        let keys = cpu_cache.lock().unwrap().get_keys();
-                        keys.iter().filter_map(|k| k.rsplit("::").next()?.parse().ok()).collect()
-                    };
-                    let gpu_indices: Vec<usize> = current_bitmaps.iter().map(|(idx, _)| *idx).collect();
-
-                    let path_preview: String = current_path_key.clone();
-                    
-                    let spread_info = if app_state.is_spread_view {
-                        let binding = if app_state.binding_direction == BindingDirection::Right { "右" } else { "左" };
-                        format!("[見開き:{}]", binding)
-                    } else {
-                        "[単ページ]".to_string()
-                    };
-
-                    let status_text = if settings.show_status_bar_info {
+                    let mut status_values = format_values(&current_path_key, &app_state, &settings, view_state.zoom_level);
+                    let cache_info = if settings.show_status_bar_info {
+                        let cpu_indices: Vec<usize> = {
+                            let keys = cpu_cache.lock().unwrap().get_keys();
+                            keys.iter().filter_map(|k| k.rsplit("::").next()?.parse().ok()).collect()
+                        };
+                        let gpu_indices: Vec<usize> = gpu_cache.indices().collect();
+                        // VRAM: キャッシュ中テクスチャの概算 (+ DXGI から取得したプロセス全体の実使用量 / 割り当て目安)
+                        const MB: u64 = 1024 * 1024;
+                        let texture_bytes = gpu_cache.total_bytes();
+                        let vram_info = match renderer.query_video_memory() {
+                            Some(mem) => format!("VRAM: {}MB (実使用 {}/{}MB)", texture_bytes / MB, mem.usage / MB, mem.budget / MB),
+                            None => format!("VRAM: 約{}MB", texture_bytes / MB),
+                        };
                         format!(
-                            "Page: {} / {} {} | Backend: {} | CPU: {}p {} | GPU: {}p {} | Key: {}",
-                            current_page_str,
-                            total_pages,
-                            spread_info,
-                            get_backend_display_name(&settings.rendering_backend),
+                            "CPU: {}p {} | GPU: {}p {} {}",
                             cpu_indices.len(),
                             format_page_list(&cpu_indices, app_state.current_page_index),
                             gpu_indices.len(),
                             format_page_list(&gpu_indices, app_state.current_page_index),
-                            path_preview
+                            vram_info
                         )
                     } else {
                         // 簡易表示（キャッシュ詳細なし）
-                        format!(
-                            "Page: {} / {} {} | Backend: {} | Key: {}",
-                            current_page_str,
-                            total_pages,
-                            spread_info,
-                            get_backend_display_name(&settings.rendering_backend),
-                            path_preview
-                        )
+                        String::new()
                     };
+                    status_values.push(("cache", cache_info));
+                    let status_text = render_format(&settings.statusbar_format, &status_values);
 
                     // ステータスバーは常に更新
                     if let Some(sb_hwnd) = status_bar_hwnd {
@@ -1153,22 +4517,135 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
 
                     // タイトルバー更新（ファイル名を表示、解像度はTODO）
-                    update_window_title(&window, &current_path_key, &app_state);
+                    update_window_title(&window, &current_path_key, &app_state, &settings, view_state.zoom_level);
+
+                    // ページ番号 OSD (ページが変わったら右下 (左手モードでは左下) に約1秒表示し、フェードアウトする)
+                    if total_pages > 0 {
+                        let page = app_state.current_page_index;
+                        match app_state.page_osd {
+                            Some((last, _)) if last == page => {}
+                            // 起動直後・ソース切り替え直後の最初のページでも表示する
+                            _ => app_state.page_osd = Some((page, std::time::Instant::now())),
+                        }
+                        let alpha = app_state.page_osd.map_or(0.0, |(_, t)| osd_alpha(t));
+                        if settings.show_page_osd && alpha > 0.0 && !app_state.is_jump_open {
+                            let osd_text = format!("{} / {}", current_page_str, total_pages);
+                            let osd_w = osd_text_width(&osd_text);
+                            // ステータスバーとシークバーの上
+                            let bottom = logical_h - status_bar_logical_height(status_bar_hwnd, ui_scale) - 16.0;
+                            let left = if settings.left_handed_mode { 16.0 } else { logical_w - 16.0 - osd_w };
+                            let osd_rect = D2D_RECT_F {
+                                left,
+                                top: bottom - OSD_HEIGHT,
+                                right: left + osd_w,
+                                bottom,
+                            };
+                            draw_osd_box(renderer.as_ref(), &osd_text, &scale_rect(&osd_rect, ui_scale), alpha);
+                        }
+                    }
+
+                    // トースト通知 (ソース切り替えなど、上部中央に表示)
+                    if let Some((ref text, start)) = app_state.osd_toast {
+                        let alpha = osd_alpha(start);
+                        if alpha > 0.0 {
+                            let toast_w = osd_text_width(text).min(logical_w - 32.0);
+                            let toast_rect = D2D_RECT_F {
+                                left: (logical_w - toast_w) / 2.0,
+                                top: 24.0,
+                                right: (logical_w + toast_w) / 2.0,
+                                bottom: 24.0 + OSD_HEIGHT,
+                            };
+                            draw_osd_box(renderer.as_ref(), text, &scale_rect(&toast_rect, ui_scale), alpha);
+                        } else {
+                            app_state.osd_toast = None;
+                        }
+                    }
+
+                    // 輝度ヒストグラム (右上。Shift + L)
+                    if app_state.show_histogram && total_pages > 0 {
+                        let index = app_state.current_page_index;
+                        if !app_state.page_histograms.contains_key(&index) {
+                            if let Some(result) = page_histogram(renderer.as_ref(), &gpu_cache, &cpu_cache, &current_path_key, index) {
+                                app_state.page_histograms.insert(index, result);
+                            }
+                        }
+                        if let Some(&(ref histogram, on_gpu)) = app_state.page_histograms.get(&index) {
+                            let (panel_w, panel_h) = HISTOGRAM_PANEL_SIZE;
+                            let left = if settings.left_handed_mode { 16.0 } else { logical_w - 16.0 - panel_w };
+                            let panel = D2D_RECT_F { left, top: 16.0, right: left + panel_w, bottom: 16.0 + panel_h };
+                            draw_histogram_overlay(renderer.as_ref(), histogram, on_gpu, &panel, ui_scale);
+                        }
+                    }
+
+                    // 画像情報 (右上。ヒストグラムを表示中はその下。I)
+                    if app_state.show_image_info && total_pages > 0 {
+                        let sections = image_info_sections(&app_state, &cpu_cache, &current_path_key);
+                        let left = if settings.left_handed_mode { 16.0 } else { logical_w - 16.0 - IMAGE_INFO_PANEL_WIDTH };
+                        let top = if app_state.show_histogram { 16.0 + HISTOGRAM_PANEL_SIZE.1 + 8.0 } else { 16.0 };
+                        draw_image_info_panel(renderer.as_ref(), &sections, left, top, ui_scale);
+                    }
+
+                    // スポイト (C): カーソル下の画素の値
+                    if app_state.is_color_picker && total_pages > 0 {
+                        let sample = sample_pixel(&app_state, &view_state.page_rects, &cpu_cache, &current_path_key, view_state.cursor_pos);
+                        let cursor = (view_state.cursor_pos.0 / ui_scale, view_state.cursor_pos.1 / ui_scale);
+                        draw_pixel_panel(renderer.as_ref(), sample.as_ref(), cursor, (logical_w, logical_h), ui_scale);
+                    }
+
+                    // 瞬時切り替えで移動先を読み込み中の進捗
+                    if let Some(pending) = app_state.pending_flip.as_ref() {
+                        let progress = pending_flip_progress(pending, &gpu_cache, &cpu_cache, &current_path_key);
+                        draw_flip_progress(renderer.as_ref(), &settings, progress, (logical_w, logical_h), ui_scale);
+                    }
+
+                    // フォルダ/アーカイブのサイドバー (ソースが変わっていれば一覧を作り直す)
+                    if let Some(ref mut pane) = app_state.browser_pane {
+                        pane.follow(&current_path_key, is_book_file);
+                        let cursor = (view_state.cursor_pos.0 / ui_scale, view_state.cursor_pos.1 / ui_scale);
+                        draw_browser_pane(renderer.as_ref(), &settings, pane, page_h / ui_scale, cursor, ui_scale);
+                    }
+
+                    // シリーズ一覧オーバーレイの描画
+                    if let Some(ref overlay) = app_state.series_overlay {
+                        let (panel, first, rows) = series_overlay_layout(overlay, logical_w, logical_h);
+                        let panel_px = scale_rect(&panel, ui_scale);
+                        renderer.fill_rectangle(&panel_px, &D2D1_COLOR_F { r: 0.05, g: 0.05, b: 0.05, a: 0.95 });
+                        renderer.draw_rectangle(&panel_px, &D2D1_COLOR_F { r: 0.3, g: 0.3, b: 0.3, a: 1.0 }, ui_scale);
+
+                        renderer.set_text_alignment(DWRITE_TEXT_ALIGNMENT_CENTER);
+                        let title_rect = scale_rect(&D2D_RECT_F { top: panel.top + 10.0, bottom: panel.top + 40.0, ..panel }, ui_scale);
+                        let title = format!("シリーズ ({}/{})  ↑↓: 選択  Enter: 開く  Esc: 閉じる", overlay.selected + 1, overlay.entries.len());
+                        renderer.draw_text(&title, &title_rect, &D2D1_COLOR_F { r: 0.6, g: 0.6, b: 0.6, a: 1.0 }, false);
+
+                        renderer.set_text_alignment(DWRITE_TEXT_ALIGNMENT_LEADING);
+                        for row in 0..rows {
+                            let index = first + row;
+                            let row_rect = series_row_rect(&panel, row);
+                            if index == overlay.selected {
+                                renderer.fill_rectangle(&scale_rect(&row_rect, ui_scale), &accent_color(&settings, 0.0, 0.8));
+                            }
+                            let name = std::path::Path::new(&overlay.entries[index])
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                                .unwrap_or_default();
+                            let marker = if Some(index) == overlay.current { "● " } else { "   " };
+                            let color = if Some(index) == overlay.current {
+                                D2D1_COLOR_F { r: 1.0, g: 0.8, b: 0.0, a: 1.0 }
+                            } else {
+                                D2D1_COLOR_F { r: 0.9, g: 0.9, b: 0.9, a: 1.0 }
+                            };
+                            let text_rect = scale_rect(&D2D_RECT_F { left: row_rect.left + 8.0, ..row_rect }, ui_scale);
+                            renderer.draw_text(&format!("{}{}", marker, name), &text_rect, &color, false);
+                        }
+                    }
 
                     // ページジャンプオーバーレイの描画
                     if app_state.is_jump_open {
-                        let jump_w = 340.0;
-                        let jump_h = 160.0;
-                        let jump_rect = D2D_RECT_F {
-                            left: (win_w - jump_w) / 2.0,
-                            top: (win_h - jump_h) / 2.0,
-                            right: (win_w + jump_w) / 2.0,
-                            bottom: (win_h + jump_h) / 2.0,
-                        };
+                        let jump_rect = jump_panel_rect(logical_w, logical_h);
                         
                         // メインパネル
-                        renderer.fill_rectangle(&jump_rect, &D2D1_COLOR_F { r: 0.05, g: 0.05, b: 0.05, a: 0.95 });
-                        renderer.draw_rectangle(&jump_rect, &D2D1_COLOR_F { r: 0.3, g: 0.3, b: 0.3, a: 1.0 }, 1.0);
+                        renderer.fill_rectangle(&scale_rect(&jump_rect, ui_scale), &D2D1_COLOR_F { r: 0.05, g: 0.05, b: 0.05, a: 0.95 });
+                        renderer.draw_rectangle(&scale_rect(&jump_rect, ui_scale), &D2D1_COLOR_F { r: 0.3, g: 0.3, b: 0.3, a: 1.0 }, ui_scale);
 
                         renderer.set_text_alignment(DWRITE_TEXT_ALIGNMENT_CENTER);
                         
@@ -1176,17 +4653,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         let mut title_rect = jump_rect.clone();
                         title_rect.top += 15.0;
                         title_rect.bottom = title_rect.top + 30.0;
-                        renderer.draw_text("ページ指定 (Enterで確定)", &title_rect, &D2D1_COLOR_F { r: 0.6, g: 0.6, b: 0.6, a: 1.0 }, false);
+                        renderer.draw_text("ページ指定 (Enterで確定)", &scale_rect(&title_rect, ui_scale), &D2D1_COLOR_F { r: 0.6, g: 0.6, b: 0.6, a: 1.0 }, false);
 
                         // 入力エリア背景（サブパネル）
                         let input_bg_w = 280.0;
                         let input_bg_h = 60.0;
-                        let input_bg_rect = D2D_RECT_F {
-                            left: (win_w - input_bg_w) / 2.0,
+                        let input_bg_rect = scale_rect(&D2D_RECT_F {
+                            left: (logical_w - input_bg_w) / 2.0,
                             top: jump_rect.top + 55.0,
-                            right: (win_w + input_bg_w) / 2.0,
+                            right: (logical_w + input_bg_w) / 2.0,
                             bottom: jump_rect.top + 55.0 + input_bg_h,
-                        };
+                        }, ui_scale);
                         renderer.fill_rectangle(&input_bg_rect, &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.6 });
 
                         // 入力中の文字と合計を一つの文字列として中央揃えで描画
@@ -1208,89 +4685,349 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                     // シークバーの描画
                     if app_state.show_seekbar && total_pages > 0 {
-                        let bar_height = if app_state.is_dragging_seekbar { 12.0 } else { 8.0 };
-                        let status_bar_height = 22.0; // Windows システムステータスバーの高さ
-                        let bar_y = win_h - status_bar_height - bar_height;
-                        let full_rect = D2D_RECT_F {
-                            left: 0.0,
-                            top: bar_y,
-                            right: win_w,
-                            bottom: bar_y + bar_height,
-                        };
+                        let full_rect = scale_rect(
+                            &seekbar_rect(logical_w, logical_h, status_bar_logical_height(status_bar_hwnd, ui_scale), app_state.is_dragging_seekbar),
+                            ui_scale,
+                        );
                         renderer.fill_rectangle(&full_rect, &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.5 });
 
                         let progress = (app_state.current_page_index as f32) / ((total_pages - 1) as f32).max(1.0);
-                        let progress_rect = if app_state.binding_direction == BindingDirection::Right {
-                            D2D_RECT_F {
-                                left: win_w * (1.0 - progress),
-                                top: bar_y,
-                                right: win_w,
-                                bottom: bar_y + bar_height,
-                            }
+                        let progress_rect = if seekbar_from_right(&app_state, &settings) {
+                            D2D_RECT_F { left: win_w * (1.0 - progress), ..full_rect }
                         } else {
-                            D2D_RECT_F {
-                                left: 0.0,
-                                top: bar_y,
-                                right: win_w * progress,
-                                bottom: bar_y + bar_height,
-                            }
+                            D2D_RECT_F { right: win_w * progress, ..full_rect }
                         };
                         let bar_color = if app_state.is_dragging_seekbar {
-                            D2D1_COLOR_F { r: 0.0, g: 0.6, b: 1.0, a: 1.0 }
+                            accent_color(&settings, 0.3, 1.0)
                         } else {
-                            D2D1_COLOR_F { r: 0.0, g: 0.4, b: 0.8, a: 0.9 }
+                            accent_color(&settings, 0.0, 0.9)
                         };
                         renderer.fill_rectangle(&progress_rect, &bar_color);
                     }
 
+                    // パフォーマンス HUD (F3。直前までのフレームの統計を表示する)
+                    if app_state.show_perf_hud {
+                        let lines = frame_stats.summary_lines(get_backend_display_name(&settings.rendering_backend));
+                        let (hud_w, line_h) = (380.0, 20.0);
+                        let left = if settings.left_handed_mode { logical_w - 16.0 - hud_w } else { 16.0 };
+                        let panel = D2D_RECT_F { left, top: 16.0, right: left + hud_w, bottom: 16.0 + 12.0 + lines.len() as f32 * line_h };
+                        renderer.fill_rectangle(&scale_rect(&panel, ui_scale), &D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.7 });
+                        for (i, line) in lines.iter().enumerate() {
+                            let top = panel.top + 6.0 + i as f32 * line_h;
+                            let line_rect = D2D_RECT_F { left: panel.left + 10.0, top, right: panel.right - 10.0, bottom: top + line_h };
+                            renderer.draw_text(line, &scale_rect(&line_rect, ui_scale), &D2D1_COLOR_F { r: 0.6, g: 1.0, b: 0.6, a: 1.0 }, false);
+                        }
+                    }
 
+                    if let Some(pages) = frozen_pages {
+                        app_state.frozen_frame.pages = pages;
+                        app_state.frozen_frame.size = (win_w, page_h);
+                    }
 
+                    let draw_time = draw_start.elapsed();
+                    let present_start = std::time::Instant::now();
                     let _ = renderer.end_draw();
+                    if let Some(ref mirror) = mirror_window {
+                        draw_mirror(renderer.as_mut(), mirror, &display_indices, &gpu_cache, &app_state.page_transforms, &page_crops, &mirror_adjustments);
+                    }
+                    if app_state.show_perf_hud {
+                        frame_stats.record(upload_time, draw_time, present_start.elapsed());
+                        // 表示中は連続して描画し、垂直同期に対する Present の間隔を計測する
+                        window.request_redraw();
+                    }
                 }
                 _ => (),
             }
         },
         Event::UserEvent(user_event) => {
             match user_event {
-                UserEvent::PageLoaded(_index) => {
+                UserEvent::PageLoadProgress(index, pct) => {
+                    app_state.page_progress.insert(index, pct);
+                    window.request_redraw();
+                }
+                UserEvent::PageLoaded(index) => {
+                    app_state.page_progress.remove(&index);
+                    // 横長のページの見開き判定に使うため、デコードした大きさを記録する
+                    let key = format!("{}::{}", current_path_key, index);
+                    let size = cpu_cache
+                        .lock()
+                        .ok()
+                        .and_then(|mut cache| cache.get(&key))
+                        .filter(|d| d.rotation == settings.effective_pre_rotation())
+                        .map(|d| (d.width, d.height));
+                    if let Some(size) = size {
+                        let was_wide = app_state.is_wide_page(index);
+                        app_state.page_sizes.insert(index, size);
+                        if app_state.is_spread_view && app_state.wide_page_single && app_state.is_wide_page(index) != was_wide {
+                            // 見開きの組み合わせが変わったため、新しい組の先読みを頼み直す
+                            request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                        }
+                    }
                     window.request_redraw();
                 }
                 UserEvent::ToggleSpreadView => {
+                    app_state.record_undo(&current_path_key, "表示モードの切り替え");
+                    if app_state.is_webtoon_view {
+                        app_state.is_webtoon_view = false;
+                        settings.webtoon_view = false;
+                        app_state.is_spread_view = false;
+                    }
                     app_state.is_spread_view = !app_state.is_spread_view;
                     settings.is_spread_view = app_state.is_spread_view;
-                    let _ = settings.save("config.json");
-                    view_state.reset();
+                    let _ = settings.save(config_path);
+                    view_state.reset_keeping_anchor(page_area_size(&window, status_bar_hwnd));
                     request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
                     window.request_redraw();
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                 }
                 UserEvent::ToggleBindingDirection => {
+                    app_state.record_undo(&current_path_key, "綴じ方向の切り替え");
                     app_state.binding_direction = match app_state.binding_direction {
                         BindingDirection::Left => BindingDirection::Right,
                         BindingDirection::Right => BindingDirection::Left,
                     };
                     settings.binding_direction = if app_state.binding_direction == BindingDirection::Right { "right".to_string() } else { "left".to_string() };
-                    let _ = settings.save("config.json");
-                    view_state.reset();
+                    let _ = settings.save(config_path);
+                    view_state.reset_keeping_anchor(page_area_size(&window, status_bar_hwnd));
                     request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
                     window.request_redraw();
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                 }
                 UserEvent::ToggleFirstPageSingle => {
                     settings.spread_view_first_page_single = !settings.spread_view_first_page_single;
-                    let _ = settings.save("config.json");
+                    app_state.spread_view_first_page_single = settings.spread_view_first_page_single;
+                    app_state.current_page_index = app_state.snap_to_spread(app_state.current_page_index);
+                    let _ = settings.save(config_path);
+                    view_state.reset();
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleChapterStartSingle => {
+                    settings.chapter_start_single = !settings.chapter_start_single;
+                    app_state.chapter_start_single = settings.chapter_start_single;
+                    app_state.current_page_index = app_state.snap_to_spread(app_state.current_page_index);
+                    let _ = settings.save(config_path);
+                    view_state.reset();
+                    request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleWidePageSingle => {
+                    settings.wide_page_single = !settings.wide_page_single;
+                    app_state.wide_page_single = settings.wide_page_single;
+                    app_state.current_page_index = app_state.snap_to_spread(app_state.current_page_index);
+                    let _ = settings.save(config_path);
+                    view_state.reset();
+                    request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleSplitWidePages => {
+                    settings.split_wide_pages = !settings.split_wide_pages;
+                    app_state.split_wide_pages = settings.split_wide_pages;
+                    let _ = settings.save(config_path);
+                    let message = if !settings.split_wide_pages {
+                        "横長ページの分割: オフ"
+                    } else if app_state.is_spread_view || app_state.is_webtoon_view {
+                        "横長ページの分割: オン (単ページ表示で有効)"
+                    } else {
+                        "横長ページの分割: オン"
+                    };
+                    app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
                     view_state.reset();
                     window.request_redraw();
+                }
+                UserEvent::ToggleReopenLastOnStartup => {
+                    settings.reopen_last_on_startup = !settings.reopen_last_on_startup;
+                    let _ = settings.save(config_path);
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::StepBrightness(dir) => {
+                    let adjustment = app_state.color_adjustment.step_brightness(dir as f32);
+                    update_color_adjustment(&mut app_state, adjustment);
+                    window.request_redraw();
+                }
+                UserEvent::StepContrast(dir) => {
+                    let adjustment = app_state.color_adjustment.step_contrast(dir as f32);
+                    update_color_adjustment(&mut app_state, adjustment);
+                    window.request_redraw();
+                }
+                UserEvent::StepGamma(dir) => {
+                    let adjustment = app_state.color_adjustment.step_gamma(dir as f32);
+                    update_color_adjustment(&mut app_state, adjustment);
+                    window.request_redraw();
+                }
+                UserEvent::ResetColorAdjustment => {
+                    update_color_adjustment(&mut app_state, ColorAdjustment::default());
+                    window.request_redraw();
+                }
+                UserEvent::RotatePage(dir) => {
+                    if !app_state.image_files.is_empty() {
+                        let index = app_state.current_page_index;
+                        let transform = app_state.page_transform(index).rotated(dir as i32);
+                        update_page_transform(&mut app_state, &current_path_key, index, transform);
+                        window.request_redraw();
+                    }
+                }
+                UserEvent::FlipPage(horizontal) => {
+                    if !app_state.image_files.is_empty() {
+                        let index = app_state.current_page_index;
+                        let transform = app_state.page_transform(index).flipped(horizontal);
+                        update_page_transform(&mut app_state, &current_path_key, index, transform);
+                        window.request_redraw();
+                    }
+                }
+                UserEvent::RotateNightMode(dir) => {
+                    // オフ -> オン (暗いページは除外) -> オン (全ページ)
+                    let modes = [(false, true), (true, true), (true, false)];
+                    let current = (settings.invert_colors, settings.smart_invert || !settings.invert_colors);
+                    let idx = modes.iter().position(|&m| m == current).unwrap_or(0);
+                    let len = modes.len() as isize;
+                    let new_idx = ((idx as isize + dir) % len + len) % len;
+                    (settings.invert_colors, settings.smart_invert) = modes[new_idx as usize];
+                    let _ = settings.save(config_path);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::StepBlueLight(dir) => {
+                    let strength = ((settings.blue_light_strength * 10.0).round() + dir as f32).clamp(0.0, 10.0) / 10.0;
+                    settings.blue_light_strength = strength;
+                    let _ = settings.save(config_path);
+                    app_state.status_message = Some((format!("ブルーライト軽減: {:.0}%", strength * 100.0), std::time::Instant::now()));
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::StepAdaptiveBackground(dir) => {
+                    let strength = ((settings.adaptive_background * 10.0).round() + dir as f32).clamp(0.0, 10.0) / 10.0;
+                    settings.adaptive_background = strength;
+                    let _ = settings.save(config_path);
+                    let message = if strength > 0.0 { format!("背景の明暗追従: {:.0}%", strength * 100.0) } else { "背景の明暗追従: オフ".to_string() };
+                    app_state.status_message = Some((message, std::time::Instant::now()));
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleBlueLightSchedule => {
+                    settings.blue_light_schedule = !settings.blue_light_schedule;
+                    let _ = settings.save(config_path);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::RotateBackgroundTheme(dir) => {
+                    // 手編集の "custom" からはプリセットの先頭に戻る
+                    let themes = &crate::config::BACKGROUND_THEMES;
+                    let len = themes.len() as isize;
+                    let next = match themes.iter().position(|(name, _, _)| *name == settings.background_theme) {
+                        Some(idx) => (idx as isize + dir).rem_euclid(len) as usize,
+                        None => 0,
+                    };
+                    settings.background_theme = themes[next].0.to_string();
+                    let _ = settings.save(config_path);
+                    app_state.status_message = Some((format!("背景: {}", themes[next].1), std::time::Instant::now()));
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleAutoLevels => {
+                    settings.auto_levels = !settings.auto_levels;
+                    let _ = settings.save(config_path);
+                    let message = if settings.auto_levels { "自動レベル補正 (ページごと): オン" } else { "自動レベル補正 (ページごと): オフ" };
+                    app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
+                    window.request_redraw();
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                 }
                 UserEvent::ToggleCpuColorConversion => {
                     settings.use_cpu_color_conversion = !settings.use_cpu_color_conversion;
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
                     view_state.reset();
                     request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
                     window.request_redraw();
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                 }
+                UserEvent::ConfigFileChanged => {
+                    if !settings.is_file_changed(config_path) {
+                        return; // 自分自身の保存による更新
+                    }
+                    match settings.merge_external(config_path) {
+                        Ok(result) => {
+                            println!("[設定] config.json の外部変更を読み込みました: {:?}", result.changed);
+                            if !result.changed.is_empty() {
+                                apply_display_mode(&mut app_state, &settings);
+                                app_state.spread_view_first_page_single = settings.spread_view_first_page_single;
+                                app_state.chapter_start_single = settings.chapter_start_single;
+                                app_state.wide_page_single = settings.wide_page_single;
+                                app_state.split_wide_pages = settings.split_wide_pages;
+                                keymap = Keymap::from_settings(&settings.key_bindings);
+                                // 合わせ方はソースごとに持つため、全体設定が変わったときだけ反映する
+                                if result.changed.iter().any(|f| f == "fit_mode") {
+                                    view_state.fit_mode = FitMode::from_setting(&settings.fit_mode);
+                                }
+                                if result.changed.iter().any(|f| f == "parallel_decoding_workers") {
+                                    crate::image::decoder::set_decode_workers(settings.parallel_decoding_workers);
+                                }
+                                if result.changed.iter().any(|f| f == "file_sort_order") {
+                                    let order = crate::image::sort::SortOrder::from_setting(&settings.file_sort_order);
+                                    let _ = proxy.send_event(UserEvent::SetSortOrder(order));
+                                }
+                                if result.changed.iter().any(|f| f.starts_with("disk_cache_")) {
+                                    let l = loader.clone();
+                                    let disk_cache = open_disk_cache(&settings);
+                                    rt.spawn(async move { let _ = l.send_request(LoaderRequest::SetDiskCache(disk_cache)).await; });
+                                }
+                                if result.changed.iter().any(|f| f == "pre_rotation") {
+                                    gpu_cache.clear();
+                                    app_state.page_sizes.clear();
+                                    loupe_prescale = None;
+                                    app_state.clear_ocr();
+                                    request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                                }
+                                let watching = hot_folder_watcher.as_ref().map(|w| w.path.clone());
+                                let wanted = settings.hot_folder_enabled.then(|| settings.hot_folder_path.clone());
+                                if watching != wanted {
+                                    hot_folder_watcher = wanted.and_then(|p| hot_folder::HotFolderWatcher::start(&p, proxy.clone()));
+                                }
+                            }
+                            let message = if !result.conflicts.is_empty() {
+                                format!("設定の競合: {} はアプリ側の値を優先しました", result.conflicts.join(", "))
+                            } else {
+                                "config.json の変更を読み込みました".to_string()
+                            };
+                            app_state.status_message = Some((message, std::time::Instant::now()));
+                            view_state.reset();
+                            request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                            window.request_redraw();
+                            if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                        }
+                        Err(e) => {
+                            // 編集途中で JSON が壊れている場合などは次の保存まで待つ
+                            println!("[設定] config.json の再読み込みに失敗: {}", e);
+                            app_state.status_message = Some(("config.json を読み込めませんでした (書式エラー)".to_string(), std::time::Instant::now()));
+                            window.request_redraw();
+                        }
+                    }
+                }
+                UserEvent::ToggleUpdateCheck => {
+                    settings.check_for_updates = !settings.check_for_updates;
+                    if settings.check_for_updates {
+                        spawn_update_check(&rt, &proxy);
+                    }
+                    let _ = settings.save(config_path);
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::UpdateCheckCompleted(result) => {
+                    match result {
+                        Ok(latest) => {
+                            println!("[更新確認] 最新バージョン: {} (現在: {})", latest, VERSION);
+                            settings.last_update_check = crate::platform::update::now_secs();
+                            if crate::platform::update::is_newer(&latest, VERSION) {
+                                app_state.status_message = Some((format!("新しいバージョン v{} が公開されています (設定 > 情報)", latest), std::time::Instant::now()));
+                                window.request_redraw();
+                            }
+                            settings.latest_version = latest;
+                            let _ = settings.save(config_path);
+                            if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                        }
+                        Err(e) => println!("[更新確認] 確認に失敗: {}", e),
+                    }
+                }
                 UserEvent::RotateResamplingCpu(dir) => {
                     let modes = ["PIL_LANCZOS", "PIL_BILINEAR", "PIL_BICUBIC", "PIL_NEAREST"];
                     let current = settings.resampling_mode_cpu.as_str();
@@ -1298,7 +5035,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let len = modes.len() as isize;
                     let new_idx = ((idx as isize + dir) % len + len) % len;
                     settings.resampling_mode_cpu = modes[new_idx as usize].to_string();
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
                     view_state.reset();
                     window.request_redraw();
                     if let Some(ref mut ms) = modern_settings {
@@ -1324,7 +5061,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     };
                     renderer.set_interpolation_mode(mode_enum);
 
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
                     view_state.reset();
                     window.request_redraw();
                     if let Some(ref mut ms) = modern_settings {
@@ -1333,7 +5070,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 UserEvent::ToggleStatusBar => {
                     settings.show_status_bar_info = !settings.show_status_bar_info;
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
                     window.request_redraw();
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                 }
@@ -1344,7 +5081,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let len = backends.len() as isize;
                     let new_idx = ((idx as isize + dir) % len + len) % len;
                     settings.rendering_backend = backends[new_idx as usize].to_string();
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
                     println!(
                         "[設定] レンダリングバックエンドを {} に変更しました。反映には再起動が必要です。",
                         settings.rendering_backend
@@ -1354,8 +5091,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
                 UserEvent::RotateDisplayMode(dir) => {
-                    // 順序: 0:単一(false, any), 1:左(true, "left"), 2:右(true, "right")
-                    let mut current_mode_idx = if !settings.is_spread_view {
+                    // 順序: 0:単一(false, any), 1:左(true, "left"), 2:右(true, "right"), 3:縦スクロール
+                    let mut current_mode_idx = if settings.webtoon_view {
+                        3
+                    } else if !settings.is_spread_view {
                         0
                     } else if settings.binding_direction == "left" {
                         1
@@ -1363,9 +5102,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         2
                     };
 
-                    let len = 3isize;
+                    let len = 4isize;
                     current_mode_idx = ((current_mode_idx as isize + dir) % len + len) % len;
 
+                    settings.webtoon_view = current_mode_idx == 3;
                     match current_mode_idx {
                         0 => {
                             settings.is_spread_view = false;
@@ -1381,14 +5121,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         _ => {}
                     }
 
-                    app_state.is_spread_view = settings.is_spread_view;
-                    app_state.binding_direction = if settings.binding_direction == "right" {
-                        BindingDirection::Right
+                    apply_display_mode(&mut app_state, &settings);
+                    let _ = settings.save(config_path);
+                    if app_state.is_webtoon_view {
+                        view_state.reset();
                     } else {
-                        BindingDirection::Left
-                    };
-                    let _ = settings.save("config.json");
-                    view_state.reset();
+                        view_state.reset_keeping_anchor(page_area_size(&window, status_bar_hwnd));
+                    }
                     request_pages_with_prefetch(
                         &app_state,
                         &loader,
@@ -1404,15 +5143,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 UserEvent::SetMagnifierZoom(zoom) => {
                     settings.magnifier_zoom = zoom;
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                 }
                 UserEvent::LoadPath(path) => {
-                    if let Some(new_source) = get_image_source(&path) {
+                    if let Some((path, new_source, page)) = resolve_open_path(&path) {
                         load_new_source(
                             new_source,
                             path,
-                            0, // デフォルト
+                            page, // 画像ファイルならその画像、それ以外は前回読んだ位置から
                             None, // デフォルト
                             &mut app_state,
                             &mut current_path_key,
@@ -1421,12 +5160,133 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             &loader,
                             &rt,
                             &mut settings,
-                            &mut current_bitmaps,
+                            &mut gpu_cache,
+                            &mut view_state,
                             false, // 通常のファイル読み込み
                         );
                         window.request_redraw();
                     }
                 }
+                UserEvent::HotFolderItem(path) => {
+                    if let Some(new_source) = get_image_source(&path) {
+                        load_new_source(
+                            new_source,
+                            path.clone(),
+                            None,
+                            None,
+                            &mut app_state,
+                            &mut current_path_key,
+                            &window,
+                            &cpu_cache,
+                            &loader,
+                            &rt,
+                            &mut settings,
+                            &mut gpu_cache,
+                            &mut view_state,
+                            false,
+                        );
+                        let name = std::path::Path::new(&path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                        app_state.osd_toast = Some((format!("ホットフォルダ: {} を開きました", name), std::time::Instant::now()));
+                        window.request_redraw();
+                    }
+                }
+                UserEvent::ClipboardImage(path) => {
+                    // 監視を止めた後に届いた通知は無視する
+                    if let Some(dir) = clipboard_watch.as_ref().map(|w| w.dir.to_string_lossy().to_string()) {
+                        let added = std::path::Path::new(&path);
+                        if current_path_key == dir {
+                            // 表示中なら読み直して、追加された画像へ移動する
+                            reload_current_folder(
+                                |files| files.iter().position(|f| std::path::Path::new(f) == added).unwrap_or(files.len().saturating_sub(1)),
+                                &mut app_state,
+                                &mut current_path_key,
+                                &window,
+                                &cpu_cache,
+                                &loader,
+                                &rt,
+                                &mut settings,
+                                &mut gpu_cache,
+                                &mut view_state,
+                                &mut staged_uploads,
+                            );
+                        } else if let Some(new_source) = get_image_source(&dir) {
+                            let last = new_source.len().saturating_sub(1);
+                            load_new_source(
+                                new_source,
+                                dir,
+                                Some(last),
+                                None,
+                                &mut app_state,
+                                &mut current_path_key,
+                                &window,
+                                &cpu_cache,
+                                &loader,
+                                &rt,
+                                &mut settings,
+                                &mut gpu_cache,
+                                &mut view_state,
+                                false, // 一時的なソース自体は sync_current_state_to_history で履歴から除く
+                            );
+                        }
+                        let count = app_state.image_files.len();
+                        app_state.osd_toast = Some((format!("クリップボード: {} 枚目を追加しました", count), std::time::Instant::now()));
+                        window.request_redraw();
+                    }
+                }
+                UserEvent::SourceChanged(path) => {
+                    // 別のソースへ移った後に届いた通知や、一覧が変わらない変更 (仕分けで読み直し済みなど) は無視する
+                    let files = match get_image_source(&path) {
+                        Some(ImageSource::Files(files)) if path == current_path_key => files,
+                        _ => Vec::new(),
+                    };
+                    if path == current_path_key && files != app_state.image_files {
+                        let before = app_state.image_files.len();
+                        let index = app_state.current_page_index;
+                        let current = app_state.image_files.get(index).cloned();
+                        println!("[フォルダ監視] 一覧を読み直します: {} ({} -> {} 枚)", path, before, files.len());
+                        // 表示中の画像はファイル名で探し、消えていれば同じ位置 (= 次の画像) を表示する
+                        let reloaded = reload_current_folder(
+                            |files| current.as_ref().and_then(|c| files.iter().position(|f| f == c)).unwrap_or(index),
+                            &mut app_state,
+                            &mut current_path_key,
+                            &window,
+                            &cpu_cache,
+                            &loader,
+                            &rt,
+                            &mut settings,
+                            &mut gpu_cache,
+                            &mut view_state,
+                            &mut staged_uploads,
+                        );
+                        if !reloaded {
+                            // 画像が無くなった: 再び置かれたら表示できるようフォルダは開いたままにする
+                            load_new_source(
+                                ImageSource::Files(Vec::new()),
+                                path,
+                                Some(0),
+                                None,
+                                &mut app_state,
+                                &mut current_path_key,
+                                &window,
+                                &cpu_cache,
+                                &loader,
+                                &rt,
+                                &mut settings,
+                                &mut gpu_cache,
+                                &mut view_state,
+                                true,
+                            );
+                        }
+                        let after = app_state.image_files.len();
+                        let message = match after.cmp(&before) {
+                            std::cmp::Ordering::Greater => format!("フォルダ: {} 枚追加されました ({} 枚)", after - before, after),
+                            std::cmp::Ordering::Less => format!("フォルダ: {} 枚削除されました ({} 枚)", before - after, after),
+                            std::cmp::Ordering::Equal => format!("フォルダ: 一覧を更新しました ({} 枚)", after),
+                        };
+                        app_state.osd_toast = Some((message, std::time::Instant::now()));
+                        window.request_redraw();
+                    }
+                }
                 UserEvent::LoadHistory(idx) => {
                     if let Some(item) = settings.history.get(idx).cloned() {
                         if let Some(new_source) = get_image_source(&item.path) {
@@ -1434,7 +5294,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             load_new_source(
                                 new_source,
                                 item.path,
-                                item.page,
+                                Some(item.page),
                                 Some(item.binding),
                                 &mut app_state,
                                 &mut current_path_key,
@@ -1443,7 +5303,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 &loader,
                                 &rt,
                                 &mut settings,
-                                &mut current_bitmaps,
+                                &mut gpu_cache,
+                                &mut view_state,
                                 true, // 履歴ナビゲーション: 履歴への再追加をスキップ
                             );
                             window.request_redraw();
@@ -1452,7 +5313,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 UserEvent::ClearHistory => {
                     settings.clear_history();
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
                     if let Some(ref mut mh) = modern_history { mh.window.request_redraw(); }
                 }
                 UserEvent::DeleteHistoryItem(idx) => {
@@ -1465,41 +5326,448 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             app_state.current_history_index = None;
                         }
                     }
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
+                    if let Some(ref mut mh) = modern_history { mh.window.request_redraw(); }
+                }
+                UserEvent::ExportPage => {
+                    let (indices, pages) = displayed_export_pages(&app_state, &cpu_cache, &current_path_key);
+                    let Some(pages) = pages else {
+                        app_state.status_message = Some(("読み込みが終わっていないため書き出せません".to_string(), std::time::Instant::now()));
+                        window.request_redraw();
+                        return;
+                    };
+                    let source_name = crate::platform::share::sanitize_file_name(&current_path_key);
+                    let mut page_numbers: Vec<usize> = indices.iter().map(|i| i + 1).collect();
+                    page_numbers.sort();
+                    let pages_label = page_numbers.iter().map(|n| format!("{:03}", n)).collect::<Vec<_>>().join("-");
+                    let default_name = format!("{}_{}.png", source_name, pages_label);
+                    let folder = (!settings.export_dir.is_empty()).then(|| std::path::PathBuf::from(&settings.export_dir));
+                    let Some(path) = ui::dialogs::save_image_file(hwnd, &default_name, folder.as_deref()) else { return; };
+                    if let Some(dir) = path.parent() {
+                        settings.export_dir = dir.to_string_lossy().to_string();
+                        let _ = settings.save(config_path);
+                    }
+                    app_state.status_message = Some(("書き出し中...".to_string(), std::time::Instant::now()));
+                    // 並べる・エンコードするのは数秒かかることがあるため別スレッドで行う
+                    let background = settings.background_rgb().map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                    let proxy = proxy.clone();
+                    rt.spawn_blocking(move || {
+                        let result = crate::image::export::compose(&pages, background)
+                            .and_then(|image| crate::image::export::save(&image, &path).map_err(|e| e.to_string()))
+                            .map(|_| path.to_string_lossy().to_string());
+                        let _ = proxy.send_event(UserEvent::PageExported(result));
+                    });
+                    window.request_redraw();
+                }
+                UserEvent::PageCopied(result) => {
+                    let message = match result {
+                        Ok((width, height)) => format!("ページをコピーしました ({} × {})", width, height),
+                        Err(e) => {
+                            println!("[クリップボード] ページのコピーに失敗: {}", e);
+                            format!("コピーに失敗しました: {}", e)
+                        }
+                    };
+                    app_state.status_message = Some((message, std::time::Instant::now()));
+                    window.request_redraw();
+                }
+                UserEvent::ShareReady(result) => {
+                    let result = result.and_then(|(path, title)| {
+                        crate::platform::share::share_file(hwnd, &path, &title).map_err(|e| e.to_string())
+                    });
+                    // 成功したときは共有 UI が開くため、準備中の表示だけ消す
+                    app_state.status_message = match result {
+                        Ok(()) => None,
+                        Err(e) => {
+                            println!("[共有] 共有に失敗: {}", e);
+                            Some(("共有に失敗しました".to_string(), std::time::Instant::now()))
+                        }
+                    };
+                    window.request_redraw();
+                }
+                UserEvent::PageExported(result) => {
+                    let message = match result {
+                        Ok(path) => {
+                            println!("[書き出し] 保存しました: {}", path);
+                            format!("保存しました: {}", path)
+                        }
+                        Err(e) => {
+                            println!("[書き出し] 保存に失敗: {}", e);
+                            format!("保存に失敗しました: {}", e)
+                        }
+                    };
+                    app_state.status_message = Some((message, std::time::Instant::now()));
+                    window.request_redraw();
+                }
+                UserEvent::SetSourceTags(path, tags) => {
+                    // タグは暗号化して保存するため、ログには内容を出さない
+                    if settings.set_source_tags(&path, tags) {
+                        println!("[メモ] タグを変更しました: {}", path);
+                        let _ = settings.save(config_path);
+                    } else {
+                        println!("[メモ] 復号できないメモのため変更しませんでした: {}", path);
+                    }
+                    if let Some(ref mut mh) = modern_history { mh.window.request_redraw(); }
+                }
+                UserEvent::SetSourceText(path, text) => {
+                    if settings.set_source_text(&path, text) {
+                        println!("[メモ] メモを変更しました: {}", path);
+                        let _ = settings.save(config_path);
+                    } else {
+                        println!("[メモ] 復号できないメモのため変更しませんでした: {}", path);
+                    }
                     if let Some(ref mut mh) = modern_history { mh.window.request_redraw(); }
                 }
-                UserEvent::SetMaxHistoryCount(count) => {
-                    settings.max_history_count = count;
-                    let _ = settings.save("config.json");
+                UserEvent::LoadBookmark(idx) => {
+                    if let Some(bookmark) = settings.bookmarks.get(idx).cloned() {
+                        if bookmark.path == current_path_key {
+                            // 同じソース内ならページ移動だけ行う
+                            app_state.record_undo(&current_path_key, "しおりへの移動");
+                            app_state.current_page_index = bookmark.page.min(app_state.image_files.len().saturating_sub(1));
+                            view_state.reset();
+                            let l = loader.clone();
+                            rt.spawn(async move { let _ = l.send_request(LoaderRequest::ClearPrefetch).await; });
+                            request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                            window.request_redraw();
+                        } else if let Some(new_source) = get_image_source(&bookmark.path) {
+                            load_new_source(
+                                new_source,
+                                bookmark.path,
+                                Some(bookmark.page),
+                                None,
+                                &mut app_state,
+                                &mut current_path_key,
+                                &window,
+                                &cpu_cache,
+                                &loader,
+                                &rt,
+                                &mut settings,
+                                &mut gpu_cache,
+                                &mut view_state,
+                                false,
+                            );
+                            window.request_redraw();
+                        } else {
+                            app_state.status_message = Some(("しおりのファイルが見つかりません".to_string(), std::time::Instant::now()));
+                        }
+                    }
+                }
+                UserEvent::DeleteBookmark(idx) => {
+                    settings.remove_bookmark(idx);
+                    let _ = settings.save(config_path);
+                    if let Some(ref mut mb) = modern_bookmarks { mb.window.request_redraw(); }
+                }
+                UserEvent::RenameBookmark(idx, label) => {
+                    settings.rename_bookmark(idx, label);
+                    let _ = settings.save(config_path);
+                    if let Some(ref mut mb) = modern_bookmarks { mb.window.request_redraw(); }
+                }
+                UserEvent::SetMaxHistoryCount(count) => {
+                    settings.max_history_count = count;
+                    let _ = settings.save(config_path);
+                }
+                UserEvent::SetMaxCacheSize(size) => {
+                    settings.max_cache_size_mb = size;
+                    let _ = settings.save(config_path);
+                    // キャッシュインスタンスの最大サイズを更新
+                    let max_bytes = (size as usize) * 1024 * 1024;
+                    if let Ok(mut cache) = cpu_cache.lock() {
+                        // cache インスタンス自体を再初期化せずに max_bytes だけ変えられる仕組みが必要だが、
+                        // 現在の ImageCache には max_bytes の setter がないので reflection 的に作り直すか setter を追加する
+                        // setter を追加する方が綺麗。
+                        cache.set_max_bytes(max_bytes);
+                    }
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::SetCpuPrefetchPages(pages) => {
+                    settings.cpu_max_prefetch_pages = pages;
+                    let _ = settings.save(config_path);
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                    request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                }
+                UserEvent::SetDecodeWorkers(workers) => {
+                    settings.parallel_decoding_workers = workers;
+                    let _ = settings.save(config_path);
+                    crate::image::decoder::set_decode_workers(workers);
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleInstantFlip => {
+                    settings.instant_flip = !settings.instant_flip;
+                    if !settings.instant_flip {
+                        app_state.pending_flip = None;
+                    }
+                    let _ = settings.save(config_path);
+                    let message = if settings.instant_flip { "瞬時切り替え: 有効 (転送済みのページにだけ移動します)" } else { "瞬時切り替え: 無効" };
+                    app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleChromaUpsampling => {
+                    settings.chroma_upsampling = if settings.chroma_upsampling == "CatmullRom" { "Bilinear" } else { "CatmullRom" }.to_string();
+                    renderer.set_chroma_upsampling(crate::render::ChromaUpsampling::from_setting(&settings.chroma_upsampling));
+                    let _ = settings.save(config_path);
+                    let message = format!("色差の補間: {} (JPEG 2000 などの YCbCr 画像)", settings.chroma_upsampling);
+                    app_state.status_message = Some((message, std::time::Instant::now()));
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleLeftHandedMode => {
+                    settings.left_handed_mode = !settings.left_handed_mode;
+                    let _ = settings.save(config_path);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::TogglePrefetchPaused => {
+                    settings.prefetch_paused = !settings.prefetch_paused;
+                    let request = if settings.prefetch_paused { LoaderRequest::Pause } else { LoaderRequest::Resume };
+                    let l = loader.clone();
+                    rt.spawn(async move { let _ = l.send_request(request).await; });
+                    let message = if settings.prefetch_paused { "先読み: 一時停止 (P で再開)" } else { "先読み: 再開" };
+                    app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::SetPathDisplayLimit(max_chars) => {
+                    settings.path_display_max_chars = max_chars;
+                    let _ = settings.save(config_path);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::SetFitMode(mode) => {
+                    view_state.fit_mode = mode;
+                    settings.fit_mode = mode.setting_name().to_string();
+                    let _ = settings.save(config_path);
+                    // 合わせ方が変わると倍率の基準も変わるため、ズームとパンは戻して読み始める端から表示する
+                    view_state.reset();
+                    app_state.status_message = Some((format!("合わせ方: {}", mode.label()), std::time::Instant::now()));
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::SetSortOrder(order) => {
+                    crate::image::sort::set_sort_order(order);
+                    settings.file_sort_order = order.setting_name().to_string();
+                    let _ = settings.save(config_path);
+                    // 画像を並べたフォルダを開いていれば、表示中の画像を保ったまま並べ直す
+                    if folder_watcher::is_image_folder(&current_path_key, &app_state.image_files) {
+                        let index = app_state.current_page_index;
+                        let current = app_state.image_files.get(index).cloned();
+                        reload_current_folder(
+                            |files| current.as_ref().and_then(|c| files.iter().position(|f| f == c)).unwrap_or(index),
+                            &mut app_state,
+                            &mut current_path_key,
+                            &window,
+                            &cpu_cache,
+                            &loader,
+                            &rt,
+                            &mut settings,
+                            &mut gpu_cache,
+                            &mut view_state,
+                            &mut staged_uploads,
+                        );
+                    }
+                    app_state.status_message = Some((format!("並び順: {}", order.label()), std::time::Instant::now()));
+                    window.request_redraw();
+                }
+                UserEvent::SetTitleFormat(format) => {
+                    settings.title_format = if format.trim().is_empty() {
+                        crate::template::DEFAULT_TITLE_FORMAT.to_string()
+                    } else {
+                        format
+                    };
+                    let _ = settings.save(config_path);
+                    println!("[設定] タイトルバーの書式: {}", settings.title_format);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::SetStatusbarFormat(format) => {
+                    settings.statusbar_format = if format.trim().is_empty() {
+                        crate::template::DEFAULT_STATUSBAR_FORMAT.to_string()
+                    } else {
+                        format
+                    };
+                    let _ = settings.save(config_path);
+                    println!("[設定] ステータスバーの書式: {}", settings.statusbar_format);
+                    window.request_redraw();
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::SetPreRotation(rotation) => {
+                    settings.pre_rotation = rotation;
+                    let _ = settings.save(config_path);
+                    println!("[設定] 事前回転を {}° に設定しました", settings.effective_pre_rotation());
+                    // 回転前のテクスチャと OCR 座標は使えないため破棄し、表示中ページから読み直す
+                    gpu_cache.clear();
+                    loupe_prescale = None;
+                    app_state.clear_ocr();
+                    app_state.page_crops.clear();
+                    app_state.page_sizes.clear();
+                    app_state.crop_edit = None;
+                    view_state.reset();
+                    request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                    window.request_redraw();
                 }
-                UserEvent::SetMaxCacheSize(size) => {
-                    settings.max_cache_size_mb = size;
-                    let _ = settings.save("config.json");
-                    // キャッシュインスタンスの最大サイズを更新
-                    let max_bytes = (size as usize) * 1024 * 1024;
-                    if let Ok(mut cache) = cpu_cache.lock() {
-                        // cache インスタンス自体を再初期化せずに max_bytes だけ変えられる仕組みが必要だが、
-                        // 現在の ImageCache には max_bytes の setter がないので reflection 的に作り直すか setter を追加する
-                        // setter を追加する方が綺麗。
-                        cache.set_max_bytes(max_bytes);
+                UserEvent::SetViewAnimation(ms_value) => {
+                    settings.view_animation_ms = ms_value;
+                    let _ = settings.save(config_path);
+                    if ms_value == 0 {
+                        view_state.animation = None;
                     }
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                 }
-                UserEvent::SetCpuPrefetchPages(pages) => {
-                    settings.cpu_max_prefetch_pages = pages;
-                    let _ = settings.save("config.json");
+                UserEvent::SetPageTurnAnimation(kind) => {
+                    settings.page_turn_animation_type = kind;
+                    let _ = settings.save(config_path);
+                    app_state.page_turn = None;
+                    let label = match settings.page_turn_animation_type.as_str() {
+                        "slide" => "スライド",
+                        "curl" if settings.rendering_backend == "direct2d" => "めくる (Direct2D ではスライドで表示)",
+                        "curl" => "めくる",
+                        _ => "なし",
+                    };
+                    app_state.status_message = Some((format!("ページめくり: {}", label), std::time::Instant::now()));
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                }
+                UserEvent::ToggleFreezeFrameOnResize => {
+                    settings.freeze_frame_on_resize = !settings.freeze_frame_on_resize;
+                    let _ = settings.save(config_path);
+                    if !settings.freeze_frame_on_resize {
+                        app_state.frozen_frame = FrozenFrame::default();
+                    }
+                    let message = if settings.freeze_frame_on_resize { "サイズ変更中: 直前の画面を縮小して表示" } else { "サイズ変更中: 毎回配置し直す" };
+                    app_state.status_message = Some((message.to_string(), std::time::Instant::now()));
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
-                    request_pages_with_prefetch(&app_state, &loader, &rt, &cpu_cache, &settings, &current_path_key);
                 }
                 UserEvent::SetGpuPrefetchPages(pages) => {
                     settings.gpu_max_prefetch_pages = pages;
-                    let _ = settings.save("config.json");
+                    let _ = settings.save(config_path);
+                    if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
+                    window.request_redraw();
+                }
+                UserEvent::SetGpuCacheSize(size) => {
+                    // 次の描画で上限を超えた分が解放される
+                    settings.gpu_max_cache_mb = size;
+                    let _ = settings.save(config_path);
                     if let Some(ref mut ms) = modern_settings { ms.window.request_redraw(); }
                     window.request_redraw();
                 }
+                UserEvent::LoupePrescaled { generation, image } => {
+                    if let Some(prescale) = loupe_prescale.as_mut().filter(|p| p.generation == generation) {
+                        match renderer.upload_image(&image) {
+                            Ok(texture) => prescale.texture = Some(texture),
+                            Err(e) => {
+                                println!("[ルーペ] 縮小済み画像の転送に失敗しました: {}", e);
+                                loupe_prescale = None;
+                            }
+                        }
+                        window.request_redraw();
+                    }
+                }
+                UserEvent::OcrCompleted { path_key, index, result } => {
+                    if path_key == current_path_key {
+                        app_state.ocr_pending.remove(&index);
+                        match result {
+                            Ok(page_result) => {
+                                app_state.status_message = Some((format!("OCR: {} 語を認識しました", page_result.words.len()), std::time::Instant::now()));
+                                app_state.ocr_results.insert(index, page_result);
+                            }
+                            Err(e) => {
+                                println!("[OCR] 認識に失敗しました (インデックス {}): {}", index, e);
+                                app_state.status_message = Some((format!("OCR に失敗しました: {}", e), std::time::Instant::now()));
+                                // 再試行を繰り返さないよう空の結果を登録する
+                                app_state.ocr_results.insert(index, crate::platform::ocr::OcrPageResult {
+                                    words: Vec::new(),
+                                    image_width: 1,
+                                    image_height: 1,
+                                });
+                            }
+                        }
+                        window.request_redraw();
+                    }
+                }
             }
         },
             Event::AboutToWait => {
+                // ドロップされたパスを開く。1 つならそのまま、複数ならプレイリストにして先頭から開く
+                if !dropped_paths.is_empty() {
+                    let paths = std::mem::take(&mut dropped_paths);
+                    let opened = if paths.len() == 1 {
+                        app_state.playlist = None;
+                        resolve_open_path(&paths[0])
+                    } else {
+                        let playlist = crate::state::playlist::Playlist::new(paths);
+                        println!("[プレイリスト] {} 件: {:?}", playlist.entries().len(), playlist.entries());
+                        app_state.osd_toast = Some((
+                            format!("プレイリスト: {} 件 ([ / ] で移動)", playlist.entries().len()),
+                            std::time::Instant::now(),
+                        ));
+                        let first = playlist
+                            .entries()
+                            .iter()
+                            .find_map(|path| get_image_source(path).map(|source| (path.clone(), source, None)));
+                        app_state.playlist = Some(playlist);
+                        first
+                    };
+                    if let Some((path, new_source, page)) = opened {
+                        load_new_source(
+                            new_source,
+                            path,
+                            page,
+                            None,
+                            &mut app_state,
+                            &mut current_path_key,
+                            &window,
+                            &cpu_cache,
+                            &loader,
+                            &rt,
+                            &mut settings,
+                            &mut gpu_cache,
+                            &mut view_state,
+                            false, // 通常のファイル読み込み
+                        );
+                    }
+                }
+                poll_archive_listing(
+                    &mut app_state,
+                    &current_path_key,
+                    &window,
+                    &cpu_cache,
+                    &loader,
+                    &rt,
+                    &settings,
+                    &mut gpu_cache,
+                    &mut staged_uploads,
+                    view_state.zoom_level,
+                );
+                // 画像フォルダを表示中なら、ファイルの増減 (ダウンロード中の追加など) を監視する
+                if watched_source != current_path_key {
+                    watched_source = current_path_key.clone();
+                    folder_watcher = None;
+                    if folder_watcher::is_image_folder(&current_path_key, &app_state.image_files) {
+                        folder_watcher = folder_watcher::FolderWatcher::start(&current_path_key, proxy.clone());
+                    }
+                }
+                // 連続した設定変更をまとめて config.json に書き込む
+                settings.flush_if_due();
+                // 数字キーで始めたページ入力は、しばらく入力が無ければ取り消す
+                if app_state.is_jump_open
+                    && app_state.jump_typed_at.map_or(false, |t| t.elapsed() > std::time::Duration::from_secs(4))
+                {
+                    app_state.is_jump_open = false;
+                    app_state.jump_input_buffer.clear();
+                    app_state.jump_typed_at = None;
+                }
+                // ホイールで時々ページを送るだけの読書中に画面が消灯しないようにする
+                crate::platform::power::set_display_required(
+                    settings.keep_display_on
+                        && !app_state.image_files.is_empty()
+                        && window.is_minimized() != Some(true),
+                );
+                if is_cursor_in_use(&app_state, &view_state) {
+                    cursor_auto_hide.on_activity(&window);
+                } else if settings.cursor_hide_seconds > 0.0
+                    && cursor_auto_hide.last_activity.elapsed().as_secs_f32() >= settings.cursor_hide_seconds
+                {
+                    cursor_auto_hide.hide(&window);
+                }
                 window.request_redraw();
             }
             _ => (),
@@ -1555,7 +5823,11 @@ fn init_opengl(window: &Arc<winit::window::Window>) -> Result<crate::render::ope
 
 
 fn request_pages_with_prefetch(app_state: &AppState, loader: &AsyncLoader, rt: &Runtime, cpu_cache: &SharedImageCache, settings: &Settings, path_key: &str) {
-    let display_indices = app_state.get_page_indices_to_display();
+    let mut display_indices = app_state.get_page_indices_to_display();
+    // 瞬時切り替えで待っている移動先も表示中のページと同じ優先度で読み込む
+    if let Some(pending) = app_state.pending_flip.as_ref() {
+        display_indices.extend(pending.pages.iter().copied().filter(|i| !display_indices.contains(i)).collect::<Vec<_>>());
+    }
     let max_idx = app_state.image_files.len() as isize - 1;
     if max_idx < 0 { return; }
 
@@ -1565,25 +5837,27 @@ fn request_pages_with_prefetch(app_state: &AppState, loader: &AsyncLoader, rt: &
     // 1. 表示対象の即時リクエスト (Priority 0)
     for &idx in &display_indices {
         let key = format!("{}::{}", path_key, idx);
-        let cached = cpu_cache.lock().unwrap().get(&key).is_some();
+        let cached = cpu_cache.lock().unwrap().get(&key).is_some_and(|img| img.rotation == settings.effective_pre_rotation());
         if !cached {
             // println!("[先読み] インデックス {} の即時読み込みをリクエスト", idx);
             let l = loader_tx.clone();
             let cpu_conv = settings.use_cpu_color_conversion;
+            let rotation = settings.effective_pre_rotation();
             rt.spawn(async move {
-                let _ = l.send(LoaderRequest::Load { index: idx, priority: 0, use_cpu_color_conversion: cpu_conv }).await;
+                let _ = l.send(LoaderRequest::Load { index: idx, priority: 0, use_cpu_color_conversion: cpu_conv, rotation }).await;
             });
         }
     }
 
     // 2. 先読み範囲の計算と「歯抜け」補充 (Priority 1)
-    let prefetch_dist = settings.cpu_max_prefetch_pages;
+    // 縦スクロール表示ではスクロールしている向きを多めに先読みする
+    let (before, after) = app_state.prefetch_window(settings.cpu_max_prefetch_pages);
     let mut targets = std::collections::HashSet::new();
     
-    // 表示中の全ページについて、その前後 prefetch_dist を先読み対象とする
+    // 表示中の全ページについて、その前後を先読み対象とする
     for &idx in &display_indices {
-        let start = (idx as isize - prefetch_dist as isize).max(0) as usize;
-        let end = (idx as isize + prefetch_dist as isize).min(max_idx) as usize;
+        let start = (idx as isize - before as isize).max(0) as usize;
+        let end = (idx as isize + after as isize).min(max_idx) as usize;
         for i in start..=end {
             if !display_indices.contains(&i) {
                 targets.insert(i);
@@ -1594,7 +5868,12 @@ fn request_pages_with_prefetch(app_state: &AppState, loader: &AsyncLoader, rt: &
     let mut targets_vec: Vec<_> = targets.into_iter().collect();
     // 現在のページに近い順にソート（効率的な補充のため）
     let current = app_state.current_page_index as isize;
-    targets_vec.sort_by_key(|&idx| (idx as isize - current).abs());
+    // 距離が同じなら進む向きを優先する
+    let forward = app_state.scroll_direction.signum() as isize;
+    targets_vec.sort_by_key(|&idx| {
+        let d = idx as isize - current;
+        (d.abs(), d.signum() != forward)
+    });
     
     // if !targets_vec.is_empty() {
     //     println!("[先読み] 補充対象インデックス: {:?}", targets_vec);
@@ -1604,14 +5883,15 @@ fn request_pages_with_prefetch(app_state: &AppState, loader: &AsyncLoader, rt: &
         let key = format!("{}::{}", path_key, idx);
         let cached = {
             let mut c = cpu_cache.lock().unwrap();
-            c.get(&key).is_some()
+            c.get(&key).is_some_and(|img| img.rotation == settings.effective_pre_rotation())
         };
         
         if !cached {
             let l = loader_tx.clone();
             let cpu_conv = settings.use_cpu_color_conversion;
+            let rotation = settings.effective_pre_rotation();
             rt.spawn(async move {
-                let _ = l.send(LoaderRequest::Load { index: idx, priority: 1, use_cpu_color_conversion: cpu_conv }).await;
+                let _ = l.send(LoaderRequest::Load { index: idx, priority: 1, use_cpu_color_conversion: cpu_conv, rotation }).await;
             });
         }
     }
@@ -1665,22 +5945,33 @@ fn format_page_list(indices: &[usize], current: usize) -> String {
     }
 }
 
-fn get_neighboring_source(current_path: &str, direction: isize) -> Option<String> {
+/// ページのパスのうちディスク上に実在するファイル (アーカイブをつなげて開いたフォルダのページはそのアーカイブ)
+fn containing_file(page: &str) -> std::path::PathBuf {
+    let path = std::path::Path::new(page);
+    path.ancestors().find(|p| p.is_file()).unwrap_or(path).to_path_buf()
+}
+
+/// 1 ファイルで 1 冊のソースになる拡張子 (アーカイブ・PDF) か
+fn is_book_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .map(|ext| ext.to_lowercase())
+        .is_some_and(|ext| ext == crate::image::PDF_EXTENSION || crate::image::ARCHIVE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// 表示中のソースと同じフォルダにあるフォルダ/アーカイブを自然順で列挙する
+/// 戻り値は (一覧, 表示中のソースの位置)
+fn list_sibling_sources(current_path: &str) -> Option<(Vec<std::path::PathBuf>, Option<usize>)> {
     let path = std::path::Path::new(current_path);
     let parent = path.parent()?;
     
     let mut entries = Vec::new();
-    let supported_archives = ["zip", "7z", "cbz", "rar", "cbr"];
     
     if let Ok(dir) = std::fs::read_dir(parent) {
         for entry in dir.flatten() {
             let p = entry.path();
-            if p.is_dir() {
+            if p.is_dir() || is_book_file(&p) {
                 entries.push(p);
-            } else if let Some(ext) = p.extension().and_then(|s| s.to_str()) {
-                if supported_archives.contains(&ext.to_lowercase().as_str()) {
-                    entries.push(p);
-                }
             }
         }
     }
@@ -1693,37 +5984,523 @@ fn get_neighboring_source(current_path: &str, direction: isize) -> Option<String
     let current_idx = entries.iter().position(|e| {
         std::fs::canonicalize(e).map(|abs| abs == current_abs).unwrap_or(false)
     });
+    Some((entries, current_idx))
+}
 
-    if let Some(idx) = current_idx {
-        let next_idx = idx as isize + direction;
-        if next_idx >= 0 && next_idx < entries.len() as isize {
-            return Some(entries[next_idx as usize].to_string_lossy().to_string());
+/// 同じフォルダ内で前後のフォルダ/アーカイブを探し、画像を含む最初のものを開く
+/// 画像の無いフォルダや開けないアーカイブは飛ばす。端まで見つからなければ None
+fn find_neighboring_source(current_path: &str, direction: isize) -> Option<(String, ImageSource)> {
+    let (entries, current_idx) = list_sibling_sources(current_path)?;
+    let mut next_idx = current_idx? as isize + direction;
+    while next_idx >= 0 && next_idx < entries.len() as isize {
+        let candidate = entries[next_idx as usize].to_string_lossy().to_string();
+        match get_image_source(&candidate) {
+            Some(source) if source.len() > 0 => return Some((candidate, source)),
+            _ => println!("フォルダ/アーカイブ移動: 画像が無いためスキップ: {}", candidate),
+        }
+        next_idx += direction;
+    }
+
+    None
+}
+
+/// 表示中のページのうち OCR 未実行のものをワーカースレッドで認識する
+/// config.json の更新日時を監視し、変化したら UserEvent::ConfigFileChanged を送る
+fn spawn_config_watcher(path: &'static str, proxy: winit::event_loop::EventLoopProxy<UserEvent>) {
+    std::thread::spawn(move || {
+        let mtime = || std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut last = mtime();
+        loop {
+            std::thread::sleep(std::time::Duration::from_millis(1000));
+            let current = mtime();
+            if current != last {
+                last = current;
+                if proxy.send_event(UserEvent::ConfigFileChanged).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// 起動引数のうち開くパス (--portable / --threads N などのオプションを除いた最初の引数)
+fn open_path_arg(args: &[String]) -> Option<&String> {
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        if arg == "--threads" {
+            rest.next();
+        } else if !arg.starts_with("--") {
+            return Some(arg);
         }
     }
-    
     None
 }
 
+/// GitHub Releases の最新バージョンをバックグラウンドで確認する
+fn spawn_update_check(rt: &Runtime, proxy: &winit::event_loop::EventLoopProxy<UserEvent>) {
+    let proxy = proxy.clone();
+    rt.spawn_blocking(move || {
+        let result = crate::platform::update::fetch_latest_version().map_err(|e| e.to_string());
+        let _ = proxy.send_event(UserEvent::UpdateCheckCompleted(result));
+    });
+}
+
+/// 表示中のページのうち OCR 未実行のものをワーカースレッドで認識する
+fn request_ocr_for_display(
+    app_state: &mut AppState,
+    cpu_cache: &SharedImageCache,
+    rt: &Runtime,
+    proxy: &winit::event_loop::EventLoopProxy<UserEvent>,
+    path_key: &str,
+) {
+    for idx in app_state.get_page_indices_to_display() {
+        if app_state.ocr_results.contains_key(&idx) || app_state.ocr_pending.contains(&idx) {
+            continue;
+        }
+        let key = format!("{}::{}", path_key, idx);
+        let Some(decoded) = cpu_cache.lock().unwrap().get(&key) else { continue; };
+        app_state.ocr_pending.insert(idx);
+        let proxy = proxy.clone();
+        let path_key = path_key.to_string();
+        rt.spawn_blocking(move || {
+            let result = crate::platform::ocr::recognize(&decoded).map_err(|e| e.to_string());
+            let _ = proxy.send_event(UserEvent::OcrCompleted { path_key, index: idx, result });
+        });
+    }
+}
+
+/// OCR 単語の座標 (画像ピクセル) をウィンドウ座標に変換する
+fn ocr_word_screen_rect(
+    word: &crate::platform::ocr::OcrWord,
+    result: &crate::platform::ocr::OcrPageResult,
+    dest: &D2D_RECT_F,
+    transform: PageTransform,
+) -> D2D_RECT_F {
+    // OCR は回転前の画像に対して行うため、正規化座標でページの回転・反転を適用してから配置する
+    let (w, h) = (result.image_width.max(1) as f32, result.image_height.max(1) as f32);
+    transform.map_rect(word.x / w, word.y / h, (word.x + word.width) / w, (word.y + word.height) / h, dest)
+}
+
+/// ウィンドウ座標をページ上の正規化座標 (回転・反転前) に変換する。ページの外は端に寄せる
+fn annotation_point(app_state: &AppState, page_rects: &[(usize, D2D_RECT_F)], index: usize, pos: (f32, f32)) -> Option<[f32; 2]> {
+    let (_, rect) = page_rects.iter().find(|(i, _)| *i == index)?;
+    let (w, h) = (rect.right - rect.left, rect.bottom - rect.top);
+    if w <= 0.0 || h <= 0.0 {
+        return None;
+    }
+    let x = ((pos.0 - rect.left) / w).clamp(0.0, 1.0);
+    let y = ((pos.1 - rect.top) / h).clamp(0.0, 1.0);
+    Some(app_state.page_transform(index).source_uv(x, y))
+}
+
+/// カーソル (指・ペン先) の下のページで注釈の線を描き始める
+fn begin_annotation_stroke(app_state: &mut AppState, page_rects: &[(usize, D2D_RECT_F)], pos: (f32, f32)) {
+    let Some(tool) = app_state.annotation_tool else { return; };
+    let Some(&(index, _)) = page_rects.iter().find(|(_, r)| point_in_rect(pos, r)) else { return; };
+    if let Some(point) = annotation_point(app_state, page_rects, index, pos) {
+        app_state.annotation_stroke = Some((index, crate::annotations::Stroke::new(tool, point)));
+    }
+}
+
+/// 描いている途中の線を伸ばす (描き始めたページの外に出た分は端に沿わせる)
+fn extend_annotation_stroke(app_state: &mut AppState, page_rects: &[(usize, D2D_RECT_F)], pos: (f32, f32)) {
+    let Some(index) = app_state.annotation_stroke.as_ref().map(|(index, _)| *index) else { return; };
+    let Some(point) = annotation_point(app_state, page_rects, index, pos) else { return; };
+    if let Some((_, stroke)) = app_state.annotation_stroke.as_mut() {
+        stroke.push(point);
+    }
+}
+
+/// 描いた線を表示中ソースの注釈に加えて保存する
+fn finish_annotation_stroke(app_state: &mut AppState, current_path_key: &str) {
+    let Some((index, stroke)) = app_state.annotation_stroke.take() else { return; };
+    let Some(entry) = app_state.image_files.get(index) else { return; };
+    let page = page_display_name(entry, current_path_key);
+    app_state.annotations.add(&page, stroke);
+    app_state.show_annotations = true;
+    if let Err(e) = app_state.annotations.save() {
+        println!("[注釈] 保存に失敗しました: {}", e);
+        app_state.status_message = Some((format!("注釈を保存できませんでした: {}", e), std::time::Instant::now()));
+    }
+}
+
+/// 表示中ページの注釈と、描いている途中の線を重ねて描画する
+fn draw_annotations(renderer: &dyn Renderer, app_state: &AppState, layout_info: &[PageDrawInfo], current_path_key: &str) {
+    let draw = |stroke: &crate::annotations::Stroke, info: &PageDrawInfo| {
+        let dest = &info.dest_rect;
+        let (w, h) = (dest.right - dest.left, dest.bottom - dest.top);
+        let points: Vec<(f32, f32)> = stroke
+            .points
+            .iter()
+            .map(|p| {
+                let (x, y) = info.transform.display_point(p[0], p[1]);
+                (dest.left + x * w, dest.top + y * h)
+            })
+            .collect();
+        let [r, g, b] = stroke.color;
+        let color = D2D1_COLOR_F { r, g, b, a: stroke.tool.alpha() };
+        renderer.draw_polyline(&points, &color, (stroke.width * w.min(h)).max(1.0));
+    };
+    for info in layout_info {
+        if app_state.show_annotations {
+            if let Some(entry) = app_state.image_files.get(info.index) {
+                for stroke in app_state.annotations.strokes(&page_display_name(entry, current_path_key)) {
+                    draw(stroke, info);
+                }
+            }
+        }
+        if let Some((index, stroke)) = &app_state.annotation_stroke {
+            if *index == info.index {
+                draw(stroke, info);
+            }
+        }
+    }
+}
+
+/// 手動トリミングを変更して履歴に保存する (None で解除)
+fn set_manual_crop(app_state: &mut AppState, settings: &mut Settings, current_path_key: &str, crop: Option<CropMargins>) {
+    let crop = crop.map(|c| c.clamped()).filter(|c| !c.is_empty());
+    if crop == app_state.manual_crop {
+        return;
+    }
+    app_state.record_undo(current_path_key, "トリミング");
+    app_state.manual_crop = crop;
+    if !settings.set_history_crop(current_path_key, crop) {
+        sync_current_state_to_history(settings, app_state, current_path_key);
+    }
+    let _ = settings.save(crate::config::config_path());
+    let message = match crop {
+        Some(crop) => format!("トリミング: {}", crop.describe()),
+        None => "トリミング: 解除しました".to_string(),
+    };
+    println!("[トリミング] {}", message);
+    app_state.osd_toast = Some((message, std::time::Instant::now()));
+}
+
+/// トリミング範囲の編集で辺を掴める距離 (論理ピクセル)
+const CROP_HANDLE_TOLERANCE: f32 = 12.0;
+/// 編集中の範囲の最小の幅・高さ (ページに対する割合)
+const CROP_MIN_SIZE: f32 = 0.05;
+
+/// 編集中の残す範囲の画面上の矩形
+fn crop_edit_screen_rect(edit: &CropEdit, page: &D2D_RECT_F) -> D2D_RECT_F {
+    let (w, h) = (page.right - page.left, page.bottom - page.top);
+    D2D_RECT_F {
+        left: page.left + edit.rect.left * w,
+        top: page.top + edit.rect.top * h,
+        right: page.left + edit.rect.right * w,
+        bottom: page.top + edit.rect.bottom * h,
+    }
+}
+
+/// カーソル位置で掴む辺 (左, 上, 右, 下)。角では 2 辺を掴み、どの辺にも近くなければ None
+fn crop_edit_grab(edit: &CropEdit, page: &D2D_RECT_F, pos: (f32, f32), tolerance: f32) -> Option<[bool; 4]> {
+    let r = crop_edit_screen_rect(edit, page);
+    let within_x = pos.0 >= r.left - tolerance && pos.0 <= r.right + tolerance;
+    let within_y = pos.1 >= r.top - tolerance && pos.1 <= r.bottom + tolerance;
+    let (dl, dt) = ((pos.0 - r.left).abs(), (pos.1 - r.top).abs());
+    let (dr, db) = ((pos.0 - r.right).abs(), (pos.1 - r.bottom).abs());
+    // 範囲が狭くて両側の辺に届く場合は近い方だけを掴む
+    let edges = [
+        within_y && dl <= tolerance && dl <= dr,
+        within_x && dt <= tolerance && dt <= db,
+        within_y && dr <= tolerance && dr < dl,
+        within_x && db <= tolerance && db < dt,
+    ];
+    edges.contains(&true).then_some(edges)
+}
+
+/// 掴んでいる辺をカーソル位置まで動かす (ページの外には出さない)
+fn drag_crop_edit(edit: &mut CropEdit, page: &D2D_RECT_F, pos: (f32, f32)) {
+    let Some([left, top, right, bottom]) = edit.dragging else { return; };
+    let (w, h) = (page.right - page.left, page.bottom - page.top);
+    if w <= 0.0 || h <= 0.0 {
+        return;
+    }
+    let x = ((pos.0 - page.left) / w).clamp(0.0, 1.0);
+    let y = ((pos.1 - page.top) / h).clamp(0.0, 1.0);
+    if left { edit.rect.left = x.min(edit.rect.right - CROP_MIN_SIZE); }
+    if right { edit.rect.right = x.max(edit.rect.left + CROP_MIN_SIZE); }
+    if top { edit.rect.top = y.min(edit.rect.bottom - CROP_MIN_SIZE); }
+    if bottom { edit.rect.bottom = y.max(edit.rect.top + CROP_MIN_SIZE); }
+}
+
+/// トリミング範囲の編集: 削る部分を暗くし、残す範囲の枠と辺・角のつまみを描画する
+fn draw_crop_edit(renderer: &dyn Renderer, settings: &Settings, edit: &CropEdit, page_rects: &[(usize, D2D_RECT_F)], ui_scale: f32) {
+    let Some(page) = page_rects.iter().find(|(i, _)| *i == edit.page_index).map(|(_, r)| *r) else { return; };
+    let crop = crop_edit_screen_rect(edit, &page);
+    let shade = D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.55 };
+    let outside = [
+        D2D_RECT_F { bottom: crop.top, ..page },
+        D2D_RECT_F { top: crop.bottom, ..page },
+        D2D_RECT_F { top: crop.top, right: crop.left, bottom: crop.bottom, ..page },
+        D2D_RECT_F { left: crop.right, top: crop.top, bottom: crop.bottom, ..page },
+    ];
+    for rect in outside.iter().filter(|r| r.right > r.left && r.bottom > r.top) {
+        renderer.fill_rectangle(rect, &shade);
+    }
+    let color = accent_color(settings, 0.3, 1.0);
+    renderer.draw_rectangle(&crop, &color, 2.0 * ui_scale);
+    let half = 5.0 * ui_scale;
+    let (cx, cy) = ((crop.left + crop.right) / 2.0, (crop.top + crop.bottom) / 2.0);
+    for (x, y) in [
+        (crop.left, crop.top), (cx, crop.top), (crop.right, crop.top),
+        (crop.left, cy), (crop.right, cy),
+        (crop.left, crop.bottom), (cx, crop.bottom), (crop.right, crop.bottom),
+    ] {
+        renderer.fill_rectangle(&D2D_RECT_F { left: x - half, top: y - half, right: x + half, bottom: y + half }, &color);
+    }
+}
+
+fn normalize_rect(a: (f32, f32), b: (f32, f32)) -> D2D_RECT_F {
+    D2D_RECT_F {
+        left: a.0.min(b.0),
+        top: a.1.min(b.1),
+        right: a.0.max(b.0),
+        bottom: a.1.max(b.1),
+    }
+}
+
+fn rects_intersect(a: &D2D_RECT_F, b: &D2D_RECT_F) -> bool {
+    a.left < b.right && b.left < a.right && a.top < b.bottom && b.top < a.bottom
+}
+
+/// 2 つの矩形の重なり (重ならなければ幅または高さが 0 以下になる)
+fn intersect_rect(a: &D2D_RECT_F, b: &D2D_RECT_F) -> D2D_RECT_F {
+    D2D_RECT_F {
+        left: a.left.max(b.left),
+        top: a.top.max(b.top),
+        right: a.right.min(b.right),
+        bottom: a.bottom.min(b.bottom),
+    }
+}
+
+fn union_rect(a: &D2D_RECT_F, b: &D2D_RECT_F) -> D2D_RECT_F {
+    D2D_RECT_F {
+        left: a.left.min(b.left),
+        top: a.top.min(b.top),
+        right: a.right.max(b.right),
+        bottom: a.bottom.max(b.bottom),
+    }
+}
+
+/// 書き出し・コピーするページ: 画面上の並び (見開きなら左から) のページ番号と、CPU キャッシュの元解像度の画像
+/// トリミングと分割表示の半分 (effective_crop) も渡し、画面に見えている範囲だけを書き出す
+/// 1 ページでも読み込みが終わっていなければ画像は None
+fn displayed_export_pages(
+    app_state: &AppState,
+    cpu_cache: &SharedImageCache,
+    path_key: &str,
+) -> (Vec<usize>, Option<Vec<crate::image::export::ExportPage>>) {
+    let indices = if app_state.is_webtoon_view {
+        vec![app_state.current_page_index]
+    } else {
+        app_state.get_page_indices_to_display()
+    };
+    let mut cache = cpu_cache.lock().unwrap();
+    let pages: Option<Vec<_>> = indices
+        .iter()
+        .map(|&i| {
+            let image = cache.get(&format!("{}::{}", path_key, i))?;
+            Some(crate::image::export::ExportPage { image, transform: app_state.page_transform(i), crop: app_state.effective_crop(i) })
+        })
+        .collect();
+    (indices, pages.filter(|p| !p.is_empty()))
+}
+
+/// サイズ変更中の簡易表示: 直前のフレームのページを width x height に収まるよう拡大縮小して描く
+/// GPU に残っているテクスチャをそのまま使い、オーバーレイは変更が落ち着いてから描き直す
+fn draw_frozen_frame(renderer: &mut dyn Renderer, frame: &FrozenFrame, gpu_cache: &GpuCache, width: f32, height: f32) {
+    renderer.begin_draw();
+    for (page, adjustment) in frame.fitted(width, height) {
+        let Some(texture) = gpu_cache.get(page.index) else { continue };
+        renderer.set_color_adjustment(adjustment);
+        renderer.set_page_transform(page.transform);
+        renderer.draw_image_clipped(texture, &page.dest_rect, &page.clip_rect);
+    }
+    let _ = renderer.end_draw();
+}
+
+/// 出力ウィンドウに表示中のページを描く (ズーム・パン・オーバーレイは映さず、合わせ方は出力ウィンドウのものを使う)
+fn draw_mirror(
+    renderer: &mut dyn Renderer,
+    mirror: &ui::mirror::MirrorWindow,
+    indices: &[usize],
+    gpu_cache: &GpuCache,
+    transforms: &HashMap<usize, PageTransform>,
+    crops: &HashMap<usize, CropMargins>,
+    adjustments: &HashMap<usize, ColorAdjustment>,
+) {
+    let size = mirror.window.inner_size();
+    let (win_w, win_h) = (size.width as f32, size.height as f32);
+    if win_w < 1.0 || win_h < 1.0 {
+        return;
+    }
+    let (mut layout_info, _, (_, content_h)) =
+        calculate_page_layout(renderer, indices, gpu_cache, transforms, crops, win_w, win_h, 1.0, (0.0, 0.0), mirror.fit_mode);
+    // 縦にはみ出すページは上端から見せる
+    if content_h > win_h {
+        let pan = (0.0, (content_h - win_h) / 2.0);
+        (layout_info, _, _) = calculate_page_layout(renderer, indices, gpu_cache, transforms, crops, win_w, win_h, 1.0, pan, mirror.fit_mode);
+    }
+    renderer.begin_mirror_draw();
+    for info in &layout_info {
+        renderer.set_color_adjustment(adjustments.get(&info.index).copied().unwrap_or_default());
+        renderer.set_page_transform(info.transform);
+        if rect_eq(&info.clip_rect, &info.dest_rect) {
+            renderer.draw_image(info.texture, &info.dest_rect);
+        } else {
+            renderer.draw_image_clipped(info.texture, &info.dest_rect, &info.clip_rect);
+        }
+    }
+    renderer.end_mirror_draw();
+}
+
+/// 表示するページが前回描き終えたページと 1 枚も重ならなくなったらページ送りとみなし、
+/// そのときの配置をめくるページにする (見開きの切り替えなどページが重なる変化はめくらない)
+fn update_page_turn(
+    app_state: &mut AppState,
+    settings: &Settings,
+    display_indices: &[usize],
+    layout_info: &[PageDrawInfo],
+    complete: bool,
+) {
+    if app_state.page_turn.as_ref().is_some_and(|turn| turn.is_finished()) {
+        app_state.page_turn = None;
+    }
+    let kind = settings.page_turn_animation_type.as_str();
+    if app_state.is_webtoon_view || settings.page_turn_animation_ms == 0 || !matches!(kind, "slide" | "curl") {
+        app_state.page_turn = None;
+        app_state.last_drawn_pages.clear();
+        return;
+    }
+
+    let previous = &app_state.last_drawn_pages;
+    let old_first = previous.iter().map(|page| page.index).min();
+    let new_first = display_indices.iter().copied().min();
+    if let (Some(old_first), Some(new_first)) = (old_first, new_first) {
+        if previous.iter().all(|page| !display_indices.contains(&page.index)) {
+            // 左綴じは次へ進むとき右端から、右綴じは左端からめくる (戻るときは逆)
+            let forward = new_first > old_first;
+            let from_right = forward == (app_state.binding_direction == BindingDirection::Left);
+            let pages = std::mem::take(&mut app_state.last_drawn_pages);
+            let area = pages.iter().skip(1).fold(pages[0].clip_rect, |area, page| union_rect(&area, &page.clip_rect));
+            app_state.page_turn = Some(PageTurnAnimation {
+                pages,
+                area,
+                from_right,
+                curl: kind == "curl",
+                start: std::time::Instant::now(),
+                duration: std::time::Duration::from_millis(settings.page_turn_animation_ms as u64),
+            });
+        }
+    }
+
+    // 読み込み待ちのページが無いフレームだけを次のページ送りでめくる配置として覚える
+    if complete && !layout_info.is_empty() {
+        app_state.last_drawn_pages = layout_info
+            .iter()
+            .map(|info| TurnedPage {
+                index: info.index,
+                dest_rect: info.dest_rect,
+                clip_rect: info.clip_rect,
+                transform: info.transform,
+            })
+            .collect();
+    }
+}
+
+/// ドラッグ範囲 (またはクリック位置) にかかる OCR 単語を選択する
+fn select_ocr_words(
+    app_state: &mut AppState,
+    page_rects: &[(usize, D2D_RECT_F)],
+    start: (f32, f32),
+    end: (f32, f32),
+    additive: bool,
+) {
+    if !additive {
+        app_state.ocr_selected.clear();
+    }
+    let mut sel = normalize_rect(start, end);
+    // ほぼ動いていない場合はクリックとみなし、カーソル位置の単語を選択する
+    if sel.right - sel.left < 3.0 && sel.bottom - sel.top < 3.0 {
+        sel = D2D_RECT_F { left: end.0, top: end.1, right: end.0 + 0.5, bottom: end.1 + 0.5 };
+    }
+    for (idx, dest) in page_rects {
+        if let Some(result) = app_state.ocr_results.get(idx) {
+            for (w_idx, word) in result.words.iter().enumerate() {
+                if rects_intersect(&sel, &ocr_word_screen_rect(word, result, dest, app_state.page_transform(*idx))) {
+                    app_state.ocr_selected.insert((*idx, w_idx));
+                }
+            }
+        }
+    }
+}
+
+/// 選択中の OCR 単語をページ順・認識順に連結する
+fn collect_selected_ocr_text(app_state: &AppState) -> String {
+    let mut selected: Vec<(usize, usize)> = app_state.ocr_selected.iter().copied().collect();
+    selected.sort();
+    let mut pages: Vec<String> = Vec::new();
+    let mut current_page: Option<usize> = None;
+    let mut words = Vec::new();
+    for (idx, w_idx) in selected {
+        if current_page != Some(idx) {
+            if !words.is_empty() {
+                pages.push(crate::platform::ocr::join_words(&words));
+                words.clear();
+            }
+            current_page = Some(idx);
+        }
+        if let Some(word) = app_state.ocr_results.get(&idx).and_then(|r| r.words.get(w_idx)) {
+            words.push(word);
+        }
+    }
+    if !words.is_empty() {
+        pages.push(crate::platform::ocr::join_words(&words));
+    }
+    pages.join("\n")
+}
+
+/// トリミング後のページの表示サイズと、表示矩形のうち残す範囲 (トリミングしなければ None)
+fn cropped_display_size(
+    renderer: &dyn Renderer,
+    bmp: &TextureHandle,
+    transform: PageTransform,
+    crop: Option<&CropMargins>,
+) -> (D2D_SIZE_F, Option<D2D_RECT_F>) {
+    let (tex_w, tex_h) = renderer.get_texture_size(bmp);
+    let (w, h) = transform.display_size(tex_w, tex_h);
+    match crop.map(|c| c.visible_rect(transform)) {
+        Some(v) => (D2D_SIZE_F { width: w * (v.right - v.left), height: h * (v.bottom - v.top) }, Some(v)),
+        None => (D2D_SIZE_F { width: w, height: h }, None),
+    }
+}
+
 fn calculate_page_layout<'a>(
     renderer: &dyn Renderer,
     indices: &[usize],
-    bitmaps: &'a [(usize, TextureHandle)],
+    bitmaps: &'a GpuCache,
+    transforms: &HashMap<usize, PageTransform>,
+    crops: &HashMap<usize, CropMargins>,
     win_w: f32,
     win_h: f32,
     zoom_level: f32,
     pan_offset: (f32, f32),
-) -> (Vec<PageDrawInfo<'a>>, (f32, f32)) {
+    fit: FitMode,
+) -> (Vec<PageDrawInfo<'a>>, Vec<(usize, D2D_RECT_F)>, (f32, f32)) {
     let mut images_info = Vec::new();
     let mut total_content_w = 0.0;
     let mut max_content_h = 0.0;
     
     for &idx in indices {
-        if let Some((_, bmp)) = bitmaps.iter().find(|(i, _)| *i == idx) {
-            let (w, h) = renderer.get_texture_size(bmp);
-            let size = D2D_SIZE_F { width: w, height: h };
-            images_info.push((idx, Some((bmp, size))));
-            total_content_w += w;
-            if h > max_content_h { max_content_h = h; }
+        if let Some(bmp) = bitmaps.get(idx) {
+            // 90° / 270° 回転したページは縦横を入れ替えた大きさで、トリミングしたページは残す範囲の大きさで配置する
+            let transform = transforms.get(&idx).copied().unwrap_or_default();
+            let (size, visible) = cropped_display_size(renderer, bmp, transform, crops.get(&idx));
+            images_info.push((idx, Some((bmp, size, visible))));
+            total_content_w += size.width;
+            if size.height > max_content_h { max_content_h = size.height; }
         } else {
             // 未ロードのページも枠を確保（仮の動作）
             images_info.push((idx, None));
@@ -1733,20 +6510,24 @@ fn calculate_page_layout<'a>(
     if max_content_h == 0.0 {
         max_content_h = win_h * 0.8;
     }
+    // 1枚も読み込まれていない場合は縦長の仮の枠を確保する (プレースホルダー表示用)
+    if total_content_w == 0.0 {
+        total_content_w = max_content_h * 0.7 * indices.len() as f32;
+    }
     
     // 未ロードの画像がある場合、total_content_w を調整
     if indices.len() == 2 && images_info.iter().any(|info| info.1.is_none()) {
-        if let Some((_, Some((_, size)))) = images_info.iter().find(|info| info.1.is_some()) {
+        if let Some((_, Some((_, size, _)))) = images_info.iter().find(|info| info.1.is_some()) {
             total_content_w = size.width * 2.0;
         } else {
             total_content_w = win_w * 0.8;
         }
     }
     if total_content_w == 0.0 {
-        return (Vec::new(), (0.0, 0.0));
+        return (Vec::new(), Vec::new(), (0.0, 0.0));
     }
     
-    let scale_fit = (win_w / total_content_w).min(win_h / max_content_h).min(1.0);
+    let scale_fit = fit.scale(total_content_w, max_content_h, win_w, win_h);
     let total_scale = scale_fit * zoom_level;
 
     let draw_total_w = total_content_w * total_scale;
@@ -1757,8 +6538,9 @@ fn calculate_page_layout<'a>(
 
     let mut current_x = base_x;
     let mut result_infos = Vec::new();
+    let mut placeholders = Vec::new();
 
-    for (_idx, info) in images_info {
+    for (idx, info) in images_info {
         let w_step = if indices.len() == 2 {
             total_content_w / 2.0 * total_scale
         } else {
@@ -1767,26 +6549,254 @@ fn calculate_page_layout<'a>(
 
         let y_center = base_y + draw_max_h / 2.0;
 
-        if let Some((bmp, size)) = info {
+        if let Some((bmp, size, visible)) = info {
             let w = size.width * total_scale;
             let h = size.height * total_scale;
             let y = y_center - h / 2.0;
             // 画像を枠内で中央寄せ
             let x = current_x + (w_step - w) / 2.0;
 
-            let dest_rect = D2D_RECT_F {
+            let clip_rect = D2D_RECT_F {
                 left: x,
                 top: y,
                 right: x + w,
                 bottom: y + h,
             };
+            // トリミングしたページは削った部分まで広げた位置にページ全体を置き、残す範囲だけを見せる
+            let dest_rect = match visible {
+                Some(v) => {
+                    let (full_w, full_h) = (w / (v.right - v.left), h / (v.bottom - v.top));
+                    let (left, top) = (x - v.left * full_w, y - v.top * full_h);
+                    D2D_RECT_F { left, top, right: left + full_w, bottom: top + full_h }
+                }
+                None => clip_rect,
+            };
             result_infos.push(PageDrawInfo {
+                index: idx,
                 texture: bmp,
                 dest_rect,
+                clip_rect,
+                transform: transforms.get(&idx).copied().unwrap_or_default(),
             });
+        } else {
+            // 未ロードのページは確保した枠をそのまま返す
+            placeholders.push((idx, D2D_RECT_F {
+                left: current_x,
+                top: base_y,
+                right: current_x + w_step,
+                bottom: base_y + draw_max_h,
+            }));
         }
         current_x += w_step;
     }
     
-    (result_infos, (draw_total_w, draw_max_h))
+    (result_infos, placeholders, (draw_total_w, draw_max_h))
+}
+
+/// 表示するページが 1 枚だけで、高さ / 幅が `ratio` 以上の縦長ページか (ratio が 0 以下なら常に false)
+fn is_tall_page(
+    renderer: &dyn Renderer,
+    indices: &[usize],
+    bitmaps: &GpuCache,
+    transforms: &HashMap<usize, PageTransform>,
+    crops: &HashMap<usize, CropMargins>,
+    ratio: f32,
+) -> bool {
+    let [index] = indices else { return false; };
+    if ratio <= 0.0 {
+        return false;
+    }
+    let Some(bmp) = bitmaps.get(*index) else { return false; };
+    let transform = transforms.get(index).copied().unwrap_or_default();
+    let (size, _) = cropped_display_size(renderer, bmp, transform, crops.get(index));
+    size.width > 0.0 && size.height / size.width >= ratio
+}
+
+/// 縦スクロール表示でのページの表示サイズ (幅をウィンドウに合わせ、拡大はしない)
+fn webtoon_page_size(
+    renderer: &dyn Renderer,
+    bitmaps: &GpuCache,
+    transforms: &HashMap<usize, PageTransform>,
+    index: usize,
+    win_w: f32,
+    win_h: f32,
+    zoom_level: f32,
+) -> (f32, f32) {
+    if let Some(bmp) = bitmaps.get(index) {
+        let (tex_w, tex_h) = renderer.get_texture_size(bmp);
+        let (w, h) = transforms.get(&index).copied().unwrap_or_default().display_size(tex_w, tex_h);
+        if w > 0.0 && h > 0.0 {
+            let scale = (win_w / w).min(1.0) * zoom_level;
+            return (w * scale, h * scale);
+        }
+    }
+    // 未ロードのページは 1 画面分の縦長の枠を仮に確保する
+    ((win_h * 0.7).min(win_w) * zoom_level, win_h * zoom_level)
+}
+
+/// 縦スクロール表示のスクロール位置を整える
+/// ズーム・ドラッグによる変化をオフセットに取り込み、オフセットが現在のページの範囲に収まるよう
+/// 現在のページを進め/戻す (先頭・末尾ではそれ以上スクロールしない)。現在のページが変わったら true を返す
+fn update_webtoon_scroll(
+    app_state: &mut AppState,
+    view_state: &mut ViewState,
+    renderer: &dyn Renderer,
+    bitmaps: &GpuCache,
+    win_w: f32,
+    win_h: f32,
+) -> bool {
+    let total = app_state.image_files.len();
+    if total == 0 {
+        return false;
+    }
+    // 縦スクロール表示ではアニメーションを使わず目標値をそのまま使う
+    view_state.animation = None;
+    let zoom = view_state.zoom_level;
+    // ズーム時は画面中央を基準に拡大したのと同じ位置になるようオフセットを合わせる
+    // (カーソル位置への補正は set_zoom が pan_offset に入れた分として下で取り込まれる)
+    if (zoom - view_state.webtoon_zoom).abs() > 1e-4 {
+        let factor = zoom / view_state.webtoon_zoom;
+        view_state.webtoon_offset = (view_state.webtoon_offset + win_h / 2.0) * factor - win_h / 2.0;
+        view_state.webtoon_zoom = zoom;
+    }
+    // 縦方向のパン (ドラッグ) はスクロールとして扱う
+    view_state.webtoon_offset -= view_state.pan_offset.1;
+    view_state.pan_offset.1 = 0.0;
+
+    let height = |index: usize| {
+        webtoon_page_size(renderer, bitmaps, &app_state.page_transforms, index, win_w, win_h, zoom).1
+    };
+    let before = app_state.current_page_index.min(total - 1);
+    let mut index = before;
+    let mut offset = view_state.webtoon_offset;
+    for _ in 0..2 {
+        while offset < 0.0 && index > 0 {
+            index -= 1;
+            offset += height(index);
+        }
+        while index + 1 < total && offset >= height(index) {
+            offset -= height(index);
+            index += 1;
+        }
+        // 末尾: 最後のページの下端が画面の下端より上に来ないようにする
+        let mut remaining = -offset;
+        for i in index..total {
+            remaining += height(i);
+            if remaining >= win_h {
+                break;
+            }
+        }
+        if remaining >= win_h {
+            break;
+        }
+        offset -= win_h - remaining;
+    }
+    if index == 0 && offset < 0.0 {
+        offset = 0.0;
+    }
+
+    view_state.webtoon_offset = offset;
+    app_state.current_page_index = index;
+    if index != before {
+        app_state.scroll_direction = if index > before { 1 } else { -1 };
+        return true;
+    }
+    false
+}
+
+/// 縦スクロール表示の配置: 現在のページの上端をオフセット分だけ画面上端より上に置き、
+/// 画面の下端に届くまで以降のページを隙間なく下へ並べる
+fn calculate_webtoon_layout<'a>(
+    renderer: &dyn Renderer,
+    first: usize,
+    total: usize,
+    bitmaps: &'a GpuCache,
+    transforms: &HashMap<usize, PageTransform>,
+    win_w: f32,
+    win_h: f32,
+    zoom_level: f32,
+    pan_x: f32,
+    offset: f32,
+) -> (Vec<PageDrawInfo<'a>>, Vec<(usize, D2D_RECT_F)>, (f32, f32)) {
+    let mut result_infos = Vec::new();
+    let mut placeholders = Vec::new();
+    let mut max_w = 0.0f32;
+    let mut y = -offset;
+    let mut idx = first;
+    while idx < total && y < win_h {
+        let (w, h) = webtoon_page_size(renderer, bitmaps, transforms, idx, win_w, win_h, zoom_level);
+        let x = (win_w - w) / 2.0 + pan_x;
+        let dest_rect = D2D_RECT_F { left: x, top: y, right: x + w, bottom: y + h };
+        if let Some(bmp) = bitmaps.get(idx) {
+            result_infos.push(PageDrawInfo {
+                index: idx,
+                texture: bmp,
+                dest_rect,
+                clip_rect: dest_rect,
+                transform: transforms.get(&idx).copied().unwrap_or_default(),
+            });
+        } else {
+            placeholders.push((idx, dest_rect));
+        }
+        max_w = max_w.max(w);
+        y += h;
+        idx += 1;
+    }
+    // 縦方向はスクロールで動かすため、内容の高さは画面と同じとして扱う (縦のパンを 0 に固定する)
+    (result_infos, placeholders, (max_w, win_h))
+}
+
+/// 未ロードのページ枠に、うっすらとした枠線と回転するスピナー、ページ番号を描画する
+/// 展開の進み具合が届いていれば (アーカイブの一括展開など) その下に進捗バーを描く
+fn draw_loading_placeholder(renderer: &dyn Renderer, rect: &D2D_RECT_F, index: usize, progress: Option<u8>, ui_scale: f32) {
+    const DOTS: usize = 8;
+    renderer.fill_rectangle(rect, &D2D1_COLOR_F { r: 0.5, g: 0.5, b: 0.5, a: 0.06 });
+    renderer.draw_rectangle(rect, &D2D1_COLOR_F { r: 0.6, g: 0.6, b: 0.6, a: 0.35 }, ui_scale);
+
+    let center = ((rect.left + rect.right) / 2.0, (rect.top + rect.bottom) / 2.0);
+    let radius = 18.0 * ui_scale;
+    let dot = 3.0 * ui_scale;
+    // 約 1 秒で 1 周。先頭のドットが最も濃く、後ろほど薄くなる
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let phase = (millis / (1000 / DOTS as u128)) as usize % DOTS;
+    for i in 0..DOTS {
+        let angle = i as f32 / DOTS as f32 * std::f32::consts::TAU;
+        let (x, y) = (center.0 + radius * angle.cos(), center.1 + radius * angle.sin());
+        let age = (phase + DOTS - i) % DOTS;
+        let alpha = 0.9 - age as f32 * 0.1;
+        renderer.fill_rectangle(
+            &D2D_RECT_F { left: x - dot, top: y - dot, right: x + dot, bottom: y + dot },
+            &D2D1_COLOR_F { r: 0.8, g: 0.8, b: 0.8, a: alpha },
+        );
+    }
+
+    let label = D2D_RECT_F {
+        left: rect.left,
+        top: center.1 + radius + 12.0 * ui_scale,
+        right: rect.right,
+        bottom: center.1 + radius + 40.0 * ui_scale,
+    };
+    let text = match progress {
+        Some(pct) => format!("{} ページ 展開中… {}%", index + 1, pct),
+        None => format!("{} ページ 読み込み中…", index + 1),
+    };
+    renderer.set_text_alignment(DWRITE_TEXT_ALIGNMENT_CENTER);
+    renderer.draw_text(&text, &label, &D2D1_COLOR_F { r: 0.7, g: 0.7, b: 0.7, a: 0.8 }, false);
+    renderer.set_text_alignment(DWRITE_TEXT_ALIGNMENT_LEADING);
+
+    if let Some(pct) = progress {
+        let bar_w = (160.0 * ui_scale).min((rect.right - rect.left) * 0.8);
+        let bar = D2D_RECT_F {
+            left: center.0 - bar_w / 2.0,
+            top: label.bottom + 4.0 * ui_scale,
+            right: center.0 + bar_w / 2.0,
+            bottom: label.bottom + 8.0 * ui_scale,
+        };
+        renderer.fill_rectangle(&bar, &D2D1_COLOR_F { r: 1.0, g: 1.0, b: 1.0, a: 0.15 });
+        let filled = D2D_RECT_F { right: bar.left + bar_w * pct as f32 / 100.0, ..bar };
+        renderer.fill_rectangle(&filled, &D2D1_COLOR_F { r: 0.8, g: 0.8, b: 0.8, a: 0.8 });
+    }
 }
@@ -0,0 +1,82 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// %APPDATA% の下に作るフォルダ名
+const APP_DIR_NAME: &str = "HayateViewer";
+
+/// 実行ファイルと同じフォルダにこのファイルがあればポータブルモード (起動引数 --portable でも有効)
+pub const PORTABLE_MARKER: &str = "portable.txt";
+
+/// 保存先が変わったときに新しい保存先へ引き継ぐファイル (設定と同じフォルダに置いているもの)
+const MIGRATED_FILES: [&str; 4] = ["config.json", "config.json.bak", "positions.json", "stats.json"];
+
+/// 設定・読書位置・クラッシュ時の記録を保存するフォルダ
+pub struct DataDir {
+    pub dir: PathBuf,
+    /// 実行ファイルの隣に保存している (USB メモリなどで持ち運ぶ)
+    pub portable: bool,
+}
+
+/// 保存先のフォルダ (初回の呼び出しで決め、以前の場所にある設定を引き継ぐ)
+/// ポータブルモードなら実行ファイルのフォルダ、そうでなければ %APPDATA%\HayateViewer、
+/// どちらも使えなければ作業フォルダ (関連付けから起動すると作業フォルダが毎回変わるため最後の手段)
+pub fn data_dir() -> &'static DataDir {
+    static DIR: OnceLock<DataDir> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let exe_dir = exe_dir();
+        let portable = std::env::args().any(|a| a == "--portable")
+            || exe_dir.as_ref().is_some_and(|dir| dir.join(PORTABLE_MARKER).is_file());
+        let app_data = std::env::var_os("APPDATA").map(|base| PathBuf::from(base).join(APP_DIR_NAME));
+        let current = std::env::current_dir().ok();
+
+        let preferred = if portable { exe_dir.clone() } else { app_data.clone() };
+        let dir = preferred
+            .filter(|dir| std::fs::create_dir_all(dir).is_ok())
+            .or_else(|| current.clone())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        // 以前の保存先から引き継ぐ (旧バージョンは実行ファイルのフォルダに保存していた。
+        // 関連付けから起動すると作業フォルダは無関係な場所になり得るので後回しにする)
+        let previous = [exe_dir, current, app_data].into_iter().flatten();
+        migrate(&dir, previous);
+
+        println!("[設定] 保存先: {}{}", dir.display(), if portable { " (ポータブルモード)" } else { "" });
+        DataDir { dir, portable }
+    })
+}
+
+/// このアプリの設定として読み込めるファイルか
+fn is_settings_file(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .is_some_and(|text| serde_json::from_str::<crate::config::Settings>(&text).is_ok())
+}
+
+fn exe_dir() -> Option<PathBuf> {
+    std::env::current_exe().ok()?.parent().map(Path::to_path_buf)
+}
+
+/// 保存先に設定が無ければ、他の候補のフォルダにある設定をコピーする (元のファイルは残す)
+/// 読み込めない config.json (別のアプリのものなど) があるフォルダは候補にしない
+fn migrate(dir: &Path, previous: impl Iterator<Item = PathBuf>) {
+    if dir.join("config.json").exists() {
+        return;
+    }
+    let same_dir = |a: &Path, b: &Path| match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    };
+    let Some(source) = previous.filter(|p| !same_dir(p, dir)).find(|p| is_settings_file(&p.join("config.json"))) else {
+        return;
+    };
+    for name in MIGRATED_FILES {
+        let from = source.join(name);
+        if !from.is_file() {
+            continue;
+        }
+        match std::fs::copy(&from, dir.join(name)) {
+            Ok(_) => println!("[設定] {} を引き継ぎました: {}", name, from.display()),
+            Err(e) => println!("[設定] {} を引き継げませんでした: {}", from.display(), e),
+        }
+    }
+}
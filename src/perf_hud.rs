@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 統計に使う直近のフレーム数 (60Hz で約2秒)
+const SAMPLE_COUNT: usize = 120;
+
+/// 1フレーム分の計測値 (ミリ秒)
+#[derive(Debug, Clone, Copy)]
+struct FrameSample {
+    /// 前のフレームの Present 完了からの間隔
+    interval: f32,
+    /// テクスチャ転送 (upload_image / 段階転送) にかかった時間
+    upload: f32,
+    /// begin_draw から end_draw 直前までの描画コマンド発行時間
+    draw: f32,
+    /// end_draw (Present を含む) にかかった時間
+    present: f32,
+}
+
+/// パフォーマンス HUD 用のフレーム時間統計
+/// バックエンドごとの滑らかさを同じ条件で比べられるよう、表示中は毎フレーム再描画して計測する
+#[derive(Default)]
+pub struct FrameStats {
+    samples: VecDeque<FrameSample>,
+    last_present: Option<Instant>,
+}
+
+fn ms(duration: Duration) -> f32 {
+    duration.as_secs_f32() * 1000.0
+}
+
+impl FrameStats {
+    /// 1フレーム分の計測値を記録する (end_draw の直後に呼ぶ)
+    pub fn record(&mut self, upload: Duration, draw: Duration, present: Duration) {
+        let now = Instant::now();
+        // HUD を開いた直後や長く描画が止まっていた後の間隔は計測から外す
+        let interval = self.last_present.map(|t| now - t).filter(|d| *d < Duration::from_secs(1));
+        self.last_present = Some(now);
+        let Some(interval) = interval else { return; };
+
+        if self.samples.len() >= SAMPLE_COUNT {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(FrameSample {
+            interval: ms(interval),
+            upload: ms(upload),
+            draw: ms(draw),
+            present: ms(present),
+        });
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.last_present = None;
+    }
+
+    /// (平均, 最大)
+    fn stat(&self, value: impl Fn(&FrameSample) -> f32) -> (f32, f32) {
+        let n = self.samples.len().max(1) as f32;
+        let sum: f32 = self.samples.iter().map(&value).sum();
+        let max = self.samples.iter().map(&value).fold(0.0, f32::max);
+        (sum / n, max)
+    }
+
+    /// HUD に表示する行
+    pub fn summary_lines(&self, backend: &str) -> Vec<String> {
+        if self.samples.is_empty() {
+            return vec![format!("{} | 計測中...", backend)];
+        }
+        let (interval, interval_max) = self.stat(|s| s.interval);
+        let (draw, draw_max) = self.stat(|s| s.draw);
+        let (present, present_max) = self.stat(|s| s.present);
+        let (upload, upload_max) = self.stat(|s| s.upload);
+        // 平均間隔の 1.5 倍を超えたフレームをコマ落ちとみなす
+        let dropped = self.samples.iter().filter(|s| s.interval > interval * 1.5).count();
+        vec![
+            format!("{} | {:.1} fps ({} フレーム)", backend, 1000.0 / interval.max(0.001), self.samples.len()),
+            format!("Present 間隔: {:.2}ms (最大 {:.2}ms / 遅延 {} 回)", interval, interval_max, dropped),
+            format!("描画: {:.2}ms (最大 {:.2}ms)", draw, draw_max),
+            format!("Present: {:.2}ms (最大 {:.2}ms)", present, present_max),
+            format!("転送: {:.2}ms (最大 {:.2}ms)", upload, upload_max),
+        ]
+    }
+}
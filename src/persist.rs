@@ -0,0 +1,102 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::time::SystemTime;
+
+/// path の末尾に suffix を付けたパス (config.json -> config.json.bak)
+pub fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// 一時ファイルに書き出してから置き換える (書き込み途中で落ちても元のファイルは壊れない)
+/// 置き換える前の内容は .bak に 1 世代だけ残す
+pub fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let tmp = sibling_path(path, ".tmp");
+    {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+    }
+    if path.exists() {
+        if let Err(e) = fs::copy(path, sibling_path(path, ".bak")) {
+            println!("[保存] バックアップを作成できませんでした ({}): {}", path.display(), e);
+        }
+    }
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
+#[derive(Default)]
+struct WriterState {
+    /// 書き込み待ちの内容 (同じパスへの要求は最新の内容だけを残す)
+    pending: BTreeMap<PathBuf, String>,
+    /// 書き込み中か
+    busy: bool,
+    /// パス -> 自分で最後に書き込んだときの更新日時
+    written: HashMap<PathBuf, SystemTime>,
+}
+
+/// config.json / positions.json を書き込むスレッド (ページ送りやソースの切り替えをディスク I/O で止めない)
+struct Writer {
+    state: Arc<(Mutex<WriterState>, Condvar)>,
+}
+
+fn writer() -> &'static Writer {
+    static WRITER: OnceLock<Writer> = OnceLock::new();
+    WRITER.get_or_init(|| {
+        let state = Arc::new((Mutex::new(WriterState::default()), Condvar::new()));
+        let shared = state.clone();
+        std::thread::spawn(move || {
+            let (lock, cvar) = &*shared;
+            loop {
+                let (path, content) = {
+                    let mut state = lock.lock().unwrap();
+                    while state.pending.is_empty() {
+                        state = cvar.wait(state).unwrap();
+                    }
+                    state.busy = true;
+                    state.pending.pop_first().unwrap()
+                };
+                let result = write_atomic(&path, &content);
+                let mtime = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                if let Err(e) = result {
+                    println!("[保存] {} を保存できませんでした: {}", path.display(), e);
+                }
+                let mut state = lock.lock().unwrap();
+                if let Some(mtime) = mtime {
+                    state.written.insert(path, mtime);
+                }
+                state.busy = false;
+                cvar.notify_all();
+            }
+        });
+        Writer { state }
+    })
+}
+
+/// content を path へ書き込むよう書き込みスレッドに頼む (すぐに戻る)
+pub fn write_in_background(path: PathBuf, content: String) {
+    let (lock, cvar) = &*writer().state;
+    lock.lock().unwrap().pending.insert(path, content);
+    cvar.notify_all();
+}
+
+/// 頼んだ書き込みがすべて終わるまで待つ (終了時)
+pub fn wait_idle() {
+    let (lock, cvar) = &*writer().state;
+    let mut state = lock.lock().unwrap();
+    while !state.pending.is_empty() || state.busy {
+        state = cvar.wait(state).unwrap();
+    }
+}
+
+/// 書き込みスレッドが最後に path へ書き込んだときの更新日時 (自分の書き込みを外部の変更と区別する)
+pub fn last_written_mtime(path: &Path) -> Option<SystemTime> {
+    let (lock, _) = &*writer().state;
+    lock.lock().unwrap().written.get(path).copied()
+}
@@ -0,0 +1,174 @@
+use windows::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+use windows::Win32::System::Registry::{
+    HKEY, HKEY_CLASSES_ROOT, HKEY_CURRENT_USER, REG_NONE, REG_SZ, RRF_RT_REG_SZ, RegDeleteKeyValueW, RegDeleteTreeW,
+    RegGetValueW, RegSetKeyValueW,
+};
+use windows::Win32::UI::Shell::{SHCNE_ASSOCCHANGED, SHCNF_IDLIST, SHChangeNotify};
+use windows::core::{HSTRING, PCWSTR};
+
+/// HKCU\Software\Classes (管理者権限なしで現在のユーザーにだけ登録する)
+const CLASSES: &str = "Software\\Classes";
+const ARCHIVE_PROG_ID: &str = "HayateViewer.Archive";
+const IMAGE_PROG_ID: &str = "HayateViewer.Image";
+const PDF_PROG_ID: &str = "HayateViewer.Pdf";
+/// 右クリックメニューの項目のキー名
+const VERB: &str = "HayateViewer";
+const VERB_LABEL: &str = "HayateViewer で開く";
+
+/// 実行ファイルのパスと、「プログラムから開く」用のキー (Applications\<実行ファイル名>)
+fn exe_path() -> Result<(String, String), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("実行ファイルのパスを取得できませんでした: {}", e))?;
+    let name = exe.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    Ok((exe.to_string_lossy().to_string(), format!("Applications\\{}", name)))
+}
+
+/// 文字列値を書き込む (キーが無ければ作る。name が None なら既定値)
+fn set_string(subkey: &str, name: Option<&str>, value: &str) -> Result<(), String> {
+    let data: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+    let name = name.map(HSTRING::from);
+    let name_ptr = name.as_ref().map_or(PCWSTR::null(), |n| PCWSTR(n.as_ptr()));
+    let path = format!("{}\\{}", CLASSES, subkey);
+    unsafe {
+        RegSetKeyValueW(
+            HKEY_CURRENT_USER,
+            &HSTRING::from(path.as_str()),
+            name_ptr,
+            REG_SZ.0,
+            Some(data.as_ptr() as *const _),
+            (data.len() * 2) as u32,
+        )
+    }
+    .ok()
+    .map_err(|e| format!("{} に書き込めませんでした: {}", path, e))
+}
+
+/// 値を持たない名前だけの値を書き込む (OpenWithProgids の登録用)
+fn set_empty(subkey: &str, name: &str) -> Result<(), String> {
+    let path = format!("{}\\{}", CLASSES, subkey);
+    unsafe { RegSetKeyValueW(HKEY_CURRENT_USER, &HSTRING::from(path.as_str()), &HSTRING::from(name), REG_NONE.0, None, 0) }
+        .ok()
+        .map_err(|e| format!("{} に書き込めませんでした: {}", path, e))
+}
+
+/// キーの既定値 (無い・空なら None)
+fn read_default(root: HKEY, subkey: &str) -> Option<String> {
+    let path = HSTRING::from(subkey);
+    let mut size = 0u32;
+    unsafe { RegGetValueW(root, &path, PCWSTR::null(), RRF_RT_REG_SZ, None, None, Some(&mut size)) }.ok().ok()?;
+    let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+    unsafe {
+        RegGetValueW(root, &path, PCWSTR::null(), RRF_RT_REG_SZ, None, Some(buffer.as_mut_ptr() as *mut _), Some(&mut size))
+    }
+    .ok()
+    .ok()?;
+    let value = String::from_utf16_lossy(&buffer);
+    let value = value.trim_end_matches('\0');
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// キーを配下ごと削除する (無ければ何もしない)
+fn delete_tree(subkey: &str) -> Result<(), String> {
+    let path = format!("{}\\{}", CLASSES, subkey);
+    let result = unsafe { RegDeleteTreeW(HKEY_CURRENT_USER, &HSTRING::from(path.as_str())) };
+    if result == ERROR_FILE_NOT_FOUND {
+        return Ok(());
+    }
+    result.ok().map_err(|e| format!("{} を削除できませんでした: {}", path, e))
+}
+
+/// 値を削除する (無ければ何もしない。name が None なら既定値)
+fn delete_value(subkey: &str, name: Option<&str>) -> Result<(), String> {
+    let path = format!("{}\\{}", CLASSES, subkey);
+    let name = name.map(HSTRING::from);
+    let name_ptr = name.as_ref().map_or(PCWSTR::null(), |n| PCWSTR(n.as_ptr()));
+    let result = unsafe { RegDeleteKeyValueW(HKEY_CURRENT_USER, &HSTRING::from(path.as_str()), name_ptr) };
+    if result == ERROR_FILE_NOT_FOUND {
+        return Ok(());
+    }
+    result.ok().map_err(|e| format!("{} の値を削除できませんでした: {}", path, e))
+}
+
+/// 登録する拡張子と ProgID の組 (拡張子は image モジュールの一覧を使う)
+fn extensions() -> impl Iterator<Item = (&'static str, &'static str)> {
+    crate::image::ARCHIVE_EXTENSIONS
+        .iter()
+        .map(|ext| (*ext, ARCHIVE_PROG_ID))
+        .chain(crate::image::IMAGE_EXTENSIONS.iter().map(|ext| (*ext, IMAGE_PROG_ID)))
+        .chain(std::iter::once((crate::image::PDF_EXTENSION, PDF_PROG_ID)))
+}
+
+/// エクスプローラーに関連付けの変更を通知する
+fn notify_shell() {
+    unsafe { SHChangeNotify(SHCNE_ASSOCCHANGED, SHCNF_IDLIST, None, None) };
+}
+
+/// 関連付けを登録する (--register)
+/// - ProgID (HayateViewer.Archive / HayateViewer.Image / HayateViewer.Pdf) と「プログラムから開く」の候補
+/// - 既定のアプリが決まっていない拡張子だけ既定の ProgID にする (他のアプリの関連付けは奪わない)
+/// - 対象の拡張子とフォルダの右クリックメニューに「HayateViewer で開く」
+pub fn register() -> Result<(), String> {
+    let (exe, app) = exe_path()?;
+    let command = format!("\"{}\" \"%1\"", exe);
+    let icon = format!("\"{}\",0", exe);
+
+    for (prog_id, label) in [
+        (ARCHIVE_PROG_ID, "HayateViewer アーカイブ"),
+        (IMAGE_PROG_ID, "HayateViewer 画像"),
+        (PDF_PROG_ID, "HayateViewer PDF"),
+    ] {
+        set_string(prog_id, None, label)?;
+        set_string(&format!("{}\\DefaultIcon", prog_id), None, &icon)?;
+        set_string(&format!("{}\\shell\\open\\command", prog_id), None, &command)?;
+    }
+
+    let app = app.as_str();
+    set_string(app, Some("FriendlyAppName"), "HayateViewer")?;
+    set_string(&format!("{}\\DefaultIcon", app), None, &icon)?;
+    set_string(&format!("{}\\shell\\open\\command", app), None, &command)?;
+
+    for (ext, prog_id) in extensions() {
+        let ext_key = format!(".{}", ext);
+        set_empty(&format!("{}\\OpenWithProgids", ext_key), prog_id)?;
+        set_empty(&format!("{}\\SupportedTypes", app), &ext_key)?;
+        if read_default(HKEY_CLASSES_ROOT, &ext_key).is_none() {
+            set_string(&ext_key, None, prog_id)?;
+            println!("[関連付け] .{} の既定のアプリに設定しました", ext);
+        }
+        let verb = format!("SystemFileAssociations\\{}\\shell\\{}", ext_key, VERB);
+        set_string(&verb, None, VERB_LABEL)?;
+        set_string(&verb, Some("Icon"), &icon)?;
+        set_string(&format!("{}\\command", verb), None, &command)?;
+    }
+
+    let folder_verb = format!("Directory\\shell\\{}", VERB);
+    set_string(&folder_verb, None, VERB_LABEL)?;
+    set_string(&folder_verb, Some("Icon"), &icon)?;
+    set_string(&format!("{}\\command", folder_verb), None, &command)?;
+
+    notify_shell();
+    println!("[関連付け] 登録しました: {}", exe);
+    Ok(())
+}
+
+/// register で書き込んだ項目を取り除く (--unregister)
+pub fn unregister() -> Result<(), String> {
+    let (_, app) = exe_path()?;
+    for (ext, prog_id) in extensions() {
+        let ext_key = format!(".{}", ext);
+        delete_value(&format!("{}\\OpenWithProgids", ext_key), Some(prog_id))?;
+        let user_default = read_default(HKEY_CURRENT_USER, &format!("{}\\{}", CLASSES, ext_key));
+        if user_default.as_deref() == Some(prog_id) {
+            delete_value(&ext_key, None)?;
+        }
+        delete_tree(&format!("SystemFileAssociations\\{}\\shell\\{}", ext_key, VERB))?;
+    }
+    delete_tree(&format!("Directory\\shell\\{}", VERB))?;
+    delete_tree(&app)?;
+    delete_tree(ARCHIVE_PROG_ID)?;
+    delete_tree(IMAGE_PROG_ID)?;
+    delete_tree(PDF_PROG_ID)?;
+
+    notify_shell();
+    println!("[関連付け] 登録を解除しました");
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use windows::Win32::Foundation::{HANDLE, HGLOBAL, HWND};
+use windows::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, GetClipboardData, GetClipboardSequenceNumber, IsClipboardFormatAvailable,
+    OpenClipboard, RegisterClipboardFormatW, SetClipboardData,
+};
+use windows::Win32::System::Memory::{GMEM_MOVEABLE, GlobalAlloc, GlobalLock, GlobalSize, GlobalUnlock};
+use windows::Win32::System::Ole::{CF_DIB, CF_UNICODETEXT};
+use windows::core::w;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// 自分で画像をコピーしたときのクリップボードの番号 (クリップボード監視が自分のコピーを取り込まないように)
+static OWN_SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+/// グローバルメモリにバイト列をコピーしてクリップボードへ登録する
+/// 呼び出し側で OpenClipboard / EmptyClipboard 済みであること
+unsafe fn set_clipboard_bytes(format: u32, bytes: &[u8]) -> windows::core::Result<()> {
+    unsafe {
+        let hmem = GlobalAlloc(GMEM_MOVEABLE, bytes.len())?;
+        let ptr = GlobalLock(hmem);
+        if ptr.is_null() {
+            return Err(windows::core::Error::from_thread());
+        }
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr as *mut u8, bytes.len());
+        let _ = GlobalUnlock(hmem);
+        // 成功した場合、メモリの所有権はシステムに移る
+        SetClipboardData(format, Some(HANDLE(hmem.0)))?;
+        Ok(())
+    }
+}
+
+/// テキストをクリップボードにコピーする
+pub fn set_text(owner: HWND, text: &str) -> windows::core::Result<()> {
+    let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    let bytes: Vec<u8> = wide.iter().flat_map(|c| c.to_le_bytes()).collect();
+    unsafe {
+        OpenClipboard(Some(owner))?;
+        let res = EmptyClipboard().and_then(|_| set_clipboard_bytes(CF_UNICODETEXT.0 as u32, &bytes));
+        let _ = CloseClipboard();
+        res
+    }
+}
+
+/// RGBA の画像を CF_DIB の形式 (BITMAPINFOHEADER + 32bit BGRA、下の行から) にする
+fn rgba_to_dib(image: &::image::RgbaImage) -> Vec<u8> {
+    let (width, height) = image.dimensions();
+    let image_size = width * height * 4;
+    let mut dib = Vec::with_capacity(40 + image_size as usize);
+    dib.extend_from_slice(&40u32.to_le_bytes()); // biSize
+    dib.extend_from_slice(&(width as i32).to_le_bytes());
+    dib.extend_from_slice(&(height as i32).to_le_bytes()); // 正の値 = 下の行から
+    dib.extend_from_slice(&1u16.to_le_bytes()); // biPlanes
+    dib.extend_from_slice(&32u16.to_le_bytes()); // biBitCount
+    dib.extend_from_slice(&0u32.to_le_bytes()); // biCompression = BI_RGB
+    dib.extend_from_slice(&image_size.to_le_bytes());
+    dib.extend_from_slice(&[0u8; 16]); // 解像度・パレット (未使用)
+    for row in image.rows().rev() {
+        for px in row {
+            dib.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+        }
+    }
+    dib
+}
+
+/// 画像を "PNG" 形式と CF_DIB でクリップボードにコピーする
+/// PNG に対応する貼り付け先 (チャット・画像編集ソフト) は透過を保ったまま、それ以外は DIB で受け取れる
+pub fn set_image(owner: HWND, image: &::image::RgbaImage) -> Result<(), Box<dyn std::error::Error>> {
+    let mut png = std::io::Cursor::new(Vec::new());
+    image.write_to(&mut png, ::image::ImageFormat::Png)?;
+    let dib = rgba_to_dib(image);
+    unsafe {
+        OpenClipboard(Some(owner))?;
+        let png_format = RegisterClipboardFormatW(w!("PNG"));
+        let res = EmptyClipboard()
+            .and_then(|_| set_clipboard_bytes(CF_DIB.0 as u32, &dib))
+            .and_then(|_| if png_format != 0 { set_clipboard_bytes(png_format, png.get_ref()) } else { Ok(()) });
+        let _ = CloseClipboard();
+        OWN_SEQUENCE.store(GetClipboardSequenceNumber(), Ordering::Relaxed);
+        res?;
+    }
+    Ok(())
+}
+
+/// sequence が自分で画像をコピーしたときの番号か
+pub fn is_own_copy(sequence: u32) -> bool {
+    sequence != 0 && OWN_SEQUENCE.load(Ordering::Relaxed) == sequence
+}
+
+/// クリップボードの内容が変わるたびに増える番号 (変化の検出用)
+pub fn sequence_number() -> u32 {
+    unsafe { GetClipboardSequenceNumber() }
+}
+
+/// クリップボードのデータをバイト列として取り出す (呼び出し側で OpenClipboard 済みであること)
+unsafe fn clipboard_bytes(format: u32) -> Option<Vec<u8>> {
+    unsafe {
+        IsClipboardFormatAvailable(format).ok()?;
+        let handle = GetClipboardData(format).ok()?;
+        let hmem = HGLOBAL(handle.0);
+        let size = GlobalSize(hmem);
+        let ptr = GlobalLock(hmem);
+        if ptr.is_null() || size == 0 {
+            return None;
+        }
+        let bytes = std::slice::from_raw_parts(ptr as *const u8, size).to_vec();
+        let _ = GlobalUnlock(hmem);
+        Some(bytes)
+    }
+}
+
+/// クリップボードの画像を PNG のバイト列で取得する (画像が無ければ None)
+/// スクリーンショットツール等が登録する "PNG" 形式を優先し、無ければ CF_DIB を PNG に変換する
+pub fn get_image_png() -> Option<Vec<u8>> {
+    let (png, dib) = unsafe {
+        OpenClipboard(None).ok()?;
+        let png_format = RegisterClipboardFormatW(w!("PNG"));
+        let png = if png_format != 0 { clipboard_bytes(png_format) } else { None };
+        let dib = if png.is_none() { clipboard_bytes(CF_DIB.0 as u32) } else { None };
+        let _ = CloseClipboard();
+        (png, dib)
+    };
+    if png.is_some() {
+        return png;
+    }
+
+    let decoder = ::image::codecs::bmp::BmpDecoder::new_without_file_header(std::io::Cursor::new(dib?)).ok()?;
+    let image = ::image::DynamicImage::from_decoder(decoder).ok()?;
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    match image.write_to(&mut encoded, ::image::ImageFormat::Png) {
+        Ok(_) => Some(encoded.into_inner()),
+        Err(e) => {
+            println!("[クリップボード] 画像を変換できませんでした: {}", e);
+            None
+        }
+    }
+}
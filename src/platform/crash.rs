@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use std::os::windows::io::AsRawHandle;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::System::Diagnostics::Debug::{
+    EXCEPTION_POINTERS, MINIDUMP_EXCEPTION_INFORMATION, MiniDumpNormal, MiniDumpWithThreadInfo,
+    MiniDumpWriteDump, SetUnhandledExceptionFilter,
+};
+use windows::Win32::System::Threading::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId};
+
+const RECOVERY_FILE: &str = "crash_recovery.json";
+const LOG_FILE: &str = "crash.log";
+const DUMP_DIR: &str = "crash_dumps";
+const EXCEPTION_CONTINUE_SEARCH: i32 = 0;
+
+/// 設定と同じ保存先のファイル (関連付けから起動しても同じ場所に残す)
+fn data_path(name: &str) -> PathBuf {
+    crate::paths::data_dir().dir.join(name)
+}
+
+/// クラッシュ時に保存する読書位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryInfo {
+    pub path: String,
+    pub page: usize,
+    pub binding: String, // "left", "right", "single", "webtoon"
+    pub message: String,
+}
+
+/// 現在の読書位置 (パス, ページ, 綴じ方向)。クラッシュハンドラから参照する
+static READING_POSITION: Mutex<Option<(String, usize, String)>> = Mutex::new(None);
+
+/// 現在の読書位置を記録する (描画のたびに呼ばれるため、変化が無ければ何もしない)
+pub fn set_reading_position(path: &str, page: usize, binding: &str) {
+    if path.is_empty() {
+        return;
+    }
+    if let Ok(mut pos) = READING_POSITION.lock() {
+        let changed = match pos.as_ref() {
+            Some((p, pg, b)) => p != path || *pg != page || b != binding,
+            None => true,
+        };
+        if changed {
+            *pos = Some((path.to_string(), page, binding.to_string()));
+        }
+    }
+}
+
+fn write_recovery(message: &str) {
+    // パニック中にロックが汚染されていても位置は読み出す
+    let pos = match READING_POSITION.lock() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    let Some((path, page, binding)) = pos else { return; };
+    let info = RecoveryInfo { path, page, binding, message: message.to_string() };
+    if let Ok(content) = serde_json::to_string_pretty(&info) {
+        let _ = std::fs::write(data_path(RECOVERY_FILE), content);
+    }
+}
+
+fn write_minidump(exception: Option<*const EXCEPTION_POINTERS>) -> Option<PathBuf> {
+    let _ = std::fs::create_dir_all(data_path(DUMP_DIR));
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = data_path(DUMP_DIR).join(format!("hayate_{}.dmp", timestamp));
+    let file = std::fs::File::create(&path).ok()?;
+
+    unsafe {
+        let info = exception.map(|ptr| MINIDUMP_EXCEPTION_INFORMATION {
+            ThreadId: GetCurrentThreadId(),
+            ExceptionPointers: ptr as *mut _,
+            ClientPointers: windows::core::BOOL(0),
+        });
+        MiniDumpWriteDump(
+            GetCurrentProcess(),
+            GetCurrentProcessId(),
+            HANDLE(file.as_raw_handle()),
+            MiniDumpNormal | MiniDumpWithThreadInfo,
+            info.as_ref().map(|i| i as *const _),
+            None,
+            None,
+        )
+        .ok()?;
+    }
+    Some(path)
+}
+
+/// パニック内容を crash.log に追記し、そのフルパスを返す
+fn append_crash_log(message: &str, dump: Option<&PathBuf>) -> PathBuf {
+    use std::io::Write;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let backtrace = std::backtrace::Backtrace::force_capture();
+    if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(data_path(LOG_FILE)) {
+        let _ = writeln!(file, "==== {} (v{}) ====", timestamp, env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(file, "{}", message);
+        if let Some(dump) = dump {
+            let _ = writeln!(file, "minidump: {}", dump.display());
+        }
+        let _ = writeln!(file, "{}\n", backtrace);
+    }
+    data_path(LOG_FILE)
+}
+
+/// リリースビルドは windows サブシステムでコンソールが無いため、パニックをメッセージボックスで知らせる
+fn show_panic_message_box(message: &str, log_path: &PathBuf) {
+    use windows::Win32::UI::WindowsAndMessaging::{MB_ICONERROR, MB_OK, MB_SETFOREGROUND, MessageBoxW};
+    use windows::core::HSTRING;
+
+    let text = format!(
+        "予期しないエラーが発生したため、HayateViewer を終了します。\n\n{}\n\n詳細はログファイルを確認してください:\n{}",
+        message,
+        log_path.display()
+    );
+    unsafe {
+        MessageBoxW(None, &HSTRING::from(text), &HSTRING::from("HayateViewer - エラー"), MB_OK | MB_ICONERROR | MB_SETFOREGROUND);
+    }
+}
+
+unsafe extern "system" fn unhandled_exception_filter(exception: *const EXCEPTION_POINTERS) -> i32 {
+    let code = unsafe { exception.as_ref().and_then(|e| e.ExceptionRecord.as_ref()).map(|r| r.ExceptionCode.0).unwrap_or(0) };
+    write_recovery(&format!("未処理の例外 (0x{:08X})", code as u32));
+    let _ = write_minidump(Some(exception));
+    EXCEPTION_CONTINUE_SEARCH
+}
+
+/// パニックフックと SEH の未処理例外フィルタを登録する
+/// どちらもミニダンプと読書位置を書き出し、次回起動時の復元に使う
+/// リリースビルドのメインスレッドのパニックは crash.log に記録し、メッセージボックスで通知して終了する
+/// ワーカースレッドのパニック (デコーダーなど) は crash.log に記録するだけにし、呼び出し側の catch_unwind などに任せる
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info.to_string();
+        if std::thread::current().name() != Some("main") {
+            append_crash_log(&message, None);
+            default_hook(info);
+            return;
+        }
+        write_recovery(&message);
+        let dump = write_minidump(None);
+        if let Some(ref path) = dump {
+            eprintln!("[クラッシュ] ミニダンプを保存しました: {}", path.display());
+        }
+        let log_path = append_crash_log(&message, dump.as_ref());
+        default_hook(info);
+
+        // UI スレッドは中途半端な状態で動き続けないよう、通知後にプロセスを終了する
+        if cfg!(not(debug_assertions)) {
+            show_panic_message_box(&message, &log_path);
+            std::process::exit(1);
+        }
+    }));
+
+    unsafe {
+        SetUnhandledExceptionFilter(Some(unhandled_exception_filter));
+    }
+}
+
+/// 前回クラッシュ時の読書位置を取り出す (取り出したファイルは削除する)
+pub fn take_recovery() -> Option<RecoveryInfo> {
+    let content = std::fs::read_to_string(data_path(RECOVERY_FILE)).ok()?;
+    let _ = std::fs::remove_file(data_path(RECOVERY_FILE));
+    serde_json::from_str(&content).ok()
+}
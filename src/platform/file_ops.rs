@@ -0,0 +1,71 @@
+use std::path::Path;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::System::Com::{CLSCTX_ALL, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx};
+use windows::Win32::UI::Shell::{
+    FILEOPERATION_FLAGS, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOCONFIRMMKDIR, FOF_SILENT, FOF_WANTNUKEWARNING,
+    FOFX_EARLYFAILURE, FOFX_RECYCLEONDELETE, FileOperation, IFileOperation, IShellItem, SHCreateItemFromParsingName,
+};
+use windows::core::HSTRING;
+
+fn shell_item(path: &Path) -> windows::core::Result<IShellItem> {
+    unsafe { SHCreateItemFromParsingName(&HSTRING::from(path.to_string_lossy().as_ref()), None) }
+}
+
+/// IFileOperation に操作を 1 つ登録して実行する
+/// エクスプローラーと同じ処理のため、別ドライブへの移動や Explorer の「元に戻す」にも対応する
+fn perform(
+    owner: HWND,
+    flags: FILEOPERATION_FLAGS,
+    queue: impl FnOnce(&IFileOperation) -> windows::core::Result<()>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+        let operation: IFileOperation = CoCreateInstance(&FileOperation, None, CLSCTX_ALL)?;
+        operation.SetOperationFlags(flags)?;
+        operation.SetOwnerWindow(owner)?;
+        queue(&operation)?;
+        operation.PerformOperations()?;
+        if operation.GetAnyOperationsAborted()?.as_bool() {
+            return Err("ファイル操作が中止されました".into());
+        }
+        Ok(())
+    }
+}
+
+/// ファイル (またはアーカイブ・フォルダ) をごみ箱へ移動する
+/// (確認はアプリ側で行うのでシェルの確認ダイアログは出さない。ごみ箱に入らない場合のみ警告が出る)
+pub fn move_to_recycle_bin(owner: HWND, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let flags = FOF_ALLOWUNDO | FOFX_RECYCLEONDELETE | FOF_NOCONFIRMATION | FOF_WANTNUKEWARNING | FOF_SILENT | FOFX_EARLYFAILURE;
+    perform(owner, flags, |op| unsafe { op.DeleteItem(&shell_item(path)?, None) })
+}
+
+/// ファイルを `destination` (フォルダとファイル名) へ移動する。同名のファイルがあれば失敗する
+pub fn move_file(owner: HWND, from: &Path, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    transfer(owner, from, destination, false)
+}
+
+/// ファイルを `destination` (フォルダとファイル名) へコピーする。同名のファイルがあれば失敗する
+pub fn copy_file(owner: HWND, from: &Path, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    transfer(owner, from, destination, true)
+}
+
+fn transfer(owner: HWND, from: &Path, destination: &Path, copy: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let folder = destination.parent().ok_or("移動先のフォルダがありません")?;
+    let name = destination.file_name().ok_or("移動先のファイル名がありません")?;
+    if destination.exists() {
+        return Err(format!("同名のファイルがあります: {}", destination.display()).into());
+    }
+    std::fs::create_dir_all(folder)?;
+    let name = HSTRING::from(name.to_string_lossy().as_ref());
+    let flags = FOF_ALLOWUNDO | FOF_NOCONFIRMMKDIR | FOF_SILENT | FOFX_EARLYFAILURE;
+    perform(owner, flags, |op| unsafe {
+        let item = shell_item(from)?;
+        let folder = shell_item(folder)?;
+        if copy {
+            op.CopyItem(&item, &folder, &name, None)
+        } else {
+            op.MoveItem(&item, &folder, &name, None)
+        }
+    })
+}
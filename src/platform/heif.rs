@@ -0,0 +1,57 @@
+use crate::image::cache::{DecodedImage, PixelData};
+use windows::Win32::Graphics::Imaging::{
+    CLSID_WICImagingFactory, GUID_WICPixelFormat32bppRGBA, IWICImagingFactory, WICConvertBitmapSource,
+    WICDecodeMetadataCacheOnDemand,
+};
+use windows::Win32::System::Com::{CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, CoCreateInstance, CoInitializeEx};
+use windows::Win32::UI::Shell::SHCreateMemStream;
+
+/// HEIF 系の major brand (AVIF は image クレートでデコードするため含めない)
+const HEIF_BRANDS: [&[u8; 4]; 8] = [b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"hevm", b"hevs"];
+
+/// ISO BMFF の ftyp ボックスから HEIC/HEIF かを判定する
+pub fn is_heif(data: &[u8]) -> bool {
+    if data.len() < 16 || &data[4..8] != b"ftyp" {
+        return false;
+    }
+    let major = &data[8..12];
+    if HEIF_BRANDS.iter().any(|b| major == *b) {
+        return true;
+    }
+    // 汎用ブランド (mif1 / msf1) の場合は互換ブランドに heic が含まれるかで判断する
+    if major == b"mif1" || major == b"msf1" {
+        let box_len = (u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize).min(data.len());
+        return data[16..box_len.max(16)].chunks_exact(4).any(|b| b == b"heic" || b == b"heix");
+    }
+    false
+}
+
+/// Windows Imaging Component で HEIC/HEIF をデコードする
+/// HEVC のデコードには「HEIF 画像拡張機能」「HEVC ビデオ拡張機能」(Microsoft Store) が必要
+pub fn decode_heif(data: &[u8]) -> Result<DecodedImage, Box<dyn std::error::Error>> {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+
+        let factory: IWICImagingFactory = CoCreateInstance(&CLSID_WICImagingFactory, None, CLSCTX_INPROC_SERVER)?;
+        let stream = SHCreateMemStream(Some(data)).ok_or("メモリストリームを作成できません")?;
+        let decoder = factory
+            .CreateDecoderFromStream(&stream, std::ptr::null(), WICDecodeMetadataCacheOnDemand)
+            .map_err(|e| format!("HEIF デコーダーがありません (HEIF 画像拡張機能を確認してください): {}", e))?;
+        let frame = decoder.GetFrame(0)?;
+        let converted = WICConvertBitmapSource(&GUID_WICPixelFormat32bppRGBA, &frame)?;
+
+        let (mut width, mut height) = (0u32, 0u32);
+        converted.GetSize(&mut width, &mut height)?;
+        let stride = width * 4;
+        let mut pixels = vec![0u8; (stride * height) as usize];
+        converted.CopyPixels(std::ptr::null(), stride, &mut pixels)?;
+
+        Ok(DecodedImage {
+            width,
+            height,
+            pixel_data: PixelData::Rgba8(pixels),
+            rotation: 0,
+            metadata: Default::default(),
+        })
+    }
+}
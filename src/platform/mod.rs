@@ -0,0 +1,13 @@
+// Windows 固有の OS 連携機能 (クリップボード・HEIF デコード・PDF 描画・OCR・共有・クラッシュ処理・更新確認・時刻取得・電源管理・ファイル操作・関連付け・暗号化など)
+pub mod clipboard;
+pub mod heif;
+pub mod ocr;
+pub mod share;
+pub mod crash;
+pub mod update;
+pub mod night_light;
+pub mod power;
+pub mod file_ops;
+pub mod pdf;
+pub mod association;
+pub mod protect;
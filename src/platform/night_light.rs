@@ -0,0 +1,29 @@
+use windows::Win32::System::SystemInformation::GetLocalTime;
+
+/// "HH:MM" を 0 時からの経過分に変換する
+pub fn parse_hhmm(text: &str) -> Option<u32> {
+    let (h, m) = text.trim().split_once(':')?;
+    let h: u32 = h.trim().parse().ok()?;
+    let m: u32 = m.trim().parse().ok()?;
+    (h < 24 && m < 60).then_some(h * 60 + m)
+}
+
+/// 現在のローカル時刻 (0 時からの経過分)
+pub fn local_minutes() -> u32 {
+    let now = unsafe { GetLocalTime() };
+    now.wHour as u32 * 60 + now.wMinute as u32
+}
+
+/// 現在時刻が start〜end の範囲内か (19:00〜07:00 のように日付を跨ぐ範囲にも対応)
+/// 時刻が解釈できない場合は常に有効とみなす
+pub fn is_active_now(start: &str, end: &str) -> bool {
+    let (Some(start), Some(end)) = (parse_hhmm(start), parse_hhmm(end)) else {
+        return true;
+    };
+    let now = local_minutes();
+    if start <= end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    }
+}
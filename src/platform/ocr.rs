@@ -0,0 +1,129 @@
+use crate::image::cache::{DecodedImage, PixelData};
+use windows::Globalization::Language;
+use windows::Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap};
+use windows::Media::Ocr::OcrEngine;
+use windows::Storage::Streams::DataWriter;
+use windows::Win32::System::Com::{COINIT_MULTITHREADED, CoInitializeEx};
+use windows::core::HSTRING;
+
+/// OCR で認識された単語 (座標は元画像のピクセル単位)
+#[derive(Debug, Clone)]
+pub struct OcrWord {
+    pub text: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// 所属する行番号 (コピー時の改行位置に使用)
+    pub line: usize,
+}
+
+/// 1ページ分の OCR 結果
+#[derive(Debug, Clone)]
+pub struct OcrPageResult {
+    pub words: Vec<OcrWord>,
+    pub image_width: u32,
+    pub image_height: u32,
+}
+
+fn create_engine() -> windows::core::Result<OcrEngine> {
+    // 日本語エンジンは英字も認識できるため優先し、無ければユーザーの言語設定に従う
+    if let Ok(lang) = Language::CreateLanguage(&HSTRING::from("ja")) {
+        if OcrEngine::IsLanguageSupported(&lang).unwrap_or(false) {
+            if let Ok(engine) = OcrEngine::TryCreateFromLanguage(&lang) {
+                return Ok(engine);
+            }
+        }
+    }
+    OcrEngine::TryCreateFromUserProfileLanguages()
+}
+
+/// Windows.Media.Ocr でページ画像を認識する (ブロッキング処理のためワーカースレッドから呼ぶこと)
+pub fn recognize(image: &DecodedImage) -> Result<OcrPageResult, Box<dyn std::error::Error>> {
+    let PixelData::Rgba8(ref data) = image.pixel_data else {
+        return Err("OCR は RGBA 画像のみ対応しています (CPU色変換を有効にしてください)".into());
+    };
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    }
+
+    // エンジンの最大サイズを超える場合は縮小して認識し、座標を元の倍率に戻す
+    let max_dim = OcrEngine::MaxImageDimension().unwrap_or(4096);
+    let (mut w, mut h) = (image.width, image.height);
+    let mut scale = 1.0f32;
+    let resized;
+    let pixels: &[u8] = if w > max_dim || h > max_dim {
+        scale = max_dim as f32 / w.max(h) as f32;
+        let src = ::image::RgbaImage::from_raw(w, h, data.clone()).ok_or("invalid image buffer")?;
+        w = ((w as f32 * scale) as u32).max(1);
+        h = ((h as f32 * scale) as u32).max(1);
+        resized = ::image::imageops::resize(&src, w, h, ::image::imageops::FilterType::Triangle);
+        resized.as_raw()
+    } else {
+        data
+    };
+
+    let writer = DataWriter::new()?;
+    writer.WriteBytes(pixels)?;
+    let buffer = writer.DetachBuffer()?;
+    let bitmap = SoftwareBitmap::CreateCopyFromBuffer(&buffer, BitmapPixelFormat::Rgba8, w as i32, h as i32)?;
+    let bitmap = SoftwareBitmap::Convert(&bitmap, BitmapPixelFormat::Bgra8)?;
+
+    let engine = create_engine()?;
+    let result = engine.RecognizeAsync(&bitmap)?.join()?;
+
+    let mut words = Vec::new();
+    for (line_idx, line) in result.Lines()?.into_iter().enumerate() {
+        for word in line.Words()? {
+            let rect = word.BoundingRect()?;
+            words.push(OcrWord {
+                text: word.Text()?.to_string(),
+                x: rect.X / scale,
+                y: rect.Y / scale,
+                width: rect.Width / scale,
+                height: rect.Height / scale,
+                line: line_idx,
+            });
+        }
+    }
+
+    println!("[OCR] 認識完了: {} 語", words.len());
+    Ok(OcrPageResult {
+        words,
+        image_width: image.width,
+        image_height: image.height,
+    })
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3000..=0x30FF   // 記号・ひらがな・カタカナ
+        | 0x3400..=0x4DBF // CJK 拡張 A
+        | 0x4E00..=0x9FFF // CJK 統合漢字
+        | 0xFF00..=0xFFEF // 全角英数・半角カナ
+    )
+}
+
+/// 選択された単語を連結してコピー用テキストを作成する
+/// 日本語の単語間には空白を入れず、行が変わる箇所で改行する
+pub fn join_words(words: &[&OcrWord]) -> String {
+    let mut text = String::new();
+    let mut prev: Option<&OcrWord> = None;
+    for word in words {
+        if let Some(p) = prev {
+            if p.line != word.line {
+                text.push('\n');
+            } else {
+                let prev_cjk = p.text.chars().last().map(is_cjk).unwrap_or(false);
+                let next_cjk = word.text.chars().next().map(is_cjk).unwrap_or(false);
+                if !(prev_cjk && next_cjk) {
+                    text.push(' ');
+                }
+            }
+        }
+        text.push_str(&word.text);
+        prev = Some(word);
+    }
+    text
+}
@@ -0,0 +1,21 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::Win32::System::Power::{ES_CONTINUOUS, ES_DISPLAY_REQUIRED, SetThreadExecutionState};
+
+/// 現在ディスプレイのスリープを抑止しているか
+static DISPLAY_REQUIRED: AtomicBool = AtomicBool::new(false);
+
+/// 読書中にディスプレイが消灯しないようにする (false で OS の既定動作に戻す)
+/// 毎フレーム呼ばれるため、状態が変わったときだけ API を呼ぶ
+/// SetThreadExecutionState はスレッド単位の設定なので、常にメインスレッドから呼ぶこと
+pub fn set_display_required(required: bool) {
+    if DISPLAY_REQUIRED.swap(required, Ordering::Relaxed) == required {
+        return;
+    }
+    let flags = if required { ES_CONTINUOUS | ES_DISPLAY_REQUIRED } else { ES_CONTINUOUS };
+    let previous = unsafe { SetThreadExecutionState(flags) };
+    if previous.0 == 0 {
+        eprintln!("[電源] SetThreadExecutionState に失敗しました");
+    } else {
+        println!("[電源] ディスプレイのスリープ抑止: {}", if required { "有効" } else { "無効" });
+    }
+}
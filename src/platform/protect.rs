@@ -0,0 +1,48 @@
+use windows::Win32::Foundation::{HLOCAL, LocalFree};
+use windows::Win32::Security::Cryptography::{
+    CRYPT_INTEGER_BLOB, CRYPTPROTECT_UI_FORBIDDEN, CryptProtectData, CryptUnprotectData,
+};
+
+/// DPAPI の出力 (LocalAlloc された領域) を Vec にコピーして解放する
+unsafe fn take_blob(blob: CRYPT_INTEGER_BLOB) -> Vec<u8> {
+    unsafe {
+        let data = std::slice::from_raw_parts(blob.pbData, blob.cbData as usize).to_vec();
+        let _ = LocalFree(Some(HLOCAL(blob.pbData as _)));
+        data
+    }
+}
+
+/// 現在の Windows ユーザーだけが復号できるよう DPAPI で暗号化する (同じ内容でも毎回異なる結果になる)
+pub fn protect(data: &[u8]) -> windows::core::Result<Vec<u8>> {
+    let input = CRYPT_INTEGER_BLOB { cbData: data.len() as u32, pbData: data.as_ptr() as *mut u8 };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptProtectData(&input, windows::core::w!("HayateViewer"), None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)?;
+        Ok(take_blob(output))
+    }
+}
+
+/// protect で暗号化したデータを復号する (別のユーザー・PC で暗号化したものはエラー)
+pub fn unprotect(data: &[u8]) -> windows::core::Result<Vec<u8>> {
+    let input = CRYPT_INTEGER_BLOB { cbData: data.len() as u32, pbData: data.as_ptr() as *mut u8 };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptUnprotectData(&input, None, None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)?;
+        Ok(take_blob(output))
+    }
+}
+
+/// バイト列を 16 進文字列にする (config.json に書くため)
+pub fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| text.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use windows::ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager};
+use windows::Foundation::TypedEventHandler;
+use windows::Storage::StorageFile;
+use windows::Storage::Streams::RandomAccessStreamReference;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::Shell::IDataTransferManagerInterop;
+use windows::core::{HSTRING, Ref};
+
+/// 前回登録した DataRequested ハンドラのトークン (共有のたびに差し替える)
+static DATA_REQUESTED_TOKEN: Mutex<Option<i64>> = Mutex::new(None);
+
+/// 共有用の一時 PNG を書き出す (大きなページはエンコードに時間がかかるため、UI スレッド以外で呼ぶ)
+pub fn write_temp_png(image: &::image::RgbaImage, name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = std::env::temp_dir().join("HayateViewer");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.png", sanitize_file_name(name)));
+    image.save_with_format(&path, ::image::ImageFormat::Png)?;
+    Ok(path)
+}
+
+/// ページ名からファイル名に使える名前を作る (拡張子を除き、使えない文字を _ にする)
+pub fn sanitize_file_name(name: &str) -> String {
+    let stem = std::path::Path::new(name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let cleaned: String = stem
+        .chars()
+        .map(|c| if matches!(c, '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect();
+    if cleaned.is_empty() { "page".to_string() } else { cleaned }
+}
+
+/// Windows の共有 UI (DataTransferManager) で write_temp_png で書き出した画像を共有する (UI スレッドで呼ぶ)
+/// `title` は共有シートに表示される名前
+pub fn share_file(hwnd: HWND, path: &Path, title: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path_str = path.to_string_lossy().to_string();
+
+    // 共有先アプリが読み取る画像ストリーム
+    let file = StorageFile::GetFileFromPathAsync(&HSTRING::from(path_str.as_str()))?.join()?;
+    let bitmap = RandomAccessStreamReference::CreateFromFile(&file)?;
+
+    let interop = windows::core::factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+    let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+
+    let mut token = DATA_REQUESTED_TOKEN.lock().unwrap();
+    if let Some(old) = token.take() {
+        let _ = manager.RemoveDataRequested(old);
+    }
+
+    let title = title.to_string();
+    let handler = TypedEventHandler::new(move |_sender: Ref<DataTransferManager>, args: Ref<DataRequestedEventArgs>| {
+        let request = args.ok()?.Request()?;
+        let data = request.Data()?;
+        data.Properties()?.SetTitle(&HSTRING::from(title.as_str()))?;
+        data.SetBitmap(&bitmap)?;
+        Ok(())
+    });
+    *token = Some(manager.DataRequested(&handler)?);
+
+    unsafe { interop.ShowShareUIForWindow(hwnd)? };
+    println!("[共有] 共有 UI を表示しました: {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use windows::Foundation::Uri;
+use windows::Web::Http::HttpClient;
+use windows::Win32::System::Com::{COINIT_MULTITHREADED, CoInitializeEx};
+use windows::core::HSTRING;
+
+const RELEASES_API: &str = "https://api.github.com/repos/TatsumakiIshino/HayateViewer-Rust/releases/latest";
+/// 更新確認の間隔 (1週間)
+pub const CHECK_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+
+pub fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// GitHub Releases の最新タグを取得する (ブロッキング処理のためワーカースレッドから呼ぶこと)
+pub fn fetch_latest_version() -> Result<String, Box<dyn std::error::Error>> {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    }
+
+    let client = HttpClient::new()?;
+    // GitHub API は User-Agent が無いリクエストを拒否する
+    client
+        .DefaultRequestHeaders()?
+        .UserAgent()?
+        .TryParseAdd(&HSTRING::from(format!("HayateViewer-Rust/{}", env!("CARGO_PKG_VERSION"))))?;
+    let uri = Uri::CreateUri(&HSTRING::from(RELEASES_API))?;
+    let body = client.GetStringAsync(&uri)?.join()?.to_string();
+
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    let tag = json
+        .get("tag_name")
+        .and_then(|v| v.as_str())
+        .ok_or("tag_name がありません")?;
+    Ok(tag.trim_start_matches('v').to_string())
+}
+
+/// "0.6.7-test" -> ([0, 6, 7], プレリリースか)
+fn parse_version(version: &str) -> (Vec<u64>, bool) {
+    let version = version.trim().trim_start_matches('v');
+    let (core, pre) = match version.split_once('-') {
+        Some((core, _)) => (core, true),
+        None => (version, false),
+    };
+    let nums = core.split('.').map(|n| n.parse().unwrap_or(0)).collect();
+    (nums, pre)
+}
+
+/// `latest` が `current` より新しいか (同じ番号ならプレリリースより正式版を新しいとみなす)
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    let (mut l, l_pre) = parse_version(latest);
+    let (mut c, c_pre) = parse_version(current);
+    let len = l.len().max(c.len());
+    l.resize(len, 0);
+    c.resize(len, 0);
+    match l.cmp(&c) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Less => false,
+        std::cmp::Ordering::Equal => c_pre && !l_pre,
+    }
+}
@@ -4,9 +4,10 @@ use windows::{
     Win32::Graphics::Direct3D::*, Win32::Graphics::Direct3D11::*, Win32::Graphics::DirectWrite::*,
     Win32::Graphics::Dxgi::Common::*, Win32::Graphics::Dxgi::*, core::*,
 };
+use windows_numerics::Matrix3x2;
 type D3DResult<T> = windows::core::Result<T>;
 
-use super::{InterpolationMode, Renderer, TextureHandle};
+use super::{ChromaUpsampling, ColorAdjustment, InterpolationMode, PageCurl, PageTransform, Renderer, TextureHandle};
 use crate::image::cache::{DecodedImage, PixelData};
 
 // 旧トレイト定義は削除
@@ -16,12 +17,85 @@ pub struct D2DRenderer {
     pub _device: ID2D1Device,
     pub context: ID2D1DeviceContext,
     pub swap_chain: IDXGISwapChain1,
-    #[allow(dead_code)]
     pub dw_factory: IDWriteFactory,
     pub text_format: IDWriteTextFormat,
     pub text_format_large: IDWriteTextFormat,
     pub brush: ID2D1SolidColorBrush,
+    /// 注釈の線用のストロークスタイル
+    round_stroke: ID2D1StrokeStyle1,
     pub interpolation_mode: D2D1_INTERPOLATION_MODE,
+    /// 色調整用のエフェクトチェーン (拡大縮小 -> ガンマ -> 明るさ・コントラスト)
+    adjust_transform: ID2D1Effect,
+    adjust_levels: ID2D1Effect,
+    adjust_gamma: ID2D1Effect,
+    adjust_matrix: ID2D1Effect,
+    color_adjustment: ColorAdjustment,
+    page_transform: PageTransform,
+    background_color: D2D1_COLOR_F,
+    /// オーバーレイ文字の拡大率 (ウィンドウの DPI スケール)
+    ui_scale: f32,
+    /// 出力ウィンドウ (セカンドモニター用) のスワップチェーンと描画先
+    mirror: Option<(IDXGISwapChain1, ID2D1Bitmap1)>,
+}
+
+/// center を軸に時計回りに回転してから反転する変換行列 (D2D は行ベクトル: p' = p * M)
+fn page_matrix(transform: &PageTransform, center: (f32, f32)) -> Matrix3x2 {
+    let (cos, sin) = match transform.rotation {
+        90 => (0.0, 1.0),
+        180 => (-1.0, 0.0),
+        270 => (0.0, -1.0),
+        _ => (1.0, 0.0),
+    };
+    let fx = if transform.flip_h { -1.0 } else { 1.0 };
+    let fy = if transform.flip_v { -1.0 } else { 1.0 };
+    let (m11, m12, m21, m22) = (cos * fx, sin * fy, -sin * fx, cos * fy);
+    Matrix3x2 {
+        M11: m11,
+        M12: m12,
+        M21: m21,
+        M22: m22,
+        M31: center.0 - (center.0 * m11 + center.1 * m21),
+        M32: center.1 - (center.0 * m12 + center.1 * m22),
+    }
+}
+
+/// 通常・大きい文字のテキストフォーマットを作成する (サイズは DPI スケールに合わせる)
+unsafe fn create_text_formats(
+    dw_factory: &IDWriteFactory,
+    scale: f32,
+) -> Result<(IDWriteTextFormat, IDWriteTextFormat)> {
+    unsafe {
+        let text_format = dw_factory.CreateTextFormat(
+            w!("Segoe UI"),
+            None,
+            DWRITE_FONT_WEIGHT_NORMAL,
+            DWRITE_FONT_STYLE_NORMAL,
+            DWRITE_FONT_STRETCH_NORMAL,
+            14.0 * scale,
+            w!("ja-jp"),
+        )?;
+        let text_format_large = dw_factory.CreateTextFormat(
+            w!("Segoe UI"),
+            None,
+            DWRITE_FONT_WEIGHT_BOLD,
+            DWRITE_FONT_STYLE_NORMAL,
+            DWRITE_FONT_STRETCH_NORMAL,
+            24.0 * scale,
+            w!("ja-jp"),
+        )?;
+        Ok((text_format, text_format_large))
+    }
+}
+
+/// エフェクトのプロパティを f32 の並びとして設定する
+unsafe fn set_effect_floats(
+    effect: &ID2D1Effect,
+    index: i32,
+    property_type: D2D1_PROPERTY_TYPE,
+    values: &[f32],
+) -> Result<()> {
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    unsafe { effect.SetValue(index as u32, property_type, &bytes) }
 }
 
 impl Renderer for D2DRenderer {
@@ -51,12 +125,7 @@ impl Renderer for D2DRenderer {
     fn begin_draw(&self) {
         unsafe {
             self.context.BeginDraw();
-            self.context.Clear(Some(&D2D1_COLOR_F {
-                r: 0.1,
-                g: 0.1,
-                b: 0.1,
-                a: 0.8,
-            }));
+            self.context.Clear(Some(&self.background_color));
         }
     }
 
@@ -84,20 +153,46 @@ impl Renderer for D2DRenderer {
     }
 
     fn draw_image(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F) {
-        if let TextureHandle::Direct2D(bitmap) = texture {
-            unsafe {
-                self.context.DrawBitmap(
-                    bitmap,
-                    Some(dest_rect),
-                    1.0,
-                    self.interpolation_mode,
-                    None,
-                    None,
-                );
-            }
+        if self.page_transform.is_identity() {
+            self.draw_bitmap(texture, dest_rect);
+            return;
+        }
+        // 回転前の矩形に描画し、dest_rect の中心を軸に回転・反転する変換行列で画面上に配置する
+        let center = ((dest_rect.left + dest_rect.right) / 2.0, (dest_rect.top + dest_rect.bottom) / 2.0);
+        let (w, h) = self
+            .page_transform
+            .display_size(dest_rect.right - dest_rect.left, dest_rect.bottom - dest_rect.top);
+        let inner = D2D_RECT_F {
+            left: center.0 - w / 2.0,
+            top: center.1 - h / 2.0,
+            right: center.0 + w / 2.0,
+            bottom: center.1 + h / 2.0,
+        };
+        unsafe {
+            let mut previous = Matrix3x2::default();
+            self.context.GetTransform(&mut previous);
+            self.context.SetTransform(&page_matrix(&self.page_transform, center));
+            self.draw_bitmap(texture, &inner);
+            self.context.SetTransform(&previous);
+        }
+    }
+
+    fn draw_image_clipped(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F) {
+        // クリップは変換行列を設定する前に積むため、画面座標のまま指定できる
+        unsafe {
+            self.context.PushAxisAlignedClip(clip_rect, D2D1_ANTIALIAS_MODE_ALIASED);
+        }
+        self.draw_image(texture, dest_rect);
+        unsafe {
+            self.context.PopAxisAlignedClip();
         }
     }
 
+    fn draw_page_turn(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F, curl: &PageCurl) {
+        // Direct2D ではメッシュを変形できないため、横にずらして代用する
+        self.draw_image_clipped(texture, &curl.slide_rect(dest_rect), &curl.slide_clip(clip_rect));
+    }
+
     fn get_texture_size(&self, texture: &TextureHandle) -> (f32, f32) {
         if let TextureHandle::Direct2D(bitmap) = texture {
             unsafe {
@@ -124,6 +219,32 @@ impl Renderer for D2DRenderer {
         }
     }
 
+    fn draw_polyline(&self, points: &[(f32, f32)], color: &D2D1_COLOR_F, stroke_width: f32) {
+        let Some(&(x, y)) = points.first() else { return; };
+        let draw = || -> Result<()> {
+            unsafe {
+                let geometry = self._factory.CreatePathGeometry()?;
+                let sink = geometry.Open()?;
+                sink.BeginFigure(windows_numerics::Vector2 { X: x, Y: y }, D2D1_FIGURE_BEGIN_HOLLOW);
+                // 1 点だけ (タップ) の場合も丸い点として描かれるよう、同じ点への線を引く
+                let lines: Vec<windows_numerics::Vector2> = if points.len() == 1 {
+                    vec![windows_numerics::Vector2 { X: x, Y: y }]
+                } else {
+                    points[1..].iter().map(|&(x, y)| windows_numerics::Vector2 { X: x, Y: y }).collect()
+                };
+                sink.AddLines(&lines);
+                sink.EndFigure(D2D1_FIGURE_END_OPEN);
+                sink.Close()?;
+                self.brush.SetColor(color);
+                self.context.DrawGeometry(&geometry, &self.brush, stroke_width, &self.round_stroke);
+            }
+            Ok(())
+        };
+        if let Err(e) = draw() {
+            eprintln!("[注釈] 線を描画できませんでした: {:?}", e);
+        }
+    }
+
     fn draw_text(&self, text: &str, rect: &D2D_RECT_F, color: &D2D1_COLOR_F, large: bool) {
         unsafe {
             self.brush.SetColor(color);
@@ -144,6 +265,10 @@ impl Renderer for D2DRenderer {
         }
     }
 
+    fn set_chroma_upsampling(&mut self, _mode: ChromaUpsampling) {
+        // Direct2D は YCbCr テクスチャを扱わない (RGBA に変換済みの画像だけを転送する)
+    }
+
     fn set_interpolation_mode(&mut self, mode: InterpolationMode) {
         self.interpolation_mode = match mode {
             InterpolationMode::NearestNeighbor => D2D1_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
@@ -153,6 +278,20 @@ impl Renderer for D2DRenderer {
         };
     }
 
+    fn set_color_adjustment(&mut self, adjustment: ColorAdjustment) {
+        if self.color_adjustment == adjustment {
+            return;
+        }
+        self.color_adjustment = adjustment;
+        if let Err(e) = self.update_adjust_effects() {
+            eprintln!("[D2D] 色調整エフェクトの更新に失敗: {}", e);
+        }
+    }
+
+    fn set_page_transform(&mut self, transform: PageTransform) {
+        self.page_transform = transform;
+    }
+
     fn set_text_alignment(&self, alignment: DWRITE_TEXT_ALIGNMENT) {
         unsafe {
             let _ = self.text_format.SetTextAlignment(alignment);
@@ -165,6 +304,119 @@ impl Renderer for D2DRenderer {
                 .SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER);
         }
     }
+
+    fn query_video_memory(&self) -> Option<super::VideoMemoryInfo> {
+        let device: IDXGIDevice = unsafe { self.swap_chain.GetDevice().ok()? };
+        super::query_dxgi_video_memory(&device)
+    }
+
+    fn set_ui_scale(&mut self, scale: f32) {
+        if (scale - self.ui_scale).abs() < f32::EPSILON {
+            return;
+        }
+        if let Ok((normal, large)) = unsafe { create_text_formats(&self.dw_factory, scale) } {
+            self.text_format = normal;
+            self.text_format_large = large;
+            self.ui_scale = scale;
+        }
+    }
+
+    fn set_background_color(&mut self, color: D2D1_COLOR_F) {
+        self.background_color = color;
+    }
+
+    fn create_staged_texture(&self, image: &DecodedImage) -> Option<TextureHandle> {
+        // YCbCr は D2D では未対応のため RGBA のみ
+        let PixelData::Rgba8(_) = image.pixel_data else { return None; };
+        let bitmap = self.create_empty_bitmap(image.width, image.height).ok()?;
+        Some(TextureHandle::Direct2D(bitmap))
+    }
+
+    fn upload_rows(
+        &self,
+        texture: &TextureHandle,
+        image: &DecodedImage,
+        start_row: u32,
+        rows: u32,
+    ) -> std::result::Result<u32, Box<dyn std::error::Error>> {
+        let (TextureHandle::Direct2D(bitmap), PixelData::Rgba8(data)) = (texture, &image.pixel_data) else {
+            return Err("staged upload is only supported for RGBA bitmaps".into());
+        };
+        let end_row = (start_row + rows).min(image.height);
+        if start_row >= end_row {
+            return Ok(image.height);
+        }
+        let pitch = image.width * 4;
+        let offset = (start_row * pitch) as usize;
+        let rect = D2D_RECT_U { left: 0, top: start_row, right: image.width, bottom: end_row };
+        unsafe { bitmap.CopyFromMemory(Some(&rect), data[offset..].as_ptr() as _, pitch)? };
+        Ok(end_row)
+    }
+
+    fn texture_uploader(&self) -> Option<Box<dyn super::TextureUploader>> {
+        // ID2D1DeviceContext はスレッドセーフではないため、先読みは段階転送で描画スレッドから行う
+        None
+    }
+
+    fn compute_histogram(&self, _texture: &TextureHandle) -> Option<[u32; 256]> {
+        // Direct2D のビットマップは CPU 側の画像から集計する
+        None
+    }
+
+    fn set_mirror_target(&mut self, hwnd: Option<HWND>) -> bool {
+        self.mirror = None;
+        let Some(hwnd) = hwnd else { return true; };
+        let target = super::create_sibling_swap_chain(&self.swap_chain, hwnd).and_then(|swap_chain| {
+            let bitmap = self.target_bitmap(&swap_chain)?;
+            Ok((swap_chain, bitmap))
+        });
+        match target {
+            Ok(target) => {
+                self.mirror = Some(target);
+                true
+            }
+            Err(e) => {
+                println!("[D2D] 出力ウィンドウの描画先を作成できません: {}", e);
+                false
+            }
+        }
+    }
+
+    fn resize_mirror(&mut self, width: u32, height: u32) {
+        // バックバッファを参照するビットマップを捨ててから作り直す
+        let Some((swap_chain, _)) = self.mirror.take() else { return; };
+        let result = unsafe {
+            swap_chain
+                .ResizeBuffers(0, width.max(1), height.max(1), DXGI_FORMAT_UNKNOWN, DXGI_SWAP_CHAIN_FLAG(0))
+                .and_then(|_| self.target_bitmap(&swap_chain))
+        };
+        match result {
+            Ok(bitmap) => self.mirror = Some((swap_chain, bitmap)),
+            Err(e) => println!("[D2D] 出力ウィンドウの描画先を作り直せません: {}", e),
+        }
+    }
+
+    fn begin_mirror_draw(&self) {
+        let Some((_, bitmap)) = self.mirror.as_ref() else { return; };
+        unsafe {
+            self.context.SetTarget(bitmap);
+            self.context.BeginDraw();
+            self.context.Clear(Some(&self.background_color));
+        }
+    }
+
+    fn end_mirror_draw(&self) {
+        let Some((swap_chain, _)) = self.mirror.as_ref() else { return; };
+        unsafe {
+            let _ = self.context.EndDraw(None, None);
+            // メインウィンドウの表示を待たせないよう垂直同期は待たない
+            let _ = swap_chain.Present(0, DXGI_PRESENT(0));
+            match self.target_bitmap(&self.swap_chain) {
+                Ok(back_buffer) => self.context.SetTarget(&back_buffer),
+                Err(e) => println!("[D2D] 描画先をメインウィンドウに戻せません: {}", e),
+            }
+        }
+    }
 }
 
 impl D2DRenderer {
@@ -226,25 +478,7 @@ impl D2DRenderer {
 
             // DirectWrite と ブラシの作成
             let dw_factory: IDWriteFactory = DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED)?;
-            let text_format = dw_factory.CreateTextFormat(
-                w!("Segoe UI"),
-                None,
-                DWRITE_FONT_WEIGHT_NORMAL,
-                DWRITE_FONT_STYLE_NORMAL,
-                DWRITE_FONT_STRETCH_NORMAL,
-                14.0,
-                w!("ja-jp"),
-            )?;
-
-            let text_format_large = dw_factory.CreateTextFormat(
-                w!("Segoe UI"),
-                None,
-                DWRITE_FONT_WEIGHT_BOLD,
-                DWRITE_FONT_STYLE_NORMAL,
-                DWRITE_FONT_STRETCH_NORMAL,
-                24.0,
-                w!("ja-jp"),
-            )?;
+            let (text_format, text_format_large) = create_text_formats(&dw_factory, 1.0)?;
 
             let brush = context.CreateSolidColorBrush(
                 &D2D1_COLOR_F {
@@ -256,7 +490,38 @@ impl D2DRenderer {
                 None,
             )?;
 
-            Ok(Self {
+            // 注釈の線 (端と折れ目を丸める)
+            let round_stroke = factory.CreateStrokeStyle(
+                &D2D1_STROKE_STYLE_PROPERTIES1 {
+                    startCap: D2D1_CAP_STYLE_ROUND,
+                    endCap: D2D1_CAP_STYLE_ROUND,
+                    dashCap: D2D1_CAP_STYLE_ROUND,
+                    lineJoin: D2D1_LINE_JOIN_ROUND,
+                    miterLimit: 10.0,
+                    dashStyle: D2D1_DASH_STYLE_SOLID,
+                    dashOffset: 0.0,
+                    transformType: D2D1_STROKE_TRANSFORM_TYPE_NORMAL,
+                },
+                None,
+            )?;
+
+            // 色調整用エフェクト (拡大縮小を先に行い、縮小表示時の処理画素数を減らす)
+            let adjust_transform = context.CreateEffect(&CLSID_D2D12DAffineTransform)?;
+            let adjust_levels = context.CreateEffect(&CLSID_D2D1ColorMatrix)?;
+            let adjust_gamma = context.CreateEffect(&CLSID_D2D1GammaTransfer)?;
+            let adjust_matrix = context.CreateEffect(&CLSID_D2D1ColorMatrix)?;
+            adjust_levels.SetInput(0, &adjust_transform.GetOutput()?, true);
+            adjust_gamma.SetInput(0, &adjust_levels.GetOutput()?, true);
+            adjust_matrix.SetInput(0, &adjust_gamma.GetOutput()?, true);
+            for matrix_effect in [&adjust_levels, &adjust_matrix] {
+                matrix_effect.SetValue(
+                    D2D1_COLORMATRIX_PROP_CLAMP_OUTPUT.0 as u32,
+                    D2D1_PROPERTY_TYPE_BOOL,
+                    &1u32.to_le_bytes(),
+                )?;
+            }
+
+            let renderer = Self {
                 _factory: factory,
                 _device: device,
                 context,
@@ -265,29 +530,172 @@ impl D2DRenderer {
                 text_format,
                 text_format_large,
                 brush,
+                round_stroke,
                 interpolation_mode: D2D1_INTERPOLATION_MODE_HIGH_QUALITY_CUBIC,
-            })
+                adjust_transform,
+                adjust_levels,
+                adjust_gamma,
+                adjust_matrix,
+                color_adjustment: ColorAdjustment::default(),
+                page_transform: PageTransform::default(),
+                background_color: D2D1_COLOR_F { r: 0.1, g: 0.1, b: 0.1, a: 1.0 },
+                ui_scale: 1.0,
+                mirror: None,
+            };
+            renderer.update_adjust_effects()?;
+            Ok(renderer)
         }
     }
 
-    pub fn create_bitmap(&self, width: u32, height: u32, data: &[u8]) -> Result<ID2D1Bitmap1> {
+    /// スワップチェーンのバックバッファを描画先にするビットマップ
+    fn target_bitmap(&self, swap_chain: &IDXGISwapChain1) -> Result<ID2D1Bitmap1> {
         unsafe {
-            let props = D2D1_BITMAP_PROPERTIES1 {
-                pixelFormat: D2D1_PIXEL_FORMAT {
-                    format: DXGI_FORMAT_R8G8B8A8_UNORM,
-                    alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
-                },
-                dpiX: 96.0,
-                dpiY: 96.0,
-                bitmapOptions: D2D1_BITMAP_OPTIONS_NONE,
-                colorContext: ManuallyDrop::new(None),
+            let surface: IDXGISurface = swap_chain.GetBuffer(0)?;
+            self.context.CreateBitmapFromDxgiSurface(&surface, None)
+        }
+    }
+
+    /// 色調整の値をレベル補正・ガンマ・カラーマトリクスエフェクトに反映する
+    fn update_adjust_effects(&self) -> Result<()> {
+        let adj = self.color_adjustment;
+        unsafe {
+            // レベル補正: out = (in - black) / (white - black)
+            let scale = 1.0 / (adj.white_point - adj.black_point).max(ColorAdjustment::MIN_LEVELS_RANGE);
+            let offset = -adj.black_point * scale;
+            #[rustfmt::skip]
+            let levels = [
+                scale,  0.0,    0.0,    0.0,
+                0.0,    scale,  0.0,    0.0,
+                0.0,    0.0,    scale,  0.0,
+                0.0,    0.0,    0.0,    1.0,
+                offset, offset, offset, 0.0,
+            ];
+            set_effect_floats(&self.adjust_levels, D2D1_COLORMATRIX_PROP_COLOR_MATRIX.0, D2D1_PROPERTY_TYPE_MATRIX_5X4, &levels)?;
+
+            let exponent = 1.0 / adj.gamma;
+            for prop in [
+                D2D1_GAMMATRANSFER_PROP_RED_EXPONENT,
+                D2D1_GAMMATRANSFER_PROP_GREEN_EXPONENT,
+                D2D1_GAMMATRANSFER_PROP_BLUE_EXPONENT,
+            ] {
+                set_effect_floats(&self.adjust_gamma, prop.0, D2D1_PROPERTY_TYPE_FLOAT, &[exponent])?;
+            }
+
+            // out = (in - 0.5) * contrast + 0.5 + brightness
+            // 反転時: out = 1 - ((in - 0.5) * contrast + 0.5) + brightness
+            // 最後にブルーライト軽減の係数を各出力チャンネル (列) に掛ける
+            let (c, bias) = if adj.invert {
+                (-adj.contrast, 0.5 + 0.5 * adj.contrast + adj.brightness)
+            } else {
+                (adj.contrast, 0.5 - 0.5 * adj.contrast + adj.brightness)
             };
+            let [tr, tg, tb] = adj.tint();
+            #[rustfmt::skip]
+            let matrix = [
+                c * tr,    0.0,       0.0,       0.0,
+                0.0,       c * tg,    0.0,       0.0,
+                0.0,       0.0,       c * tb,    0.0,
+                0.0,       0.0,       0.0,       1.0,
+                bias * tr, bias * tg, bias * tb, 0.0,
+            ];
+            set_effect_floats(
+                &self.adjust_matrix,
+                D2D1_COLORMATRIX_PROP_COLOR_MATRIX.0,
+                D2D1_PROPERTY_TYPE_MATRIX_5X4,
+                &matrix,
+            )
+        }
+    }
 
+    /// ビットマップを dest_rect に描画する (現在の変換行列に従う)
+    fn draw_bitmap(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F) {
+        if let TextureHandle::Direct2D(bitmap) = texture {
+            // 色調整が有効な場合はエフェクト経由で描画し、失敗時は通常描画にフォールバック
+            if !self.color_adjustment.is_identity()
+                && self.draw_adjusted_bitmap(bitmap, dest_rect).is_ok()
+            {
+                return;
+            }
+            unsafe {
+                self.context.DrawBitmap(
+                    bitmap,
+                    Some(dest_rect),
+                    1.0,
+                    self.interpolation_mode,
+                    None,
+                    None,
+                );
+            }
+        }
+    }
+
+    /// エフェクトチェーンを通してビットマップを dest_rect に描画する
+    fn draw_adjusted_bitmap(&self, bitmap: &ID2D1Bitmap1, dest_rect: &D2D_RECT_F) -> Result<()> {
+        unsafe {
+            let size = bitmap.GetSize();
+            if size.width <= 0.0 || size.height <= 0.0 {
+                return Ok(());
+            }
+            let scale_x = (dest_rect.right - dest_rect.left) / size.width;
+            let scale_y = (dest_rect.bottom - dest_rect.top) / size.height;
+            // 3x2 行列: [m11, m12, m21, m22, dx, dy]
+            set_effect_floats(
+                &self.adjust_transform,
+                D2D1_2DAFFINETRANSFORM_PROP_TRANSFORM_MATRIX.0,
+                D2D1_PROPERTY_TYPE_MATRIX_3X2,
+                &[scale_x, 0.0, 0.0, scale_y, dest_rect.left, dest_rect.top],
+            )?;
+            self.adjust_transform.SetValue(
+                D2D1_2DAFFINETRANSFORM_PROP_INTERPOLATION_MODE.0 as u32,
+                D2D1_PROPERTY_TYPE_ENUM,
+                &(self.interpolation_mode.0 as u32).to_le_bytes(),
+            )?;
+            self.adjust_transform.SetInput(0, bitmap, true);
+
+            let output = self.adjust_matrix.GetOutput()?;
+            self.context.DrawImage(
+                &output,
+                None,
+                None,
+                D2D1_INTERPOLATION_MODE_LINEAR,
+                D2D1_COMPOSITE_MODE_SOURCE_OVER,
+            );
+        }
+        Ok(())
+    }
+
+    fn bitmap_properties() -> D2D1_BITMAP_PROPERTIES1 {
+        D2D1_BITMAP_PROPERTIES1 {
+            pixelFormat: D2D1_PIXEL_FORMAT {
+                format: DXGI_FORMAT_R8G8B8A8_UNORM,
+                alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+            },
+            dpiX: 96.0,
+            dpiY: 96.0,
+            bitmapOptions: D2D1_BITMAP_OPTIONS_NONE,
+            colorContext: ManuallyDrop::new(None),
+        }
+    }
+
+    pub fn create_bitmap(&self, width: u32, height: u32, data: &[u8]) -> Result<ID2D1Bitmap1> {
+        unsafe {
             self.context.CreateBitmap(
                 D2D_SIZE_U { width, height },
                 Some(data.as_ptr() as _),
                 width * 4,
-                &props,
+                &Self::bitmap_properties(),
+            )
+        }
+    }
+
+    /// 中身が未初期化のビットマップを作成する (段階転送で CopyFromMemory により埋める)
+    pub fn create_empty_bitmap(&self, width: u32, height: u32) -> Result<ID2D1Bitmap1> {
+        unsafe {
+            self.context.CreateBitmap(
+                D2D_SIZE_U { width, height },
+                None,
+                0,
+                &Self::bitmap_properties(),
             )
         }
     }
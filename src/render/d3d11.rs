@@ -1,4 +1,4 @@
-use super::{InterpolationMode, Renderer, TextureHandle};
+use super::{ChromaUpsampling, ColorAdjustment, InterpolationMode, PageCurl, PageTransform, Renderer, TextureHandle};
 use crate::image::cache::{DecodedImage, PixelData};
 
 use windows::{
@@ -16,19 +16,148 @@ pub struct D3D11Renderer {
 
     // D3D11 Resources
     render_target_view: ID3D11RenderTargetView,
+    /// 出力ウィンドウ (セカンドモニター用) の描画先
+    mirror: Option<MirrorTarget>,
+    /// begin_mirror_draw 〜 end_mirror_draw の間は出力ウィンドウへ描画する
+    drawing_mirror: std::sync::atomic::AtomicBool,
     vertex_shader: ID3D11VertexShader,
+    /// ページめくりのメッシュを変形する頂点シェーダ (page_curl.hlsl)
+    curl_vertex_shader: ID3D11VertexShader,
     input_layout: ID3D11InputLayout,
     pixel_shader_rgba: ID3D11PixelShader,
     pixel_shader_ycbcr: ID3D11PixelShader,
     vertex_buffer: ID3D11Buffer,
+    /// ページめくり用の分割したメッシュ (描画ごとに書き換える)
+    curl_vertex_buffer: ID3D11Buffer,
     constant_buffer: ID3D11Buffer,
+    adjust_buffer: ID3D11Buffer,
+    curl_buffer: ID3D11Buffer,
     sampler_linear: ID3D11SamplerState,
     sampler_nearest: ID3D11SamplerState,
     rasterizer_state: ID3D11RasterizerState,
+    /// シザーテスト有効 (ルーペの部分描画用)
+    rasterizer_state_scissor: ID3D11RasterizerState,
 
     // Settings
     pub interpolation_mode: InterpolationMode,
+    chroma_upsampling: ChromaUpsampling,
+    color_adjustment: ColorAdjustment,
+    page_transform: PageTransform,
+    background_color: D2D1_COLOR_F,
     pub text_alignment: std::sync::atomic::AtomicI32, // GDI 用
+    ui_scale: f32,
+    /// 輝度ヒストグラム集計用 (コンピュートシェーダ非対応の環境では None)
+    histogram: Option<HistogramPipeline>,
+}
+
+/// 出力ウィンドウのスワップチェーンとレンダーターゲット
+struct MirrorTarget {
+    swap_chain: IDXGISwapChain1,
+    render_target_view: ID3D11RenderTargetView,
+}
+
+fn create_render_target_view(device: &ID3D11Device, swap_chain: &IDXGISwapChain1) -> Result<ID3D11RenderTargetView> {
+    unsafe {
+        let back_buffer: ID3D11Texture2D = swap_chain.GetBuffer(0)?;
+        let mut rtv: Option<ID3D11RenderTargetView> = None;
+        device.CreateRenderTargetView(&back_buffer, None, Some(&mut rtv))?;
+        Ok(rtv.unwrap())
+    }
+}
+
+/// ヒストグラム集計用のコンピュートシェーダとバッファ
+struct HistogramPipeline {
+    cs_rgba: ID3D11ComputeShader,
+    cs_ycbcr: ID3D11ComputeShader,
+    params: ID3D11Buffer,
+    bins: ID3D11Buffer,
+    bins_uav: ID3D11UnorderedAccessView,
+    /// CPU で読み出すためのコピー先
+    staging: ID3D11Buffer,
+}
+
+/// シェーダの HistogramParams (b0) に対応
+#[repr(C)]
+struct HistogramConstants {
+    size: [u32; 2],
+    y_scale: f32,
+    y_bias: f32,
+}
+
+fn create_compute_shader(device: &ID3D11Device, source: &[u8], entry_point: &str) -> Result<ID3D11ComputeShader> {
+    unsafe {
+        let blob = compile_shader(source, entry_point, "cs_5_0")?;
+        let mut shader: Option<ID3D11ComputeShader> = None;
+        device.CreateComputeShader(
+            std::slice::from_raw_parts(blob.GetBufferPointer() as *const u8, blob.GetBufferSize()),
+            None,
+            Some(&mut shader),
+        )?;
+        Ok(shader.unwrap())
+    }
+}
+
+impl HistogramPipeline {
+    fn new(device: &ID3D11Device) -> Result<Self> {
+        unsafe {
+            let src = include_bytes!("shaders/histogram.hlsl");
+            let cs_rgba = create_compute_shader(device, src, "CSRgba")?;
+            let cs_ycbcr = create_compute_shader(device, src, "CSYCbCr")?;
+
+            let params_desc = D3D11_BUFFER_DESC {
+                ByteWidth: std::mem::size_of::<HistogramConstants>().next_multiple_of(16) as u32,
+                Usage: D3D11_USAGE_DYNAMIC,
+                BindFlags: D3D11_BIND_CONSTANT_BUFFER.0 as u32,
+                CPUAccessFlags: D3D11_CPU_ACCESS_WRITE.0 as u32,
+                ..Default::default()
+            };
+            let mut params: Option<ID3D11Buffer> = None;
+            device.CreateBuffer(&params_desc, None, Some(&mut params))?;
+
+            let bins_desc = D3D11_BUFFER_DESC {
+                ByteWidth: 256 * 4,
+                Usage: D3D11_USAGE_DEFAULT,
+                BindFlags: D3D11_BIND_UNORDERED_ACCESS.0 as u32,
+                MiscFlags: D3D11_RESOURCE_MISC_BUFFER_ALLOW_RAW_VIEWS.0 as u32,
+                ..Default::default()
+            };
+            let mut bins: Option<ID3D11Buffer> = None;
+            device.CreateBuffer(&bins_desc, None, Some(&mut bins))?;
+            let bins = bins.unwrap();
+
+            let uav_desc = D3D11_UNORDERED_ACCESS_VIEW_DESC {
+                Format: DXGI_FORMAT_R32_TYPELESS,
+                ViewDimension: D3D11_UAV_DIMENSION_BUFFER,
+                Anonymous: D3D11_UNORDERED_ACCESS_VIEW_DESC_0 {
+                    Buffer: D3D11_BUFFER_UAV {
+                        FirstElement: 0,
+                        NumElements: 256,
+                        Flags: D3D11_BUFFER_UAV_FLAG_RAW.0 as u32,
+                    },
+                },
+            };
+            let mut bins_uav: Option<ID3D11UnorderedAccessView> = None;
+            device.CreateUnorderedAccessView(&bins, Some(&uav_desc), Some(&mut bins_uav))?;
+
+            let staging_desc = D3D11_BUFFER_DESC {
+                ByteWidth: 256 * 4,
+                Usage: D3D11_USAGE_STAGING,
+                CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+                ..Default::default()
+            };
+            let mut staging: Option<ID3D11Buffer> = None;
+            device.CreateBuffer(&staging_desc, None, Some(&mut staging))?;
+
+            Ok(Self {
+                cs_rgba,
+                cs_ycbcr,
+                params: params.unwrap(),
+                bins,
+                bins_uav: bins_uav.unwrap(),
+                staging: staging.unwrap(),
+            })
+        }
+    }
 }
 
 use windows::Win32::Graphics::Direct3D::Fxc::*;
@@ -39,13 +168,65 @@ struct Vertex {
     tex_coord: [f32; 2],
 }
 
+/// 全画面クアッド (トライアングルストリップ) の頂点
+/// UV はページの回転・反転に合わせて入れ替える (位置は固定でビューポートで配置する)
+fn quad_vertices(transform: &PageTransform) -> [Vertex; 4] {
+    // 左上, 右上, 左下, 右下
+    let corners = [(-1.0, 1.0, 0.0, 0.0), (1.0, 1.0, 1.0, 0.0), (-1.0, -1.0, 0.0, 1.0), (1.0, -1.0, 1.0, 1.0)];
+    corners.map(|(x, y, u, v)| Vertex {
+        position: [x, y, 0.0],
+        tex_coord: transform.source_uv(u, v),
+    })
+}
+
+/// ページめくり用に横へ PageCurl::SEGMENTS 分割したメッシュ (トライアングルストリップ)
+/// 位置はページ内の 0.0〜1.0 で、見せる範囲 visible (left, top, right, bottom) だけを覆う
+/// めくれて重なる部分が後から描かれるよう、めくり始めの辺から遠い列から並べる
+fn curl_vertices(visible: [f32; 4], transform: &PageTransform, from_right: bool) -> Vec<Vertex> {
+    let [left, top, right, bottom] = visible;
+    (0..=PageCurl::SEGMENTS)
+        .map(|i| i as f32 / PageCurl::SEGMENTS as f32)
+        .map(|t| if from_right { t } else { 1.0 - t })
+        .flat_map(|t| {
+            let x = left + (right - left) * t;
+            [(x, top), (x, bottom)]
+        })
+        .map(|(x, y)| Vertex { position: [x, y, 0.0], tex_coord: transform.source_uv(x, y) })
+        .collect()
+}
+
+/// page_curl.hlsl の CurlParams (b2) に対応
+#[repr(C)]
+struct CurlConstants {
+    dest_rect: [f32; 4],
+    area: [f32; 4],
+    target_size: [f32; 2],
+    fold: f32,
+    radius: f32,
+    from_right: f32,
+    _padding: [f32; 3],
+}
+
 #[repr(C)]
 struct YCbCrConstants {
     color_matrix: [[f32; 4]; 4],
     offset: [f32; 4],
     scale: [f32; 4],
     interpolation_mode: i32,
-    _padding: [i32; 3], // 16バイトアライメント用パディング
+    chroma_mode: i32,   // 0=Bilinear, 1=Catmull-Rom
+    _padding: [i32; 2], // 16バイトアライメント用パディング
+}
+
+/// シェーダの AdjustParams (b1) に対応
+#[repr(C)]
+struct AdjustConstants {
+    brightness: f32,
+    contrast: f32,
+    gamma: f32,
+    invert: f32,
+    tint: [f32; 4],
+    levels: [f32; 2],
+    _levels_padding: [f32; 2], // 16バイトアライメント用パディング
 }
 
 fn compile_shader(source: &[u8], entry_point: &str, target: &str) -> Result<ID3DBlob> {
@@ -97,27 +278,7 @@ impl Renderer for D3D11Renderer {
     }
 
     fn begin_draw(&self) {
-        unsafe {
-            let rtv = self.render_target_view.clone();
-            // 背景色 (ダークグレー)
-            let clear_color = [0.1, 0.1, 0.1, 1.0];
-            self.context.ClearRenderTargetView(&rtv, &clear_color);
-
-            // ビューポートをバックバッファ全体に設定
-            let mut desc = D3D11_TEXTURE2D_DESC::default();
-            let back_buffer: ID3D11Texture2D = self.swap_chain.GetBuffer(0).unwrap();
-            back_buffer.GetDesc(&mut desc);
-
-            let viewport = D3D11_VIEWPORT {
-                TopLeftX: 0.0,
-                TopLeftY: 0.0,
-                Width: desc.Width as f32,
-                Height: desc.Height as f32,
-                MinDepth: 0.0,
-                MaxDepth: 1.0,
-            };
-            self.context.RSSetViewports(Some(&[viewport]));
-        }
+        self.clear_target();
     }
 
     fn end_draw(&self) -> std::result::Result<(), Box<dyn std::error::Error>> {
@@ -132,75 +293,384 @@ impl Renderer for D3D11Renderer {
         &self,
         image: &DecodedImage,
     ) -> std::result::Result<TextureHandle, Box<dyn std::error::Error>> {
-        match &image.pixel_data {
-            PixelData::Rgba8(data) => {
-                let srv = self.create_rgba_texture(image.width, image.height, data)?;
-                Ok(TextureHandle::D3D11Rgba(srv))
-            }
-            PixelData::Ycbcr {
-                planes,
-                subsampling,
-                precision,
-                y_is_signed,
-                c_is_signed,
-            } => {
-                if planes.len() != 3 {
-                    return Err("Invalid plane count for YCbCr".into());
+        Self::upload_to_device(&self.device, image)
+    }
+
+    fn draw_image(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F) {
+        self.draw_texture(texture, dest_rect, &self.color_adjustment, &self.page_transform, None, None);
+    }
+
+    fn draw_image_clipped(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F) {
+        self.draw_texture(texture, dest_rect, &self.color_adjustment, &self.page_transform, Some(clip_rect), None);
+    }
+
+    fn draw_page_turn(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F, curl: &PageCurl) {
+        self.draw_texture(
+            texture,
+            dest_rect,
+            &self.color_adjustment,
+            &self.page_transform,
+            Some(&curl.area),
+            Some((clip_rect, curl)),
+        );
+    }
+
+    fn get_texture_size(&self, texture: &TextureHandle) -> (f32, f32) {
+        match texture {
+            TextureHandle::D3D11Rgba(srv) => unsafe {
+                if let Ok(res) = srv.GetResource() {
+                    let texture2d: ID3D11Texture2D = res.cast().unwrap();
+                    let mut desc = D3D11_TEXTURE2D_DESC::default();
+                    texture2d.GetDesc(&mut desc);
+                    (desc.Width as f32, desc.Height as f32)
+                } else {
+                    (0.0, 0.0)
                 }
+            },
+            TextureHandle::D3D11YCbCr { width, height, .. } => (*width as f32, *height as f32),
+            _ => (0.0, 0.0),
+        }
+    }
 
-                let y_srv = self.create_r32_texture(image.width, image.height, &planes[0])?;
-                let (dx, dy) = *subsampling;
-                let c_width = (image.width + dx as u32 - 1) / dx as u32;
-                let c_height = (image.height + dy as u32 - 1) / dy as u32;
+    fn fill_rectangle(&self, _rect: &D2D_RECT_F, _color: &D2D1_COLOR_F) {}
 
-                let cb_srv = self.create_r32_texture(c_width, c_height, &planes[1])?;
-                let cr_srv = self.create_r32_texture(c_width, c_height, &planes[2])?;
+    fn draw_rectangle(&self, _rect: &D2D_RECT_F, _color: &D2D1_COLOR_F, _stroke_width: f32) {}
 
-                Ok(TextureHandle::D3D11YCbCr {
-                    y: y_srv,
-                    cb: cb_srv,
-                    cr: cr_srv,
-                    width: image.width,
-                    height: image.height,
-                    _subsampling: *subsampling,
-                    _precision: *precision,
-                    _y_is_signed: *y_is_signed,
-                    _c_is_signed: *c_is_signed,
-                })
+    fn draw_polyline(&self, _points: &[(f32, f32)], _color: &D2D1_COLOR_F, _stroke_width: f32) {}
+
+    fn draw_text(&self, text: &str, rect: &D2D_RECT_F, color: &D2D1_COLOR_F, large: bool) {
+        self.draw_text_internal(text, rect, color, large);
+    }
+
+    fn set_interpolation_mode(&mut self, mode: InterpolationMode) {
+        self.interpolation_mode = mode;
+    }
+
+    fn set_chroma_upsampling(&mut self, mode: ChromaUpsampling) {
+        self.chroma_upsampling = mode;
+    }
+
+    fn set_color_adjustment(&mut self, adjustment: ColorAdjustment) {
+        self.color_adjustment = adjustment;
+    }
+
+    fn set_page_transform(&mut self, transform: PageTransform) {
+        self.page_transform = transform;
+    }
+
+    fn set_text_alignment(&self, alignment: DWRITE_TEXT_ALIGNMENT) {
+        self.text_alignment
+            .store(alignment.0, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn set_ui_scale(&mut self, scale: f32) {
+        self.ui_scale = scale;
+    }
+
+    fn set_background_color(&mut self, color: D2D1_COLOR_F) {
+        self.background_color = color;
+    }
+
+    fn compute_histogram(&self, texture: &TextureHandle) -> Option<[u32; 256]> {
+        let pipeline = self.histogram.as_ref()?;
+        let (width, height) = self.get_texture_size(texture);
+        let (width, height) = (width as u32, height as u32);
+        if width == 0 || height == 0 {
+            return None;
+        }
+        unsafe {
+            let constants = match texture {
+                TextureHandle::D3D11Rgba(srv) => {
+                    self.context.CSSetShader(&pipeline.cs_rgba, None);
+                    self.context.CSSetShaderResources(0, Some(&[Some(srv.clone())]));
+                    HistogramConstants { size: [width, height], y_scale: 1.0, y_bias: 0.0 }
+                }
+                TextureHandle::D3D11YCbCr { y, _precision: precision, _y_is_signed: y_is_signed, .. } => {
+                    self.context.CSSetShader(&pipeline.cs_ycbcr, None);
+                    self.context.CSSetShaderResources(1, Some(&[Some(y.clone())]));
+                    let max_val = ((1u32 << precision) - 1).max(1) as f32;
+                    let bias = if *y_is_signed { (1u32 << precision.saturating_sub(1)) as f32 } else { 0.0 };
+                    HistogramConstants { size: [width, height], y_scale: 1.0 / max_val, y_bias: bias }
+                }
+                _ => return None,
+            };
+
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            self.context
+                .Map(&pipeline.params, 0, D3D11_MAP_WRITE_DISCARD, 0, Some(&mut mapped))
+                .ok()?;
+            std::ptr::copy_nonoverlapping(&constants, mapped.pData as *mut HistogramConstants, 1);
+            self.context.Unmap(&pipeline.params, 0);
+
+            self.context.ClearUnorderedAccessViewUint(&pipeline.bins_uav, &[0; 4]);
+            self.context.CSSetConstantBuffers(0, Some(&[Some(pipeline.params.clone())]));
+            let uav = Some(pipeline.bins_uav.clone());
+            self.context.CSSetUnorderedAccessViews(0, 1, Some(&uav), None);
+            self.context.Dispatch(width.div_ceil(16), height.div_ceil(16), 1);
+
+            // 描画パイプラインと競合しないようバインドを外してから読み出す
+            let no_uav: Option<ID3D11UnorderedAccessView> = None;
+            self.context.CSSetUnorderedAccessViews(0, 1, Some(&no_uav), None);
+            self.context.CSSetShaderResources(0, Some(&[None, None]));
+            self.context.CopyResource(&pipeline.staging, &pipeline.bins);
+
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            self.context
+                .Map(&pipeline.staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+                .ok()?;
+            let mut histogram = [0u32; 256];
+            std::ptr::copy_nonoverlapping(mapped.pData as *const u32, histogram.as_mut_ptr(), 256);
+            self.context.Unmap(&pipeline.staging, 0);
+            Some(histogram)
+        }
+    }
+
+    fn query_video_memory(&self) -> Option<super::VideoMemoryInfo> {
+        let device: IDXGIDevice = self.device.cast().ok()?;
+        super::query_dxgi_video_memory(&device)
+    }
+
+    fn create_staged_texture(&self, _image: &DecodedImage) -> Option<TextureHandle> {
+        // D3D11 はテクスチャ作成と同時に転送するため段階転送は行わない (先読みは texture_uploader で別スレッドから転送する)
+        None
+    }
+
+    fn upload_rows(
+        &self,
+        _texture: &TextureHandle,
+        _image: &DecodedImage,
+        _start_row: u32,
+        _rows: u32,
+    ) -> std::result::Result<u32, Box<dyn std::error::Error>> {
+        Err("staged upload is not supported on Direct3D 11".into())
+    }
+
+    fn texture_uploader(&self) -> Option<Box<dyn super::TextureUploader>> {
+        Some(Box::new(D3D11Uploader { device: self.device.clone() }))
+    }
+
+    fn set_mirror_target(&mut self, hwnd: Option<HWND>) -> bool {
+        self.mirror = None;
+        let Some(hwnd) = hwnd else { return true; };
+        let target = super::create_sibling_swap_chain(&self.swap_chain, hwnd).and_then(|swap_chain| {
+            let render_target_view = create_render_target_view(&self.device, &swap_chain)?;
+            Ok(MirrorTarget { swap_chain, render_target_view })
+        });
+        match target {
+            Ok(target) => {
+                self.mirror = Some(target);
+                true
+            }
+            Err(e) => {
+                println!("[D3D11] 出力ウィンドウの描画先を作成できません: {}", e);
+                false
             }
         }
     }
 
-    fn draw_image(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F) {
+    fn resize_mirror(&mut self, width: u32, height: u32) {
+        let Some(mirror) = self.mirror.take() else { return; };
+        let swap_chain = mirror.swap_chain;
+        // バックバッファへの参照をすべて外してから作り直す
+        drop(mirror.render_target_view);
+        let result = unsafe {
+            self.context.OMSetRenderTargets(None, None);
+            swap_chain
+                .ResizeBuffers(0, width.max(1), height.max(1), DXGI_FORMAT_UNKNOWN, DXGI_SWAP_CHAIN_FLAG(0))
+                .and_then(|_| create_render_target_view(&self.device, &swap_chain))
+        };
+        match result {
+            Ok(render_target_view) => self.mirror = Some(MirrorTarget { swap_chain, render_target_view }),
+            Err(e) => println!("[D3D11] 出力ウィンドウの描画先を作り直せません: {}", e),
+        }
+    }
+
+    fn begin_mirror_draw(&self) {
+        if self.mirror.is_some() {
+            self.drawing_mirror.store(true, std::sync::atomic::Ordering::Relaxed);
+            self.clear_target();
+        }
+    }
+
+    fn end_mirror_draw(&self) {
+        if !self.drawing_mirror.swap(false, std::sync::atomic::Ordering::Relaxed) {
+            return;
+        }
+        if let Some(mirror) = self.mirror.as_ref() {
+            // メインウィンドウの表示を待たせないよう垂直同期は待たない
+            unsafe {
+                let _ = mirror.swap_chain.Present(0, DXGI_PRESENT(0));
+            }
+        }
+    }
+}
+
+/// 別スレッドでテクスチャを作成するアップローダー
+/// デバイスは D3D11_CREATE_DEVICE_SINGLETHREADED を付けずに作成しているため、リソースの作成はどのスレッドからでも行える
+/// (描画に使うイミディエイトコンテキストには触れない)
+struct D3D11Uploader {
+    device: ID3D11Device,
+}
+
+impl super::TextureUploader for D3D11Uploader {
+    fn upload(&self, image: &DecodedImage) -> std::result::Result<TextureHandle, Box<dyn std::error::Error>> {
+        D3D11Renderer::upload_to_device(&self.device, image)
+    }
+}
+
+impl D3D11Renderer {
+    /// 描画先 (出力ウィンドウへ描画中ならそのスワップチェーン) とレンダーターゲット
+    fn target(&self) -> (&IDXGISwapChain1, &ID3D11RenderTargetView) {
+        match self.mirror.as_ref() {
+            Some(mirror) if self.drawing_mirror.load(std::sync::atomic::Ordering::Relaxed) => {
+                (&mirror.swap_chain, &mirror.render_target_view)
+            }
+            _ => (&self.swap_chain, &self.render_target_view),
+        }
+    }
+
+    /// 描画先を背景色で塗りつぶし、ビューポートをバックバッファ全体にする
+    fn clear_target(&self) {
+        let (swap_chain, rtv) = self.target();
         unsafe {
-            // ビューポートを描画領域に合わせて設定
+            // 背景色 (設定のテーマ)
+            let c = self.background_color;
+            let clear_color = [c.r, c.g, c.b, 1.0];
+            self.context.ClearRenderTargetView(rtv, &clear_color);
+
+            // ビューポートをバックバッファ全体に設定
+            let mut desc = D3D11_TEXTURE2D_DESC::default();
+            let back_buffer: ID3D11Texture2D = swap_chain.GetBuffer(0).unwrap();
+            back_buffer.GetDesc(&mut desc);
+
             let viewport = D3D11_VIEWPORT {
-                TopLeftX: dest_rect.left,
-                TopLeftY: dest_rect.top,
-                Width: dest_rect.right - dest_rect.left,
-                Height: dest_rect.bottom - dest_rect.top,
+                TopLeftX: 0.0,
+                TopLeftY: 0.0,
+                Width: desc.Width as f32,
+                Height: desc.Height as f32,
                 MinDepth: 0.0,
                 MaxDepth: 1.0,
             };
             self.context.RSSetViewports(Some(&[viewport]));
-            self.context.RSSetState(&self.rasterizer_state);
+        }
+    }
+
+    /// テクスチャを描画する (テキスト描画では色調整・回転を無効にして呼ぶ)
+    /// clip を指定するとシザー矩形の範囲だけをラスタライズする
+    /// curl (見せる範囲, めくりの状態) を指定すると、四角形の代わりに変形したメッシュで描く
+    fn draw_texture(
+        &self,
+        texture: &TextureHandle,
+        dest_rect: &D2D_RECT_F,
+        adjustment: &ColorAdjustment,
+        transform: &PageTransform,
+        clip: Option<&D2D_RECT_F>,
+        curl: Option<(&D2D_RECT_F, &PageCurl)>,
+    ) {
+        let (swap_chain, render_target_view) = self.target();
+        unsafe {
+            // ビューポートを描画領域に合わせて設定 (ページめくりはメッシュが描画領域の外へ動くため全体)
+            let viewport = match curl {
+                Some(_) => {
+                    let desc = swap_chain.GetDesc1().unwrap_or_default();
+                    D3D11_VIEWPORT {
+                        TopLeftX: 0.0,
+                        TopLeftY: 0.0,
+                        Width: desc.Width as f32,
+                        Height: desc.Height as f32,
+                        MinDepth: 0.0,
+                        MaxDepth: 1.0,
+                    }
+                }
+                None => D3D11_VIEWPORT {
+                    TopLeftX: dest_rect.left,
+                    TopLeftY: dest_rect.top,
+                    Width: dest_rect.right - dest_rect.left,
+                    Height: dest_rect.bottom - dest_rect.top,
+                    MinDepth: 0.0,
+                    MaxDepth: 1.0,
+                },
+            };
+            self.context.RSSetViewports(Some(&[viewport]));
+            match clip {
+                Some(clip) => {
+                    let scissor = RECT {
+                        left: clip.left.floor() as i32,
+                        top: clip.top.floor() as i32,
+                        right: clip.right.ceil() as i32,
+                        bottom: clip.bottom.ceil() as i32,
+                    };
+                    self.context.RSSetScissorRects(Some(&[scissor]));
+                    self.context.RSSetState(&self.rasterizer_state_scissor);
+                }
+                None => self.context.RSSetState(&self.rasterizer_state),
+            }
 
             // レンダーターゲット設定
-            let rtv = [Some(self.render_target_view.clone())];
+            let rtv = [Some(render_target_view.clone())];
             self.context.OMSetRenderTargets(Some(&rtv), None);
 
             // シェーダー設定
-            self.context.VSSetShader(&self.vertex_shader, None);
+            match curl {
+                Some((_, curl)) => {
+                    self.context.VSSetShader(&self.curl_vertex_shader, None);
+                    let constants = CurlConstants {
+                        dest_rect: [dest_rect.left, dest_rect.top, dest_rect.right, dest_rect.bottom],
+                        area: [curl.area.left, curl.area.top, curl.area.right, curl.area.bottom],
+                        target_size: [viewport.Width.max(1.0), viewport.Height.max(1.0)],
+                        fold: curl.fold(),
+                        radius: PageCurl::RADIUS,
+                        from_right: if curl.from_right { 1.0 } else { 0.0 },
+                        _padding: [0.0; 3],
+                    };
+                    let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+                    if self
+                        .context
+                        .Map(&self.curl_buffer, 0, D3D11_MAP_WRITE_DISCARD, 0, Some(&mut mapped))
+                        .is_ok()
+                    {
+                        std::ptr::copy_nonoverlapping(&constants, mapped.pData as *mut CurlConstants, 1);
+                        self.context.Unmap(&self.curl_buffer, 0);
+                    }
+                    self.context
+                        .VSSetConstantBuffers(2, Some(&[Some(self.curl_buffer.clone())]));
+                }
+                None => self.context.VSSetShader(&self.vertex_shader, None),
+            }
 
             // Input Layout
             self.context.IASetInputLayout(&self.input_layout);
             self.context
                 .IASetPrimitiveTopology(D3D11_PRIMITIVE_TOPOLOGY_TRIANGLESTRIP);
 
-            // Vertex Buffer
+            // Vertex Buffer (回転・反転は UV の入れ替えで表現する)
+            let (vertices, vertex_buffer) = match curl {
+                Some((clip_rect, curl)) => {
+                    let width = (dest_rect.right - dest_rect.left).max(1.0);
+                    let height = (dest_rect.bottom - dest_rect.top).max(1.0);
+                    let visible = [
+                        ((clip_rect.left - dest_rect.left) / width).clamp(0.0, 1.0),
+                        ((clip_rect.top - dest_rect.top) / height).clamp(0.0, 1.0),
+                        ((clip_rect.right - dest_rect.left) / width).clamp(0.0, 1.0),
+                        ((clip_rect.bottom - dest_rect.top) / height).clamp(0.0, 1.0),
+                    ];
+                    (curl_vertices(visible, transform, curl.from_right), &self.curl_vertex_buffer)
+                }
+                None => (quad_vertices(transform).to_vec(), &self.vertex_buffer),
+            };
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            if self
+                .context
+                .Map(vertex_buffer, 0, D3D11_MAP_WRITE_DISCARD, 0, Some(&mut mapped))
+                .is_ok()
+            {
+                std::ptr::copy_nonoverlapping(vertices.as_ptr(), mapped.pData as *mut Vertex, vertices.len());
+                self.context.Unmap(vertex_buffer, 0);
+            }
+            let vertex_count = vertices.len() as u32;
             let stride = std::mem::size_of::<Vertex>() as u32;
             let offset = 0;
-            let buffers = [Some(self.vertex_buffer.clone())];
+            let buffers = [Some(vertex_buffer.clone())];
             self.context.IASetVertexBuffers(
                 0,
                 1,
@@ -217,12 +687,37 @@ impl Renderer for D3D11Renderer {
             self.context
                 .PSSetSamplers(0, Some(&[Some(sampler.clone())]));
 
+            // 色調整 (RGBA / YCbCr 共通で b1 に設定)
+            let adjust = AdjustConstants {
+                brightness: adjustment.brightness,
+                contrast: adjustment.contrast,
+                gamma: adjustment.gamma,
+                invert: if adjustment.invert { 1.0 } else { 0.0 },
+                tint: {
+                    let [r, g, b] = adjustment.tint();
+                    [r, g, b, 1.0]
+                },
+                levels: [adjustment.black_point, adjustment.white_point],
+                _levels_padding: [0.0; 2],
+            };
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            if self
+                .context
+                .Map(&self.adjust_buffer, 0, D3D11_MAP_WRITE_DISCARD, 0, Some(&mut mapped))
+                .is_ok()
+            {
+                std::ptr::copy_nonoverlapping(&adjust, mapped.pData as *mut AdjustConstants, 1);
+                self.context.Unmap(&self.adjust_buffer, 0);
+            }
+            self.context
+                .PSSetConstantBuffers(1, Some(&[Some(self.adjust_buffer.clone())]));
+
             match texture {
                 TextureHandle::D3D11Rgba(srv) => {
                     self.context.PSSetShader(&self.pixel_shader_rgba, None);
                     self.context
                         .PSSetShaderResources(0, Some(&[Some(srv.clone())]));
-                    self.context.Draw(4, 0);
+                    self.context.Draw(vertex_count, 0);
                 }
                 TextureHandle::D3D11YCbCr {
                     y,
@@ -258,7 +753,11 @@ impl Renderer for D3D11Renderer {
                             InterpolationMode::Cubic => 2,
                             InterpolationMode::Lanczos => 3,
                         },
-                        _padding: [0, 0, 0],
+                        chroma_mode: match self.chroma_upsampling {
+                            ChromaUpsampling::Bilinear => 0,
+                            ChromaUpsampling::CatmullRom => 1,
+                        },
+                        _padding: [0, 0],
                     };
 
                     let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
@@ -281,49 +780,13 @@ impl Renderer for D3D11Renderer {
                     self.context
                         .PSSetConstantBuffers(0, Some(&[Some(self.constant_buffer.clone())]));
 
-                    self.context.Draw(4, 0);
+                    self.context.Draw(vertex_count, 0);
                 }
                 _ => {}
             }
         }
     }
 
-    fn get_texture_size(&self, texture: &TextureHandle) -> (f32, f32) {
-        match texture {
-            TextureHandle::D3D11Rgba(srv) => unsafe {
-                if let Ok(res) = srv.GetResource() {
-                    let texture2d: ID3D11Texture2D = res.cast().unwrap();
-                    let mut desc = D3D11_TEXTURE2D_DESC::default();
-                    texture2d.GetDesc(&mut desc);
-                    (desc.Width as f32, desc.Height as f32)
-                } else {
-                    (0.0, 0.0)
-                }
-            },
-            TextureHandle::D3D11YCbCr { width, height, .. } => (*width as f32, *height as f32),
-            _ => (0.0, 0.0),
-        }
-    }
-
-    fn fill_rectangle(&self, _rect: &D2D_RECT_F, _color: &D2D1_COLOR_F) {}
-
-    fn draw_rectangle(&self, _rect: &D2D_RECT_F, _color: &D2D1_COLOR_F, _stroke_width: f32) {}
-
-    fn draw_text(&self, text: &str, rect: &D2D_RECT_F, color: &D2D1_COLOR_F, large: bool) {
-        self.draw_text_internal(text, rect, color, large);
-    }
-
-    fn set_interpolation_mode(&mut self, mode: InterpolationMode) {
-        self.interpolation_mode = mode;
-    }
-
-    fn set_text_alignment(&self, alignment: DWRITE_TEXT_ALIGNMENT) {
-        self.text_alignment
-            .store(alignment.0, std::sync::atomic::Ordering::Relaxed);
-    }
-}
-
-impl D3D11Renderer {
     pub fn new(hwnd: HWND) -> Result<Self> {
         unsafe {
             let mut device: Option<ID3D11Device> = None;
@@ -369,10 +832,7 @@ impl D3D11Renderer {
                 dxgi_factory.CreateSwapChainForHwnd(&device, hwnd, &swap_chain_desc, None, None)?;
 
             // Create RenderTargetView
-            let back_buffer: ID3D11Texture2D = swap_chain.GetBuffer(0)?;
-            let mut rtv: Option<ID3D11RenderTargetView> = None;
-            device.CreateRenderTargetView(&back_buffer, None, Some(&mut rtv))?;
-            let render_target_view = rtv.unwrap();
+            let render_target_view = create_render_target_view(&device, &swap_chain)?;
 
             // --- Shader Compilation & Resource Creation ---
             let quad_src = include_bytes!("shaders/texture_quad.hlsl");
@@ -390,6 +850,20 @@ impl D3D11Renderer {
             )?;
             let vertex_shader = vertex_shader.unwrap();
 
+            // ページめくり (入力レイアウトは VSMain と共通)
+            let curl_src = include_bytes!("shaders/page_curl.hlsl");
+            let curl_vs_blob = compile_shader(curl_src, "VSCurl", "vs_5_0")?;
+            let mut curl_vertex_shader: Option<ID3D11VertexShader> = None;
+            device.CreateVertexShader(
+                std::slice::from_raw_parts(
+                    curl_vs_blob.GetBufferPointer() as *const u8,
+                    curl_vs_blob.GetBufferSize(),
+                ),
+                None,
+                Some(&mut curl_vertex_shader),
+            )?;
+            let curl_vertex_shader = curl_vertex_shader.unwrap();
+
             let ps_rgba_blob = compile_shader(quad_src, "PSMain", "ps_5_0")?;
             let mut pixel_shader_rgba: Option<ID3D11PixelShader> = None;
             device.CreatePixelShader(
@@ -448,28 +922,13 @@ impl D3D11Renderer {
             let input_layout = input_layout.unwrap();
 
             // Vertex Buffer (Full screen quad)
-            let vertices = [
-                Vertex {
-                    position: [-1.0, 1.0, 0.0],
-                    tex_coord: [0.0, 0.0],
-                },
-                Vertex {
-                    position: [1.0, 1.0, 0.0],
-                    tex_coord: [1.0, 0.0],
-                },
-                Vertex {
-                    position: [-1.0, -1.0, 0.0],
-                    tex_coord: [0.0, 1.0],
-                },
-                Vertex {
-                    position: [1.0, -1.0, 0.0],
-                    tex_coord: [1.0, 1.0],
-                },
-            ];
+            // ページの回転・反転に合わせて描画ごとに UV を書き換えるため DYNAMIC にする
+            let vertices = quad_vertices(&PageTransform::default());
             let vb_desc = D3D11_BUFFER_DESC {
                 ByteWidth: (std::mem::size_of::<Vertex>() * vertices.len()) as u32,
-                Usage: D3D11_USAGE_IMMUTABLE,
+                Usage: D3D11_USAGE_DYNAMIC,
                 BindFlags: D3D11_BIND_VERTEX_BUFFER.0 as u32,
+                CPUAccessFlags: D3D11_CPU_ACCESS_WRITE.0 as u32,
                 ..Default::default()
             };
             let vb_data = D3D11_SUBRESOURCE_DATA {
@@ -480,6 +939,14 @@ impl D3D11Renderer {
             device.CreateBuffer(&vb_desc, Some(&vb_data), Some(&mut vertex_buffer))?;
             let vertex_buffer = vertex_buffer.unwrap();
 
+            let curl_vb_desc = D3D11_BUFFER_DESC {
+                ByteWidth: (std::mem::size_of::<Vertex>() * (PageCurl::SEGMENTS + 1) * 2) as u32,
+                ..vb_desc
+            };
+            let mut curl_vertex_buffer: Option<ID3D11Buffer> = None;
+            device.CreateBuffer(&curl_vb_desc, None, Some(&mut curl_vertex_buffer))?;
+            let curl_vertex_buffer = curl_vertex_buffer.unwrap();
+
             // Constant Buffer
             let cb_desc = D3D11_BUFFER_DESC {
                 ByteWidth: std::mem::size_of::<YCbCrConstants>() as u32,
@@ -492,6 +959,22 @@ impl D3D11Renderer {
             device.CreateBuffer(&cb_desc, None, Some(&mut constant_buffer))?;
             let constant_buffer = constant_buffer.unwrap();
 
+            let adjust_cb_desc = D3D11_BUFFER_DESC {
+                ByteWidth: std::mem::size_of::<AdjustConstants>() as u32,
+                ..cb_desc
+            };
+            let mut adjust_buffer: Option<ID3D11Buffer> = None;
+            device.CreateBuffer(&adjust_cb_desc, None, Some(&mut adjust_buffer))?;
+            let adjust_buffer = adjust_buffer.unwrap();
+
+            let curl_cb_desc = D3D11_BUFFER_DESC {
+                ByteWidth: std::mem::size_of::<CurlConstants>() as u32,
+                ..cb_desc
+            };
+            let mut curl_buffer: Option<ID3D11Buffer> = None;
+            device.CreateBuffer(&curl_cb_desc, None, Some(&mut curl_buffer))?;
+            let curl_buffer = curl_buffer.unwrap();
+
             // Samplers
             let sampler_desc = D3D11_SAMPLER_DESC {
                 Filter: D3D11_FILTER_MIN_MAG_MIP_LINEAR,
@@ -524,31 +1007,104 @@ impl D3D11Renderer {
             let mut rasterizer_state: Option<ID3D11RasterizerState> = None;
             device.CreateRasterizerState(&rs_desc, Some(&mut rasterizer_state))?;
             let rasterizer_state = rasterizer_state.unwrap();
+            let rs_scissor_desc = D3D11_RASTERIZER_DESC {
+                ScissorEnable: true.into(),
+                ..rs_desc
+            };
+            let mut rasterizer_state_scissor: Option<ID3D11RasterizerState> = None;
+            device.CreateRasterizerState(&rs_scissor_desc, Some(&mut rasterizer_state_scissor))?;
+            let rasterizer_state_scissor = rasterizer_state_scissor.unwrap();
+
+            // コンピュートシェーダは機能レベル 11.0 以上が必要。作れなければ CPU で集計する
+            let histogram = match HistogramPipeline::new(&device) {
+                Ok(pipeline) => Some(pipeline),
+                Err(e) => {
+                    println!("[D3D11] ヒストグラム集計シェーダを作成できません (CPU で集計します): {}", e);
+                    None
+                }
+            };
 
             Ok(Self {
                 device,
                 context,
                 swap_chain,
                 render_target_view,
+                mirror: None,
+                drawing_mirror: std::sync::atomic::AtomicBool::new(false),
                 vertex_shader,
+                curl_vertex_shader,
                 input_layout,
                 pixel_shader_rgba,
                 pixel_shader_ycbcr,
                 vertex_buffer,
+                curl_vertex_buffer,
                 constant_buffer,
+                adjust_buffer,
+                curl_buffer,
                 sampler_linear,
                 sampler_nearest,
                 rasterizer_state,
+                rasterizer_state_scissor,
                 interpolation_mode: InterpolationMode::Linear,
+                chroma_upsampling: ChromaUpsampling::Bilinear,
+                color_adjustment: ColorAdjustment::default(),
+                page_transform: PageTransform::default(),
+                background_color: D2D1_COLOR_F { r: 0.1, g: 0.1, b: 0.1, a: 1.0 },
                 text_alignment: std::sync::atomic::AtomicI32::new(
                     windows::Win32::Graphics::DirectWrite::DWRITE_TEXT_ALIGNMENT_LEADING.0,
                 ),
+                ui_scale: 1.0,
+                histogram,
             })
         }
     }
 
+    /// DecodedImage からテクスチャを作成する (描画スレッドと別スレッドのアップローダーで共用)
+    fn upload_to_device(
+        device: &ID3D11Device,
+        image: &DecodedImage,
+    ) -> std::result::Result<TextureHandle, Box<dyn std::error::Error>> {
+        match &image.pixel_data {
+            PixelData::Rgba8(data) => {
+                let srv = Self::create_rgba_texture(device, image.width, image.height, data)?;
+                Ok(TextureHandle::D3D11Rgba(srv))
+            }
+            PixelData::Ycbcr {
+                planes,
+                subsampling,
+                precision,
+                y_is_signed,
+                c_is_signed,
+            } => {
+                if planes.len() != 3 {
+                    return Err("Invalid plane count for YCbCr".into());
+                }
+
+                let y_srv = Self::create_r32_texture(device, image.width, image.height, &planes[0])?;
+                let (dx, dy) = *subsampling;
+                let c_width = (image.width + dx as u32 - 1) / dx as u32;
+                let c_height = (image.height + dy as u32 - 1) / dy as u32;
+
+                let cb_srv = Self::create_r32_texture(device, c_width, c_height, &planes[1])?;
+                let cr_srv = Self::create_r32_texture(device, c_width, c_height, &planes[2])?;
+
+                Ok(TextureHandle::D3D11YCbCr {
+                    y: y_srv,
+                    cb: cb_srv,
+                    cr: cr_srv,
+                    width: image.width,
+                    height: image.height,
+                    _subsampling: *subsampling,
+                    _precision: *precision,
+                    _y_is_signed: *y_is_signed,
+                    _c_is_signed: *c_is_signed,
+                })
+            }
+        }
+    }
+
     pub fn create_r32_texture(
-        &self,
+        device: &ID3D11Device,
         width: u32,
         height: u32,
         data: &[i32],
@@ -577,7 +1133,7 @@ impl D3D11Renderer {
             };
 
             let mut texture: Option<ID3D11Texture2D> = None;
-            self.device
+            device
                 .CreateTexture2D(&desc, Some(&init_data), Some(&mut texture))?;
             let texture = texture.unwrap();
 
@@ -593,7 +1149,7 @@ impl D3D11Renderer {
             };
 
             let mut srv: Option<ID3D11ShaderResourceView> = None;
-            self.device
+            device
                 .CreateShaderResourceView(&texture, Some(&srv_desc), Some(&mut srv))?;
             Ok(srv.unwrap())
         }
@@ -601,7 +1157,7 @@ impl D3D11Renderer {
 
     // ヘルパー: RGBA データからテクスチャを作成
     fn create_rgba_texture(
-        &self,
+        device: &ID3D11Device,
         width: u32,
         height: u32,
         data: &[u8],
@@ -629,11 +1185,11 @@ impl D3D11Renderer {
                 SysMemSlicePitch: 0,
             };
             let mut texture: Option<ID3D11Texture2D> = None;
-            self.device
+            device
                 .CreateTexture2D(&desc, Some(&init_data), Some(&mut texture))?;
 
             let mut srv: Option<ID3D11ShaderResourceView> = None;
-            self.device
+            device
                 .CreateShaderResourceView(&texture.unwrap(), None, Some(&mut srv))?;
             Ok(srv.unwrap())
         }
@@ -672,7 +1228,7 @@ impl D3D11Renderer {
             // バッファをクリア (完全透過)
             std::ptr::write_bytes(p_bits, 0, (width * height * 4) as usize);
 
-            let font_height = if large { 32 } else { 18 };
+            let font_height = ((if large { 32.0 } else { 18.0 }) * self.ui_scale).round() as i32;
             let weight = if large { FW_BOLD } else { FW_NORMAL };
             let hfont = CreateFontW(
                 font_height,
@@ -753,8 +1309,8 @@ impl D3D11Renderer {
             }
 
             // D3D11 テクスチャ作成
-            let texture_srv = self
-                .create_rgba_texture(
+            let texture_srv = Self::create_rgba_texture(
+                    &self.device,
                     width as u32,
                     height as u32,
                     std::slice::from_raw_parts(p_bits as *const u8, (width * height * 4) as usize),
@@ -762,8 +1318,8 @@ impl D3D11Renderer {
                 .unwrap();
             let texture_handle = TextureHandle::D3D11Rgba(texture_srv);
 
-            // 描画
-            self.draw_image(&texture_handle, rect);
+            // 描画 (文字色が変わらないよう色調整は適用しない)
+            self.draw_texture(&texture_handle, rect, &ColorAdjustment::default(), &PageTransform::default(), None, None);
 
             // cleanup
             let _ = SelectObject(hdc, old_font);
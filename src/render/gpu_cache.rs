@@ -0,0 +1,179 @@
+use super::TextureHandle;
+
+/// GPU キャッシュの上限 (0 = 制限なし)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuCacheLimits {
+    pub max_pages: usize,
+    pub max_bytes: u64,
+}
+
+/// 解放判定に使うエントリの情報 (ページ番号, 推定バイト数, 最終使用時刻)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryStat {
+    pub index: usize,
+    pub bytes: u64,
+    pub last_used: u64,
+}
+
+/// 上限に収めるために解放するページを、最後に使われたのが古い順に返す
+/// protected (表示中・先読み範囲) のページは上限を超えていても解放しない
+pub fn plan_eviction(entries: &[EntryStat], protected: &[usize], limits: GpuCacheLimits) -> Vec<usize> {
+    let mut pages = entries.len();
+    let mut bytes: u64 = entries.iter().map(|e| e.bytes).sum();
+    let over = |pages: usize, bytes: u64| {
+        (limits.max_pages > 0 && pages > limits.max_pages) || (limits.max_bytes > 0 && bytes > limits.max_bytes)
+    };
+
+    let mut candidates: Vec<&EntryStat> = entries.iter().filter(|e| !protected.contains(&e.index)).collect();
+    candidates.sort_by_key(|e| e.last_used);
+
+    let mut victims = Vec::new();
+    for entry in candidates {
+        if !over(pages, bytes) {
+            break;
+        }
+        pages -= 1;
+        bytes -= entry.bytes;
+        victims.push(entry.index);
+    }
+    victims
+}
+
+struct Entry {
+    index: usize,
+    texture: TextureHandle,
+    bytes: u64,
+    last_used: u64,
+}
+
+/// ページ番号ごとの GPU テクスチャ (LRU で解放する)
+#[derive(Default)]
+pub struct GpuCache {
+    entries: Vec<Entry>,
+    total_bytes: u64,
+    /// 最終使用時刻の代わりに使う単調増加カウンタ
+    clock: u64,
+}
+
+impl GpuCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.entries.iter().any(|e| e.index == index)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&TextureHandle> {
+        self.entries.iter().find(|e| e.index == index).map(|e| &e.texture)
+    }
+
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.entries.iter().map(|e| e.index)
+    }
+
+    /// テクスチャを追加する (同じページがあれば置き換える)
+    pub fn insert(&mut self, index: usize, texture: TextureHandle) {
+        self.remove(index);
+        self.clock += 1;
+        let bytes = texture.estimated_bytes();
+        self.total_bytes += bytes;
+        self.entries.push(Entry { index, texture, bytes, last_used: self.clock });
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<TextureHandle> {
+        let pos = self.entries.iter().position(|e| e.index == index)?;
+        let entry = self.entries.remove(pos);
+        self.total_bytes -= entry.bytes;
+        Some(entry.texture)
+    }
+
+    /// 表示したページを最近使ったものとして記録する
+    pub fn touch(&mut self, indices: &[usize]) {
+        self.clock += 1;
+        for entry in self.entries.iter_mut().filter(|e| indices.contains(&e.index)) {
+            entry.last_used = self.clock;
+        }
+    }
+
+    /// additional バイトを追加しても上限に収まるか (先読みの転送を止める判定用)
+    pub fn has_room_for(&self, additional: u64, limits: GpuCacheLimits) -> bool {
+        limits.max_bytes == 0 || self.total_bytes + additional <= limits.max_bytes
+    }
+
+    /// 上限を超えた分を古い順に解放し、解放した枚数を返す
+    pub fn evict(&mut self, protected: &[usize], limits: GpuCacheLimits) -> usize {
+        let stats: Vec<EntryStat> = self
+            .entries
+            .iter()
+            .map(|e| EntryStat { index: e.index, bytes: e.bytes, last_used: e.last_used })
+            .collect();
+        let victims = plan_eviction(&stats, protected, limits);
+        for &index in &victims {
+            self.remove(index);
+        }
+        victims.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.total_bytes = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(index: usize, bytes: u64, last_used: u64) -> EntryStat {
+        EntryStat { index, bytes, last_used }
+    }
+
+    fn limits(max_pages: usize, max_bytes: u64) -> GpuCacheLimits {
+        GpuCacheLimits { max_pages, max_bytes }
+    }
+
+    #[test]
+    fn evicts_down_to_page_limit() {
+        let entries = [entry(0, 10, 1), entry(1, 10, 2), entry(2, 10, 3), entry(3, 10, 4)];
+        assert_eq!(plan_eviction(&entries, &[], limits(2, 0)), vec![0, 1]);
+    }
+
+    #[test]
+    fn evicts_down_to_byte_limit() {
+        let entries = [entry(0, 50, 1), entry(1, 30, 2), entry(2, 40, 3)];
+        // 120 バイトから 100 バイト以下にするには最も古い 50 バイトの 1 枚で足りる
+        assert_eq!(plan_eviction(&entries, &[], limits(0, 100)), vec![0]);
+        assert_eq!(plan_eviction(&entries, &[], limits(0, 40)), vec![0, 1]);
+    }
+
+    #[test]
+    fn never_evicts_protected_pages() {
+        let entries = [entry(0, 10, 1), entry(1, 10, 2), entry(2, 10, 3)];
+        assert_eq!(plan_eviction(&entries, &[0], limits(1, 0)), vec![1, 2]);
+        // 保護したページだけで上限を超えていても解放しない
+        assert!(plan_eviction(&entries, &[0, 1, 2], limits(1, 0)).is_empty());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_first() {
+        let entries = [entry(0, 10, 5), entry(1, 10, 1), entry(2, 10, 9), entry(3, 10, 3)];
+        assert_eq!(plan_eviction(&entries, &[], limits(1, 0)), vec![1, 3, 0]);
+    }
+
+    #[test]
+    fn zero_limits_mean_unlimited() {
+        let entries: Vec<EntryStat> = (0..100).map(|i| entry(i, 1 << 30, i as u64)).collect();
+        assert!(plan_eviction(&entries, &[], limits(0, 0)).is_empty());
+    }
+
+    #[test]
+    fn within_limits_evicts_nothing() {
+        let entries = [entry(0, 10, 1), entry(1, 10, 2)];
+        assert!(plan_eviction(&entries, &[], limits(2, 20)).is_empty());
+    }
+}
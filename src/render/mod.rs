@@ -1,10 +1,13 @@
 use crate::image::cache::DecodedImage;
+use serde::{Deserialize, Serialize};
 use windows::Win32::Graphics::Direct2D::Common::{D2D_RECT_F, D2D1_COLOR_F};
 use windows::Win32::Graphics::DirectWrite::DWRITE_TEXT_ALIGNMENT;
 
 pub mod d2d;
 pub mod d3d11;
+pub mod gpu_cache;
 pub mod opengl;
+pub mod uploader;
 
 /// レンダラーバックエンドが共通で実装すべきトレイト
 pub trait Renderer: Send + Sync {
@@ -19,6 +22,12 @@ pub trait Renderer: Send + Sync {
 
     /// 抽象化されたテクスチャを描画
     fn draw_image(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F);
+    /// dest_rect に配置したテクスチャのうち clip_rect の範囲だけを描画する (ルーペ用)
+    /// 拡大後の画像全体ではなく clip_rect 内のピクセルだけをサンプリングする
+    fn draw_image_clipped(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F);
+    /// dest_rect に配置したページの clip_rect の範囲を、curl.area の中でめくるように変形して描画する
+    /// GPU バックエンドは分割したメッシュを円筒に巻き付け、それ以外は横にずらして代用する
+    fn draw_page_turn(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F, curl: &PageCurl);
 
     /// テクスチャのサイズを取得
     fn get_texture_size(&self, texture: &TextureHandle) -> (f32, f32);
@@ -26,18 +35,145 @@ pub trait Renderer: Send + Sync {
     /// 基本的な図形描画
     fn fill_rectangle(&self, rect: &D2D_RECT_F, color: &D2D1_COLOR_F);
     fn draw_rectangle(&self, rect: &D2D_RECT_F, color: &D2D1_COLOR_F, stroke_width: f32);
+    /// 折れ線 (注釈の線) を端と折れ目を丸めて描画する
+    fn draw_polyline(&self, points: &[(f32, f32)], color: &D2D1_COLOR_F, stroke_width: f32);
 
     // ネイティブダイアログ移行に伴い draw_text, fill_rounded_rectangle は廃止予定
     fn draw_text(&self, text: &str, rect: &D2D_RECT_F, color: &D2D1_COLOR_F, large: bool);
 
     fn set_interpolation_mode(&mut self, mode: InterpolationMode);
+    /// YCbCr テクスチャの色差の補間 (YCbCr をシェーダで変換しないバックエンドでは何もしない)
+    fn set_chroma_upsampling(&mut self, mode: ChromaUpsampling);
+    /// 以降の draw_image に適用する色調整 (テキスト・図形には適用しない)
+    fn set_color_adjustment(&mut self, adjustment: ColorAdjustment);
+    /// 以降の draw_image に適用するページの回転・反転
+    /// dest_rect は回転後 (画面上) の矩形で、テクスチャはその中に回転・反転して収める
+    fn set_page_transform(&mut self, transform: PageTransform);
     fn set_text_alignment(&self, alignment: DWRITE_TEXT_ALIGNMENT);
+    /// オーバーレイ文字の拡大率 (ウィンドウの DPI スケール、1.0 = 96 DPI) を設定する
+    fn set_ui_scale(&mut self, scale: f32);
+    /// begin_draw で塗りつぶす背景色 (ページの外側の余白)
+    fn set_background_color(&mut self, color: D2D1_COLOR_F);
+
+    /// アダプタのビデオメモリ使用量 (取得できないバックエンドは None)
+    fn query_video_memory(&self) -> Option<VideoMemoryInfo>;
+
+    /// 先読みページを複数フレームに分けて転送するための空テクスチャを作成する
+    /// 段階転送に対応しないバックエンドは None を返し、呼び出し側は upload_image で一括転送する
+    fn create_staged_texture(&self, image: &DecodedImage) -> Option<TextureHandle>;
+    /// 段階転送中のテクスチャへ start_row から最大 rows 行を転送し、転送済みの行数 (次の開始行) を返す
+    fn upload_rows(
+        &self,
+        texture: &TextureHandle,
+        image: &DecodedImage,
+        start_row: u32,
+        rows: u32,
+    ) -> std::result::Result<u32, Box<dyn std::error::Error>>;
+
+    /// 描画スレッド以外でテクスチャを作成するためのアップローダー
+    /// 別スレッドからのテクスチャ作成に対応しないバックエンドは None を返し、呼び出し側は段階転送か一括転送を行う
+    fn texture_uploader(&self) -> Option<Box<dyn TextureUploader>>;
+
+    /// テクスチャの輝度ヒストグラム (256 ビン) を GPU のコンピュートシェーダで集計する
+    /// 対応しないバックエンド・環境では None を返し、呼び出し側が CPU で集計する
+    fn compute_histogram(&self, texture: &TextureHandle) -> Option<[u32; 256]>;
+
+    /// 出力ウィンドウ (セカンドモニター用) の描画先を作る (None で破棄する)
+    /// 同じデバイスで描画するため、GPU キャッシュのテクスチャをそのまま使える。対応しないバックエンドは false を返す
+    fn set_mirror_target(&mut self, hwnd: Option<windows::Win32::Foundation::HWND>) -> bool;
+    /// 出力ウィンドウの大きさが変わったときに描画先を作り直す
+    fn resize_mirror(&mut self, width: u32, height: u32);
+    /// 以降の描画を出力ウィンドウに向けて背景色で塗りつぶす (begin_draw 〜 end_draw の外で呼ぶ)
+    fn begin_mirror_draw(&self);
+    /// 出力ウィンドウの描画を表示し、描画先をメインウィンドウに戻す
+    fn end_mirror_draw(&self);
+}
+
+/// 輝度ヒストグラムの下位・上位 clip の割合を切り捨てた黒点・白点 (0.0 ~ 1.0)
+pub fn histogram_levels(histogram: &[u32; 256], clip: f32) -> (f32, f32) {
+    let total: u64 = histogram.iter().map(|&n| n as u64).sum();
+    if total == 0 {
+        return (0.0, 1.0);
+    }
+    let threshold = (total as f64 * clip as f64) as u64;
+    let mut acc = 0u64;
+    let black = histogram
+        .iter()
+        .position(|&n| {
+            acc += n as u64;
+            acc > threshold
+        })
+        .unwrap_or(0);
+    acc = 0;
+    let white = 255 - histogram
+        .iter()
+        .rev()
+        .position(|&n| {
+            acc += n as u64;
+            acc > threshold
+        })
+        .unwrap_or(0);
+    (black as f32 / 255.0, white.max(black) as f32 / 255.0)
+}
+
+/// DXGI から取得したローカル (専用) ビデオメモリの使用状況
+#[derive(Debug, Clone, Copy)]
+pub struct VideoMemoryInfo {
+    /// このプロセスが現在使用しているバイト数
+    pub usage: u64,
+    /// OS がこのプロセスに割り当てている目安のバイト数
+    pub budget: u64,
+}
+
+/// DXGI デバイスの所属アダプタにビデオメモリの使用状況を問い合わせる (Windows 10 以降)
+pub(crate) fn query_dxgi_video_memory(
+    device: &windows::Win32::Graphics::Dxgi::IDXGIDevice,
+) -> Option<VideoMemoryInfo> {
+    use windows::Win32::Graphics::Dxgi::{
+        DXGI_MEMORY_SEGMENT_GROUP_LOCAL, DXGI_QUERY_VIDEO_MEMORY_INFO, IDXGIAdapter3,
+    };
+    use windows::core::Interface;
+    unsafe {
+        let adapter: IDXGIAdapter3 = device.GetAdapter().ok()?.cast().ok()?;
+        let mut info = DXGI_QUERY_VIDEO_MEMORY_INFO::default();
+        adapter
+            .QueryVideoMemoryInfo(0, DXGI_MEMORY_SEGMENT_GROUP_LOCAL, &mut info)
+            .ok()?;
+        Some(VideoMemoryInfo { usage: info.CurrentUsage, budget: info.Budget })
+    }
+}
+
+/// 既存のスワップチェーンと同じデバイス・形式で、別のウィンドウのスワップチェーンを作る (出力ウィンドウ用)
+pub(crate) fn create_sibling_swap_chain(
+    swap_chain: &windows::Win32::Graphics::Dxgi::IDXGISwapChain1,
+    hwnd: windows::Win32::Foundation::HWND,
+) -> windows::core::Result<windows::Win32::Graphics::Dxgi::IDXGISwapChain1> {
+    use windows::Win32::Graphics::Dxgi::{IDXGIDevice, IDXGIFactory2};
+    unsafe {
+        let device: IDXGIDevice = swap_chain.GetDevice()?;
+        let factory: IDXGIFactory2 = swap_chain.GetParent()?;
+        let mut desc = swap_chain.GetDesc1()?;
+        // 大きさはウィンドウに合わせる
+        desc.Width = 0;
+        desc.Height = 0;
+        factory.CreateSwapChainForHwnd(&device, hwnd, &desc, None, None)
+    }
 }
 
 #[derive(Clone)]
 pub struct PageDrawInfo<'a> {
+    pub index: usize,
     pub texture: &'a TextureHandle,
+    /// ページ全体を置く矩形 (トリミングしたページでは削った部分を含む)
     pub dest_rect: D2D_RECT_F,
+    /// 画面に見せる範囲 (トリミングしていなければ dest_rect と同じ)
+    pub clip_rect: D2D_RECT_F,
+    pub transform: PageTransform,
+}
+
+/// 描画スレッドとは別のスレッドでテクスチャを作成する (作成したテクスチャはそのまま描画に使える)
+pub trait TextureUploader: Send {
+    fn upload(&self, image: &DecodedImage) -> std::result::Result<TextureHandle, Box<dyn std::error::Error>>;
 }
 
 /// バックエンドを跨いでテクスチャを管理するためのハンドル
@@ -81,6 +217,38 @@ pub enum TextureHandle {
     // Cpu(Arc<Vec<u8>>),
 }
 
+impl TextureHandle {
+    /// テクスチャが占有する GPU メモリの概算 (バイト)
+    /// RGBA は 4 バイト/画素、YCbCr の各プレーンは 32bit (R32) として計算する
+    pub fn estimated_bytes(&self) -> u64 {
+        fn ycbcr_bytes(width: u32, height: u32, subsampling: (u8, u8)) -> u64 {
+            let (sx, sy) = (subsampling.0.max(1) as u32, subsampling.1.max(1) as u32);
+            let luma = width as u64 * height as u64;
+            let chroma = width.div_ceil(sx) as u64 * height.div_ceil(sy) as u64;
+            (luma + chroma * 2) * 4
+        }
+        match self {
+            TextureHandle::Direct2D(bitmap) => {
+                let size = unsafe { bitmap.GetPixelSize() };
+                size.width as u64 * size.height as u64 * 4
+            }
+            TextureHandle::D3D11Rgba(srv) => unsafe {
+                use windows::Win32::Graphics::Direct3D11::{D3D11_TEXTURE2D_DESC, ID3D11Texture2D};
+                use windows::core::Interface;
+                let Some(texture) = srv.GetResource().ok().and_then(|r| r.cast::<ID3D11Texture2D>().ok()) else {
+                    return 0;
+                };
+                let mut desc = D3D11_TEXTURE2D_DESC::default();
+                texture.GetDesc(&mut desc);
+                desc.Width as u64 * desc.Height as u64 * 4
+            },
+            TextureHandle::D3D11YCbCr { width, height, _subsampling, .. } => ycbcr_bytes(*width, *height, *_subsampling),
+            TextureHandle::OpenGL { width, height, .. } => *width as u64 * *height as u64 * 4,
+            TextureHandle::OpenGLYCbCr { width, height, _subsampling, .. } => ycbcr_bytes(*width, *height, *_subsampling),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InterpolationMode {
     NearestNeighbor,
@@ -88,3 +256,281 @@ pub enum InterpolationMode {
     Cubic,
     Lanczos,
 }
+
+/// ページめくりの状態 (Renderer::draw_page_turn に渡す)
+#[derive(Debug, Clone, Copy)]
+pub struct PageCurl {
+    /// めくる範囲 (見開きなら 2 ページ分をまとめた画面上の矩形)。この外側には描画しない
+    pub area: D2D_RECT_F,
+    /// 進み具合 (0.0 = めくり始め, 1.0 = 範囲の外までめくり終わった)
+    pub progress: f32,
+    /// 右端からめくる (false なら左端から)
+    pub from_right: bool,
+}
+
+impl PageCurl {
+    /// 巻き付ける円筒の半径 (めくる範囲の幅に対する割合)
+    pub const RADIUS: f32 = 0.12;
+    /// メッシュの横方向の分割数
+    pub const SEGMENTS: usize = 64;
+
+    /// 折り目の位置 (めくり始めの辺を 1.0、反対側の辺を 0.0 とする)
+    /// 最後は円筒ごと範囲の外へ出るまで動かす
+    pub fn fold(&self) -> f32 {
+        1.0 - self.progress.clamp(0.0, 1.0) * (1.0 + Self::RADIUS)
+    }
+
+    /// 平らなまま横にずらす場合の rect の位置 (スライド表示と、変形に対応しないバックエンド用)
+    pub fn slide_rect(&self, rect: &D2D_RECT_F) -> D2D_RECT_F {
+        let distance = (self.area.right - self.area.left) * self.progress.clamp(0.0, 1.0);
+        let dx = if self.from_right { -distance } else { distance };
+        D2D_RECT_F { left: rect.left + dx, top: rect.top, right: rect.right + dx, bottom: rect.bottom }
+    }
+
+    /// 横にずらしたページの見せる範囲 (めくる範囲の外は切り取る)
+    pub fn slide_clip(&self, clip_rect: &D2D_RECT_F) -> D2D_RECT_F {
+        let rect = self.slide_rect(clip_rect);
+        D2D_RECT_F {
+            left: rect.left.max(self.area.left),
+            top: rect.top.max(self.area.top),
+            right: rect.right.min(self.area.right).max(rect.left.max(self.area.left)),
+            bottom: rect.bottom.min(self.area.bottom).max(rect.top.max(self.area.top)),
+        }
+    }
+}
+
+/// YCbCr テクスチャの色差プレーンの補間 (輝度の InterpolationMode とは別に指定する)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChromaUpsampling {
+    Bilinear,
+    CatmullRom,
+}
+
+impl ChromaUpsampling {
+    /// 設定値 (chroma_upsampling) から変換する。不明な値はバイリニア
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "CatmullRom" => Self::CatmullRom,
+            _ => Self::Bilinear,
+        }
+    }
+}
+
+/// 表示時の色調整 (元画像は変更せず、各バックエンドのシェーダ/エフェクトで描画時に適用する)
+/// 適用順はレベル補正 -> ガンマ -> コントラスト -> 反転 -> 明るさ -> 暖色化 (ブルーライト軽減)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorAdjustment {
+    /// 明るさ (加算量, 0.0 = 変更なし)
+    pub brightness: f32,
+    /// コントラスト (中間調 0.5 を中心とした倍率, 1.0 = 変更なし)
+    pub contrast: f32,
+    /// ガンマ (1.0 = 変更なし, 大きいほど暗部が明るくなる)
+    pub gamma: f32,
+    /// 色反転 (夜間モード)。ソース別には保存せず、描画時にページごとに決める
+    pub invert: bool,
+    /// ブルーライト軽減の強さ (0.0 = なし, 1.0 = 最大)。設定とスケジュールから描画時に決める
+    pub warmth: f32,
+    /// レベル補正の黒点・白点 (この範囲を 0.0 ~ 1.0 に引き伸ばす)。自動レベル補正の設定からページごとに決める
+    pub black_point: f32,
+    pub white_point: f32,
+}
+
+impl Default for ColorAdjustment {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            gamma: 1.0,
+            invert: false,
+            warmth: 0.0,
+            black_point: 0.0,
+            white_point: 1.0,
+        }
+    }
+}
+
+impl ColorAdjustment {
+    pub const BRIGHTNESS_RANGE: (f32, f32) = (-0.5, 0.5);
+    pub const CONTRAST_RANGE: (f32, f32) = (0.25, 3.0);
+    pub const GAMMA_RANGE: (f32, f32) = (0.25, 4.0);
+    /// 黒点と白点の最小の間隔 (ほぼ単色のページを極端に引き伸ばさない)
+    pub const MIN_LEVELS_RANGE: f32 = 1.0 / Self::CONTRAST_RANGE.1;
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 各値を有効範囲に収める (設定ファイルの手編集対策も兼ねる)
+    pub fn clamped(self) -> Self {
+        let black_point = self.black_point.clamp(0.0, 1.0 - Self::MIN_LEVELS_RANGE);
+        Self {
+            brightness: self.brightness.clamp(Self::BRIGHTNESS_RANGE.0, Self::BRIGHTNESS_RANGE.1),
+            contrast: self.contrast.clamp(Self::CONTRAST_RANGE.0, Self::CONTRAST_RANGE.1),
+            gamma: self.gamma.clamp(Self::GAMMA_RANGE.0, Self::GAMMA_RANGE.1),
+            invert: self.invert,
+            warmth: self.warmth.clamp(0.0, 1.0),
+            black_point,
+            white_point: self.white_point.clamp(black_point + Self::MIN_LEVELS_RANGE, 1.0),
+        }
+    }
+
+    /// ページの黒点・白点を設定する (描画時にシェーダ/エフェクトで引き伸ばす)
+    pub fn with_levels(self, black: f32, white: f32) -> Self {
+        Self { black_point: black, white_point: white, ..self }.clamped()
+    }
+
+    /// 1段階分ずつ調整する (step は +1 / -1)
+    /// 往復したときに既定値へ正確に戻るよう、小数第2位で丸める
+    pub fn step_brightness(self, step: f32) -> Self {
+        Self { brightness: round2(self.brightness + step * 0.05), ..self }.clamped()
+    }
+
+    pub fn step_contrast(self, step: f32) -> Self {
+        Self { contrast: round2(self.contrast + step * 0.1), ..self }.clamped()
+    }
+
+    pub fn step_gamma(self, step: f32) -> Self {
+        Self { gamma: round2(self.gamma * 1.1f32.powf(step)), ..self }.clamped()
+    }
+
+    /// 黒点・白点を 0.0 / 1.0 に引き伸ばす自動レベル補正 (色あせたスキャン画像向け)
+    /// ガンマは 1.0 に戻し、コントラストと明るさで黒点〜白点を全域に広げる
+    pub fn auto_levels(self, black: f32, white: f32) -> Self {
+        // 1段階も広げられないほど狭い範囲はノイズとみなし、コントラストの上限で止める
+        let range = (white - black).max(Self::MIN_LEVELS_RANGE);
+        let contrast = 1.0 / range;
+        // 黒点〜白点の中央が 0.5 に来るよう明るさで平行移動する
+        let brightness = -((black + white) / 2.0 - 0.5) * contrast;
+        Self { brightness: round2(brightness), contrast: round2(contrast), gamma: 1.0, ..self }.clamped()
+    }
+
+    /// 最終段で RGB に掛ける暖色化係数 (赤はそのまま、緑と青を減衰させる)
+    pub fn tint(&self) -> [f32; 3] {
+        let w = self.warmth.clamp(0.0, 1.0);
+        [1.0, 1.0 - 0.2 * w, 1.0 - 0.6 * w]
+    }
+
+    /// ステータス表示用の文字列
+    pub fn describe(&self) -> String {
+        format!(
+            "明るさ {:+.0}% / コントラスト {:.0}% / ガンマ {:.2}",
+            self.brightness * 100.0,
+            self.contrast * 100.0,
+            self.gamma
+        )
+    }
+}
+
+/// ページごとの表示時の回転・反転 (元画像は変更せず、描画時に各バックエンドで適用する)
+/// 画面上の見た目は「時計回りに rotation 度回転してから反転」した結果になる
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PageTransform {
+    /// 時計回りの回転角 (0 / 90 / 180 / 270)
+    pub rotation: u32,
+    /// 左右反転
+    pub flip_h: bool,
+    /// 上下反転
+    pub flip_v: bool,
+}
+
+impl PageTransform {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 90° / 270° 回転で縦横が入れ替わるか
+    pub fn swaps_axes(&self) -> bool {
+        self.rotation % 180 == 90
+    }
+
+    /// 回転後の表示サイズ
+    pub fn display_size(&self, width: f32, height: f32) -> (f32, f32) {
+        if self.swaps_axes() { (height, width) } else { (width, height) }
+    }
+
+    /// 画面上で 90° ずつ回転する (step は +1 = 時計回り / -1 = 反時計回り)
+    pub fn rotated(self, step: i32) -> Self {
+        // 反転の後に回転を重ねるので、片方だけ反転している場合は回転の向きが逆になる
+        let step = if self.flip_h != self.flip_v { -step } else { step };
+        Self {
+            rotation: (self.rotation as i32 + 90 * step).rem_euclid(360) as u32,
+            ..self
+        }
+    }
+
+    /// 画面上で左右 (horizontal) または上下に反転する
+    pub fn flipped(self, horizontal: bool) -> Self {
+        let mut next = self;
+        if horizontal {
+            next.flip_h = !next.flip_h;
+        } else {
+            next.flip_v = !next.flip_v;
+        }
+        next.normalized()
+    }
+
+    /// 回転を 90° 単位に丸め、左右と上下の両方の反転は 180° 回転にまとめる (表現を一意にする)
+    /// 設定ファイルの手編集対策も兼ねる
+    pub fn normalized(self) -> Self {
+        let mut next = Self { rotation: (self.rotation % 360) / 90 * 90, ..self };
+        if next.flip_h && next.flip_v {
+            next.flip_h = false;
+            next.flip_v = false;
+            next.rotation = (next.rotation + 180) % 360;
+        }
+        next
+    }
+
+    /// 表示矩形内の正規化座標 (0.0 ~ 1.0, 左上原点) に対応するテクスチャ座標
+    /// D3D11 / OpenGL の頂点 UV の入れ替えに使う
+    pub fn source_uv(&self, x: f32, y: f32) -> [f32; 2] {
+        let x = if self.flip_h { 1.0 - x } else { x };
+        let y = if self.flip_v { 1.0 - y } else { y };
+        match self.rotation {
+            90 => [y, 1.0 - x],
+            180 => [1.0 - x, 1.0 - y],
+            270 => [1.0 - y, x],
+            _ => [x, y],
+        }
+    }
+
+    /// テクスチャの正規化座標から表示矩形内の正規化座標への変換 (source_uv の逆変換)
+    pub fn display_point(&self, u: f32, v: f32) -> (f32, f32) {
+        let (x, y) = match self.rotation {
+            90 => (1.0 - v, u),
+            180 => (1.0 - u, 1.0 - v),
+            270 => (v, 1.0 - u),
+            _ => (u, v),
+        };
+        (
+            if self.flip_h { 1.0 - x } else { x },
+            if self.flip_v { 1.0 - y } else { y },
+        )
+    }
+
+    /// テクスチャ上の矩形 (正規化座標) を dest_rect 上の矩形に変換する (OCR の単語枠など)
+    pub fn map_rect(&self, left: f32, top: f32, right: f32, bottom: f32, dest: &D2D_RECT_F) -> D2D_RECT_F {
+        let (x0, y0) = self.display_point(left, top);
+        let (x1, y1) = self.display_point(right, bottom);
+        let (w, h) = (dest.right - dest.left, dest.bottom - dest.top);
+        D2D_RECT_F {
+            left: dest.left + x0.min(x1) * w,
+            top: dest.top + y0.min(y1) * h,
+            right: dest.left + x0.max(x1) * w,
+            bottom: dest.top + y0.max(y1) * h,
+        }
+    }
+
+    /// ステータス表示用の文字列
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!("{}°", self.rotation)];
+        if self.flip_h { parts.push("左右反転".to_string()); }
+        if self.flip_v { parts.push("上下反転".to_string()); }
+        parts.join(" / ")
+    }
+}
+
+fn round2(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
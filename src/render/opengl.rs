@@ -1,4 +1,4 @@
-use super::{InterpolationMode, Renderer, TextureHandle};
+use super::{ChromaUpsampling, ColorAdjustment, InterpolationMode, PageCurl, PageTransform, Renderer, TextureHandle};
 use crate::image::cache::DecodedImage;
 use crate::image::cache::PixelData;
 use glow::*;
@@ -21,6 +21,27 @@ use windows::Win32::Graphics::Gdi::{
 };
 use windows::core::w;
 
+/// ページめくり用に横へ PageCurl::SEGMENTS 分割したメッシュ (トライアングルストリップ、x, y, z, u, v)
+/// 四角形と同じく位置は -1.0〜1.0、UV は表示矩形内の 0.0〜1.0 で、見せる範囲 clip_rect だけを覆う
+/// めくれて重なる部分が後から描かれるよう、めくり始めの辺から遠い列から並べる
+fn curl_vertices(dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F, from_right: bool) -> Vec<f32> {
+    let width = (dest_rect.right - dest_rect.left).max(1.0);
+    let height = (dest_rect.bottom - dest_rect.top).max(1.0);
+    let left = ((clip_rect.left - dest_rect.left) / width).clamp(0.0, 1.0);
+    let right = ((clip_rect.right - dest_rect.left) / width).clamp(0.0, 1.0);
+    let top = ((clip_rect.top - dest_rect.top) / height).clamp(0.0, 1.0);
+    let bottom = ((clip_rect.bottom - dest_rect.top) / height).clamp(0.0, 1.0);
+    (0..=PageCurl::SEGMENTS)
+        .map(|i| i as f32 / PageCurl::SEGMENTS as f32)
+        .map(|t| if from_right { t } else { 1.0 - t })
+        .flat_map(|t| {
+            let u = left + (right - left) * t;
+            [(u, top), (u, bottom)]
+        })
+        .flat_map(|(u, v)| [u * 2.0 - 1.0, 1.0 - v * 2.0, 0.0, u, v])
+        .collect()
+}
+
 pub struct OpenGLRenderer {
     gl: Arc<glow::Context>,
     context: PossiblyCurrentContext,
@@ -28,6 +49,9 @@ pub struct OpenGLRenderer {
     program: Program,
     vao: VertexArray,
     _vbo: Buffer,
+    /// ページめくり用の分割したメッシュ (描画ごとに書き換える)
+    curl_vao: VertexArray,
+    curl_vbo: Buffer,
 
     // Shader Uniforms
     u_color_matrix: UniformLocation,
@@ -37,13 +61,101 @@ pub struct OpenGLRenderer {
     u_tex_cr: UniformLocation,
     u_dest_rect: UniformLocation,
     u_window_size: UniformLocation,
+    u_uv_transform: UniformLocation,
     u_is_ycbcr: UniformLocation,
     u_ui_color: UniformLocation,
     u_is_ui: UniformLocation,
     u_interpolation_mode: UniformLocation,
+    u_chroma_mode: UniformLocation,
     u_source_texture_size: UniformLocation,
+    u_adjust: UniformLocation,
+    u_tint: UniformLocation,
+    u_levels: UniformLocation,
+    u_curl: UniformLocation,
+    u_curl_area: UniformLocation,
     interpolation_mode: InterpolationMode,
+    chroma_upsampling: ChromaUpsampling,
+    color_adjustment: ColorAdjustment,
+    page_transform: PageTransform,
+    background_color: D2D1_COLOR_F,
     text_alignment: AtomicI32,
+    ui_scale: f32,
+    /// 輝度ヒストグラム集計用 (OpenGL 4.3 未満ではコンピュートシェーダが無いため None)
+    histogram: Option<HistogramProgram>,
+}
+
+/// ヒストグラム集計用のコンピュートシェーダと結果バッファ (SSBO)
+struct HistogramProgram {
+    program: Program,
+    bins: Buffer,
+    u_tex: UniformLocation,
+    u_is_ycbcr: UniformLocation,
+    u_y_bias: UniformLocation,
+}
+
+/// 16x16 のワークグループごとに共有メモリの 256 ビンへ集計し、最後に SSBO へまとめて加算する
+const HISTOGRAM_COMPUTE_SRC: &str = r#"#version 430 core
+    layout (local_size_x = 16, local_size_y = 16) in;
+    uniform sampler2D tex;
+    uniform int isYCbCr;
+    uniform float yBias; // 符号付き Y の場合の加算量 (正規化済み)
+    layout (std430, binding = 0) buffer Histogram { uint bins[256]; };
+    shared uint localBins[256];
+
+    void main() {
+        uint gi = gl_LocalInvocationIndex;
+        localBins[gi] = 0u;
+        barrier();
+
+        ivec2 size = textureSize(tex, 0);
+        ivec2 p = ivec2(gl_GlobalInvocationID.xy);
+        if (p.x < size.x && p.y < size.y) {
+            vec4 c = texelFetch(tex, p, 0);
+            float luma = isYCbCr != 0 ? c.r + yBias : dot(c.rgb, vec3(0.299, 0.587, 0.114));
+            atomicAdd(localBins[uint(clamp(luma, 0.0, 1.0) * 255.0 + 0.5)], 1u);
+        }
+        barrier();
+        if (localBins[gi] > 0u) {
+            atomicAdd(bins[gi], localBins[gi]);
+        }
+    }
+"#;
+
+impl HistogramProgram {
+    unsafe fn new(gl: &glow::Context) -> Result<Self, Box<dyn std::error::Error>> {
+        unsafe {
+            let version = gl.version();
+            if (version.major, version.minor) < (4, 3) {
+                return Err(format!("OpenGL {}.{} はコンピュートシェーダに非対応です", version.major, version.minor).into());
+            }
+            let program = gl.create_program()?;
+            let cs = gl.create_shader(COMPUTE_SHADER)?;
+            gl.shader_source(cs, HISTOGRAM_COMPUTE_SRC);
+            gl.compile_shader(cs);
+            if !gl.get_shader_compile_status(cs) {
+                return Err(format!("CS Compile Error: {}", gl.get_shader_info_log(cs)).into());
+            }
+            gl.attach_shader(program, cs);
+            gl.link_program(program);
+            gl.delete_shader(cs);
+            if !gl.get_program_link_status(program) {
+                return Err(format!("Program Link Error: {}", gl.get_program_info_log(program)).into());
+            }
+
+            let bins = gl.create_buffer()?;
+            gl.bind_buffer(SHADER_STORAGE_BUFFER, Some(bins));
+            gl.buffer_data_size(SHADER_STORAGE_BUFFER, 256 * 4, DYNAMIC_READ);
+            gl.bind_buffer(SHADER_STORAGE_BUFFER, None);
+
+            Ok(Self {
+                program,
+                bins,
+                u_tex: gl.get_uniform_location(program, "tex").ok_or("Uniform tex not found")?,
+                u_is_ycbcr: gl.get_uniform_location(program, "isYCbCr").ok_or("Uniform isYCbCr not found")?,
+                u_y_bias: gl.get_uniform_location(program, "yBias").ok_or("Uniform yBias not found")?,
+            })
+        }
+    }
 }
 
 impl OpenGLRenderer {
@@ -68,24 +180,56 @@ impl OpenGLRenderer {
                 layout (location = 0) in vec3 aPos;
                 layout (location = 1) in vec2 aTexCoord;
                 out vec2 TexCoord;
+                out vec2 Lighting; // ページめくりの陰影 (明るさの倍率, 紙の裏の色へ寄せる割合)
                 uniform vec4 uDestRect; // [left, top, right, bottom]
                 uniform vec2 uWindowSize;
+                uniform mat3 uUvTransform; // ページの回転・反転 (表示矩形内の座標 -> テクスチャ座標)
+                uniform vec4 uCurl;     // ページめくり [折り目の位置, 円筒の半径, 右端からめくるか, 有効か]
+                uniform vec4 uCurlArea; // めくる範囲 [left, top, right, bottom]
+                const float PI = 3.14159265359;
                 void main() {
                     // NDC 変換: [0, w] -> [-1, 1], [0, h] -> [1, -1]
                     float x_coord = mix(uDestRect.x, uDestRect.z, aPos.x * 0.5 + 0.5);
                     float y_coord = mix(uDestRect.y, uDestRect.w, 0.5 - aPos.y * 0.5);
+
+                    // ページめくり: めくり始めの辺を 1.0 とする座標で、折り目より先を円筒に巻き付ける
+                    Lighting = vec2(1.0, 0.0);
+                    if (uCurl.w > 0.5) {
+                        float width = max(uCurlArea.z - uCurlArea.x, 1.0);
+                        float x = (x_coord - uCurlArea.x) / width;
+                        if (uCurl.z < 0.5) x = 1.0 - x;
+                        float d = x - uCurl.x;
+                        if (d > 0.0) {
+                            float theta = d / uCurl.y;
+                            if (theta < PI) {
+                                // 手前の半周は傾くほど暗く、奥の半周は紙の裏が見える
+                                x = uCurl.x + uCurl.y * sin(theta);
+                                float facing = abs(cos(theta));
+                                Lighting = theta < PI * 0.5
+                                    ? vec2(0.55 + 0.45 * facing, 0.0)
+                                    : vec2(0.6 + 0.3 * facing, 0.7);
+                            } else {
+                                // 巻き終わって裏返しに重なった部分
+                                x = uCurl.x - (d - PI * uCurl.y);
+                                Lighting = vec2(0.9, 0.7);
+                            }
+                        }
+                        if (uCurl.z < 0.5) x = 1.0 - x;
+                        x_coord = uCurlArea.x + x * width;
+                    }
                     
                     float x_ndc = (x_coord / max(uWindowSize.x, 1.0)) * 2.0 - 1.0;
                     float y_ndc = 1.0 - (y_coord / max(uWindowSize.y, 1.0)) * 2.0;
                     
                     gl_Position = vec4(x_ndc, y_ndc, 0.0, 1.0);
-                    TexCoord = aTexCoord;
+                    TexCoord = (uUvTransform * vec3(aTexCoord, 1.0)).xy;
                 }
             "#;
 
             let frag_src = r#"#version 330 core
                 out vec4 FragColor;
                 in vec2 TexCoord;
+                in vec2 Lighting;
                 uniform sampler2D texY;
                 uniform sampler2D texCb;
                 uniform sampler2D texCr;
@@ -94,8 +238,12 @@ impl OpenGLRenderer {
                 uniform int isYCbCr; // bool ではなく int を使用 (互換性のため)
                 uniform int isUI;
                 uniform vec4 uiColor;
-                uniform int interpolationMode; // 0=Nearest, 1=Linear, 2=Cubic, 3=Lanczos
+                uniform int interpolationMode; // 0=Nearest, 1=Linear, 2=Cubic, 3=Lanczos (輝度)
+                uniform int chromaMode;        // 0=Bilinear, 1=Catmull-Rom (色差)
                 uniform vec2 sourceTextureSize;
+                uniform vec4 adjust; // 色調整 (明るさ, コントラスト, ガンマ, 反転)
+                uniform vec3 tint;   // ブルーライト軽減の RGB 係数
+                uniform vec2 levels; // レベル補正の黒点・白点
 
                 const float PI = 3.14159265359;
 
@@ -123,22 +271,57 @@ impl OpenGLRenderer {
                     return 0.0;
                 }
 
-                // サンプル関数 (YCbCr or RGBA に応じて分岐)
+                // サンプル関数 (YCbCr は輝度だけを返し、色差は sampleChroma で別に補間する)
                 vec4 sampleTexture(vec2 uv) {
                     if (isYCbCr != 0) {
-                        float y = texture(texY, uv).r;
-                        float cb = texture(texCb, uv).r;
-                        float cr = texture(texCr, uv).r;
-                        vec4 ycbcr = vec4(y, cb, cr, 1.0);
-                        ycbcr = ycbcr + offset;
-                        vec4 rgba = colorMatrix * ycbcr;
-                        rgba.a = 1.0;
-                        return clamp(rgba, 0.0, 1.0);
+                        return vec4(texture(texY, uv).r);
                     } else {
                         return texture(texY, uv);
                     }
                 }
 
+                // 色差 (Cb, Cr) の 1 画素 (範囲外は端の画素)
+                vec2 chromaTexel(ivec2 pos, ivec2 size) {
+                    pos = clamp(pos, ivec2(0), size - 1);
+                    return vec2(texelFetch(texCb, pos, 0).r, texelFetch(texCr, pos, 0).r);
+                }
+
+                // 色差の補間。間引かれた色差プレーンを輝度と同じ最近傍で拡大すると色の境界がブロック状ににじむため、
+                // 輝度の補間モードやテクスチャのフィルタに関わらず Bilinear / Catmull-Rom で滑らかにする
+                vec2 sampleChroma(vec2 uv) {
+                    ivec2 size = textureSize(texCb, 0);
+                    vec2 pixelPos = uv * vec2(size) - 0.5;
+                    vec2 fracPart = fract(pixelPos);
+                    ivec2 basePos = ivec2(floor(pixelPos));
+
+                    if (chromaMode == 1) {
+                        vec2 chroma = vec2(0.0);
+                        float totalWeight = 0.0;
+                        for (int j = -1; j <= 2; j++) {
+                            for (int i = -1; i <= 2; i++) {
+                                float w = cubic_weight(float(i) - fracPart.x) * cubic_weight(float(j) - fracPart.y);
+                                chroma += chromaTexel(basePos + ivec2(i, j), size) * w;
+                                totalWeight += w;
+                            }
+                        }
+                        return chroma / max(totalWeight, 0.001);
+                    }
+
+                    vec2 c00 = chromaTexel(basePos, size);
+                    vec2 c10 = chromaTexel(basePos + ivec2(1, 0), size);
+                    vec2 c01 = chromaTexel(basePos + ivec2(0, 1), size);
+                    vec2 c11 = chromaTexel(basePos + ivec2(1, 1), size);
+                    return mix(mix(c00, c10, fracPart.x), mix(c01, c11, fracPart.x), fracPart.y);
+                }
+
+                // 補間済みの輝度と色差から RGB への変換
+                vec4 ycbcrToRgba(float y, vec2 chroma) {
+                    vec4 ycbcr = vec4(y, chroma, 1.0) + offset;
+                    vec4 rgba = colorMatrix * ycbcr;
+                    rgba.a = 1.0;
+                    return clamp(rgba, 0.0, 1.0);
+                }
+
                 // Cubic 補間 (4x4 サンプリング)
                 vec4 sampleCubic(vec2 uv) {
                     vec2 texelSize = 1.0 / sourceTextureSize;
@@ -191,6 +374,17 @@ impl OpenGLRenderer {
                     return color / max(totalWeight, 0.001);
                 }
 
+                // 色調整の適用 (レベル補正 -> ガンマ -> コントラスト -> 反転 -> 明るさ -> 暖色化)
+                vec4 applyAdjustment(vec4 color) {
+                    vec3 rgb = clamp((color.rgb - levels.x) / (levels.y - levels.x), 0.0, 1.0);
+                    rgb = pow(rgb, vec3(1.0 / adjust.z));
+                    rgb = (rgb - 0.5) * adjust.y + 0.5;
+                    rgb = mix(rgb, 1.0 - rgb, adjust.w);
+                    rgb += adjust.x;
+                    rgb *= tint;
+                    return vec4(clamp(rgb, 0.0, 1.0), color.a);
+                }
+
                 void main() {
                     if (isUI != 0) {
                         FragColor = uiColor;
@@ -208,6 +402,11 @@ impl OpenGLRenderer {
                         // Nearest (0) / Linear (1) - ハードウェアサンプラーに任せる
                         FragColor = sampleTexture(TexCoord);
                     }
+                    if (isYCbCr != 0) {
+                        FragColor = ycbcrToRgba(FragColor.r, sampleChroma(TexCoord));
+                    }
+                    FragColor = applyAdjustment(FragColor);
+                    FragColor = vec4(mix(FragColor.rgb, vec3(1.0), Lighting.y) * Lighting.x, FragColor.a);
                 }
             "#;
 
@@ -259,6 +458,9 @@ impl OpenGLRenderer {
             let u_window_size = gl
                 .get_uniform_location(program, "uWindowSize")
                 .ok_or("Uniform uWindowSize not found")?;
+            let u_uv_transform = gl
+                .get_uniform_location(program, "uUvTransform")
+                .ok_or("Uniform uUvTransform not found")?;
             let u_is_ycbcr = gl
                 .get_uniform_location(program, "isYCbCr")
                 .ok_or("Uniform isYCbCr not found")?;
@@ -271,9 +473,35 @@ impl OpenGLRenderer {
             let u_interpolation_mode = gl
                 .get_uniform_location(program, "interpolationMode")
                 .ok_or("Uniform interpolationMode not found")?;
+            let u_chroma_mode = gl
+                .get_uniform_location(program, "chromaMode")
+                .ok_or("Uniform chromaMode not found")?;
             let u_source_texture_size = gl
                 .get_uniform_location(program, "sourceTextureSize")
                 .ok_or("Uniform sourceTextureSize not found")?;
+            let u_adjust = gl
+                .get_uniform_location(program, "adjust")
+                .ok_or("Uniform adjust not found")?;
+            let u_tint = gl
+                .get_uniform_location(program, "tint")
+                .ok_or("Uniform tint not found")?;
+            let u_levels = gl
+                .get_uniform_location(program, "levels")
+                .ok_or("Uniform levels not found")?;
+            let u_curl = gl
+                .get_uniform_location(program, "uCurl")
+                .ok_or("Uniform uCurl not found")?;
+            let u_curl_area = gl
+                .get_uniform_location(program, "uCurlArea")
+                .ok_or("Uniform uCurlArea not found")?;
+
+            let histogram = match HistogramProgram::new(&gl) {
+                Ok(histogram) => Some(histogram),
+                Err(e) => {
+                    println!("[OpenGL] ヒストグラム集計シェーダを作成できません (CPU で集計します): {}", e);
+                    None
+                }
+            };
 
             // Quad Setup
             let vao = gl.create_vertex_array()?;
@@ -292,6 +520,18 @@ impl OpenGLRenderer {
             gl.vertex_attrib_pointer_f32(1, 2, FLOAT, false, 20, 12);
             gl.enable_vertex_attrib_array(1);
 
+            // ページめくり用のメッシュ (頂点の形式は四角形と同じ)
+            let curl_vao = gl.create_vertex_array()?;
+            gl.bind_vertex_array(Some(curl_vao));
+            let curl_vbo = gl.create_buffer()?;
+            gl.bind_buffer(ARRAY_BUFFER, Some(curl_vbo));
+            gl.buffer_data_size(ARRAY_BUFFER, ((PageCurl::SEGMENTS + 1) * 2 * 20) as i32, DYNAMIC_DRAW);
+            gl.vertex_attrib_pointer_f32(0, 3, FLOAT, false, 20, 0);
+            gl.enable_vertex_attrib_array(0);
+            gl.vertex_attrib_pointer_f32(1, 2, FLOAT, false, 20, 12);
+            gl.enable_vertex_attrib_array(1);
+            gl.bind_vertex_array(Some(vao));
+
             Ok(Self {
                 gl,
                 context,
@@ -299,6 +539,8 @@ impl OpenGLRenderer {
                 program,
                 vao,
                 _vbo: vbo,
+                curl_vao,
+                curl_vbo,
                 u_color_matrix,
                 u_offset,
                 u_tex_y,
@@ -306,17 +548,181 @@ impl OpenGLRenderer {
                 u_tex_cr,
                 u_dest_rect,
                 u_window_size,
+                u_uv_transform,
                 u_is_ycbcr,
                 u_is_ui,
                 u_ui_color,
                 u_interpolation_mode,
+                u_chroma_mode,
                 u_source_texture_size,
+                u_adjust,
+                u_tint,
+                u_levels,
+                u_curl,
+                u_curl_area,
                 interpolation_mode: InterpolationMode::Linear,
+                chroma_upsampling: ChromaUpsampling::Bilinear,
+                color_adjustment: ColorAdjustment::default(),
+                page_transform: PageTransform::default(),
+                background_color: D2D1_COLOR_F { r: 0.1, g: 0.1, b: 0.1, a: 1.0 },
                 text_alignment: AtomicI32::new(DWRITE_TEXT_ALIGNMENT_LEADING.0),
+                ui_scale: 1.0,
+                histogram,
             })
         }
     }
 
+    /// テクスチャを描画する (テキスト描画では色調整・回転を無効にして呼ぶ)
+    /// curl (見せる範囲, めくりの状態) を指定すると、四角形の代わりに変形したメッシュで描く
+    fn draw_texture(
+        &self,
+        texture: &TextureHandle,
+        dest_rect: &D2D_RECT_F,
+        adjustment: &ColorAdjustment,
+        transform: &PageTransform,
+        curl: Option<(&D2D_RECT_F, &PageCurl)>,
+    ) {
+        unsafe {
+            self.gl.use_program(Some(self.program));
+            self.gl.uniform_1_i32(Some(&self.u_is_ui), 0);
+
+            let sw = self.surface.width().map(|v| v as f32).unwrap_or(1.0);
+            let sh = self.surface.height().map(|v| v as f32).unwrap_or(1.0);
+            self.gl.uniform_2_f32(Some(&self.u_window_size), sw, sh);
+            self.gl.uniform_4_f32(
+                Some(&self.u_dest_rect),
+                dest_rect.left,
+                dest_rect.top,
+                dest_rect.right,
+                dest_rect.bottom,
+            );
+            // 回転・反転: 3 隅の UV から表示座標 -> テクスチャ座標のアフィン変換 (列優先の mat3) を作る
+            let origin = transform.source_uv(0.0, 0.0);
+            let x_axis = transform.source_uv(1.0, 0.0);
+            let y_axis = transform.source_uv(0.0, 1.0);
+            #[rustfmt::skip]
+            let uv_matrix = [
+                x_axis[0] - origin[0], x_axis[1] - origin[1], 0.0,
+                y_axis[0] - origin[0], y_axis[1] - origin[1], 0.0,
+                origin[0],             origin[1],             1.0,
+            ];
+            self.gl
+                .uniform_matrix_3_f32_slice(Some(&self.u_uv_transform), false, &uv_matrix);
+
+            // 補間モードをシェーダーに渡す
+            let mode_int = match self.interpolation_mode {
+                InterpolationMode::NearestNeighbor => 0,
+                InterpolationMode::Linear => 1,
+                InterpolationMode::Cubic => 2,
+                InterpolationMode::Lanczos => 3,
+            };
+            self.gl
+                .uniform_1_i32(Some(&self.u_interpolation_mode), mode_int);
+            let chroma_int = match self.chroma_upsampling {
+                ChromaUpsampling::Bilinear => 0,
+                ChromaUpsampling::CatmullRom => 1,
+            };
+            self.gl.uniform_1_i32(Some(&self.u_chroma_mode), chroma_int);
+            self.gl.uniform_4_f32(
+                Some(&self.u_adjust),
+                adjustment.brightness,
+                adjustment.contrast,
+                adjustment.gamma,
+                if adjustment.invert { 1.0 } else { 0.0 },
+            );
+            let [r, g, b] = adjustment.tint();
+            self.gl.uniform_3_f32(Some(&self.u_tint), r, g, b);
+            self.gl.uniform_2_f32(Some(&self.u_levels), adjustment.black_point, adjustment.white_point);
+
+            match texture {
+                TextureHandle::OpenGL { id, width, height } => {
+                    self.gl.uniform_1_i32(Some(&self.u_is_ycbcr), 0);
+                    self.gl.uniform_2_f32(
+                        Some(&self.u_source_texture_size),
+                        *width as f32,
+                        *height as f32,
+                    );
+                    self.gl.active_texture(TEXTURE0);
+                    let tex: Texture = std::mem::transmute_copy::<u32, Texture>(id);
+                    self.gl.bind_texture(TEXTURE_2D, Some(tex));
+                    self.gl.uniform_1_i32(Some(&self.u_tex_y), 0);
+                }
+                TextureHandle::OpenGLYCbCr {
+                    y,
+                    cb,
+                    cr,
+                    width,
+                    height,
+                    y_is_signed,
+                    ..
+                } => {
+                    self.gl.uniform_1_i32(Some(&self.u_is_ycbcr), 1);
+                    self.gl.uniform_2_f32(
+                        Some(&self.u_source_texture_size),
+                        *width as f32,
+                        *height as f32,
+                    );
+                    let y_offset = if *y_is_signed { 0.5 } else { 0.0 };
+                    let c_offset = -0.5;
+                    let matrix = [
+                        1.0, 1.0, 1.0, 0.0, 0.0, -0.344136, 1.772, 0.0, 1.402, -0.714136, 0.0, 0.0,
+                        0.0, 0.0, 0.0, 1.0,
+                    ];
+                    self.gl
+                        .uniform_matrix_4_f32_slice(Some(&self.u_color_matrix), false, &matrix);
+                    self.gl
+                        .uniform_4_f32(Some(&self.u_offset), y_offset, c_offset, c_offset, 0.0);
+
+                    self.gl.active_texture(TEXTURE0);
+                    self.gl.bind_texture(
+                        TEXTURE_2D,
+                        Some(std::mem::transmute_copy::<u32, Texture>(y)),
+                    );
+                    self.gl.uniform_1_i32(Some(&self.u_tex_y), 0);
+                    self.gl.active_texture(TEXTURE1);
+                    self.gl.bind_texture(
+                        TEXTURE_2D,
+                        Some(std::mem::transmute_copy::<u32, Texture>(cb)),
+                    );
+                    self.gl.uniform_1_i32(Some(&self.u_tex_cb), 1);
+                    self.gl.active_texture(TEXTURE2);
+                    self.gl.bind_texture(
+                        TEXTURE_2D,
+                        Some(std::mem::transmute_copy::<u32, Texture>(cr)),
+                    );
+                    self.gl.uniform_1_i32(Some(&self.u_tex_cr), 2);
+                }
+                _ => return,
+            }
+            match curl {
+                Some((clip_rect, curl)) => {
+                    let a = &curl.area;
+                    self.gl.uniform_4_f32(
+                        Some(&self.u_curl),
+                        curl.fold(),
+                        PageCurl::RADIUS,
+                        if curl.from_right { 1.0 } else { 0.0 },
+                        1.0,
+                    );
+                    self.gl.uniform_4_f32(Some(&self.u_curl_area), a.left, a.top, a.right, a.bottom);
+                    let vertices = curl_vertices(dest_rect, clip_rect, curl.from_right);
+                    self.gl.bind_vertex_array(Some(self.curl_vao));
+                    self.gl.bind_buffer(ARRAY_BUFFER, Some(self.curl_vbo));
+                    self.gl.buffer_sub_data_u8_slice(ARRAY_BUFFER, 0, bytemuck::cast_slice(&vertices));
+                    self.gl.draw_arrays(TRIANGLE_STRIP, 0, (vertices.len() / 5) as i32);
+                    // 以降の描画 (テキスト・図形も同じプログラムを使う) に変形を残さない
+                    self.gl.uniform_4_f32(Some(&self.u_curl), 0.0, 0.0, 0.0, 0.0);
+                    self.gl.bind_vertex_array(Some(self.vao));
+                }
+                None => {
+                    self.gl.uniform_4_f32(Some(&self.u_curl), 0.0, 0.0, 0.0, 0.0);
+                    self.gl.bind_vertex_array(Some(self.vao));
+                    self.gl.draw_arrays(TRIANGLES, 0, 6);
+                }
+            }
+        }
+    }
+
     fn create_texture_f32(
         &self,
         width: u32,
@@ -411,7 +817,8 @@ impl Renderer for OpenGLRenderer {
             let sw = self.surface.width().map(|v| v as i32).unwrap_or(0);
             let sh = self.surface.height().map(|v| v as i32).unwrap_or(0);
             self.gl.viewport(0, 0, sw, sh);
-            self.gl.clear_color(0.1, 0.1, 0.1, 1.0);
+            let c = self.background_color;
+            self.gl.clear_color(c.r, c.g, c.b, 1.0);
             self.gl.clear(COLOR_BUFFER_BIT);
         }
     }
@@ -470,93 +877,43 @@ impl Renderer for OpenGLRenderer {
     }
 
     fn draw_image(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F) {
-        unsafe {
-            self.gl.use_program(Some(self.program));
-            self.gl.uniform_1_i32(Some(&self.u_is_ui), 0);
+        self.draw_texture(texture, dest_rect, &self.color_adjustment, &self.page_transform, None);
+    }
 
-            let sw = self.surface.width().map(|v| v as f32).unwrap_or(1.0);
-            let sh = self.surface.height().map(|v| v as f32).unwrap_or(1.0);
-            self.gl.uniform_2_f32(Some(&self.u_window_size), sw, sh);
-            self.gl.uniform_4_f32(
-                Some(&self.u_dest_rect),
-                dest_rect.left,
-                dest_rect.top,
-                dest_rect.right,
-                dest_rect.bottom,
+    fn draw_image_clipped(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F) {
+        let sh = self.surface.height().map(|v| v as f32).unwrap_or(1.0);
+        unsafe {
+            // glScissor は左下原点
+            self.gl.enable(SCISSOR_TEST);
+            self.gl.scissor(
+                clip_rect.left.floor() as i32,
+                (sh - clip_rect.bottom).floor() as i32,
+                (clip_rect.right - clip_rect.left).ceil() as i32,
+                (clip_rect.bottom - clip_rect.top).ceil() as i32,
             );
+        }
+        self.draw_texture(texture, dest_rect, &self.color_adjustment, &self.page_transform, None);
+        unsafe {
+            self.gl.disable(SCISSOR_TEST);
+        }
+    }
 
-            // 補間モードをシェーダーに渡す
-            let mode_int = match self.interpolation_mode {
-                InterpolationMode::NearestNeighbor => 0,
-                InterpolationMode::Linear => 1,
-                InterpolationMode::Cubic => 2,
-                InterpolationMode::Lanczos => 3,
-            };
-            self.gl
-                .uniform_1_i32(Some(&self.u_interpolation_mode), mode_int);
-
-            match texture {
-                TextureHandle::OpenGL { id, width, height } => {
-                    self.gl.uniform_1_i32(Some(&self.u_is_ycbcr), 0);
-                    self.gl.uniform_2_f32(
-                        Some(&self.u_source_texture_size),
-                        *width as f32,
-                        *height as f32,
-                    );
-                    self.gl.active_texture(TEXTURE0);
-                    let tex: Texture = std::mem::transmute_copy::<u32, Texture>(id);
-                    self.gl.bind_texture(TEXTURE_2D, Some(tex));
-                    self.gl.uniform_1_i32(Some(&self.u_tex_y), 0);
-                }
-                TextureHandle::OpenGLYCbCr {
-                    y,
-                    cb,
-                    cr,
-                    width,
-                    height,
-                    y_is_signed,
-                    ..
-                } => {
-                    self.gl.uniform_1_i32(Some(&self.u_is_ycbcr), 1);
-                    self.gl.uniform_2_f32(
-                        Some(&self.u_source_texture_size),
-                        *width as f32,
-                        *height as f32,
-                    );
-                    let y_offset = if *y_is_signed { 0.5 } else { 0.0 };
-                    let c_offset = -0.5;
-                    let matrix = [
-                        1.0, 1.0, 1.0, 0.0, 0.0, -0.344136, 1.772, 0.0, 1.402, -0.714136, 0.0, 0.0,
-                        0.0, 0.0, 0.0, 1.0,
-                    ];
-                    self.gl
-                        .uniform_matrix_4_f32_slice(Some(&self.u_color_matrix), false, &matrix);
-                    self.gl
-                        .uniform_4_f32(Some(&self.u_offset), y_offset, c_offset, c_offset, 0.0);
-
-                    self.gl.active_texture(TEXTURE0);
-                    self.gl.bind_texture(
-                        TEXTURE_2D,
-                        Some(std::mem::transmute_copy::<u32, Texture>(y)),
-                    );
-                    self.gl.uniform_1_i32(Some(&self.u_tex_y), 0);
-                    self.gl.active_texture(TEXTURE1);
-                    self.gl.bind_texture(
-                        TEXTURE_2D,
-                        Some(std::mem::transmute_copy::<u32, Texture>(cb)),
-                    );
-                    self.gl.uniform_1_i32(Some(&self.u_tex_cb), 1);
-                    self.gl.active_texture(TEXTURE2);
-                    self.gl.bind_texture(
-                        TEXTURE_2D,
-                        Some(std::mem::transmute_copy::<u32, Texture>(cr)),
-                    );
-                    self.gl.uniform_1_i32(Some(&self.u_tex_cr), 2);
-                }
-                _ => return,
-            }
-            self.gl.bind_vertex_array(Some(self.vao));
-            self.gl.draw_arrays(TRIANGLES, 0, 6);
+    fn draw_page_turn(&self, texture: &TextureHandle, dest_rect: &D2D_RECT_F, clip_rect: &D2D_RECT_F, curl: &PageCurl) {
+        let sh = self.surface.height().map(|v| v as f32).unwrap_or(1.0);
+        let area = &curl.area;
+        unsafe {
+            // めくり終わりに範囲の外へ出た部分は描かない
+            self.gl.enable(SCISSOR_TEST);
+            self.gl.scissor(
+                area.left.floor() as i32,
+                (sh - area.bottom).floor() as i32,
+                (area.right - area.left).ceil() as i32,
+                (area.bottom - area.top).ceil() as i32,
+            );
+        }
+        self.draw_texture(texture, dest_rect, &self.color_adjustment, &self.page_transform, Some((clip_rect, curl)));
+        unsafe {
+            self.gl.disable(SCISSOR_TEST);
         }
     }
 
@@ -640,6 +997,25 @@ impl Renderer for OpenGLRenderer {
         );
     }
 
+    fn draw_polyline(&self, points: &[(f32, f32)], color: &D2D1_COLOR_F, stroke_width: f32) {
+        // 線分に沿って正方形を並べて描く (半透明の線は重なった部分が少し濃くなる)
+        let half = (stroke_width / 2.0).max(0.5);
+        let step = half.max(1.0);
+        let stamp = |(x, y): (f32, f32)| {
+            self.fill_rectangle(&D2D_RECT_F { left: x - half, top: y - half, right: x + half, bottom: y + half }, color);
+        };
+        let Some(&first) = points.first() else { return; };
+        stamp(first);
+        for pair in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            let count = ((x1 - x0).hypot(y1 - y0) / step).ceil().max(1.0) as usize;
+            for i in 1..=count {
+                let t = i as f32 / count as f32;
+                stamp((x0 + (x1 - x0) * t, y0 + (y1 - y0) * t));
+            }
+        }
+    }
+
     fn draw_text(&self, text: &str, rect: &D2D_RECT_F, color: &D2D1_COLOR_F, large: bool) {
         let width = (rect.right - rect.left).ceil() as i32;
         let height = (rect.bottom - rect.top).ceil() as i32;
@@ -670,7 +1046,7 @@ impl Renderer for OpenGLRenderer {
             // Clear to transparent (0)
             std::ptr::write_bytes(p_bits, 0, (width * height * 4) as usize);
 
-            let font_height = if large { 32 } else { 18 };
+            let font_height = ((if large { 32.0 } else { 18.0 }) * self.ui_scale).round() as i32;
             let weight = if large { FW_BOLD } else { FW_NORMAL };
             let hfont = CreateFontW(
                 font_height,
@@ -754,14 +1130,17 @@ impl Renderer for OpenGLRenderer {
                 )
                 .unwrap();
 
-            // Draw
-            self.draw_image(
+            // Draw (文字色が変わらないよう色調整は適用しない)
+            self.draw_texture(
                 &TextureHandle::OpenGL {
                     id: std::mem::transmute_copy::<Texture, u32>(&tex),
                     width: width as u32,
                     height: height as u32,
                 },
                 rect,
+                &ColorAdjustment::default(),
+                &PageTransform::default(),
+                None,
             );
 
             // Cleanup texture
@@ -779,7 +1158,101 @@ impl Renderer for OpenGLRenderer {
     fn set_interpolation_mode(&mut self, mode: InterpolationMode) {
         self.interpolation_mode = mode;
     }
+    fn set_chroma_upsampling(&mut self, mode: ChromaUpsampling) {
+        self.chroma_upsampling = mode;
+    }
+    fn set_color_adjustment(&mut self, adjustment: ColorAdjustment) {
+        self.color_adjustment = adjustment;
+    }
+    fn set_page_transform(&mut self, transform: PageTransform) {
+        self.page_transform = transform;
+    }
     fn set_text_alignment(&self, alignment: DWRITE_TEXT_ALIGNMENT) {
         self.text_alignment.store(alignment.0, Ordering::Relaxed);
     }
+
+    fn set_ui_scale(&mut self, scale: f32) {
+        self.ui_scale = scale;
+    }
+
+    fn set_background_color(&mut self, color: D2D1_COLOR_F) {
+        self.background_color = color;
+    }
+
+    fn query_video_memory(&self) -> Option<super::VideoMemoryInfo> {
+        // OpenGL には標準の問い合わせ手段が無いため、概算値のみ表示する
+        None
+    }
+
+    fn create_staged_texture(&self, _image: &DecodedImage) -> Option<TextureHandle> {
+        None
+    }
+
+    fn upload_rows(
+        &self,
+        _texture: &TextureHandle,
+        _image: &DecodedImage,
+        _start_row: u32,
+        _rows: u32,
+    ) -> std::result::Result<u32, Box<dyn std::error::Error>> {
+        Err("staged upload is not supported on OpenGL".into())
+    }
+
+    fn texture_uploader(&self) -> Option<Box<dyn super::TextureUploader>> {
+        // 別スレッドで転送するには wglShareLists で共有したコンテキストをスレッドごとに用意する必要があるため、
+        // OpenGL は描画スレッドでフレームごとの時間枠に収まる分だけ転送する
+        None
+    }
+
+    fn compute_histogram(&self, texture: &TextureHandle) -> Option<[u32; 256]> {
+        let histogram = self.histogram.as_ref()?;
+        let (id, width, height, is_ycbcr, y_bias) = match texture {
+            TextureHandle::OpenGL { id, width, height } => (*id, *width, *height, false, 0.0),
+            // Y プレーンはアップロード時に 0.0 ~ 1.0 へ正規化済み
+            TextureHandle::OpenGLYCbCr { y, width, height, y_is_signed, .. } => {
+                (*y, *width, *height, true, if *y_is_signed { 0.5 } else { 0.0 })
+            }
+            _ => return None,
+        };
+        unsafe {
+            let gl = &self.gl;
+            let zeros = [0u8; 256 * 4];
+            gl.bind_buffer(SHADER_STORAGE_BUFFER, Some(histogram.bins));
+            gl.buffer_sub_data_u8_slice(SHADER_STORAGE_BUFFER, 0, &zeros);
+            gl.bind_buffer_base(SHADER_STORAGE_BUFFER, 0, Some(histogram.bins));
+
+            gl.use_program(Some(histogram.program));
+            gl.active_texture(TEXTURE0);
+            gl.bind_texture(TEXTURE_2D, Some(std::mem::transmute_copy::<u32, Texture>(&id)));
+            gl.uniform_1_i32(Some(&histogram.u_tex), 0);
+            gl.uniform_1_i32(Some(&histogram.u_is_ycbcr), is_ycbcr as i32);
+            gl.uniform_1_f32(Some(&histogram.u_y_bias), y_bias);
+            gl.dispatch_compute(width.div_ceil(16), height.div_ceil(16), 1);
+            gl.memory_barrier(SHADER_STORAGE_BARRIER_BIT | BUFFER_UPDATE_BARRIER_BIT);
+
+            let mut bytes = [0u8; 256 * 4];
+            gl.get_buffer_sub_data(SHADER_STORAGE_BUFFER, 0, &mut bytes);
+            gl.bind_buffer_base(SHADER_STORAGE_BUFFER, 0, None);
+            gl.bind_buffer(SHADER_STORAGE_BUFFER, None);
+            // 描画用プログラムは draw_texture / fill_rectangle の先頭で毎回設定し直される
+            gl.use_program(None);
+
+            let mut result = [0u32; 256];
+            for (bin, chunk) in result.iter_mut().zip(bytes.chunks_exact(4)) {
+                *bin = u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            }
+            Some(result)
+        }
+    }
+
+    fn set_mirror_target(&mut self, hwnd: Option<windows::Win32::Foundation::HWND>) -> bool {
+        // GL コンテキストはメインウィンドウのサーフェスに結び付いているため、出力ウィンドウには対応しない
+        hwnd.is_none()
+    }
+
+    fn resize_mirror(&mut self, _width: u32, _height: u32) {}
+
+    fn begin_mirror_draw(&self) {}
+
+    fn end_mirror_draw(&self) {}
 }
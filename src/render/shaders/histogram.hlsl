@@ -0,0 +1,60 @@
+// 輝度ヒストグラム集計シェーダ (Direct3D 11 コンピュートシェーダ)
+// 1スレッドグループ (16x16) ごとに groupshared の 256 ビンへ集計し、
+// 最後にグループの集計結果をまとめて出力バッファへ加算する (グローバルなアトミック操作を減らすため)
+
+cbuffer HistogramParams : register(b0)
+{
+    uint2 size;     // テクスチャサイズ
+    float yScale;   // YCbCr: Y の正規化係数 (1 / 最大値)
+    float yBias;    // YCbCr: 符号付き Y の場合の加算量
+};
+
+Texture2D<float4> texRgba : register(t0);  // RGBA テクスチャ
+Texture2D<int> texY       : register(t1);  // YCbCr の Y プレーン (R32_SINT)
+
+RWByteAddressBuffer histogram : register(u0);  // 256 ビン x uint
+
+groupshared uint localBins[256];
+
+void AccumulateLuma(float luma)
+{
+    uint bin = min((uint)(saturate(luma) * 255.0f + 0.5f), 255u);
+    InterlockedAdd(localBins[bin], 1u);
+}
+
+void FlushBins(uint groupIndex)
+{
+    GroupMemoryBarrierWithGroupSync();
+    if (localBins[groupIndex] > 0)
+    {
+        histogram.InterlockedAdd(groupIndex * 4, localBins[groupIndex]);
+    }
+}
+
+[numthreads(16, 16, 1)]
+void CSRgba(uint3 id : SV_DispatchThreadID, uint groupIndex : SV_GroupIndex)
+{
+    localBins[groupIndex] = 0;
+    GroupMemoryBarrierWithGroupSync();
+
+    if (all(id.xy < size))
+    {
+        float3 rgb = texRgba.Load(int3(id.xy, 0)).rgb;
+        AccumulateLuma(dot(rgb, float3(0.299f, 0.587f, 0.114f)));
+    }
+    FlushBins(groupIndex);
+}
+
+[numthreads(16, 16, 1)]
+void CSYCbCr(uint3 id : SV_DispatchThreadID, uint groupIndex : SV_GroupIndex)
+{
+    localBins[groupIndex] = 0;
+    GroupMemoryBarrierWithGroupSync();
+
+    if (all(id.xy < size))
+    {
+        float y = (float)texY.Load(int3(id.xy, 0));
+        AccumulateLuma((y + yBias) * yScale);
+    }
+    FlushBins(groupIndex);
+}
@@ -0,0 +1,70 @@
+// Page curl vertex shader for Direct3D 11
+// 横に分割したページのメッシュを、折り目に置いた円筒へ巻き付けるように変形する
+// ピクセルシェーダは texture_quad.hlsl / ycbcr_to_rgb.hlsl のものをそのまま使い、陰影は lighting で渡す
+
+cbuffer CurlParams : register(b2)
+{
+    float4 destRect;   // ページ全体を置く矩形 (ピクセル, left, top, right, bottom)
+    float4 area;       // めくる範囲 (ピクセル, 見開きなら 2 ページ分)
+    float2 targetSize; // レンダーターゲットの大きさ (ピクセル)
+    float fold;        // 折り目の位置 (めくる範囲の幅に対する割合、めくり始めの辺が 1.0)
+    float radius;      // 円筒の半径 (めくる範囲の幅に対する割合)
+    float fromRight;   // 1.0 = 右端からめくる, 0.0 = 左端から
+    float3 _padding;
+};
+
+static const float PI = 3.14159265359f;
+
+struct VSInput
+{
+    float3 position : POSITION; // ページ内の位置 (0.0〜1.0)
+    float2 texCoord : TEXCOORD0;
+};
+
+struct PSInput
+{
+    float4 position : SV_POSITION;
+    float2 texCoord : TEXCOORD0;
+    float2 lighting : TEXCOORD1;
+};
+
+PSInput VSCurl(VSInput input)
+{
+    float2 pixel = lerp(destRect.xy, destRect.zw, input.position.xy);
+    float width = max(area.z - area.x, 1.0f);
+    // めくり始めの辺を 1.0 とする座標で変形する
+    float x = (pixel.x - area.x) / width;
+    if (fromRight < 0.5f)
+        x = 1.0f - x;
+
+    float2 lighting = float2(1.0f, 0.0f);
+    float d = x - fold;
+    if (d > 0.0f)
+    {
+        float theta = d / radius;
+        if (theta < PI)
+        {
+            // 円筒の表面。手前の半周は傾くほど暗く、奥の半周 (θ > π/2) は紙の裏が見える
+            x = fold + radius * sin(theta);
+            float facing = abs(cos(theta));
+            lighting = theta < PI * 0.5f
+                ? float2(0.55f + 0.45f * facing, 0.0f)
+                : float2(0.6f + 0.3f * facing, 0.7f);
+        }
+        else
+        {
+            // 巻き終わって裏返しに重なった部分
+            x = fold - (d - PI * radius);
+            lighting = float2(0.9f, 0.7f);
+        }
+    }
+    if (fromRight < 0.5f)
+        x = 1.0f - x;
+    pixel.x = area.x + x * width;
+
+    PSInput output;
+    output.position = float4(pixel.x / targetSize.x * 2.0f - 1.0f, 1.0f - pixel.y / targetSize.y * 2.0f, 0.0f, 1.0f);
+    output.texCoord = input.texCoord;
+    output.lighting = lighting;
+    return output;
+}
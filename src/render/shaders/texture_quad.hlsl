@@ -7,6 +7,18 @@ cbuffer TransformParams : register(b0)
     float4 srcRect;   // left, top, right, bottom (normalized, for texture atlas)
 };
 
+// 色調整 (明るさ・コントラスト・ガンマ)
+cbuffer AdjustParams : register(b1)
+{
+    float brightness;   // 加算量 (0.0 = 変更なし)
+    float contrast;     // 0.5 を中心とした倍率 (1.0 = 変更なし)
+    float gamma;        // ガンマ (1.0 = 変更なし)
+    float invert;       // 1.0 = 色反転
+    float4 tint;        // ブルーライト軽減の RGB 係数 (最終段で乗算)
+    float2 levels;      // レベル補正の黒点・白点 (0.0, 1.0 = 変更なし)
+    float2 _levelsPadding;
+};
+
 Texture2D<float4> texDiffuse : register(t0);
 SamplerState samplerLinear : register(s0);
 
@@ -19,6 +31,7 @@ struct PSInput
 {
     float4 position : SV_POSITION;
     float2 texCoord : TEXCOORD0;
+    float2 lighting : TEXCOORD1; // ページめくりの陰影 (page_curl.hlsl)。通常は (1, 0)
 };
 
 // 頂点シェーダ（頂点バッファなしでクアッドを生成）
@@ -56,12 +69,31 @@ PSInput VSMain(VSInput input)
     // NDC: -1,-1 左下, 1,1 右上
     output.position = float4(pos.x * 2.0f - 1.0f, 1.0f - pos.y * 2.0f, 0.0f, 1.0f);
     output.texCoord = uv;
+    output.lighting = float2(1.0f, 0.0f);
     
     return output;
 }
 
+// 色調整の適用 (レベル補正 -> ガンマ -> コントラスト -> 反転 -> 明るさ)
+float4 apply_adjustment(float4 color)
+{
+    float3 rgb = saturate((color.rgb - levels.x) / (levels.y - levels.x));
+    rgb = pow(rgb, 1.0f / gamma);
+    rgb = (rgb - 0.5f) * contrast + 0.5f;
+    rgb = lerp(rgb, 1.0f - rgb, invert);
+    rgb += brightness;
+    rgb *= tint.rgb;
+    return float4(saturate(rgb), color.a);
+}
+
+// ページめくりの陰影 (x = 明るさの倍率, y = 紙の裏の色へ寄せる割合)。色調整の後に掛ける
+float4 apply_lighting(float4 color, float2 lighting)
+{
+    return float4(lerp(color.rgb, 1.0f, lighting.y) * lighting.x, color.a);
+}
+
 // ピクセルシェーダ
 float4 PSMain(PSInput input) : SV_TARGET
 {
-    return texDiffuse.Sample(samplerLinear, input.texCoord);
+    return apply_lighting(apply_adjustment(texDiffuse.Sample(samplerLinear, input.texCoord)), input.lighting);
 }
@@ -4,6 +4,7 @@
 // Output: RGBA color
 // 
 // 補間モード対応: Nearest, Linear, Cubic (Catmull-Rom), Lanczos3
+// 色差 (Cb, Cr) は輝度とは別に Bilinear / Catmull-Rom で補間してから RGB に変換する
 
 // 定数バッファ：変換行列とオフセット
 cbuffer YCbCrParams : register(b0)
@@ -11,8 +12,21 @@ cbuffer YCbCrParams : register(b0)
     float4x4 colorMatrix;  // YCbCr to RGB 変換行列
     float4 offset;         // オフセット (bias correction)
     float4 scale;          // スケール (precision adjustment)
-    int interpolationMode; // 0=Nearest, 1=Linear, 2=Cubic, 3=Lanczos
-    int3 _padding;         // アライメント用パディング
+    int interpolationMode; // 0=Nearest, 1=Linear, 2=Cubic, 3=Lanczos (輝度)
+    int chromaMode;        // 0=Bilinear, 1=Catmull-Rom (色差)
+    int2 _padding;         // アライメント用パディング
+};
+
+// 色調整 (明るさ・コントラスト・ガンマ)
+cbuffer AdjustParams : register(b1)
+{
+    float brightness;   // 加算量 (0.0 = 変更なし)
+    float contrast;     // 0.5 を中心とした倍率 (1.0 = 変更なし)
+    float gamma;        // ガンマ (1.0 = 変更なし)
+    float invert;       // 1.0 = 色反転
+    float4 tint;        // ブルーライト軽減の RGB 係数 (最終段で乗算)
+    float2 levels;      // レベル補正の黒点・白点 (0.0, 1.0 = 変更なし)
+    float2 _levelsPadding;
 };
 
 // テクスチャとサンプラー
@@ -35,6 +49,7 @@ struct PSInput
 {
     float4 position : SV_POSITION;
     float2 texCoord : TEXCOORD0;
+    float2 lighting : TEXCOORD1; // ページめくりの陰影 (page_curl.hlsl)。通常は (1, 0)
 };
 
 // 頂点シェーダ
@@ -43,6 +58,7 @@ PSInput VSMain(VSInput input)
     PSInput output;
     output.position = float4(input.position, 1.0f);
     output.texCoord = input.texCoord;
+    output.lighting = float2(1.0f, 0.0f);
     return output;
 }
 
@@ -86,119 +102,133 @@ float4 ycbcr_to_rgba(float y, float cb, float cr)
     return saturate(rgba);
 }
 
-// 単一ピクセルサンプリング (Nearest / Linear 用)
-float3 sampleYCbCr(int2 pos, uint2 y_dim, uint2 c_dim)
-{
-    int2 y_pos = clamp(pos, int2(0, 0), int2(y_dim.x - 1, y_dim.y - 1));
-    float2 uv = float2(pos) / float2(y_dim);
-    int2 c_pos = int2(uv * float2(c_dim));
-    c_pos = clamp(c_pos, int2(0, 0), int2(c_dim.x - 1, c_dim.y - 1));
-    
-    float y = (float)texY.Load(int3(y_pos, 0));
-    float cb = (float)texCb.Load(int3(c_pos, 0));
-    float cr = (float)texCr.Load(int3(c_pos, 0));
-    return float3(y, cb, cr);
+// 輝度の 1 画素 (範囲外は端の画素)
+float loadLuma(int2 pos, uint2 y_dim)
+{
+    pos = clamp(pos, int2(0, 0), int2(y_dim.x - 1, y_dim.y - 1));
+    return (float)texY.Load(int3(pos, 0));
 }
 
-// ピクセルシェーダ (汎用 - 定数バッファから行列を使用)
-float4 PSMain_Generic(PSInput input) : SV_TARGET
+// 色差 (Cb, Cr) の 1 画素 (範囲外は端の画素)
+float2 loadChroma(int2 pos, uint2 c_dim)
+{
+    pos = clamp(pos, int2(0, 0), int2(c_dim.x - 1, c_dim.y - 1));
+    return float2((float)texCb.Load(int3(pos, 0)), (float)texCr.Load(int3(pos, 0)));
+}
+
+// 色差の補間。間引かれた色差プレーンを輝度と同じ最近傍で拡大すると色の境界がブロック状ににじむため、
+// 輝度の補間モードに関わらず Bilinear / Catmull-Rom で滑らかにする
+float2 sampleChroma(float2 texCoord, uint2 c_dim)
+{
+    float2 pixelPos = texCoord * float2(c_dim) - 0.5f;
+    float2 fracPart = frac(pixelPos);
+    int2 basePos = int2(floor(pixelPos));
+
+    if (chromaMode == 1)
+    {
+        float2 chroma = float2(0.0f, 0.0f);
+        float totalWeight = 0.0f;
+        [unroll]
+        for (int j = -1; j <= 2; j++)
+        {
+            [unroll]
+            for (int i = -1; i <= 2; i++)
+            {
+                float w = cubic_weight((float)i - fracPart.x) * cubic_weight((float)j - fracPart.y);
+                chroma += loadChroma(basePos + int2(i, j), c_dim) * w;
+                totalWeight += w;
+            }
+        }
+        return chroma / max(totalWeight, 0.001f);
+    }
+
+    float2 c00 = loadChroma(basePos, c_dim);
+    float2 c10 = loadChroma(basePos + int2(1, 0), c_dim);
+    float2 c01 = loadChroma(basePos + int2(0, 1), c_dim);
+    float2 c11 = loadChroma(basePos + int2(1, 1), c_dim);
+    return lerp(lerp(c00, c10, fracPart.x), lerp(c01, c11, fracPart.x), fracPart.y);
+}
+
+// 色調整の適用 (レベル補正 -> ガンマ -> コントラスト -> 反転 -> 明るさ)
+float4 apply_adjustment(float4 color)
+{
+    float3 rgb = saturate((color.rgb - levels.x) / (levels.y - levels.x));
+    rgb = pow(rgb, 1.0f / gamma);
+    rgb = (rgb - 0.5f) * contrast + 0.5f;
+    rgb = lerp(rgb, 1.0f - rgb, invert);
+    rgb += brightness;
+    rgb *= tint.rgb;
+    return float4(saturate(rgb), color.a);
+}
+
+// ページめくりの陰影 (x = 明るさの倍率, y = 紙の裏の色へ寄せる割合)。色調整の後に掛ける
+float4 apply_lighting(float4 color, float2 lighting)
+{
+    return float4(lerp(color.rgb, 1.0f, lighting.y) * lighting.x, color.a);
+}
+
+// 輝度を補間モードに応じて補間する
+float sampleLuma(float2 texCoord, uint2 y_dim)
 {
-    uint y_width, y_height;
-    texY.GetDimensions(y_width, y_height);
-    uint2 y_dim = uint2(y_width, y_height);
-    
-    uint c_width, c_height;
-    texCb.GetDimensions(c_width, c_height);
-    uint2 c_dim = uint2(c_width, c_height);
-    
-    float2 texCoord = input.texCoord;
-    
     // Nearest Neighbor または Linear (ハードウェアサンプラーを使えないのでどちらも点サンプリング)
     if (interpolationMode <= 1)
     {
-        int2 i_pos = int2(texCoord.x * (float)y_width, texCoord.y * (float)y_height);
-        i_pos = clamp(i_pos, int2(0, 0), int2(y_width - 1, y_height - 1));
-        float y = (float)texY.Load(int3(i_pos, 0));
-        
-        int2 c_i_pos = int2(texCoord.x * (float)c_width, texCoord.y * (float)c_height);
-        c_i_pos = clamp(c_i_pos, int2(0, 0), int2(c_width - 1, c_height - 1));
-        
-        float cb = (float)texCb.Load(int3(c_i_pos, 0));
-        float cr = (float)texCr.Load(int3(c_i_pos, 0));
-        
-        return ycbcr_to_rgba(y, cb, cr);
+        return loadLuma(int2(texCoord * float2(y_dim)), y_dim);
     }
-    
+
+    float2 pixelPos = texCoord * float2(y_dim) - 0.5f;
+    float2 fracPart = frac(pixelPos);
+    int2 basePos = int2(floor(pixelPos));
+    float luma = 0.0f;
+    float totalWeight = 0.0f;
+
     // Cubic (4x4 サンプリング)
     if (interpolationMode == 2)
     {
-        float2 pixelPos = texCoord * float2(y_dim) - 0.5f;
-        float2 fracPart = frac(pixelPos);
-        int2 basePos = int2(floor(pixelPos));
-        
-        float4 color = float4(0.0f, 0.0f, 0.0f, 0.0f);
-        float totalWeight = 0.0f;
-        
         [unroll]
         for (int j = -1; j <= 2; j++)
         {
             [unroll]
             for (int i = -1; i <= 2; i++)
             {
-                int2 samplePos = basePos + int2(i, j);
-                float3 ycbcr = sampleYCbCr(samplePos, y_dim, c_dim);
-                
-                float wx = cubic_weight((float)i - fracPart.x);
-                float wy = cubic_weight((float)j - fracPart.y);
-                float w = wx * wy;
-                
-                color += ycbcr_to_rgba(ycbcr.x, ycbcr.y, ycbcr.z) * w;
+                float w = cubic_weight((float)i - fracPart.x) * cubic_weight((float)j - fracPart.y);
+                luma += loadLuma(basePos + int2(i, j), y_dim) * w;
                 totalWeight += w;
             }
         }
-        return color / max(totalWeight, 0.001f);
+        return luma / max(totalWeight, 0.001f);
     }
-    
+
     // Lanczos3 (6x6 サンプリング)
-    if (interpolationMode == 3)
+    [unroll]
+    for (int j = -2; j <= 3; j++)
     {
-        float2 pixelPos = texCoord * float2(y_dim) - 0.5f;
-        float2 fracPart = frac(pixelPos);
-        int2 basePos = int2(floor(pixelPos));
-        
-        float4 color = float4(0.0f, 0.0f, 0.0f, 0.0f);
-        float totalWeight = 0.0f;
-        
         [unroll]
-        for (int j = -2; j <= 3; j++)
+        for (int i = -2; i <= 3; i++)
         {
-            [unroll]
-            for (int i = -2; i <= 3; i++)
-            {
-                int2 samplePos = basePos + int2(i, j);
-                float3 ycbcr = sampleYCbCr(samplePos, y_dim, c_dim);
-                
-                float wx = lanczos_weight((float)i - fracPart.x);
-                float wy = lanczos_weight((float)j - fracPart.y);
-                float w = wx * wy;
-                
-                color += ycbcr_to_rgba(ycbcr.x, ycbcr.y, ycbcr.z) * w;
-                totalWeight += w;
-            }
+            float w = lanczos_weight((float)i - fracPart.x) * lanczos_weight((float)j - fracPart.y);
+            luma += loadLuma(basePos + int2(i, j), y_dim) * w;
+            totalWeight += w;
         }
-        return color / max(totalWeight, 0.001f);
     }
-    
-    // Default fallback
-    int2 i_pos = int2(texCoord.x * (float)y_width, texCoord.y * (float)y_height);
-    i_pos = clamp(i_pos, int2(0, 0), int2(y_width - 1, y_height - 1));
-    float y = (float)texY.Load(int3(i_pos, 0));
-    
-    int2 c_i_pos = int2(texCoord.x * (float)c_width, texCoord.y * (float)c_height);
-    c_i_pos = clamp(c_i_pos, int2(0, 0), int2(c_width - 1, c_height - 1));
-    
-    float cb = (float)texCb.Load(int3(c_i_pos, 0));
-    float cr = (float)texCr.Load(int3(c_i_pos, 0));
-    
-    return ycbcr_to_rgba(y, cb, cr);
+    return luma / max(totalWeight, 0.001f);
+}
+
+// 輝度と色差をそれぞれ補間してから RGB に変換する
+float4 sample_generic(PSInput input)
+{
+    uint y_width, y_height;
+    texY.GetDimensions(y_width, y_height);
+    uint c_width, c_height;
+    texCb.GetDimensions(c_width, c_height);
+
+    float y = sampleLuma(input.texCoord, uint2(y_width, y_height));
+    float2 chroma = sampleChroma(input.texCoord, uint2(c_width, c_height));
+    return ycbcr_to_rgba(y, chroma.x, chroma.y);
 }
+
+// ピクセルシェーダ (汎用 - 定数バッファから行列を使用)
+float4 PSMain_Generic(PSInput input) : SV_TARGET
+{
+    return apply_lighting(apply_adjustment(sample_generic(input)), input.lighting);
+}
\ No newline at end of file
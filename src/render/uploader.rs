@@ -0,0 +1,80 @@
+use crate::image::cache::DecodedImage;
+use crate::render::{TextureHandle, TextureUploader};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::mpsc;
+
+/// 同時に転送を頼んでおくページ数 (先読みの範囲が変わったときに古い依頼が溜まらないよう少なくする)
+const MAX_IN_FLIGHT: usize = 3;
+
+struct UploadRequest {
+    path_key: String,
+    index: usize,
+    image: Arc<DecodedImage>,
+}
+
+/// 転送が終わったページ
+pub struct UploadResult {
+    pub path_key: String,
+    pub index: usize,
+    /// 転送元の画像 (転送中にキャッシュの画像が差し替わったものは捨てる)
+    pub image: Arc<DecodedImage>,
+    pub texture: Result<TextureHandle, String>,
+}
+
+/// 先読みページのテクスチャを描画スレッドとは別のスレッドで作成する
+/// 描画中のフレームは止めず、出来上がったテクスチャを毎フレーム poll で受け取って GPU キャッシュに入れる
+pub struct AsyncUploader {
+    requests: mpsc::Sender<UploadRequest>,
+    results: mpsc::Receiver<UploadResult>,
+    /// 依頼済みで結果を受け取っていないページ (パス, ページ番号)
+    pending: HashSet<(String, usize)>,
+}
+
+impl AsyncUploader {
+    pub fn start(uploader: Box<dyn TextureUploader>) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<UploadRequest>();
+        let (result_tx, result_rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for request in request_rx {
+                let texture = uploader.upload(&request.image).map_err(|e| e.to_string());
+                let result = UploadResult {
+                    path_key: request.path_key,
+                    index: request.index,
+                    image: request.image,
+                    texture,
+                };
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { requests: request_tx, results: result_rx, pending: HashSet::new() }
+    }
+
+    pub fn is_pending(&self, path_key: &str, index: usize) -> bool {
+        self.pending.contains(&(path_key.to_string(), index))
+    }
+
+    /// 転送を頼む。依頼済み、または同時に頼める数に達していれば false (次のフレームで頼み直す)
+    pub fn request(&mut self, path_key: &str, index: usize, image: Arc<DecodedImage>) -> bool {
+        if self.pending.len() >= MAX_IN_FLIGHT || self.is_pending(path_key, index) {
+            return false;
+        }
+        let request = UploadRequest { path_key: path_key.to_string(), index, image };
+        if self.requests.send(request).is_err() {
+            return false;
+        }
+        self.pending.insert((path_key.to_string(), index));
+        true
+    }
+
+    /// 転送が終わったページを受け取る (古いソース・範囲外のものを捨てるのは呼び出し側)
+    pub fn poll(&mut self) -> Vec<UploadResult> {
+        let results: Vec<UploadResult> = self.results.try_iter().collect();
+        for result in &results {
+            self.pending.remove(&(result.path_key.clone(), result.index));
+        }
+        results
+    }
+}
@@ -0,0 +1,71 @@
+use crate::image::postprocess::CropMargins;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// サイドカーファイル名。アーカイブ/フォルダと同じ場所に置く
+pub const SIDECAR_FILE_NAME: &str = ".hayate.json";
+
+/// 1アイテム (アーカイブ/フォルダ) 分の上書き設定。指定の無い項目は None
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SidecarEntry {
+    pub binding: Option<String>, // "left", "right", "single", "webtoon"
+    pub first_page_single: Option<bool>,
+    /// 見開きの組み合わせをずらすページ数 (奇数なら1ページずらす)
+    pub page_offset: Option<u32>,
+    /// 暗号化されたアーカイブのパスワード
+    pub password: Option<String>,
+    pub crop: Option<CropMargins>,
+}
+
+impl SidecarEntry {
+    /// `other` で指定された項目だけを上書きする
+    fn merge(&mut self, other: &SidecarEntry) {
+        if other.binding.is_some() { self.binding = other.binding.clone(); }
+        if other.first_page_single.is_some() { self.first_page_single = other.first_page_single; }
+        if other.page_offset.is_some() { self.page_offset = other.page_offset; }
+        if other.password.is_some() { self.password = other.password.clone(); }
+        if other.crop.is_some() { self.crop = other.crop; }
+    }
+}
+
+/// .hayate.json の内容
+/// 直下の項目はフォルダ内の全アイテムに適用し、items の同名エントリで個別に上書きする
+/// 例: { "binding": "right", "items": { "vol01.zip": { "password": "...", "page_offset": 1 } } }
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SidecarFile {
+    #[serde(flatten)]
+    defaults: SidecarEntry,
+    items: BTreeMap<String, SidecarEntry>,
+}
+
+/// ソースの隣にあるサイドカーファイルから、そのソース向けの設定を読み込む
+/// ファイルが無い・読めない場合は空の設定を返す
+pub fn load_for(source_path: &str) -> SidecarEntry {
+    let path = Path::new(source_path);
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return SidecarEntry::default();
+    };
+    let sidecar_path = dir.join(SIDECAR_FILE_NAME);
+    let Ok(content) = std::fs::read_to_string(&sidecar_path) else {
+        return SidecarEntry::default();
+    };
+    let file: SidecarFile = match serde_json::from_str(&content) {
+        Ok(file) => file,
+        Err(e) => {
+            println!("[設定] サイドカーファイルを読み込めませんでした ({}): {}", sidecar_path.display(), e);
+            return SidecarEntry::default();
+        }
+    };
+
+    let name = name.to_string_lossy();
+    let mut entry = file.defaults;
+    // ファイル名の大文字小文字は区別しない (Windows のファイルシステムに合わせる)
+    if let Some((_, item)) = file.items.iter().find(|(key, _)| key.eq_ignore_ascii_case(&name)) {
+        entry.merge(item);
+    }
+    println!("[設定] サイドカーファイルを適用: {}", sidecar_path.display());
+    entry
+}
@@ -1,18 +1,317 @@
+pub mod playlist;
+pub mod positions;
+pub mod stats;
+
+use crate::image::postprocess::CropMargins;
+
 pub struct AppState {
     pub image_files: Vec<String>,
+    /// フォルダ (章) が切り替わる位置のページ番号 (先頭ページは含まない)
     pub folder_start_indices: Vec<usize>,
+    /// 見開き時に章の先頭ページを単独表示するか
+    pub chapter_start_single: bool,
+    /// 見開き時に横長のページ (見開きでスキャンしたページ) を単独表示するか
+    pub wide_page_single: bool,
+    /// ページ番号 -> 展開中のページの進み具合 (0〜100%)。読み込みが終わったら取り除く
+    pub page_progress: std::collections::HashMap<usize, u8>,
+    /// ページ番号 -> デコードした画像の大きさ (事前回転後の幅, 高さ)。読み込み完了時に記録する
+    pub page_sizes: std::collections::HashMap<usize, (u32, u32)>,
+    /// 単ページ表示で横長のページを左右の半分に分け、1 ページずつ順に表示するか (Y)
+    pub split_wide_pages: bool,
+    /// 分割表示で表示している半分 (ページ番号, 半分)。current_page_index と違うページを指していれば前半を表示する
+    /// 読み込み・キャッシュはファイル単位のページ番号のままで、半分はトリミングとして描画する
+    pub split_half: Option<(usize, PageHalf)>,
     pub current_page_index: usize,
     pub is_spread_view: bool,
+    /// 縦スクロール (Webtoon) 表示。有効な間は見開きを使わず、ページを縦に連続して並べる
+    pub is_webtoon_view: bool,
+    /// 縦スクロール表示で現在のページから数えて画面に入っているページ数 (描画時に更新する)
+    pub webtoon_visible_pages: usize,
+    /// 直近の読み進める向き (+1 = 次のページ方向 / -1 = 前のページ方向)。先読みの偏りに使う
+    pub scroll_direction: i32,
     pub binding_direction: BindingDirection,
     pub spread_view_first_page_single: bool,
     pub is_jump_open: bool,
     pub jump_input_buffer: String,
+    /// 数字キーの直接入力で開いたジャンプ入力の最終入力時刻 (一定時間入力が無ければ自動で閉じる)
+    pub jump_typed_at: Option<std::time::Instant>,
     pub show_seekbar: bool,
     pub is_dragging_seekbar: bool,
     /// 履歴ナビゲーション用: 現在の履歴インデックス (None = 履歴未使用)
     pub current_history_index: Option<usize>,
     /// ステータスバーに表示する一時メッセージ ((メッセージ, 表示開始時刻))
     pub status_message: Option<(String, std::time::Instant)>,
+    /// OCR テキスト選択モード
+    pub is_ocr_mode: bool,
+    /// ページ番号 -> OCR 結果
+    pub ocr_results: std::collections::HashMap<usize, crate::platform::ocr::OcrPageResult>,
+    /// OCR 実行中のページ番号
+    pub ocr_pending: std::collections::HashSet<usize>,
+    /// 選択中の単語 (ページ番号, 単語インデックス)
+    pub ocr_selected: std::collections::HashSet<(usize, usize)>,
+    /// 範囲選択の開始位置 (ウィンドウ座標)
+    pub ocr_drag_start: Option<(f32, f32)>,
+    /// 表示中ソースの色調整 (明るさ・コントラスト・ガンマ)
+    pub color_adjustment: crate::render::ColorAdjustment,
+    /// ページ番号 -> 回転・反転 (E / X)。無変換のページは含めない
+    pub page_transforms: std::collections::HashMap<usize, crate::render::PageTransform>,
+    /// ページ番号 -> 平均輝度 (夜間モードの自動判定用)
+    pub page_luminance: std::collections::HashMap<usize, f32>,
+    /// 輝度ヒストグラムのオーバーレイ表示 (Shift + L)
+    pub show_histogram: bool,
+    /// スポイトモード (C)。カーソル下の画素の値を表示し、クリックでカラーコードをコピーする
+    pub is_color_picker: bool,
+    /// ページ番号 -> (輝度ヒストグラム, GPU で集計したか)
+    pub page_histograms: std::collections::HashMap<usize, ([u32; 256], bool)>,
+    /// パフォーマンス HUD の表示 (F3)
+    pub show_perf_hud: bool,
+    /// 画像情報パネルの表示 (I)
+    pub show_image_info: bool,
+    /// ページ番号 OSD: (最後に表示したページ, 表示開始時刻)
+    pub page_osd: Option<(usize, std::time::Instant)>,
+    /// 画面上部に一時表示するトースト ((メッセージ, 表示開始時刻))
+    pub osd_toast: Option<(String, std::time::Instant)>,
+    /// シリーズ一覧オーバーレイ (Tab)。開いていなければ None
+    pub series_overlay: Option<SeriesOverlay>,
+    /// フォルダ/アーカイブのサイドバー (Shift + Tab)。閉じていれば None
+    pub browser_pane: Option<crate::ui::browser::BrowserPane>,
+    /// 仕分けモード (K)。数字キーがページジャンプではなく振り分けになる
+    pub triage_mode: bool,
+    /// 取り消し履歴 (Ctrl + Z)。ページジャンプ・表示モード・回転と仕分けを新しい順に取り消す
+    pub undo: crate::undo::UndoStack,
+    /// 表示中ソースの注釈 (ソースの隣の .annotations.json)
+    pub annotations: crate::annotations::Annotations,
+    /// 注釈モード (D) で使う道具。None なら注釈モードではない
+    pub annotation_tool: Option<crate::annotations::AnnotationTool>,
+    /// 注釈を表示するか (Shift + D)
+    pub show_annotations: bool,
+    /// 描いている途中の線 (ページ番号, 線)
+    pub annotation_stroke: Option<(usize, crate::annotations::Stroke)>,
+    /// ソースごとの最後に読んだ位置 (positions.json)。起動時に読み込む
+    pub positions: positions::PositionStore,
+    /// 読書の統計 (stats.json)。起動時に読み込み、ソースを切り替えたときと終了時に保存する
+    pub stats: stats::ReadingStats,
+    /// 瞬時切り替えモードで、移動先のページが GPU に揃うのを待っているページ送り
+    pub pending_flip: Option<PendingFlip>,
+    /// バックグラウンドで取得中のアーカイブの一覧 (巨大なアーカイブは取得できた分から表示する)
+    pub archive_listing: Option<PendingListing>,
+    /// 複数ドロップで作ったプレイリスト ([ / ] で移動)
+    pub playlist: Option<playlist::Playlist>,
+    /// 自動トリミング (G)。このセッションだけ有効で、設定には保存しない
+    pub auto_crop: bool,
+    /// ページ番号 -> 検出した余白 (転送時に計測する。余白が無ければ空の値)
+    pub page_crops: std::collections::HashMap<usize, CropMargins>,
+    /// 表示中ソースの手動トリミング (Shift + G)。自動トリミングより優先し、履歴に保存する
+    pub manual_crop: Option<CropMargins>,
+    /// トリミング範囲の編集中の状態 (Shift + G)
+    pub crop_edit: Option<CropEdit>,
+    /// 実行中のページ送りのアニメーション
+    pub page_turn: Option<PageTurnAnimation>,
+    /// 直前に全ページを描き終えたフレームの配置 (ページ送りのアニメーションでめくるページ)
+    pub last_drawn_pages: Vec<TurnedPage>,
+    /// 直前に描いたフレーム (ウィンドウのサイズ変更中はこれを拡大縮小して表示する)
+    pub frozen_frame: FrozenFrame,
+}
+
+/// 保留中のページ送り (揃ったら current_page_index を page_index にする)
+pub struct PendingFlip {
+    pub page_index: usize,
+    /// 移動先で表示するページ
+    pub pages: Vec<usize>,
+}
+
+/// 取得中のアーカイブの一覧
+pub struct PendingListing {
+    pub receiver: crate::image::archive::ListingReceiver,
+    /// 開くときに指定されたページと、いま代わりに表示しているページ (一覧が揃うまでページ番号が確定しないため)
+    /// ページを移動しないまま続きが届いたら、指定されたページへ移動する
+    pub deferred_page: Option<(usize, usize)>,
+}
+
+/// トリミング範囲の編集 (編集中のページはトリミングせずに全体を表示する)
+pub struct CropEdit {
+    pub page_index: usize,
+    /// 残す範囲 (ページの表示矩形に対する 0.0〜1.0)
+    pub rect: windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F,
+    /// ドラッグ中の辺 (左, 上, 右, 下)
+    pub dragging: Option<[bool; 4]>,
+}
+
+/// 直前に描いたフレームのページの配置 (ウィンドウのサイズ変更中の簡易表示に使う)
+#[derive(Debug, Clone, Default)]
+pub struct FrozenFrame {
+    /// ページの配置と、そのページに適用した色調整
+    pub pages: Vec<(TurnedPage, crate::render::ColorAdjustment)>,
+    /// 描いたときのページ領域の大きさ (幅, 高さ)
+    pub size: (f32, f32),
+    /// 最後にサイズが変わった時刻 (落ち着くまではサイズ変更中とみなす)
+    pub resized_at: Option<std::time::Instant>,
+}
+
+impl FrozenFrame {
+    /// 最後の Resized からこの時間が経ったらサイズ変更が終わったとみなす
+    pub const SETTLE: std::time::Duration = std::time::Duration::from_millis(200);
+
+    /// サイズ変更中で、使い回せるフレームがあるか
+    pub fn is_active(&self) -> bool {
+        !self.pages.is_empty() && self.resized_at.is_some_and(|t| t.elapsed() < Self::SETTLE)
+    }
+
+    /// width x height の領域に縦横比を保って収めた配置 (はみ出さないよう縮小し、余った部分は背景色の余白にする)
+    pub fn fitted(&self, width: f32, height: f32) -> Vec<(TurnedPage, crate::render::ColorAdjustment)> {
+        let (old_w, old_h) = (self.size.0.max(1.0), self.size.1.max(1.0));
+        let scale = (width / old_w).min(height / old_h);
+        let (dx, dy) = ((width - old_w * scale) / 2.0, (height - old_h * scale) / 2.0);
+        let map = |r: &windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F| windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F {
+            left: r.left * scale + dx,
+            top: r.top * scale + dy,
+            right: r.right * scale + dx,
+            bottom: r.bottom * scale + dy,
+        };
+        self.pages
+            .iter()
+            .filter_map(|(page, adjustment)| {
+                // 拡大してはみ出していた部分は元の画面の範囲で切り、余白に描かないようにする
+                let clip = windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F {
+                    left: page.clip_rect.left.max(0.0),
+                    top: page.clip_rect.top.max(0.0),
+                    right: page.clip_rect.right.min(old_w),
+                    bottom: page.clip_rect.bottom.min(old_h),
+                };
+                if clip.right <= clip.left || clip.bottom <= clip.top {
+                    return None;
+                }
+                let page = TurnedPage { dest_rect: map(&page.dest_rect), clip_rect: map(&clip), ..page.clone() };
+                Some((page, *adjustment))
+            })
+            .collect()
+    }
+}
+
+/// ページ送りのアニメーションで重ねて描く、送る前のページ
+#[derive(Debug, Clone)]
+pub struct TurnedPage {
+    pub index: usize,
+    pub dest_rect: windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F,
+    pub clip_rect: windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F,
+    pub transform: crate::render::PageTransform,
+}
+
+/// ページ送りのアニメーション (送る前のページを新しいページの上でめくる・ずらす)
+#[derive(Debug, Clone)]
+pub struct PageTurnAnimation {
+    pub pages: Vec<TurnedPage>,
+    /// めくる範囲 (送る前のページの見えていた範囲をまとめた矩形)
+    pub area: windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F,
+    /// 右端からめくる (左綴じで次へ / 右綴じで前へ)
+    pub from_right: bool,
+    /// 紙をめくるように変形する (false なら横にずらす)
+    pub curl: bool,
+    pub start: std::time::Instant,
+    pub duration: std::time::Duration,
+}
+
+impl PageTurnAnimation {
+    /// 進み具合 (0.0〜1.0)。終わり際を緩やかにする
+    pub fn progress(&self) -> f32 {
+        let t = (self.start.elapsed().as_secs_f32() / self.duration.as_secs_f32().max(0.001)).min(1.0);
+        1.0 - (1.0 - t) * (1.0 - t)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.start.elapsed() >= self.duration
+    }
+}
+
+/// 分割表示する横長ページの半分 (読む順で先 / 後)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageHalf {
+    First,
+    Second,
+}
+
+/// 同じフォルダにあるアーカイブ/フォルダの一覧 (巻の直接選択用)
+pub struct SeriesOverlay {
+    pub entries: Vec<String>,
+    /// 表示中のソースの位置
+    pub current: Option<usize>,
+    pub selected: usize,
+}
+
+impl SeriesOverlay {
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.entries.len().saturating_sub(1) as isize;
+        self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+    }
+}
+
+/// ページをウィンドウに合わせる方法 (Q で切り替え)。ズームはこの大きさを 1.0 として掛ける
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitMode {
+    /// ページ全体をウィンドウに収める (縦長ページは tall_page_fit_width_ratio に従って幅に合わせる)
+    FitWindow,
+    /// 幅をウィンドウに合わせ、はみ出した分はホイールで縦にスクロールする
+    FitWidth,
+    /// 高さをウィンドウに合わせる
+    FitHeight,
+    /// 原寸 (画像の 1 ピクセルを画面の 1 ピクセルで表示する)
+    ActualSize,
+    /// 余白が出ないようウィンドウ全体を覆う (はみ出した分はスクロールで見る)
+    FillCrop,
+}
+
+impl FitMode {
+    const ALL: [FitMode; 5] = [Self::FitWindow, Self::FitWidth, Self::FitHeight, Self::ActualSize, Self::FillCrop];
+
+    /// 設定値 ("window" / "width" / "height" / "actual" / "fill") から変換する。不明な値は全体表示とみなす
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "width" => Self::FitWidth,
+            "height" => Self::FitHeight,
+            "actual" => Self::ActualSize,
+            "fill" => Self::FillCrop,
+            _ => Self::FitWindow,
+        }
+    }
+
+    pub fn setting_name(self) -> &'static str {
+        match self {
+            Self::FitWindow => "window",
+            Self::FitWidth => "width",
+            Self::FitHeight => "height",
+            Self::ActualSize => "actual",
+            Self::FillCrop => "fill",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FitWindow => "全体表示",
+            Self::FitWidth => "幅に合わせる",
+            Self::FitHeight => "高さに合わせる",
+            Self::ActualSize => "原寸",
+            Self::FillCrop => "全面表示",
+        }
+    }
+
+    /// 次 (direction < 0 なら前) の合わせ方
+    pub fn cycle(self, direction: isize) -> Self {
+        let current = Self::ALL.iter().position(|&m| m == self).unwrap_or(0) as isize;
+        Self::ALL[(current + direction).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// 内容 (content_w x content_h) をウィンドウに合わせる倍率
+    /// 全体・幅・高さ合わせでは小さい画像を拡大しない
+    pub fn scale(self, content_w: f32, content_h: f32, win_w: f32, win_h: f32) -> f32 {
+        let (sx, sy) = (win_w / content_w, win_h / content_h);
+        match self {
+            Self::FitWindow => sx.min(sy).min(1.0),
+            Self::FitWidth => sx.min(1.0),
+            Self::FitHeight => sy.min(1.0),
+            Self::ActualSize => 1.0,
+            Self::FillCrop => sx.max(sy),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,44 +325,243 @@ impl AppState {
         Self {
             image_files: Vec::new(),
             folder_start_indices: Vec::new(),
+            chapter_start_single: true,
+            wide_page_single: true,
+            page_progress: std::collections::HashMap::new(),
+            page_sizes: std::collections::HashMap::new(),
+            split_wide_pages: false,
+            split_half: None,
             current_page_index: 0,
             is_spread_view: true,
+            is_webtoon_view: false,
+            webtoon_visible_pages: 1,
+            scroll_direction: 1,
             binding_direction: BindingDirection::Right,
             spread_view_first_page_single: true,
             is_jump_open: false,
             jump_input_buffer: String::new(),
+            jump_typed_at: None,
             show_seekbar: false,
             is_dragging_seekbar: false,
             current_history_index: None,
             status_message: None,
+            is_ocr_mode: false,
+            ocr_results: std::collections::HashMap::new(),
+            ocr_pending: std::collections::HashSet::new(),
+            ocr_selected: std::collections::HashSet::new(),
+            ocr_drag_start: None,
+            color_adjustment: crate::render::ColorAdjustment::default(),
+            page_transforms: std::collections::HashMap::new(),
+            page_luminance: std::collections::HashMap::new(),
+            show_histogram: false,
+            is_color_picker: false,
+            page_histograms: std::collections::HashMap::new(),
+            show_perf_hud: false,
+            show_image_info: false,
+            page_osd: None,
+            osd_toast: None,
+            series_overlay: None,
+            browser_pane: None,
+            triage_mode: false,
+            undo: crate::undo::UndoStack::default(),
+            annotations: crate::annotations::Annotations::default(),
+            annotation_tool: None,
+            show_annotations: true,
+            annotation_stroke: None,
+            positions: positions::PositionStore::default(),
+            stats: stats::ReadingStats::default(),
+            pending_flip: None,
+            archive_listing: None,
+            playlist: None,
+            auto_crop: false,
+            page_crops: std::collections::HashMap::new(),
+            manual_crop: None,
+            crop_edit: None,
+            page_turn: None,
+            last_drawn_pages: Vec::new(),
+            frozen_frame: FrozenFrame::default(),
         }
     }
 
+    /// OCR の結果と選択状態を破棄する (ソース切り替え時など)
+    pub fn clear_ocr(&mut self) {
+        self.is_ocr_mode = false;
+        self.ocr_results.clear();
+        self.ocr_pending.clear();
+        self.ocr_selected.clear();
+        self.ocr_drag_start = None;
+    }
+
+    /// ページの回転・反転 (未設定なら無変換)
+    pub fn page_transform(&self, index: usize) -> crate::render::PageTransform {
+        self.page_transforms.get(&index).copied().unwrap_or_default()
+    }
+
+    /// 現在の表示状態 (取り消し用)
+    pub fn view_snapshot(&self, path_key: &str) -> crate::undo::ViewSnapshot {
+        crate::undo::ViewSnapshot {
+            path_key: path_key.to_string(),
+            page_index: self.current_page_index,
+            is_spread_view: self.is_spread_view,
+            is_webtoon_view: self.is_webtoon_view,
+            binding_direction: self.binding_direction,
+            page_transforms: self.page_transforms.clone(),
+            manual_crop: self.manual_crop,
+        }
+    }
+
+    /// これから行う操作の前の表示状態を取り消し履歴に積む
+    pub fn record_undo(&mut self, path_key: &str, label: &'static str) {
+        if self.image_files.is_empty() {
+            return;
+        }
+        let snapshot = self.view_snapshot(path_key);
+        self.undo.push_view(label, snapshot);
+    }
+
+    /// 取り消し履歴の表示状態に戻す
+    pub fn restore_view(&mut self, snapshot: crate::undo::ViewSnapshot) {
+        self.is_spread_view = snapshot.is_spread_view;
+        self.is_webtoon_view = snapshot.is_webtoon_view;
+        self.binding_direction = snapshot.binding_direction;
+        self.page_transforms = snapshot.page_transforms;
+        self.manual_crop = snapshot.manual_crop;
+        self.current_page_index = snapshot.page_index.min(self.image_files.len().saturating_sub(1));
+    }
+
+    /// ページに適用するトリミング (編集中・縦スクロール表示・余白が無い場合は None)
+    /// 分割表示中のページは、残す範囲のうち表示している半分だけを残す
+    pub fn effective_crop(&self, index: usize) -> Option<CropMargins> {
+        if self.is_webtoon_view || self.crop_edit.is_some() {
+            return None;
+        }
+        let crop = match self.manual_crop {
+            Some(crop) => Some(crop),
+            None if self.auto_crop => self.page_crops.get(&index).copied(),
+            None => None,
+        };
+        let crop = crop.filter(|c| !c.is_empty());
+        match self.page_half(index) {
+            Some(half) => Some(crop.unwrap_or_default().half(self.page_transform(index), self.is_left_half(half))),
+            None => crop,
+        }
+    }
+
+    /// 分割して表示するページか (単ページ表示の横長のページ)
+    pub fn is_split_page(&self, index: usize) -> bool {
+        self.split_wide_pages && !self.is_spread_view && !self.is_webtoon_view && self.is_wide_page(index)
+    }
+
+    /// index のページで表示している半分 (分割しないページは None)
+    pub fn page_half(&self, index: usize) -> Option<PageHalf> {
+        if !self.is_split_page(index) {
+            return None;
+        }
+        match self.split_half {
+            Some((page, half)) if page == index => Some(half),
+            _ => Some(PageHalf::First),
+        }
+    }
+
+    /// 半分が画面上の左側か (右綴じは右半分から読む)
+    pub fn is_left_half(&self, half: PageHalf) -> bool {
+        (half == PageHalf::First) == (self.binding_direction == BindingDirection::Left)
+    }
+
+    /// 元から暗いページか (夜間モードで反転しないページ)。輝度が未計測なら false
+    pub fn is_dark_page(&self, index: usize) -> bool {
+        const DARK_PAGE_THRESHOLD: f32 = 0.45;
+        self.page_luminance.get(&index).map_or(false, |&l| l < DARK_PAGE_THRESHOLD)
+    }
+
+    /// 横長のページか (ページの回転で縦横が入れ替わる場合は回転後の向きで判定する)。大きさが未計測なら false
+    pub fn is_wide_page(&self, index: usize) -> bool {
+        let Some(&(width, height)) = self.page_sizes.get(&index) else { return false; };
+        let (width, height) = if self.page_transform(index).swaps_axes() { (height, width) } else { (width, height) };
+        width > height
+    }
+
+    /// 見開き時に単独表示するページ (先頭ページ・章の先頭ページ・横長のページ)
+    /// 横長のページは読み込むまで分からないため、デコードが届いた時点で組み合わせが変わる
+    fn single_page_indices(&self) -> std::collections::HashSet<usize> {
+        let mut indices = std::collections::HashSet::new();
+        if self.spread_view_first_page_single {
+            indices.insert(0);
+        }
+        if self.chapter_start_single {
+            indices.extend(self.folder_start_indices.iter().copied());
+        }
+        if self.wide_page_single {
+            indices.extend(self.page_sizes.keys().copied().filter(|&i| self.is_wide_page(i)));
+        }
+        indices
+    }
+
+    /// 章の数と、現在のページが何番目の章か (1始まり)
+    pub fn chapter_position(&self) -> (usize, usize) {
+        let current = self
+            .folder_start_indices
+            .iter()
+            .filter(|&&idx| idx <= self.current_page_index)
+            .count();
+        (current + 1, self.folder_start_indices.len() + 1)
+    }
+
+    /// 次 (direction < 0 なら前) の章の先頭ページ。移動先が無ければ None
+    /// 前へは章の途中なら今の章の先頭、章の先頭にいれば 1 つ前の章の先頭へ戻る
+    pub fn chapter_start(&self, direction: isize) -> Option<usize> {
+        if direction > 0 {
+            // 見開きで次の章の先頭を既に表示していればその次の章へ
+            let last_shown = self.get_page_indices_to_display().into_iter().max().unwrap_or(self.current_page_index);
+            self.folder_start_indices.iter().copied().find(|&idx| idx > last_shown)
+        } else {
+            std::iter::once(0)
+                .chain(self.folder_start_indices.iter().copied())
+                .rev()
+                .find(|&idx| idx < self.current_page_index)
+        }
+    }
+
+    /// 現在のページの章内での位置 (章番号, 章内のページ番号, 章のページ数)。章が 1 つだけなら None
+    pub fn chapter_page_position(&self) -> Option<(usize, usize, usize)> {
+        if self.folder_start_indices.is_empty() || self.image_files.is_empty() {
+            return None;
+        }
+        let (chapter, _) = self.chapter_position();
+        let start = if chapter == 1 { 0 } else { self.folder_start_indices[chapter - 2] };
+        let end = self.folder_start_indices.get(chapter - 1).copied().unwrap_or(self.image_files.len());
+        Some((chapter, self.current_page_index - start + 1, end - start))
+    }
+
     pub fn get_page_indices_to_display(&self) -> Vec<usize> {
+        self.page_indices_at(self.current_page_index)
+    }
+
+    /// index の位置で表示するページ (get_page_indices_to_display を任意の位置で求める)
+    pub fn page_indices_at(&self, index: usize) -> Vec<usize> {
         let total_pages = self.image_files.len();
         if total_pages == 0 {
             return Vec::new();
         }
 
+        if self.is_webtoon_view {
+            let end = (index + self.webtoon_visible_pages.max(1)).min(total_pages);
+            return (index..end).collect();
+        }
+
         if !self.is_spread_view {
-            return vec![self.current_page_index];
+            return vec![index];
         }
 
         // 見開き表示モード
-        let mut single_page_indices = std::collections::HashSet::new();
-        if self.spread_view_first_page_single {
-            single_page_indices.insert(0);
-            for &idx in &self.folder_start_indices {
-                single_page_indices.insert(idx);
-            }
-        }
+        let single_page_indices = self.single_page_indices();
 
-        if single_page_indices.contains(&self.current_page_index) {
-            return vec![self.current_page_index];
+        if single_page_indices.contains(&index) {
+            return vec![index];
         }
 
-        let page1 = self.current_page_index;
-        let page2 = self.current_page_index + 1;
+        let page1 = index;
+        let page2 = index + 1;
 
         if page2 >= total_pages || single_page_indices.contains(&page2) {
             return vec![page1];
@@ -75,41 +573,109 @@ impl AppState {
         }
     }
 
+    /// 先読みする範囲 (現在のページより前のページ数, 後のページ数)
+    /// 縦スクロール表示ではスクロールの向きの先を優先し、戻る側は少しだけ残す
+    pub fn prefetch_window(&self, distance: usize) -> (usize, usize) {
+        if !self.is_webtoon_view {
+            return (distance, distance);
+        }
+        let behind = (distance / 3).max(1).min(distance);
+        if self.scroll_direction < 0 { (distance, behind) } else { (behind, distance) }
+    }
+
+    /// 前後に表示する組 (見開きなら 2 ページ) を辿って先読みするページを集める (表示中のページは含めない)
+    /// ページ番号の前後で数えると右綴じの見開きで片側に偏るため、実際に次/前に表示する組の単位で数え、組は途中で切らない
+    /// 並びは近い組から順で、同じ距離なら進む向きを先にする
+    pub fn prefetch_pages(&self, before: usize, after: usize) -> Vec<usize> {
+        let displayed = self.get_page_indices_to_display();
+        let forward = if self.scroll_direction < 0 { -1 } else { 1 };
+        let mut pages = Vec::new();
+        // (向き, 残りページ数, 辿っている位置)
+        let mut walks = [(forward, if forward > 0 { after } else { before }, self.current_page_index), (-forward, if forward > 0 { before } else { after }, self.current_page_index)];
+        while walks.iter().any(|&(_, remaining, _)| remaining > 0) {
+            for (direction, remaining, index) in walks.iter_mut() {
+                if *remaining == 0 {
+                    continue;
+                }
+                let next = self.step_from(*index, *direction);
+                // 端に着いた (進めない) らその向きは終わり
+                if (next as i64 - *index as i64).signum() != *direction as i64 {
+                    *remaining = 0;
+                    continue;
+                }
+                *index = next;
+                for page in self.page_indices_at(next) {
+                    if !displayed.contains(&page) && !pages.contains(&page) {
+                        pages.push(page);
+                        *remaining = remaining.saturating_sub(1);
+                    }
+                }
+            }
+        }
+        pages
+    }
+
     pub fn navigate(&mut self, direction: i32) {
+        if self.image_files.is_empty() {
+            return;
+        }
+        self.scroll_direction = direction.signum();
+        let current = self.current_page_index;
+        // 分割表示中は、ページを移る前に同じページのもう半分を表示する
+        if direction.abs() == 1 {
+            match self.page_half(current) {
+                Some(PageHalf::First) if direction > 0 => {
+                    self.split_half = Some((current, PageHalf::Second));
+                    return;
+                }
+                Some(PageHalf::Second) if direction < 0 => {
+                    self.split_half = Some((current, PageHalf::First));
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.current_page_index = self.step_from(current, direction);
+        if self.current_page_index != current {
+            // 戻ってきたページは後半から表示する (分割するかは読み込むまで分からないため、向きだけ記録しておく)
+            let half = if direction > 0 { PageHalf::First } else { PageHalf::Second };
+            self.split_half = Some((self.current_page_index, half));
+        }
+    }
+
+    /// from の位置から direction 方向へページ送りしたときの位置 (navigate と同じ規則)
+    pub fn step_from(&self, from: usize, direction: i32) -> usize {
         let total_pages = self.image_files.len();
         if total_pages == 0 {
-            return;
+            return from;
         }
 
-        let is_offset = self.snap_to_spread(self.current_page_index) != self.current_page_index;
+        let is_offset = self.snap_to_spread(from) != from;
 
         let mut step = if self.is_spread_view { 2 } else { 1 };
         step *= direction.abs() as usize;
 
         let mut single_page_indices = std::collections::HashSet::new();
-        if self.is_spread_view && self.spread_view_first_page_single {
-            single_page_indices.insert(0);
-            for &idx in &self.folder_start_indices {
-                single_page_indices.insert(idx);
-            }
+        if self.is_spread_view {
+            single_page_indices = self.single_page_indices();
 
             if direction > 0 {
-                if single_page_indices.contains(&self.current_page_index)
-                    || single_page_indices.contains(&(self.current_page_index + 1))
+                if single_page_indices.contains(&from)
+                    || single_page_indices.contains(&(from + 1))
                 {
                     step = 1 * direction.abs() as usize;
                 }
             } else {
-                if single_page_indices.contains(&(self.current_page_index.saturating_sub(1))) {
+                if single_page_indices.contains(&(from.saturating_sub(1))) {
                     step = 1 * direction.abs() as usize;
                 }
             }
         }
 
         let new_index = if direction > 0 {
-            (self.current_page_index + step).min(total_pages - 1)
+            (from + step).min(total_pages - 1)
         } else {
-            self.current_page_index.saturating_sub(step)
+            from.saturating_sub(step)
         };
 
         let mut snapped = self.snap_to_spread(new_index);
@@ -120,7 +686,7 @@ impl AppState {
             }
         }
 
-        self.current_page_index = snapped;
+        snapped
     }
 
     pub fn snap_to_spread(&self, index: usize) -> usize {
@@ -134,13 +700,7 @@ impl AppState {
             return index;
         }
 
-        let mut single_page_indices = std::collections::HashSet::new();
-        if self.spread_view_first_page_single {
-            single_page_indices.insert(0);
-            for &idx in &self.folder_start_indices {
-                single_page_indices.insert(idx);
-            }
-        }
+        let single_page_indices = self.single_page_indices();
 
         // 単ページ表示すべきインデックスならそのまま
         if single_page_indices.contains(&index) {
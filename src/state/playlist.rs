@@ -0,0 +1,36 @@
+/// 複数のファイル/フォルダをまとめてドロップしたときのプレイリスト (このセッションのみ)
+/// [ / ] は表示中のソースがプレイリストにある間はプレイリスト内を移動し、それ以外は隣のフォルダ/アーカイブへ移動する
+#[derive(Debug, Clone)]
+pub struct Playlist {
+    entries: Vec<String>,
+}
+
+impl Playlist {
+    /// ドロップされたパスを名前順 (自然順) に並べ、重複を除いて作る
+    pub fn new(mut entries: Vec<String>) -> Self {
+        entries.sort_by(|a, b| natord::compare(a, b));
+        entries.dedup();
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    pub fn position(&self, path: &str) -> Option<usize> {
+        self.entries.iter().position(|e| e == path)
+    }
+
+    /// path の次 (direction > 0) / 前のエントリ。path がプレイリストに無い場合と端の場合は None
+    pub fn neighbor(&self, path: &str, direction: i32) -> Option<(usize, &str)> {
+        let current = self.position(path)? as isize;
+        let next = current + direction.signum() as isize;
+        let index = usize::try_from(next).ok()?;
+        self.entries.get(index).map(|e| (index, e.as_str()))
+    }
+
+    /// 開けなかったエントリを取り除く
+    pub fn remove(&mut self, path: &str) {
+        self.entries.retain(|e| e != path);
+    }
+}
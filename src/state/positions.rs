@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 記録するソースの上限 (履歴の件数とは別)。超えたら最後に読んだのが古いものから
+/// POSITIONS_AFTER_TRIM 件まで捨てる (上限付近で記録のたびに並べ替えないように)
+const MAX_POSITIONS: usize = 20000;
+const POSITIONS_AFTER_TRIM: usize = MAX_POSITIONS * 9 / 10;
+
+/// 検索用のキー。Windows のパスは大文字小文字と区切り文字の違いを同じソースとして扱う
+fn position_key(source: &str) -> String {
+    source.trim_end_matches(['\\', '/']).replace('/', "\\").to_lowercase()
+}
+
+/// ソースごとの最後に読んだ位置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadingPosition {
+    pub page: usize,
+    /// 表示モード ("single" / "left" / "right" / "webtoon")
+    pub binding: String,
+    /// 記録時のページ数 (読了の目安)
+    #[serde(default)]
+    pub total_pages: usize,
+    /// 最後に読んだ日時 (UNIX 秒)
+    #[serde(default)]
+    pub updated: u64,
+    /// 記録時に表示していたページのファイル名/エントリ名
+    /// フォルダの並び順 (シャッフル・更新日時順) は起動やファイルの更新で変わるため、再開時はページ番号よりこちらを優先する
+    #[serde(default)]
+    pub file: String,
+}
+
+/// 読書位置データベース (positions.json)
+/// 履歴 (max_history_count 件の「最近開いた項目」) とは別に持ち、履歴から押し出されたソースも
+/// 開き直したときに前回の位置から再開できるようにする
+#[derive(Debug, Default)]
+pub struct PositionStore {
+    path: Option<PathBuf>,
+    /// position_key で正規化したパス -> 位置
+    entries: HashMap<String, ReadingPosition>,
+    dirty: bool,
+}
+
+impl PositionStore {
+    /// 設定ファイルと同じフォルダの positions.json を読み込む。無い・読めない場合は空
+    pub fn load(config_path: &str) -> Self {
+        let path = Path::new(config_path).with_file_name("positions.json");
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<HashMap<String, ReadingPosition>>(&content) {
+                Ok(entries) => {
+                    println!("[位置] 読み込み: {} ({} 件)", path.display(), entries.len());
+                    // 手編集されたファイルも正規化したキーで引けるようにする
+                    entries.into_iter().map(|(k, v)| (position_key(&k), v)).collect()
+                }
+                Err(e) => {
+                    println!("[位置] 読書位置を読み込めませんでした ({}): {}", path.display(), e);
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+        Self { path: Some(path), entries, dirty: false }
+    }
+
+    pub fn get(&self, source: &str) -> Option<&ReadingPosition> {
+        self.entries.get(&position_key(source))
+    }
+
+    pub fn record(&mut self, source: &str, page: usize, binding: &str, total_pages: usize, file: &str) {
+        let updated = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let position = ReadingPosition { page, binding: binding.to_string(), total_pages, updated, file: file.to_string() };
+        self.entries.insert(position_key(source), position);
+        if self.entries.len() > MAX_POSITIONS {
+            let mut by_age: Vec<(u64, String)> = self.entries.iter().map(|(k, v)| (v.updated, k.clone())).collect();
+            by_age.sort_unstable();
+            let excess = self.entries.len() - POSITIONS_AFTER_TRIM;
+            for (_, key) in by_age.into_iter().take(excess) {
+                self.entries.remove(&key);
+            }
+            println!("[位置] 記録が上限を超えたため古い {} 件を削除しました", excess);
+        }
+        self.dirty = true;
+    }
+
+    /// ごみ箱へ移動したソースの記録を消す
+    pub fn remove(&mut self, source: &str) {
+        if self.entries.remove(&position_key(source)).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// 変更があれば positions.json への書き込みを書き込みスレッドに頼む (書き込みの完了は待たない)
+    pub fn save(&mut self) -> std::io::Result<()> {
+        let Some(path) = self.path.as_ref() else { return Ok(()); };
+        if !self.dirty {
+            return Ok(());
+        }
+        let content = serde_json::to_string_pretty(&self.entries).map_err(std::io::Error::other)?;
+        crate::persist::write_in_background(path.clone(), content);
+        self.dirty = false;
+        Ok(())
+    }
+}
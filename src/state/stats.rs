@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// 1 回の表示で数える時間の上限 (開いたまま離席した時間を読書時間に含めない)
+const MAX_VIEW_SECONDS: f64 = 300.0;
+/// 日ごとの記録を残す日数
+const KEEP_DAYS: i64 = 400;
+/// 記録するソースの上限。超えたら最後に読んだのが古いものから捨てる
+const MAX_SOURCES: usize = 5000;
+/// 統計画面に並べるソースの数
+const RECENT_SOURCES: usize = 5;
+
+/// ソースごとの累計
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceStats {
+    /// 読んでいた時間 (秒)
+    pub seconds: f64,
+    /// 読んだページ数 (1 回の閲覧で同じページは 1 回だけ数える)
+    pub pages: u64,
+    /// 開いた回数
+    pub sessions: u64,
+    /// 最後に読んだ日時 (UNIX 秒)
+    pub last_read: u64,
+}
+
+/// 1 日分の合計 (ローカル時刻の日付ごと)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DayStats {
+    pub seconds: f64,
+    pub pages: u64,
+    pub sessions: u64,
+}
+
+impl DayStats {
+    fn add(&mut self, other: &DayStats) {
+        self.seconds += other.seconds;
+        self.pages += other.pages;
+        self.sessions += other.sessions;
+    }
+
+    /// 1 ページあたりの平均時間 (秒)。ページを読んでいなければ None
+    pub fn seconds_per_page(&self) -> Option<f64> {
+        (self.pages > 0).then(|| self.seconds / self.pages as f64)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct StatsFile {
+    sources: HashMap<String, SourceStats>,
+    /// 1970-01-01 からの日数 (ローカル時刻) -> その日の合計
+    days: BTreeMap<i64, DayStats>,
+}
+
+/// 開いているソースの閲覧 (ソースを切り替えるか終了するまで)
+#[derive(Debug)]
+struct Session {
+    source: String,
+    /// 表示中のページ
+    pages: Vec<usize>,
+    /// この閲覧で表示したページ (読んだページ数を数える)
+    seen: HashSet<usize>,
+    /// 表示中のページを表示し始めた時刻
+    since: Instant,
+}
+
+/// 統計画面に表示する集計
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsSummary {
+    pub today: DayStats,
+    /// 今週 (月曜日から今日まで)
+    pub week: DayStats,
+    pub total: DayStats,
+    /// 最近読んだソース (パス, 今までの累計)
+    pub recent_sources: Vec<(String, SourceStats)>,
+}
+
+/// 読書の統計 (stats.json)
+/// 表示中のページを描画のたびに observe に渡し、ページが変わったときに前のページを表示していた時間を加える
+#[derive(Debug, Default)]
+pub struct ReadingStats {
+    path: Option<PathBuf>,
+    data: StatsFile,
+    session: Option<Session>,
+    dirty: bool,
+    /// 記録が変わるたびに増える (統計画面の集計を作り直す目安)
+    revision: u64,
+}
+
+impl ReadingStats {
+    /// 設定ファイルと同じフォルダの stats.json を読み込む。無い・読めない場合は空
+    pub fn load(config_path: &str) -> Self {
+        let path = Path::new(config_path).with_file_name("stats.json");
+        let data = match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<StatsFile>(&content) {
+                Ok(data) => {
+                    println!("[統計] 読み込み: {} ({} 件)", path.display(), data.sources.len());
+                    data
+                }
+                Err(e) => {
+                    println!("[統計] 読書の統計を読み込めませんでした ({}): {}", path.display(), e);
+                    StatsFile::default()
+                }
+            },
+            Err(_) => StatsFile::default(),
+        };
+        Self { path: Some(path), data, ..Default::default() }
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 表示中のページを記録する (描画のたびに呼ぶ。表示が変わったときだけ記録する)
+    pub fn observe(&mut self, source: &str, pages: &[usize]) {
+        if source.is_empty() || pages.is_empty() {
+            return;
+        }
+        let same_source = self.session.as_ref().is_some_and(|s| s.source == source);
+        if same_source && self.session.as_ref().is_some_and(|s| s.pages == pages) {
+            return;
+        }
+
+        let today = local_day();
+        if same_source {
+            self.add_elapsed();
+        } else {
+            self.end_session();
+            let stats = self.data.sources.entry(source.to_string()).or_default();
+            stats.sessions += 1;
+            self.data.days.entry(today).or_default().sessions += 1;
+            self.session = Some(Session { source: source.to_string(), pages: Vec::new(), seen: HashSet::new(), since: Instant::now() });
+        }
+
+        let Some(session) = self.session.as_mut() else { return; };
+        session.pages = pages.to_vec();
+        session.since = Instant::now();
+        let new_pages = pages.iter().filter(|&&p| session.seen.insert(p)).count() as u64;
+        let stats = self.data.sources.entry(source.to_string()).or_default();
+        stats.pages += new_pages;
+        stats.last_read = now_secs();
+        self.data.days.entry(today).or_default().pages += new_pages;
+        self.dirty = true;
+        self.revision += 1;
+    }
+
+    /// 表示中のページの時間を加えて閲覧を終える (ソースを閉じる・終了するとき)
+    pub fn end_session(&mut self) {
+        self.add_elapsed();
+        if self.session.take().is_some() {
+            self.trim();
+        }
+    }
+
+    /// 表示中のページを表示し始めてからの時間を、開いているソースと今日の合計に加える
+    fn add_elapsed(&mut self) {
+        let Some(session) = self.session.as_mut() else { return; };
+        let seconds = session.since.elapsed().as_secs_f64().min(MAX_VIEW_SECONDS);
+        session.since = Instant::now();
+        if seconds <= 0.0 {
+            return;
+        }
+        self.data.sources.entry(session.source.clone()).or_default().seconds += seconds;
+        self.data.days.entry(local_day()).or_default().seconds += seconds;
+        self.dirty = true;
+        self.revision += 1;
+    }
+
+    /// 古い日の記録と、上限を超えたソースの記録を捨てる
+    fn trim(&mut self) {
+        let oldest = local_day() - KEEP_DAYS;
+        self.data.days.retain(|&day, _| day >= oldest);
+        if self.data.sources.len() > MAX_SOURCES {
+            let mut by_age: Vec<(u64, String)> = self.data.sources.iter().map(|(k, v)| (v.last_read, k.clone())).collect();
+            by_age.sort_unstable();
+            let excess = self.data.sources.len() - MAX_SOURCES;
+            for (_, key) in by_age.into_iter().take(excess) {
+                self.data.sources.remove(&key);
+            }
+        }
+    }
+
+    /// 今日・今週・全期間の合計と、最近読んだソース
+    pub fn summary(&self) -> StatsSummary {
+        let today = local_day();
+        let week_start = today - local_weekday_from_monday();
+        let mut summary = StatsSummary::default();
+        for (&day, stats) in &self.data.days {
+            summary.total.add(stats);
+            if day >= week_start {
+                summary.week.add(stats);
+            }
+            if day == today {
+                summary.today.add(stats);
+            }
+        }
+        let mut sources: Vec<(&String, &SourceStats)> = self.data.sources.iter().collect();
+        sources.sort_by(|a, b| b.1.last_read.cmp(&a.1.last_read));
+        summary.recent_sources = sources.into_iter().take(RECENT_SOURCES).map(|(k, v)| (k.clone(), v.clone())).collect();
+        summary
+    }
+
+    /// 変更があれば stats.json への書き込みを書き込みスレッドに頼む (書き込みの完了は待たない)
+    pub fn save(&mut self) -> std::io::Result<()> {
+        let Some(path) = self.path.as_ref() else { return Ok(()); };
+        if !self.dirty {
+            return Ok(());
+        }
+        let content = serde_json::to_string_pretty(&self.data).map_err(std::io::Error::other)?;
+        crate::persist::write_in_background(path.clone(), content);
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 今日の日付 (ローカル時刻) の 1970-01-01 からの日数
+fn local_day() -> i64 {
+    let now = unsafe { windows::Win32::System::SystemInformation::GetLocalTime() };
+    days_from_civil(now.wYear as i64, now.wMonth as i64, now.wDay as i64)
+}
+
+/// 今日が月曜日から何日目か (月曜日 = 0)
+fn local_weekday_from_monday() -> i64 {
+    let now = unsafe { windows::Win32::System::SystemInformation::GetLocalTime() };
+    (now.wDayOfWeek as i64 + 6) % 7
+}
+
+/// 年月日を 1970-01-01 からの日数に変換する (グレゴリオ暦)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
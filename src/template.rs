@@ -0,0 +1,53 @@
+/// タイトルバーの既定の書式 (ソースを開いていないときは "HayateViewer v{version}" だけを表示する)
+pub const DEFAULT_TITLE_FORMAT: &str = "HayateViewer v{version} - {archive} / {file}";
+
+/// ステータスバーの既定の書式 ({cache} はキャッシュ詳細を表示しない設定では空になり、区切りごと省かれる)
+pub const DEFAULT_STATUSBAR_FORMAT: &str = "Page: {position} {mode} | Backend: {backend} | {cache} | Key: {path}";
+
+/// 書式で使えるプレースホルダーと説明 (設定画面の案内に使う)
+pub const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("version", "バージョン"),
+    ("archive", "ソース名"),
+    ("path", "ソースのパス :: ページ名"),
+    ("file", "表示中の画像名"),
+    ("page", "ページ番号 (見開きは 3-4)"),
+    ("total", "総ページ数"),
+    ("position", "ページ / 総ページ数 (章の位置付き)"),
+    ("mode", "表示モードと状態"),
+    ("zoom", "ズーム倍率"),
+    ("backend", "描画エンジン"),
+    ("cache", "キャッシュと VRAM (ステータスバーのみ)"),
+];
+
+/// {name} を values の値に置き換える ({{ / }} は波括弧そのもの、未知の名前はそのまま残す)
+/// 値が空になって " | " の間が空いた区切りは取り除き、末尾に残った空白や区切り記号も詰める
+pub fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len() * 2);
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('{') {
+            if let Some(end) = tail.find('}') {
+                let name = &tail[1..end];
+                match values.iter().find(|(key, _)| *key == name) {
+                    Some((_, value)) => out.push_str(value),
+                    None => out.push_str(&tail[..=end]),
+                }
+                rest = &tail[end + 1..];
+                continue;
+            }
+        }
+        out.push_str(&tail[..1]);
+        rest = &tail[1..];
+    }
+    out.push_str(rest);
+
+    let segments: Vec<&str> = out.split(" | ").map(str::trim).filter(|s| !s.is_empty()).collect();
+    segments.join(" | ").trim_end_matches([' ', '/', '-', '|']).trim_start().to_string()
+}
@@ -0,0 +1,62 @@
+use crate::config::TriageTarget;
+use crate::platform::file_ops;
+use crate::undo::{UndoEntry, UndoStack};
+use std::path::{Path, PathBuf};
+use windows::Win32::Foundation::HWND;
+
+/// 仕分けで行った 1 回分の移動/コピー (取り消し用)
+#[derive(Debug, Clone)]
+pub struct TriageRecord {
+    pub copied: bool,
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// `dir` に `file_name` を置くときのパス。同名のファイルがあれば "名前 (2).拡張子" のように番号を付ける
+fn unique_destination(dir: &Path, file_name: &Path) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let stem = file_name.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = file_name.extension().map(|s| format!(".{}", s.to_string_lossy())).unwrap_or_default();
+    (2..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap()
+}
+
+/// 画像ファイルを振り分け先へ移動/コピーし、取り消し用の記録を履歴に積む
+pub fn apply(owner: HWND, target: &TriageTarget, file: &Path, undo: &mut UndoStack) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let name = file.file_name().ok_or("ファイル名がありません")?;
+    let destination = unique_destination(Path::new(&target.path), Path::new(name));
+    if target.copy {
+        file_ops::copy_file(owner, file, &destination)?;
+    } else {
+        file_ops::move_file(owner, file, &destination)?;
+    }
+    println!(
+        "[仕分け] {}しました: {} -> {}",
+        if target.copy { "コピー" } else { "移動" },
+        file.display(),
+        destination.display()
+    );
+    undo.push(UndoEntry::Triage(TriageRecord { copied: target.copy, from: file.to_path_buf(), to: destination.clone() }));
+    Ok(destination)
+}
+
+/// 仕分けを取り消す。移動は元のフォルダへ戻し、コピーは作ったファイルをごみ箱へ移動する
+/// 戻したファイルのパス (コピーの場合は元のファイル) を返す
+pub fn undo(owner: HWND, record: &TriageRecord) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if record.copied {
+        file_ops::move_to_recycle_bin(owner, &record.to)?;
+        println!("[仕分け] コピーを取り消しました: {}", record.to.display());
+        return Ok(record.from.clone());
+    }
+    let dir = record.from.parent().ok_or("元のフォルダがありません")?;
+    let name = record.from.file_name().ok_or("ファイル名がありません")?;
+    let restored = unique_destination(dir, Path::new(name));
+    file_ops::move_file(owner, &record.to, &restored)?;
+    println!("[仕分け] 移動を取り消しました: {} -> {}", record.to.display(), restored.display());
+    Ok(restored)
+}
@@ -0,0 +1,428 @@
+use crate::config::Settings;
+use crate::image::loader::UserEvent;
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use windows::{
+    Win32::Foundation::*, Win32::Graphics::Direct2D::Common::*, Win32::Graphics::Direct2D::*,
+    Win32::Graphics::Direct3D::*, Win32::Graphics::Direct3D11::*, Win32::Graphics::DirectWrite::*,
+    Win32::Graphics::Dxgi::Common::*, Win32::Graphics::Dxgi::*, Win32::UI::WindowsAndMessaging::*,
+    core::*,
+};
+use winit::{
+    event::*,
+    event_loop::EventLoopWindowTarget,
+    keyboard::{Key, NamedKey},
+    window::{Window, WindowBuilder},
+};
+
+const ITEM_HEIGHT: f32 = 30.0;
+const START_Y: f32 = 50.0;
+
+pub struct BookmarksWindow {
+    pub window: Arc<Window>,
+    pub _factory: ID2D1Factory1,
+    pub _device: ID2D1Device,
+    pub context: ID2D1DeviceContext,
+    pub swap_chain: IDXGISwapChain1,
+    pub brush: ID2D1SolidColorBrush,
+    pub text_format: IDWriteTextFormat,
+    pub event_proxy: winit::event_loop::EventLoopProxy<UserEvent>,
+    pub selected_index: usize,
+    pub mouse_pos: (f32, f32),
+    pub last_click_time: Instant,
+    pub last_click_idx: Option<usize>,
+    /// 名前の変更中のテキスト (F2 で開始、Enter で確定、Esc で取り消し)
+    pub editing: Option<String>,
+}
+
+impl BookmarksWindow {
+    pub fn new<T>(
+        elwt: &EventLoopWindowTarget<T>,
+        parent_hwnd: HWND,
+        event_proxy: winit::event_loop::EventLoopProxy<UserEvent>,
+    ) -> Result<Self> {
+        let window = WindowBuilder::new()
+            .with_title("しおり")
+            .with_inner_size(winit::dpi::LogicalSize::new(800.0, 400.0))
+            .with_min_inner_size(winit::dpi::LogicalSize::new(420.0, 150.0)) // ヘッダーが収まる最小サイズ
+            .with_decorations(true)
+            .with_resizable(true)
+            .build(elwt)
+            .map_err(|_| Error::new(HRESULT(-1), "Failed to build window"))?;
+
+        let hwnd = match window.raw_window_handle() {
+            RawWindowHandle::Win32(handle) => HWND(handle.hwnd as _),
+            _ => return Err(Error::new(HRESULT(-1), "Unsupported window handle")),
+        };
+
+        // Parent window setting for Win32
+        unsafe {
+            SetWindowLongPtrW(hwnd, GWLP_HWNDPARENT, parent_hwnd.0 as isize);
+        }
+
+        // 名前の変更で日本語を入力できるよう IME を有効にする
+        window.set_ime_allowed(true);
+        let window = Arc::new(window);
+
+        unsafe {
+            let mut d3d_device: Option<ID3D11Device> = None;
+            D3D11CreateDevice(
+                None,
+                D3D_DRIVER_TYPE_HARDWARE,
+                HMODULE::default(),
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                None,
+                D3D11_SDK_VERSION,
+                Some(&mut d3d_device),
+                None,
+                None,
+            )?;
+            let d3d_device = d3d_device.unwrap();
+            let dxgi_device: IDXGIDevice = d3d_device.cast()?;
+
+            let factory: ID2D1Factory1 =
+                D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None)?;
+            let device = factory.CreateDevice(&dxgi_device)?;
+            let context = device.CreateDeviceContext(D2D1_DEVICE_CONTEXT_OPTIONS_NONE)?;
+
+            let dxgi_factory: IDXGIFactory2 = CreateDXGIFactory1()?;
+            let sc_desc = DXGI_SWAP_CHAIN_DESC1 {
+                Width: 0,
+                Height: 0,
+                Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                Stereo: false.into(),
+                SampleDesc: DXGI_SAMPLE_DESC {
+                    Count: 1,
+                    Quality: 0,
+                },
+                BufferUsage: DXGI_USAGE_RENDER_TARGET_OUTPUT,
+                BufferCount: 2,
+                Scaling: DXGI_SCALING_STRETCH,
+                SwapEffect: DXGI_SWAP_EFFECT_FLIP_SEQUENTIAL,
+                AlphaMode: DXGI_ALPHA_MODE_IGNORE,
+                Flags: DXGI_SWAP_CHAIN_FLAG(0).0 as _,
+            };
+            let swap_chain =
+                dxgi_factory.CreateSwapChainForHwnd(&d3d_device, hwnd, &sc_desc, None, None)?;
+
+            let surface: IDXGISurface = swap_chain.GetBuffer(0)?;
+            let back_buffer: ID2D1Bitmap1 = context.CreateBitmapFromDxgiSurface(&surface, None)?;
+            context.SetTarget(&back_buffer);
+            // 描画座標・フォントを論理座標で扱い、モニターの拡大率に合わせる
+            super::dpi::apply_window_dpi(&context, &window);
+
+            let brush = context.CreateSolidColorBrush(
+                &D2D1_COLOR_F {
+                    r: 1.0,
+                    g: 1.0,
+                    b: 1.0,
+                    a: 1.0,
+                },
+                None,
+            )?;
+
+            let dw_factory: IDWriteFactory = DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED)?;
+            let text_format = dw_factory.CreateTextFormat(
+                w!("Yu Gothic UI"),
+                None,
+                DWRITE_FONT_WEIGHT_NORMAL,
+                DWRITE_FONT_STYLE_NORMAL,
+                DWRITE_FONT_STRETCH_NORMAL,
+                16.0,
+                w!("ja-jp"),
+            )?;
+            text_format.SetTextAlignment(DWRITE_TEXT_ALIGNMENT_LEADING)?;
+
+            Ok(Self {
+                window,
+                _factory: factory,
+                _device: device,
+                context,
+                swap_chain,
+                brush,
+                text_format,
+                event_proxy,
+                selected_index: 0,
+                mouse_pos: (0.0, 0.0),
+                last_click_time: Instant::now(),
+                last_click_idx: None,
+                editing: None,
+            })
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &WindowEvent, settings: &Settings) -> bool {
+        let count = settings.bookmarks.len();
+        if count > 0 && self.selected_index >= count {
+            // 削除などで件数が減った場合は末尾に合わせる
+            self.selected_index = count - 1;
+        }
+
+        match event {
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        logical_key,
+                        text,
+                        state: ElementState::Pressed,
+                        ..
+                    },
+                ..
+            } => {
+                if self.editing.is_some() {
+                    self.handle_edit_key(logical_key, text.as_deref());
+                    self.window.request_redraw();
+                    return false;
+                }
+                match logical_key {
+                    Key::Named(NamedKey::ArrowUp) => {
+                        if self.selected_index > 0 {
+                            self.selected_index -= 1;
+                        } else if count > 0 {
+                            self.selected_index = count - 1;
+                        }
+                        self.window.request_redraw();
+                    }
+                    Key::Named(NamedKey::ArrowDown) => {
+                        if count > 0 {
+                            self.selected_index = (self.selected_index + 1) % count;
+                        }
+                        self.window.request_redraw();
+                    }
+                    Key::Named(NamedKey::Enter) => {
+                        self.confirm_selection(settings);
+                        return true;
+                    }
+                    Key::Named(NamedKey::F2) => {
+                        if let Some(bookmark) = settings.bookmarks.get(self.selected_index) {
+                            self.editing = Some(bookmark.label.clone());
+                            self.window.request_redraw();
+                        }
+                    }
+                    Key::Named(NamedKey::Delete) => {
+                        if count > 0 {
+                            let _ = self.event_proxy.send_event(UserEvent::DeleteBookmark(self.selected_index));
+                        }
+                    }
+                    Key::Named(NamedKey::Escape) => {
+                        return true;
+                    }
+                    _ => {}
+                }
+            }
+            WindowEvent::Ime(Ime::Commit(text)) => {
+                if let Some(ref mut editing) = self.editing {
+                    editing.push_str(text);
+                    self.window.request_redraw();
+                }
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.mouse_pos = super::dpi::logical_position(&self.window, position);
+                self.window.request_redraw();
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                if self.editing.is_some() {
+                    return false;
+                }
+                let now = Instant::now();
+                let hover = self.get_hover_index(settings);
+                let is_double_click = self.last_click_idx.is_some()
+                    && self.last_click_idx == hover
+                    && now.duration_since(self.last_click_time) < Duration::from_millis(500);
+
+                if let Some(idx) = hover {
+                    self.selected_index = idx;
+                    if is_double_click {
+                        self.confirm_selection(settings);
+                        return true;
+                    }
+                    self.last_click_idx = Some(idx);
+                } else {
+                    self.last_click_idx = None;
+                }
+                self.last_click_time = now;
+                self.window.request_redraw();
+            }
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                super::dpi::resize_target(&self.context, &self.swap_chain, &self.window);
+                self.window.request_redraw();
+            }
+            WindowEvent::RedrawRequested => {
+                self.draw(settings);
+            }
+            WindowEvent::CloseRequested => {
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// 名前の変更中のキー入力 (IME で確定した文字は Ime::Commit で受け取る)
+    fn handle_edit_key(&mut self, logical_key: &Key, text: Option<&str>) {
+        let Some(ref mut editing) = self.editing else { return; };
+        match logical_key {
+            Key::Named(NamedKey::Enter) => {
+                let label = editing.trim().to_string();
+                let _ = self.event_proxy.send_event(UserEvent::RenameBookmark(self.selected_index, label));
+                self.editing = None;
+            }
+            Key::Named(NamedKey::Escape) => {
+                self.editing = None;
+            }
+            Key::Named(NamedKey::Backspace) => {
+                editing.pop();
+            }
+            _ => {
+                if let Some(text) = text {
+                    editing.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+
+    fn item_rect(&self, index: usize) -> D2D_RECT_F {
+        let scale_factor = self.window.scale_factor() as f32;
+        let win_w = self.window.inner_size().width as f32 / scale_factor;
+        let top = START_Y + (index as f32) * ITEM_HEIGHT;
+        D2D_RECT_F {
+            left: 10.0,
+            top,
+            right: win_w - 10.0,
+            bottom: top + ITEM_HEIGHT,
+        }
+    }
+
+    fn get_hover_index(&self, settings: &Settings) -> Option<usize> {
+        (0..settings.bookmarks.len()).find(|&i| self.is_in_rect(self.item_rect(i)))
+    }
+
+    fn is_in_rect(&self, rect: D2D_RECT_F) -> bool {
+        self.mouse_pos.0 >= rect.left
+            && self.mouse_pos.0 <= rect.right
+            && self.mouse_pos.1 >= rect.top
+            && self.mouse_pos.1 <= rect.bottom
+    }
+
+    fn confirm_selection(&self, settings: &Settings) {
+        if settings.bookmarks.get(self.selected_index).is_some() {
+            let _ = self.event_proxy.send_event(UserEvent::LoadBookmark(self.selected_index));
+        }
+    }
+
+    /// 登録からの経過日数の表示 ("今日" / "3日前")
+    fn format_age(timestamp: u64) -> String {
+        let days = crate::platform::update::now_secs().saturating_sub(timestamp) / (24 * 60 * 60);
+        if days == 0 { "今日".to_string() } else { format!("{}日前", days) }
+    }
+
+    fn draw_text(&self, text: &str, rect: &D2D_RECT_F) {
+        let wide: Vec<u16> = text.encode_utf16().collect();
+        unsafe {
+            self.context.DrawText(
+                &wide,
+                &self.text_format,
+                rect,
+                &self.brush,
+                D2D1_DRAW_TEXT_OPTIONS_NONE,
+                DWRITE_MEASURING_MODE_NATURAL,
+            );
+        }
+    }
+
+    pub fn draw(&self, settings: &Settings) {
+        unsafe {
+            self.context.BeginDraw();
+            self.context.Clear(Some(&D2D1_COLOR_F {
+                r: 0.15,
+                g: 0.15,
+                b: 0.15,
+                a: 1.0,
+            }));
+
+            let scale_factor = self.window.scale_factor() as f32;
+            let win_w = self.window.inner_size().width as f32 / scale_factor;
+
+            self.brush.SetColor(&D2D1_COLOR_F {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+                a: 1.0,
+            });
+            let header_rect = D2D_RECT_F {
+                left: 10.0,
+                top: 10.0,
+                right: win_w - 10.0,
+                bottom: 40.0,
+            };
+            let header = if self.editing.is_some() {
+                "名前の変更中 (Enter で確定 / Esc で取り消し)"
+            } else if settings.bookmarks.is_empty() {
+                "しおりはありません (メイン画面の M で現在のページを登録)"
+            } else {
+                "しおり (Wクリックで開く / F2で名前の変更 / DELで削除)"
+            };
+            self.draw_text(header, &header_rect);
+
+            for (i, bookmark) in settings.bookmarks.iter().enumerate() {
+                let rect = self.item_rect(i);
+                let is_hovered = self.is_in_rect(rect);
+                let is_selected = i == self.selected_index;
+
+                if is_selected || is_hovered {
+                    let bg_color = if is_selected {
+                        D2D1_COLOR_F {
+                            r: 0.0,
+                            g: 0.4,
+                            b: 0.8,
+                            a: 0.5,
+                        }
+                    } else {
+                        D2D1_COLOR_F {
+                            r: 1.0,
+                            g: 1.0,
+                            b: 1.0,
+                            a: 0.1,
+                        }
+                    };
+                    self.brush.SetColor(&bg_color);
+                    self.context.FillRectangle(&rect, &self.brush);
+                }
+
+                self.brush.SetColor(&D2D1_COLOR_F {
+                    r: 0.9,
+                    g: 0.9,
+                    b: 0.9,
+                    a: 1.0,
+                });
+                let label = match self.editing {
+                    Some(ref editing) if is_selected => format!("{}|", editing),
+                    _ => bookmark.label.clone(),
+                };
+                let display_text = format!(
+                    "({:3})  {}  -  {}  [{}]",
+                    bookmark.page + 1,
+                    label,
+                    bookmark.path,
+                    Self::format_age(bookmark.timestamp)
+                );
+                // テキストは矩形外にもはみ出して描画し、ウィンドウクリッピングに任せる
+                let text_rect = D2D_RECT_F {
+                    left: 20.0,
+                    top: rect.top + 5.0,
+                    right: 10000.0,
+                    bottom: rect.bottom - 5.0,
+                };
+                self.draw_text(&display_text, &text_rect);
+            }
+
+            let _ = self.context.EndDraw(None, None);
+            let _ = self.swap_chain.Present(1, DXGI_PRESENT(0));
+        }
+    }
+}
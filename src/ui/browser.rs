@@ -0,0 +1,151 @@
+use std::path::{Path, PathBuf};
+use windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F;
+
+/// サイドバーの幅 (論理ピクセル)
+pub const PANE_WIDTH: f32 = 280.0;
+pub const ROW_HEIGHT: f32 = 26.0;
+/// フォルダ名を表示する見出しの高さ
+pub const HEADER_HEIGHT: f32 = 40.0;
+
+pub struct BrowserEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+impl BrowserEntry {
+    pub fn name(&self) -> String {
+        self.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+    }
+}
+
+/// 行の中身 (先頭は親フォルダへ戻る行)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserRow {
+    Parent,
+    Entry(usize),
+}
+
+/// フォルダ/アーカイブのサイドバー (Shift + Tab)
+/// 表示中のソースと同じフォルダにあるフォルダ/アーカイブを自然順で並べ、クリックで開く。「..」で親フォルダの一覧に移る
+pub struct BrowserPane {
+    /// 一覧を表示しているフォルダ
+    pub dir: Option<PathBuf>,
+    pub entries: Vec<BrowserEntry>,
+    /// 一覧を作ったときのソース (ソースが変わったら一覧を作り直す)
+    pub source: String,
+    /// 表示中のソース (またはそれを含むフォルダ) の位置
+    pub current: Option<usize>,
+    /// 先頭に表示している行
+    pub scroll: usize,
+}
+
+impl BrowserPane {
+    pub fn new() -> Self {
+        Self { dir: None, entries: Vec::new(), source: String::new(), current: None, scroll: 0 }
+    }
+
+    /// ソースが変わったら、そのソースがあるフォルダの一覧に切り替える (同じフォルダなら印だけ付け直す)
+    pub fn follow(&mut self, source: &str, is_source: impl Fn(&Path) -> bool) {
+        if self.source == source {
+            return;
+        }
+        self.source = source.to_string();
+        let path = Path::new(source);
+        let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else { return; };
+        if self.dir.as_deref() != Some(parent) {
+            self.show_dir(parent.to_path_buf(), &is_source);
+        }
+        self.current = self.position_of(path);
+        self.scroll_into_view();
+    }
+
+    /// 親フォルダの一覧に移る (いま表示していたフォルダに印を付ける)
+    pub fn go_up(&mut self, is_source: impl Fn(&Path) -> bool) {
+        let Some(dir) = self.dir.clone() else { return; };
+        let Some(parent) = dir.parent() else { return; };
+        self.show_dir(parent.to_path_buf(), &is_source);
+        self.current = self.position_of(&dir);
+        self.scroll_into_view();
+    }
+
+    fn show_dir(&mut self, dir: PathBuf, is_source: &impl Fn(&Path) -> bool) {
+        let mut entries: Vec<BrowserEntry> = std::fs::read_dir(&dir)
+            .map(|rd| {
+                rd.flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir() || is_source(p))
+                    .map(|path| BrowserEntry { is_dir: path.is_dir(), path })
+                    .collect()
+            })
+            .unwrap_or_default();
+        // フォルダを先に、それぞれ自然順で並べる
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| natord::compare(&a.name(), &b.name())));
+        self.entries = entries;
+        self.dir = Some(dir);
+        self.current = None;
+        self.scroll = 0;
+    }
+
+    fn position_of(&self, path: &Path) -> Option<usize> {
+        let target = std::fs::canonicalize(path).ok()?;
+        self.entries
+            .iter()
+            .position(|e| std::fs::canonicalize(&e.path).is_ok_and(|abs| abs == target))
+    }
+
+    fn has_parent(&self) -> bool {
+        self.dir.as_deref().and_then(Path::parent).is_some()
+    }
+
+    /// 行の総数 (親フォルダの行を含む)
+    pub fn row_count(&self) -> usize {
+        self.entries.len() + usize::from(self.has_parent())
+    }
+
+    pub fn row(&self, row: usize) -> Option<BrowserRow> {
+        let offset = usize::from(self.has_parent());
+        if row < offset {
+            Some(BrowserRow::Parent)
+        } else {
+            (row - offset < self.entries.len()).then(|| BrowserRow::Entry(row - offset))
+        }
+    }
+
+    /// 高さ height のサイドバーに入る行数
+    pub fn visible_rows(height: f32) -> usize {
+        (((height - HEADER_HEIGHT) / ROW_HEIGHT).floor() as usize).max(1)
+    }
+
+    pub fn scroll_by(&mut self, rows: isize, height: f32) {
+        let max = self.row_count().saturating_sub(Self::visible_rows(height));
+        self.scroll = (self.scroll as isize + rows).clamp(0, max as isize) as usize;
+    }
+
+    /// 表示中のソースの行が見えるように、その行を上から数行目に置く
+    fn scroll_into_view(&mut self) {
+        let offset = usize::from(self.has_parent());
+        self.scroll = self.current.map_or(0, |c| (c + offset).saturating_sub(3));
+    }
+
+    /// 画面に表示している row 行目 (0 = 一番上) の矩形
+    pub fn row_rect(row: usize) -> D2D_RECT_F {
+        let top = HEADER_HEIGHT + row as f32 * ROW_HEIGHT;
+        D2D_RECT_F { left: 0.0, top, right: PANE_WIDTH, bottom: top + ROW_HEIGHT }
+    }
+
+    /// 論理座標 (x, y) にある行 (サイドバーの外や見出しなら None)
+    pub fn row_at(&self, x: f32, y: f32, height: f32) -> Option<BrowserRow> {
+        if x < 0.0 || x > PANE_WIDTH || y < HEADER_HEIGHT || y > height {
+            return None;
+        }
+        let visible = ((y - HEADER_HEIGHT) / ROW_HEIGHT).floor() as usize;
+        if visible >= Self::visible_rows(height) {
+            return None;
+        }
+        self.row(self.scroll + visible)
+    }
+
+    pub fn contains(x: f32, y: f32, height: f32) -> bool {
+        x >= 0.0 && x <= PANE_WIDTH && y >= 0.0 && y <= height
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use windows::{
     Win32::Foundation::*, Win32::System::Com::*, Win32::UI::Shell::Common::*, Win32::UI::Shell::*,
     core::*,
@@ -33,8 +33,8 @@ pub fn select_archive_file(parent: HWND) -> Option<PathBuf> {
 
         let filter = [
             COMDLG_FILTERSPEC {
-                pszName: w!("Supported Archives"),
-                pszSpec: w!("*.zip;*.7z;*.cbz;*.rar;*.cbr"),
+                pszName: w!("Supported Archives / PDF"),
+                pszSpec: w!("*.zip;*.7z;*.cbz;*.rar;*.cbr;*.pdf"),
             },
             COMDLG_FILTERSPEC {
                 pszName: w!("All Files"),
@@ -57,7 +57,47 @@ pub fn select_archive_file(parent: HWND) -> Option<PathBuf> {
     }
 }
 
-#[allow(dead_code)]
+/// 画像の保存先を選ぶ (PNG / JPEG)。拡張子が入力されなければ選んだ種類の拡張子を付ける
+pub fn save_image_file(parent: HWND, default_name: &str, folder: Option<&Path>) -> Option<PathBuf> {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+
+        let dialog: IFileSaveDialog = CoCreateInstance(&FileSaveDialog, None, CLSCTX_ALL).ok()?;
+
+        let filter = [
+            COMDLG_FILTERSPEC {
+                pszName: w!("PNG"),
+                pszSpec: w!("*.png"),
+            },
+            COMDLG_FILTERSPEC {
+                pszName: w!("JPEG"),
+                pszSpec: w!("*.jpg;*.jpeg"),
+            },
+        ];
+
+        dialog.SetFileTypes(&filter).ok()?;
+        dialog.SetDefaultExtension(w!("png")).ok()?;
+        dialog.SetFileName(&HSTRING::from(default_name)).ok()?;
+        if let Some(folder) = folder {
+            // 前回保存したフォルダから開く (無くなっていれば既定の場所)
+            if let Ok(item) = SHCreateItemFromParsingName::<_, _, IShellItem>(&HSTRING::from(folder.as_os_str()), None) {
+                let _ = dialog.SetFolder(&item);
+            }
+        }
+
+        if dialog.Show(Some(parent)).is_err() {
+            return None;
+        }
+
+        let result = dialog.GetResult().ok()?;
+        let path_pwstr = result.GetDisplayName(SIGDN_FILESYSPATH).ok()?;
+        let path = path_pwstr.to_string().ok()?;
+        CoTaskMemFree(Some(path_pwstr.as_ptr() as *const _));
+
+        Some(PathBuf::from(path))
+    }
+}
+
 pub fn show_confirm_dialog(parent: HWND, title: &str, message: &str) -> bool {
     unsafe {
         use windows::Win32::UI::WindowsAndMessaging::{
@@ -79,3 +119,20 @@ pub fn show_confirm_dialog(parent: HWND, title: &str, message: &str) -> bool {
         result == IDYES
     }
 }
+
+/// 親ウィンドウなしで結果を知らせるメッセージボックスを表示する (コマンドライン実行用)
+pub fn show_message_dialog(title: &str, message: &str, is_error: bool) {
+    unsafe {
+        use windows::Win32::UI::WindowsAndMessaging::{
+            MB_ICONERROR, MB_ICONINFORMATION, MB_OK, MB_SETFOREGROUND, MessageBoxW,
+        };
+
+        let icon = if is_error { MB_ICONERROR } else { MB_ICONINFORMATION };
+        MessageBoxW(
+            None,
+            &HSTRING::from(message),
+            &HSTRING::from(title),
+            MB_OK | icon | MB_SETFOREGROUND,
+        );
+    }
+}
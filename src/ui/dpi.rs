@@ -0,0 +1,40 @@
+use windows::Win32::Graphics::Direct2D::ID2D1DeviceContext;
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_UNKNOWN;
+use windows::Win32::Graphics::Dxgi::{DXGI_SWAP_CHAIN_FLAG, IDXGISurface, IDXGISwapChain1};
+use winit::window::Window;
+
+/// D2D の描画単位をウィンドウの DPI に合わせる
+/// 以降の描画座標・フォントサイズは論理座標 (96 DPI 基準) で指定し、モニターの拡大率に合わせて描画される
+pub fn apply_window_dpi(context: &ID2D1DeviceContext, window: &Window) {
+    let dpi = 96.0 * window.scale_factor() as f32;
+    unsafe { context.SetDpi(dpi, dpi) };
+}
+
+/// スワップチェーンをウィンドウの現在の物理サイズで作り直し、DPI を設定し直す
+/// (Resized と、別の拡大率のモニターへ移動したときの ScaleFactorChanged で呼ぶ)
+pub fn resize_target(context: &ID2D1DeviceContext, swap_chain: &IDXGISwapChain1, window: &Window) {
+    let size = window.inner_size();
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+    unsafe {
+        context.SetTarget(None);
+        if swap_chain
+            .ResizeBuffers(0, size.width, size.height, DXGI_FORMAT_UNKNOWN, DXGI_SWAP_CHAIN_FLAG(0))
+            .is_err()
+        {
+            return;
+        }
+        let Ok(surface) = swap_chain.GetBuffer::<IDXGISurface>(0) else { return; };
+        if let Ok(back_buffer) = context.CreateBitmapFromDxgiSurface(&surface, None) {
+            context.SetTarget(&back_buffer);
+        }
+    }
+    apply_window_dpi(context, window);
+}
+
+/// カーソル位置 (物理ピクセル) を描画と同じ論理座標に変換する
+pub fn logical_position(window: &Window, position: &winit::dpi::PhysicalPosition<f64>) -> (f32, f32) {
+    let scale = window.scale_factor();
+    ((position.x / scale) as f32, (position.y / scale) as f32)
+}
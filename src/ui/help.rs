@@ -12,7 +12,92 @@ use winit::{
 };
 use windows::Win32::Graphics::DirectWrite::DWRITE_FONT_WEIGHT_BOLD;
 
+/// 1 行の高さ (セクション見出しは 1.5 倍)
+const ROW_HEIGHT: f32 = 20.0;
+/// 一覧を表示する範囲の上端 (ヘッダーの下) と、ウィンドウ下端との余白
+const LIST_TOP: f32 = 70.0;
+const LIST_BOTTOM_MARGIN: f32 = 20.0;
+/// 一覧の上下の余白
+const LIST_PADDING: f32 = 10.0;
 
+/// ショートカットの一覧 (説明が空の行はセクション見出し)
+const HELP_ITEMS: &[(&str, &str)] = &[
+    ("--- ページ移動 ---", ""),
+    ("ホイール / ← →", "次/前のページ"),
+    ("Home / End", "最初/最後のページ"),
+    ("PgUp / PgDown", "履歴ナビゲーション"),
+    ("[ / ]", "前/次のフォルダまたはアーカイブ (複数ドロップしたときはプレイリスト内)"),
+    ("Ctrl + [ / ]", "前/次の章 (アーカイブ内のサブフォルダなど) の先頭"),
+    ("Tab", "シリーズ一覧 (巻を直接選択)"),
+    ("Shift + Tab", "フォルダ/アーカイブのサイドバー"),
+    ("メディアキー / リモコン", "次/前のページ (割当変更可)"),
+    ("-----------------", ""),
+    ("--- 表示操作 ---", ""),
+    ("Ctrl + ホイール", "ズームイン/アウト"),
+    ("+ / -", "ズームイン/アウト"),
+    ("左ドラッグ (ズーム時)", "パン (画面移動)"),
+    ("Space / Shift+Space", "ズーム時は画面単位で読み進める"),
+    ("右クリック押しっぱなし", "ルーペ表示"),
+    ("Numpad *", "ズームリセット"),
+    ("Q / Shift+Q", "合わせ方 (全体 / 幅 / 高さ / 原寸 / 全面)"),
+    ("Y", "横長ページを左右に分けて表示 (単ページ表示)"),
+    ("J / Shift+J", "フォルダの並び順 (名前 / 更新日時 / サイズ / シャッフル)"),
+    ("Alt + 1〜3", "ズーム (全体 / 100% / 200%)"),
+    ("Z + 左ドラッグ", "選択範囲を拡大"),
+    ("Ctrl + (+ / -)", "明るさ調整"),
+    ("Alt + (+ / -)", "コントラスト調整"),
+    ("Ctrl+Alt + (+ / -)", "ガンマ調整"),
+    ("Ctrl + 0", "色調整リセット"),
+    ("L / Shift+L", "自動レベル補正 / ヒストグラム"),
+    ("N", "夜間モード (色反転)"),
+    ("E / Shift+E", "ページを右 / 左に 90° 回転"),
+    ("X / Shift+X", "ページを左右 / 上下に反転"),
+    ("F11 / Enter", "全画面表示の切り替え"),
+    ("V", "縦スクロール表示 (Webtoon) の切り替え"),
+    ("G", "自動トリミング (スキャンの余白を削る)"),
+    ("Shift+G", "トリミング範囲の編集 (Delete で解除)"),
+    ("-----------------", ""),
+    ("--- 機能 ---", ""),
+    ("O", "設定画面を開く"),
+    ("R", "履歴画面を開く"),
+    ("M / Shift+M", "しおりの登録・解除 / 一覧"),
+    ("S", "シークバー表示切替"),
+    ("Shift+S / 数字", "ページジャンプ"),
+    ("Ctrl+S", "表示中のページ (見開き) を共有"),
+    ("Ctrl+C", "表示中のページ (見開き) をコピー"),
+    ("Ctrl+Shift+S", "表示中のページ (見開き) を PNG / JPEG で保存"),
+    ("F", "フォルダを開く"),
+    ("Shift+F", "ファイルを直接開く"),
+    ("Ctrl+F5", "再読み込み (差し替えたファイルを反映)"),
+    ("Delete", "表示中のファイルをごみ箱へ移動"),
+    ("K", "仕分けモード (1〜5 で振り分け)"),
+    ("Ctrl + 1〜5 (仕分け中)", "振り分け先フォルダを設定"),
+    ("Ctrl+Z", "直前の操作 (ジャンプ・表示切替・回転・トリミング・仕分け) を取り消す"),
+    ("T", "OCR 文字選択・コピー"),
+    ("C", "スポイト (クリックでカラーコードをコピー)"),
+    ("I", "画像情報 (カメラ・解像度・色空間・ファイルサイズ)"),
+    ("D / Shift+D", "注釈 (ペン / 蛍光ペン) / 表示切替"),
+    ("Backspace (注釈中)", "最後に描いた線を消す"),
+    ("2 本指ドラッグ / ピンチ", "パン / ズーム (タッチ)"),
+    ("P", "先読みの一時停止/再開"),
+    ("W / Shift+W", "ホットフォルダ監視 / フォルダ変更"),
+    ("Ctrl+V", "クリップボード監視 (コピーした画像を表示)"),
+    ("H", "ヘルプ画面を開く"),
+    ("F3", "パフォーマンス HUD"),
+    ("F12", "出力ウィンドウ (別のモニターに表示)"),
+    ("Esc", "各種ウィンドウを閉じる"),
+];
+
+fn row_height(desc: &str) -> f32 {
+    if desc.is_empty() { ROW_HEIGHT * 1.5 } else { ROW_HEIGHT }
+}
+
+/// 一覧全体の高さ (上下の余白を含む)
+fn content_height() -> f32 {
+    HELP_ITEMS.iter().map(|(_, desc)| row_height(desc)).sum::<f32>() + LIST_PADDING * 2.0
+}
+
+/// ショートカット一覧のウィンドウ (H)。一覧がウィンドウに収まらない分はホイール・矢印キーでスクロールする
 pub struct HelpWindow {
     pub window: Arc<Window>,
     pub _factory: ID2D1Factory1,
@@ -23,6 +108,8 @@ pub struct HelpWindow {
     pub text_format: IDWriteTextFormat,
     pub text_format_bold: IDWriteTextFormat, // 追加
     pub text_format_small: IDWriteTextFormat,
+    /// 一覧のスクロール量 (論理ピクセル)
+    scroll: f32,
 }
 
 impl HelpWindow {
@@ -96,6 +183,8 @@ impl HelpWindow {
             let surface: IDXGISurface = swap_chain.GetBuffer(0)?;
             let back_buffer: ID2D1Bitmap1 = context.CreateBitmapFromDxgiSurface(&surface, None)?;
             context.SetTarget(&back_buffer);
+            // 描画座標・フォントを論理座標で扱い、モニターの拡大率に合わせる
+            super::dpi::apply_window_dpi(&context, &window);
             let brush = context.CreateSolidColorBrush(
                 &D2D1_COLOR_F {
                     r: 1.0,
@@ -144,25 +233,63 @@ impl HelpWindow {
                 text_format,
                 text_format_bold,
                 text_format_small,
+                scroll: 0.0,
             })
         }
     }
 
+    /// 一覧を表示する範囲の高さ (論理ピクセル)
+    fn viewport_height(&self) -> f32 {
+        let size = self.window.inner_size().to_logical::<f32>(self.window.scale_factor());
+        (size.height - LIST_TOP - LIST_BOTTOM_MARGIN).max(0.0)
+    }
+
+    /// スクロール量を変えて描き直す (一覧の端を越えない範囲に収める)
+    fn scroll_by(&mut self, delta: f32) {
+        let max_scroll = (content_height() - self.viewport_height()).max(0.0);
+        let scroll = (self.scroll + delta).clamp(0.0, max_scroll);
+        if scroll != self.scroll {
+            self.scroll = scroll;
+            self.window.request_redraw();
+        }
+    }
+
     /// イベント処理。ウィンドウを閉じる必要がある場合に true を返す。
-    pub fn handle_event(&self, event: &WindowEvent) -> bool {
+    pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
         match event {
             WindowEvent::KeyboardInput { event: req, .. } => {
                 if req.state == ElementState::Pressed {
                     use winit::keyboard::{Key, NamedKey};
+                    let page = (self.viewport_height() - ROW_HEIGHT).max(ROW_HEIGHT);
                     match req.logical_key {
-                        Key::Named(NamedKey::Escape) => true,
-                        _ => false,
+                        Key::Named(NamedKey::Escape) => return true,
+                        Key::Named(NamedKey::ArrowDown) => self.scroll_by(ROW_HEIGHT),
+                        Key::Named(NamedKey::ArrowUp) => self.scroll_by(-ROW_HEIGHT),
+                        Key::Named(NamedKey::PageDown) | Key::Named(NamedKey::Space) => self.scroll_by(page),
+                        Key::Named(NamedKey::PageUp) => self.scroll_by(-page),
+                        Key::Named(NamedKey::Home) => self.scroll_by(-self.scroll),
+                        Key::Named(NamedKey::End) => self.scroll_by(content_height()),
+                        _ => {}
                     }
-                } else {
-                    false
                 }
+                false
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let dy = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => y * ROW_HEIGHT * 3.0,
+                    MouseScrollDelta::PixelDelta(pos) => pos.y as f32 / self.window.scale_factor() as f32,
+                };
+                self.scroll_by(-dy);
+                false
             }
             WindowEvent::CloseRequested => true,
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                super::dpi::resize_target(&self.context, &self.swap_chain, &self.window);
+                // 表示範囲が変わるとスクロールできる量も変わる
+                self.scroll_by(0.0);
+                self.window.request_redraw();
+                false
+            }
             _ => false,
         }
     }
@@ -218,7 +345,9 @@ impl HelpWindow {
                 DWRITE_MEASURING_MODE_NATURAL,
             );
 
-            // 内容エリア背景
+            // 内容エリア背景 (一覧の高さに合わせ、ウィンドウに収まらない分は表示範囲で切る)
+            let viewport_height = self.viewport_height();
+            let list_bottom = LIST_TOP + content_height().min(viewport_height);
             self.brush.SetColor(&D2D1_COLOR_F {
                 r: 0.14,
                 g: 0.15,
@@ -228,44 +357,27 @@ impl HelpWindow {
             self.context.FillRectangle(
                 &D2D_RECT_F {
                     left: 20.0,
-                    top: 70.0,
+                    top: LIST_TOP,
                     right: 330.0,
-                    bottom: 630.0,
+                    bottom: list_bottom,
                 },
                 &self.brush,
             );
 
-            // ヘルプ項目
-            let help_items = [
-                ("--- ページ移動 ---", ""),
-                ("ホイール / ← →", "次/前のページ"),
-                ("Home / End", "最初/最後のページ"),
-                ("PgUp / PgDown", "履歴ナビゲーション"),
-                ("[ / ]", "前/次のフォルダまたはアーカイブ"),
-                ("-----------------", ""),
-                ("--- 表示操作 ---", ""),
-                ("Ctrl + ホイール", "ズームイン/アウト"),
-                ("+ / -", "ズームイン/アウト"),
-                ("左ドラッグ (ズーム時)", "パン (画面移動)"),
-                ("右クリック押しっぱなし", "ルーペ表示"),
-                ("Numpad *", "ズームリセット"),
-                ("-----------------", ""),
-                ("--- 機能 ---", ""),
-                ("O", "設定画面を開く"),
-                ("R", "履歴画面を開く"),
-                ("S", "シークバー表示切替"),
-                ("Shift+S", "ページジャンプ"),
-                ("F", "フォルダを開く"),
-                ("Shift+F", "ファイルを直接開く"),
-                ("H", "ヘルプ画面を開く"),
-                ("Esc", "各種ウィンドウを閉じる"),
-            ];
-            
-            let mut y = 80.0;
-            let row_height = 20.0;
+            // 一覧は内容エリアの中だけに描く
+            self.context.PushAxisAlignedClip(
+                &D2D_RECT_F { left: 20.0, top: LIST_TOP, right: 330.0, bottom: list_bottom },
+                D2D1_ANTIALIAS_MODE_ALIASED,
+            );
+            let mut y = LIST_TOP + LIST_PADDING - self.scroll;
             let key_width = 150.0;
 
-            for (key, desc) in help_items.iter() {
+            for (key, desc) in HELP_ITEMS.iter() {
+                let height = row_height(desc);
+                if y + height < LIST_TOP || y > list_bottom {
+                    y += height;
+                    continue;
+                }
                 // キー（左側、太字）
                 let is_section = desc.is_empty();
                 
@@ -286,7 +398,7 @@ impl HelpWindow {
                     left: 30.0,
                     top: y,
                     right: 30.0 + key_width,
-                    bottom: y + row_height,
+                    bottom: y + ROW_HEIGHT,
                 };
                 let wide_key: Vec<u16> = key.encode_utf16().collect();
                 self.context.DrawText(
@@ -311,7 +423,7 @@ impl HelpWindow {
                         left: 30.0 + key_width,
                         top: y,
                         right: 320.0,
-                        bottom: y + row_height,
+                        bottom: y + ROW_HEIGHT,
                     };
                     let wide_desc: Vec<u16> = desc.encode_utf16().collect();
                     self.context.DrawText(
@@ -324,8 +436,21 @@ impl HelpWindow {
                     );
                 }
                 
-                // セクションタイトルは行高さを広く取る
-                y += if is_section { row_height * 1.5 } else { row_height };
+                y += height;
+            }
+            self.context.PopAxisAlignedClip();
+
+            // 一覧が収まらないときはスクロールバーで位置を示す
+            let content = content_height();
+            if content > viewport_height && viewport_height > 0.0 {
+                let track = list_bottom - LIST_TOP;
+                let thumb = (track * viewport_height / content).max(20.0);
+                let top = LIST_TOP + (track - thumb) * self.scroll / (content - viewport_height);
+                self.brush.SetColor(&D2D1_COLOR_F { r: 0.5, g: 0.5, b: 0.55, a: 0.8 });
+                self.context.FillRectangle(
+                    &D2D_RECT_F { left: 332.0, top, right: 336.0, bottom: top + thumb },
+                    &self.brush,
+                );
             }
 
             let _ = self.context.EndDraw(None, None);
@@ -1,4 +1,5 @@
-use crate::config::Settings;
+use crate::config::{Settings, SourceNote};
+use crate::image::loader::UserEvent;
 use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -15,6 +16,15 @@ use winit::{
     window::{Window, WindowBuilder},
 };
 
+/// 選択中の項目で編集しているもの
+#[derive(Clone, Copy, PartialEq)]
+pub enum NoteField {
+    /// タグ (F2、カンマ区切り)
+    Tags,
+    /// メモ (F3)
+    Text,
+}
+
 pub struct HistoryWindow {
     pub window: Arc<Window>,
     pub _factory: ID2D1Factory1,
@@ -28,6 +38,11 @@ pub struct HistoryWindow {
     pub mouse_pos: (f32, f32),
     pub last_click_time: Instant,
     pub last_click_idx: Option<usize>,
+    /// 絞り込みの文字列 (文字を入力すると絞り込み、Backspace で削除、Esc で解除)
+    /// パス・タグ・メモを検索する。selected_index と last_click_idx は絞り込んだ一覧での位置
+    pub filter: String,
+    /// タグ / メモの編集中の内容 (Enter で確定、Esc で取り消し)
+    pub editing: Option<(NoteField, String)>,
 }
 
 impl HistoryWindow {
@@ -45,6 +60,8 @@ impl HistoryWindow {
             .with_resizable(true)
             .build(elwt)
             .map_err(|_| Error::new(HRESULT(-1), "Failed to build window"))?;
+        // 絞り込みとメモの入力に IME を使えるようにする
+        window.set_ime_allowed(true);
 
         let hwnd = match window.raw_window_handle() {
             RawWindowHandle::Win32(handle) => HWND(handle.hwnd as _),
@@ -102,6 +119,8 @@ impl HistoryWindow {
             let surface: IDXGISurface = swap_chain.GetBuffer(0)?;
             let back_buffer: ID2D1Bitmap1 = context.CreateBitmapFromDxgiSurface(&surface, None)?;
             context.SetTarget(&back_buffer);
+            // 描画座標・フォントを論理座標で扱い、モニターの拡大率に合わせる
+            super::dpi::apply_window_dpi(&context, &window);
 
             let brush = context.CreateSolidColorBrush(
                 &D2D1_COLOR_F {
@@ -139,22 +158,36 @@ impl HistoryWindow {
                 mouse_pos: (0.0, 0.0),
                 last_click_time: Instant::now(),
                 last_click_idx: None,
+                filter: String::new(),
+                editing: None,
             })
         }
     }
 
     pub fn handle_event(&mut self, event: &WindowEvent, settings: &Settings) -> bool {
+        let visible = self.visible_items(settings);
+        let history_len = visible.len();
+        if history_len > 0 && self.selected_index >= history_len {
+            // 削除や絞り込みで件数が減った場合は末尾に合わせる
+            self.selected_index = history_len - 1;
+        }
+
         match event {
             WindowEvent::KeyboardInput {
                 event:
                     KeyEvent {
                         logical_key,
+                        text,
                         state: ElementState::Pressed,
                         ..
                     },
                 ..
             } => {
-                let history_len = settings.history.len();
+                if self.editing.is_some() {
+                    self.handle_edit_key(logical_key, text.as_deref(), &visible, settings);
+                    self.window.request_redraw();
+                    return false;
+                }
                 match logical_key {
                     Key::Named(NamedKey::ArrowUp) => {
                         if self.selected_index > 0 {
@@ -175,22 +208,63 @@ impl HistoryWindow {
                         return true;
                     }
                     Key::Named(NamedKey::Delete) => {
-                        if history_len > 0 {
+                        if let Some(&index) = visible.get(self.selected_index) {
                             let _ = self.event_proxy.send_event(
-                                crate::image::loader::UserEvent::DeleteHistoryItem(
-                                    self.selected_index,
-                                ),
+                                crate::image::loader::UserEvent::DeleteHistoryItem(index),
                             );
                         }
                     }
+                    Key::Named(NamedKey::F2) | Key::Named(NamedKey::F3) => {
+                        if let Some(item) = visible.get(self.selected_index).and_then(|&i| settings.history.get(i)) {
+                            let note = settings.source_notes.get(&item.path);
+                            // 復号できないメモは編集すると元の内容を上書きしてしまうため、編集を始めない
+                            if note.is_some_and(|n| n.is_locked()) {
+                                return true;
+                            }
+                            self.editing = Some(if *logical_key == Key::Named(NamedKey::F2) {
+                                (NoteField::Tags, note.map(|n| n.tags().join(", ")).unwrap_or_default())
+                            } else {
+                                (NoteField::Text, note.map(|n| n.text().to_string()).unwrap_or_default())
+                            });
+                            self.window.request_redraw();
+                        }
+                    }
+                    Key::Named(NamedKey::Backspace) => {
+                        if self.filter.pop().is_some() {
+                            self.selected_index = 0;
+                            self.window.request_redraw();
+                        }
+                    }
                     Key::Named(NamedKey::Escape) => {
-                        return true;
+                        if self.filter.is_empty() {
+                            return true;
+                        }
+                        self.filter.clear();
+                        self.selected_index = 0;
+                        self.window.request_redraw();
+                    }
+                    _ => {
+                        // 文字の入力は絞り込みに加える
+                        if let Some(text) = text.as_deref().filter(|t| !t.chars().any(char::is_control)) {
+                            self.filter.push_str(text);
+                            self.selected_index = 0;
+                            self.window.request_redraw();
+                        }
                     }
-                    _ => {}
                 }
             }
+            WindowEvent::Ime(Ime::Commit(text)) => {
+                match self.editing {
+                    Some((_, ref mut editing)) => editing.push_str(text),
+                    None => {
+                        self.filter.push_str(text);
+                        self.selected_index = 0;
+                    }
+                }
+                self.window.request_redraw();
+            }
             WindowEvent::CursorMoved { position, .. } => {
-                self.mouse_pos = (position.x as f32, position.y as f32);
+                self.mouse_pos = super::dpi::logical_position(&self.window, position);
                 self.window.request_redraw();
             }
             WindowEvent::MouseInput {
@@ -198,6 +272,9 @@ impl HistoryWindow {
                 button: MouseButton::Left,
                 ..
             } => {
+                if self.editing.is_some() {
+                    return false;
+                }
                 let now = Instant::now();
                 let is_double_click = if let Some(idx) = self.last_click_idx {
                     Some(idx) == self.get_hover_index(settings)
@@ -219,26 +296,8 @@ impl HistoryWindow {
                 self.last_click_time = now;
                 self.window.request_redraw();
             }
-            WindowEvent::Resized(size) => {
-                unsafe {
-                    self.context.SetTarget(None);
-                    self.swap_chain
-                        .ResizeBuffers(
-                            0,
-                            size.width,
-                            size.height,
-                            DXGI_FORMAT_UNKNOWN,
-                            DXGI_SWAP_CHAIN_FLAG(0),
-                        )
-                        .ok();
-                    let surface: IDXGISurface = self.swap_chain.GetBuffer(0).ok().unwrap();
-                    let back_buffer: ID2D1Bitmap1 = self
-                        .context
-                        .CreateBitmapFromDxgiSurface(&surface, None)
-                        .ok()
-                        .unwrap();
-                    self.context.SetTarget(&back_buffer);
-                }
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                super::dpi::resize_target(&self.context, &self.swap_chain, &self.window);
                 self.window.request_redraw();
             }
             WindowEvent::RedrawRequested => {
@@ -252,13 +311,48 @@ impl HistoryWindow {
         false
     }
 
+    /// 絞り込みに合う履歴のインデックス (settings.history の位置)
+    fn visible_items(&self, settings: &Settings) -> Vec<usize> {
+        (0..settings.history.len())
+            .filter(|&i| self.filter.is_empty() || settings.history_matches(&settings.history[i], &self.filter))
+            .collect()
+    }
+
+    /// タグ / メモの編集中のキー入力 (IME で確定した文字は Ime::Commit で受け取る)
+    fn handle_edit_key(&mut self, logical_key: &Key, text: Option<&str>, visible: &[usize], settings: &Settings) {
+        let Some((field, ref mut editing)) = self.editing else { return; };
+        match logical_key {
+            Key::Named(NamedKey::Enter) => {
+                if let Some(item) = visible.get(self.selected_index).and_then(|&i| settings.history.get(i)) {
+                    let event = match field {
+                        NoteField::Tags => UserEvent::SetSourceTags(item.path.clone(), SourceNote::parse_tags(editing)),
+                        NoteField::Text => UserEvent::SetSourceText(item.path.clone(), editing.trim().to_string()),
+                    };
+                    let _ = self.event_proxy.send_event(event);
+                }
+                self.editing = None;
+            }
+            Key::Named(NamedKey::Escape) => {
+                self.editing = None;
+            }
+            Key::Named(NamedKey::Backspace) => {
+                editing.pop();
+            }
+            _ => {
+                if let Some(text) = text {
+                    editing.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+
     fn get_hover_index(&self, settings: &Settings) -> Option<usize> {
         let item_height = 30.0;
         let start_y = 50.0;
         let scale_factor = self.window.scale_factor() as f32;
         let win_w = self.window.inner_size().width as f32 / scale_factor;
 
-        for (i, _) in settings.history.iter().enumerate() {
+        for i in 0..self.visible_items(settings).len() {
             let top = start_y + (i as f32) * item_height;
             let rect = D2D_RECT_F {
                 left: 10.0,
@@ -281,12 +375,10 @@ impl HistoryWindow {
     }
 
     fn confirm_selection(&self, settings: &Settings) {
-        if settings.history.get(self.selected_index).is_some() {
+        if let Some(&index) = self.visible_items(settings).get(self.selected_index) {
             let _ = self
                 .event_proxy
-                .send_event(crate::image::loader::UserEvent::LoadHistory(
-                    self.selected_index,
-                ));
+                .send_event(crate::image::loader::UserEvent::LoadHistory(index));
         }
     }
 
@@ -317,9 +409,13 @@ impl HistoryWindow {
                 right: win_w - 10.0,
                 bottom: 40.0,
             };
-            let header_text: Vec<u16> = "最近使った項目 (Wクリックで開く / DELで削除)"
-                .encode_utf16()
-                .collect();
+            let header = match (&self.editing, self.filter.is_empty()) {
+                (Some((NoteField::Tags, _)), _) => "タグを編集中 (カンマ区切り / Enter で確定 / Esc で取り消し)".to_string(),
+                (Some((NoteField::Text, _)), _) => "メモを編集中 (Enter で確定 / Esc で取り消し)".to_string(),
+                (None, true) => "最近使った項目 (Wクリックで開く / DELで削除 / F2 タグ / F3 メモ / 入力で絞り込み)".to_string(),
+                (None, false) => format!("絞り込み: {}| (Esc で解除)", self.filter),
+            };
+            let header_text: Vec<u16> = header.encode_utf16().collect();
             self.context.DrawText(
                 &header_text,
                 &self.text_format,
@@ -332,7 +428,8 @@ impl HistoryWindow {
             let item_height = 30.0;
             let start_y = 50.0;
 
-            for (i, item) in settings.history.iter().enumerate() {
+            let visible = self.visible_items(settings);
+            for (i, item) in visible.iter().map(|&index| &settings.history[index]).enumerate() {
                 let top = start_y + (i as f32) * item_height;
                 let rect = D2D_RECT_F {
                     left: 10.0,
@@ -375,10 +472,28 @@ impl HistoryWindow {
                     "left" => "L",
                     "right" => "R",
                     "single" => "S",
+                    "webtoon" => "W",
                     _ => "?",
                 };
-                let display_text =
+                let mut display_text =
                     format!("({:3} / {})  {}", item.page + 1, binding_char, item.path);
+                // 編集中の項目は入力中の内容を、それ以外はタグとメモの 1 行目を後ろに並べる
+                match (&self.editing, settings.source_notes.get(&item.path)) {
+                    (Some((field, editing)), _) if is_selected => {
+                        let label = if *field == NoteField::Tags { "タグ" } else { "メモ" };
+                        display_text.push_str(&format!("  {}: {}|", label, editing));
+                    }
+                    (_, Some(note)) if note.is_locked() => display_text.push_str("  (復号できないメモ)"),
+                    (_, Some(note)) => {
+                        if !note.tags().is_empty() {
+                            display_text.push_str(&format!("  [{}]", note.tags().join(", ")));
+                        }
+                        if let Some(line) = note.text().lines().next().filter(|l| !l.is_empty()) {
+                            display_text.push_str(&format!("  - {}", line));
+                        }
+                    }
+                    _ => {}
+                }
                 let text_wide: Vec<u16> = display_text.encode_utf16().collect();
                 // テキストは矩形外にもはみ出して描画し、ウィンドウクリッピングに任せる
                 let extended_text_rect = D2D_RECT_F {
@@ -0,0 +1,92 @@
+use crate::state::FitMode;
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use windows::Win32::Foundation::HWND;
+use winit::{
+    event::*,
+    event_loop::EventLoopWindowTarget,
+    keyboard::ModifiersState,
+    window::{Fullscreen, Window, WindowBuilder},
+};
+
+/// 出力ウィンドウで起きたこと (描画先の作り直しや設定の保存はメイン側で行う)
+pub enum MirrorAction {
+    None,
+    Close,
+    /// 描画し直す (メインウィンドウの描画と一緒に描く)
+    Redraw,
+    /// ウィンドウの大きさが変わった (物理ピクセル)
+    Resized(u32, u32),
+    /// ページの合わせ方を切り替えた
+    FitModeChanged,
+}
+
+/// 表示中のページを別のモニターに映す読み取り専用の出力ウィンドウ (F12)
+/// ページはメインウィンドウのレンダラーで描くため、GPU キャッシュのテクスチャをそのまま使う
+/// ズーム・パン・オーバーレイは映さず、ページの合わせ方だけはメインウィンドウと別に切り替える
+pub struct MirrorWindow {
+    pub window: Window,
+    pub hwnd: HWND,
+    pub fit_mode: FitMode,
+    modifiers: ModifiersState,
+}
+
+impl MirrorWindow {
+    /// メインウィンドウと別のモニターがあればその全画面で開き、無ければ通常のウィンドウで開く
+    pub fn new<T>(
+        elwt: &EventLoopWindowTarget<T>,
+        main_window: &Window,
+        fit_mode: FitMode,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let current = main_window.current_monitor();
+        let other = elwt.available_monitors().find(|m| Some(m) != current.as_ref());
+        let mut builder = WindowBuilder::new()
+            .with_title("HayateViewer 出力")
+            .with_inner_size(winit::dpi::LogicalSize::new(800.0, 600.0));
+        if let Some(monitor) = other {
+            builder = builder
+                .with_position(monitor.position())
+                .with_fullscreen(Some(Fullscreen::Borderless(Some(monitor))));
+        }
+        let window = builder.build(elwt)?;
+        let hwnd = match window.raw_window_handle() {
+            RawWindowHandle::Win32(handle) => HWND(handle.hwnd as _),
+            _ => return Err("Unsupported window handle".into()),
+        };
+        Ok(Self { window, hwnd, fit_mode, modifiers: ModifiersState::default() })
+    }
+
+    pub fn handle_event(&mut self, event: &WindowEvent) -> MirrorAction {
+        match event {
+            WindowEvent::CloseRequested => MirrorAction::Close,
+            WindowEvent::RedrawRequested => MirrorAction::Redraw,
+            WindowEvent::Resized(size) => MirrorAction::Resized(size.width, size.height),
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
+                MirrorAction::None
+            }
+            WindowEvent::KeyboardInput { event: req, .. } if req.state == ElementState::Pressed => {
+                use winit::keyboard::{Key, NamedKey};
+                match req.logical_key {
+                    Key::Named(NamedKey::Escape) | Key::Named(NamedKey::F12) => MirrorAction::Close,
+                    Key::Named(NamedKey::F11) | Key::Named(NamedKey::Enter) => {
+                        // F11 / Enter: いまのモニターでの全画面とウィンドウ表示を切り替える
+                        let fullscreen = match self.window.fullscreen() {
+                            Some(_) => None,
+                            None => Some(Fullscreen::Borderless(None)),
+                        };
+                        self.window.set_fullscreen(fullscreen);
+                        MirrorAction::None
+                    }
+                    Key::Character(ref s) if s.to_lowercase() == "q" => {
+                        // Q / Shift + Q: 出力ウィンドウのページの合わせ方を切り替える
+                        let direction = if self.modifiers.shift_key() { -1 } else { 1 };
+                        self.fit_mode = self.fit_mode.cycle(direction);
+                        MirrorAction::FitModeChanged
+                    }
+                    _ => MirrorAction::None,
+                }
+            }
+            _ => MirrorAction::None,
+        }
+    }
+}
@@ -1,4 +1,8 @@
 pub mod dialogs;
+pub mod dpi;
 pub mod history;
+pub mod bookmarks;
 pub mod modern_settings;
 pub mod help;
+pub mod browser;
+pub mod mirror;
@@ -12,6 +12,20 @@ use winit::{
     window::{Window, WindowBuilder},
 };
 
+/// タブ数 (全般, レンダリング, 画質, 操作, 書式, 情報)
+const TAB_COUNT: usize = 7;
+
+const TAB_NAMES: [&str; TAB_COUNT] = ["全般", "レンダリング", "画質", "操作", "書式", "情報", "統計"];
+
+/// 全般タブの項目の上端 (11 項目を収めるため他のタブより間隔を詰める)
+const GENERAL_ITEM_TOPS: [f32; 11] = [196.0, 229.0, 262.0, 295.0, 328.0, 361.0, 394.0, 427.0, 460.0, 493.0, 526.0];
+
+/// レンダリングタブの項目の上端 (10 項目を収めるため他のタブより間隔を詰める)
+const RENDERING_ITEM_TOPS: [f32; 10] = [200.0, 236.0, 272.0, 308.0, 344.0, 380.0, 416.0, 452.0, 488.0, 524.0];
+
+/// 書式タブの項目の上端 (タイトルバー, ステータスバー, 既定に戻す)
+const FORMAT_ITEM_TOPS: [f32; 3] = [175.0, 250.0, 325.0];
+
 pub struct ModernSettingsWindow {
     pub window: Arc<Window>,
     pub _factory: ID2D1Factory1,
@@ -28,6 +42,16 @@ pub struct ModernSettingsWindow {
     pub selected_tab: usize,
     pub focus_index: usize,
     pub is_focus_on_tabs: bool,
+    /// 書式の編集中の入力 (編集対象は focus_index の項目)
+    pub editing: Option<String>,
+    /// 表示中ソースの色調整 (ソース別の値のため Settings ではなくメイン側から反映される)
+    pub color_adjustment: crate::render::ColorAdjustment,
+    /// 表示中ページの回転・反転 (ページ別の値のためメイン側から反映される)
+    pub page_transform: crate::render::PageTransform,
+    /// 読書の統計の集計 (記録が変わったときにメイン側から反映される)
+    pub stats_summary: crate::state::stats::StatsSummary,
+    /// stats_summary を作ったときの記録の revision
+    pub stats_revision: u64,
     pub event_proxy: winit::event_loop::EventLoopProxy<crate::image::loader::UserEvent>,
 }
 
@@ -46,6 +70,7 @@ impl ModernSettingsWindow {
                 .build(elwt)
                 .map_err(|e| Error::new(HRESULT(-1), format!("{}", e)))?,
         );
+        window.set_ime_allowed(true);
 
         let hwnd = match window.raw_window_handle() {
             RawWindowHandle::Win32(handle) => HWND(handle.hwnd as _),
@@ -104,6 +129,8 @@ impl ModernSettingsWindow {
             let surface: IDXGISurface = swap_chain.GetBuffer(0)?;
             let back_buffer: ID2D1Bitmap1 = context.CreateBitmapFromDxgiSurface(&surface, None)?;
             context.SetTarget(&back_buffer);
+            // 描画座標・フォントを論理座標で扱い、モニターの拡大率に合わせる
+            super::dpi::apply_window_dpi(&context, &window);
             let brush = context.CreateSolidColorBrush(
                 &D2D1_COLOR_F {
                     r: 1.0,
@@ -157,6 +184,11 @@ impl ModernSettingsWindow {
                 selected_tab: 0,
                 focus_index: 0,
                 is_focus_on_tabs: true,
+                editing: None,
+                color_adjustment: crate::render::ColorAdjustment::default(),
+                page_transform: crate::render::PageTransform::default(),
+                stats_summary: Default::default(),
+                stats_revision: u64::MAX,
                 event_proxy,
             })
         }
@@ -165,19 +197,24 @@ impl ModernSettingsWindow {
     pub fn handle_event(&mut self, event: &WindowEvent, settings: &Settings) -> bool {
         match event {
             WindowEvent::KeyboardInput { event: req, .. } => {
+                if req.state == ElementState::Pressed && self.editing.is_some() {
+                    self.handle_edit_key(&req.logical_key, req.text.as_deref());
+                    self.window.request_redraw();
+                    return false;
+                }
                 if req.state == ElementState::Pressed {
                     use winit::keyboard::{Key, NamedKey};
                     match req.logical_key {
                         Key::Named(NamedKey::ArrowLeft) => {
                             if self.is_focus_on_tabs {
-                                self.selected_tab = (self.selected_tab + 2) % 3;
+                                self.selected_tab = (self.selected_tab + TAB_COUNT - 1) % TAB_COUNT;
                             } else {
                                 self.handle_action_at(self.focus_index, settings, -1);
                             }
                         }
                         Key::Named(NamedKey::ArrowRight) => {
                             if self.is_focus_on_tabs {
-                                self.selected_tab = (self.selected_tab + 1) % 3;
+                                self.selected_tab = (self.selected_tab + 1) % TAB_COUNT;
                             } else {
                                 self.handle_action_at(self.focus_index, settings, 1);
                             }
@@ -218,9 +255,21 @@ impl ModernSettingsWindow {
                 self.window.request_redraw();
                 false
             }
+            WindowEvent::Ime(Ime::Commit(text)) => {
+                if let Some(ref mut editing) = self.editing {
+                    editing.push_str(text);
+                    self.window.request_redraw();
+                }
+                false
+            }
             WindowEvent::CloseRequested => true,
             WindowEvent::CursorMoved { position, .. } => {
-                self.mouse_pos = (position.x as f32, position.y as f32);
+                self.mouse_pos = super::dpi::logical_position(&self.window, position);
+                self.window.request_redraw();
+                false
+            }
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                super::dpi::resize_target(&self.context, &self.swap_chain, &self.window);
                 self.window.request_redraw();
                 false
             }
@@ -230,7 +279,7 @@ impl ModernSettingsWindow {
                 ..
             } => {
                 self.is_clicking = *state == ElementState::Pressed;
-                if !self.is_clicking {
+                if !self.is_clicking && self.editing.is_none() {
                     self.handle_click(settings);
                 }
                 self.window.request_redraw();
@@ -240,15 +289,51 @@ impl ModernSettingsWindow {
         }
     }
 
+    /// 書式の編集中のキー入力 (IME で確定した文字は Ime::Commit で受け取る)
+    fn handle_edit_key(&mut self, logical_key: &winit::keyboard::Key, text: Option<&str>) {
+        use winit::keyboard::{Key, NamedKey};
+        let Some(ref mut editing) = self.editing else { return; };
+        match logical_key {
+            Key::Named(NamedKey::Enter) => {
+                let format = editing.trim().to_string();
+                let event = if self.focus_index == 0 {
+                    crate::image::loader::UserEvent::SetTitleFormat(format)
+                } else {
+                    crate::image::loader::UserEvent::SetStatusbarFormat(format)
+                };
+                let _ = self.event_proxy.send_event(event);
+                self.editing = None;
+            }
+            Key::Named(NamedKey::Escape) => {
+                self.editing = None;
+            }
+            Key::Named(NamedKey::Backspace) => {
+                editing.pop();
+            }
+            _ => {
+                if let Some(text) = text {
+                    editing.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+
+    /// タブの矩形 (「レンダリング」が収まるよう名前の長さに合わせた幅にする)
+    fn tab_rect(i: usize) -> D2D_RECT_F {
+        let width = |name: &str| name.chars().count() as f32 * 14.0 + 20.0;
+        let left = 20.0 + TAB_NAMES[..i].iter().map(|name| width(name) + 6.0).sum::<f32>();
+        D2D_RECT_F {
+            left,
+            top: 70.0,
+            right: left + width(TAB_NAMES[i]),
+            bottom: 105.0,
+        }
+    }
+
     fn handle_click(&mut self, settings: &Settings) {
         // タブ切り替え判定
-        for i in 0..3 {
-            let rect = D2D_RECT_F {
-                left: 20.0 + i as f32 * 110.0,
-                top: 70.0,
-                right: 120.0 + i as f32 * 110.0,
-                bottom: 105.0,
-            };
+        for i in 0..TAB_COUNT {
+            let rect = Self::tab_rect(i);
             if self.is_in_rect(rect) {
                 self.selected_tab = i;
                 return;
@@ -257,8 +342,7 @@ impl ModernSettingsWindow {
 
         // 全般タブ内のクリック判定
         if self.selected_tab == 0 {
-            let items = [210.0, 250.0, 290.0, 330.0, 370.0, 410.0, 450.0, 490.0];
-            for (idx, &top) in items.iter().enumerate() {
+            for (idx, &top) in GENERAL_ITEM_TOPS.iter().enumerate() {
                 let rect = D2D_RECT_F {
                     left: 40.0,
                     top,
@@ -272,9 +356,13 @@ impl ModernSettingsWindow {
                     return;
                 }
             }
-        } else if self.selected_tab == 1 {
-            let items = [210.0, 250.0, 290.0, 330.0, 370.0, 410.0, 450.0];
-            for (idx, &top) in items.iter().enumerate() {
+        } else if (1..=3).contains(&self.selected_tab) {
+            let items: &[f32] = if self.selected_tab == 1 {
+                &RENDERING_ITEM_TOPS
+            } else {
+                &[210.0, 250.0, 290.0, 330.0, 370.0, 410.0, 450.0, 490.0, 530.0]
+            };
+            for (idx, &top) in items.iter().enumerate().take(self.get_item_count()) {
                 let rect = D2D_RECT_F {
                     left: 40.0,
                     top,
@@ -288,6 +376,33 @@ impl ModernSettingsWindow {
                     return;
                 }
             }
+        } else if self.selected_tab == 4 {
+            for (idx, &top) in FORMAT_ITEM_TOPS.iter().enumerate() {
+                let rect = D2D_RECT_F {
+                    left: 40.0,
+                    top,
+                    right: 200.0,
+                    bottom: top + 30.0,
+                };
+                if self.is_in_rect(rect) {
+                    self.is_focus_on_tabs = false;
+                    self.focus_index = idx;
+                    self.handle_action_at(idx, settings, 1);
+                    return;
+                }
+            }
+        } else if self.selected_tab == 5 {
+            let rect = D2D_RECT_F {
+                left: 40.0,
+                top: 505.0,
+                right: 200.0,
+                bottom: 535.0,
+            };
+            if self.is_in_rect(rect) {
+                self.is_focus_on_tabs = false;
+                self.focus_index = 0;
+                self.handle_action_at(0, settings, 1);
+            }
         }
     }
 
@@ -350,14 +465,8 @@ impl ModernSettingsWindow {
             );
 
             // タブ描画 (日本語)
-            let tabs = ["全般", "レンダリング", "情報"];
-            for (i, &name) in tabs.iter().enumerate() {
-                let rect = D2D_RECT_F {
-                    left: 20.0 + i as f32 * 110.0,
-                    top: 70.0,
-                    right: 120.0 + i as f32 * 110.0,
-                    bottom: 105.0,
-                };
+            for (i, &name) in TAB_NAMES.iter().enumerate() {
+                let rect = Self::tab_rect(i);
                 let is_hover = self.is_in_rect(rect);
                 let is_selected = self.selected_tab == i;
                 let is_focused = self.is_focus_on_tabs && is_selected;
@@ -458,7 +567,11 @@ impl ModernSettingsWindow {
             match self.selected_tab {
                 0 => self.draw_general_tab(settings),
                 1 => self.draw_rendering_tab(settings),
-                2 => self.draw_about_tab(settings),
+                2 => self.draw_quality_tab(settings),
+                3 => self.draw_controls_tab(settings),
+                4 => self.draw_format_tab(settings),
+                5 => self.draw_about_tab(settings),
+                6 => self.draw_stats_tab(),
                 _ => {}
             }
 
@@ -478,7 +591,9 @@ impl ModernSettingsWindow {
         let guide_text = "■ 基本設定\n\n(※ 項目をクリック、または矢印キーとEnterで変更できます)";
         self.draw_debug_text(guide_text, 130.0);
 
-        let display_mode_text = if !settings.is_spread_view {
+        let display_mode_text = if settings.webtoon_view {
+            "縦スクロール"
+        } else if !settings.is_spread_view {
             "単一ページ"
         } else if settings.binding_direction == "left" {
             "見開き・左綴じ（左開き）"
@@ -500,17 +615,17 @@ impl ModernSettingsWindow {
             "表示モード",
             display_mode_text,
             40.0,
-            210.0,
+            GENERAL_ITEM_TOPS[0],
             160.0,
             30.0,
-            settings.is_spread_view,
+            settings.is_spread_view || settings.webtoon_view,
             focus_idx == Some(0),
         );
         self.draw_button(
             "先頭単一表示",
             first_page_text,
             40.0,
-            250.0,
+            GENERAL_ITEM_TOPS[1],
             160.0,
             30.0,
             settings.spread_view_first_page_single,
@@ -520,7 +635,7 @@ impl ModernSettingsWindow {
             "ステータスバー",
             status_text,
             40.0,
-            290.0,
+            GENERAL_ITEM_TOPS[2],
             160.0,
             30.0,
             settings.show_status_bar_info,
@@ -530,7 +645,7 @@ impl ModernSettingsWindow {
             "ルーペ倍率",
             &format!("{:.1}x", settings.magnifier_zoom),
             40.0,
-            330.0,
+            GENERAL_ITEM_TOPS[3],
             160.0,
             30.0,
             false,
@@ -540,7 +655,7 @@ impl ModernSettingsWindow {
             "履歴件数",
             &format!("{} 件", settings.max_history_count),
             40.0,
-            370.0,
+            GENERAL_ITEM_TOPS[4],
             160.0,
             30.0,
             false,
@@ -550,7 +665,7 @@ impl ModernSettingsWindow {
             "CPUキャッシュ",
             &format!("{} MB", settings.max_cache_size_mb),
             40.0,
-            410.0,
+            GENERAL_ITEM_TOPS[5],
             160.0,
             30.0,
             false,
@@ -560,7 +675,7 @@ impl ModernSettingsWindow {
             "先読み(CPU)",
             &format!("{} ページ", settings.cpu_max_prefetch_pages),
             40.0,
-            450.0,
+            GENERAL_ITEM_TOPS[6],
             160.0,
             30.0,
             false,
@@ -570,12 +685,46 @@ impl ModernSettingsWindow {
             "先読み(GPU)",
             &format!("{} ページ", settings.gpu_max_prefetch_pages),
             40.0,
-            490.0,
+            GENERAL_ITEM_TOPS[7],
             160.0,
             30.0,
             false,
             focus_idx == Some(7),
         );
+        self.draw_button(
+            "章頭単一表示",
+            if settings.chapter_start_single {
+                "有効"
+            } else {
+                "無効"
+            },
+            40.0,
+            GENERAL_ITEM_TOPS[8],
+            160.0,
+            30.0,
+            settings.chapter_start_single,
+            focus_idx == Some(8),
+        );
+        self.draw_button(
+            "横長ページ単独表示",
+            if settings.wide_page_single { "有効 (幅 > 高さのページ)" } else { "無効" },
+            40.0,
+            GENERAL_ITEM_TOPS[9],
+            160.0,
+            30.0,
+            settings.wide_page_single,
+            focus_idx == Some(9),
+        );
+        self.draw_button(
+            "前回の続きから起動",
+            if settings.reopen_last_on_startup { "有効 (最後に読んだソース)" } else { "無効" },
+            40.0,
+            GENERAL_ITEM_TOPS[10],
+            160.0,
+            30.0,
+            settings.reopen_last_on_startup,
+            focus_idx == Some(10),
+        );
     }
 
     fn draw_button(
@@ -704,7 +853,7 @@ impl ModernSettingsWindow {
             "レンダリングエンジン",
             backend_display,
             40.0,
-            210.0,
+            RENDERING_ITEM_TOPS[0],
             160.0,
             30.0,
             false,
@@ -721,7 +870,7 @@ impl ModernSettingsWindow {
             "CPUサンプリング",
             cpu_res_text,
             40.0,
-            250.0,
+            RENDERING_ITEM_TOPS[1],
             160.0,
             30.0,
             false,
@@ -738,7 +887,7 @@ impl ModernSettingsWindow {
             "GPUサンプリング",
             gpu_res_text,
             40.0,
-            290.0,
+            RENDERING_ITEM_TOPS[2],
             160.0,
             30.0,
             false,
@@ -752,12 +901,461 @@ impl ModernSettingsWindow {
                 "無効"
             },
             40.0,
-            330.0,
+            RENDERING_ITEM_TOPS[3],
             160.0,
             30.0,
             settings.use_cpu_color_conversion,
             focus_idx == Some(3),
         );
+        let auto_workers = crate::image::decoder::effective_workers(0);
+        let workers_text = if settings.parallel_decoding_workers == 0 {
+            format!("自動 ({})", auto_workers)
+        } else {
+            settings.parallel_decoding_workers.to_string()
+        };
+        self.draw_button(
+            "デコードスレッド数",
+            &workers_text,
+            40.0,
+            RENDERING_ITEM_TOPS[4],
+            160.0,
+            30.0,
+            false,
+            focus_idx == Some(4),
+        );
+        let animation_text = if settings.view_animation_ms == 0 {
+            "オフ".to_string()
+        } else {
+            format!("{}ms", settings.view_animation_ms)
+        };
+        self.draw_button(
+            "ズームアニメーション",
+            &animation_text,
+            40.0,
+            RENDERING_ITEM_TOPS[5],
+            160.0,
+            30.0,
+            settings.view_animation_ms > 0,
+            focus_idx == Some(5),
+        );
+        let rotation_text = if settings.effective_pre_rotation() == 0 {
+            "なし".to_string()
+        } else {
+            format!("{}°", settings.effective_pre_rotation())
+        };
+        self.draw_button(
+            "事前回転 (縦置き)",
+            &rotation_text,
+            40.0,
+            RENDERING_ITEM_TOPS[6],
+            160.0,
+            30.0,
+            settings.effective_pre_rotation() != 0,
+            focus_idx == Some(6),
+        );
+        self.draw_button(
+            "先読み (P キー)",
+            if settings.prefetch_paused { "一時停止中" } else { "動作中" },
+            40.0,
+            RENDERING_ITEM_TOPS[7],
+            160.0,
+            30.0,
+            !settings.prefetch_paused,
+            focus_idx == Some(7),
+        );
+        self.draw_button(
+            "GPUキャッシュ",
+            &if settings.gpu_max_cache_mb > 0 {
+                format!("{} MB", settings.gpu_max_cache_mb)
+            } else {
+                "制限なし (ページ数のみ)".to_string()
+            },
+            40.0,
+            RENDERING_ITEM_TOPS[8],
+            160.0,
+            30.0,
+            false,
+            focus_idx == Some(8),
+        );
+        self.draw_button(
+            "自動レベル補正",
+            if settings.auto_levels { "有効 (ページごと)" } else { "無効" },
+            40.0,
+            RENDERING_ITEM_TOPS[9],
+            160.0,
+            30.0,
+            settings.auto_levels,
+            focus_idx == Some(9),
+        );
+    }
+
+    fn draw_quality_tab(&self, settings: &Settings) {
+        let focus_idx = if !self.is_focus_on_tabs {
+            Some(self.focus_index)
+        } else {
+            None
+        };
+
+        let guide_text = "■ 画質設定\n\n(※ 明るさ・コントラスト・ガンマは表示中のソースごとに保存されます)";
+        self.draw_debug_text(guide_text, 130.0);
+
+        // 色調整 (表示中のソースに保存される)
+        let adj = self.color_adjustment;
+        self.draw_button(
+            "明るさ",
+            &format!("{:+.0}%", adj.brightness * 100.0),
+            40.0,
+            210.0,
+            160.0,
+            30.0,
+            adj.brightness != 0.0,
+            focus_idx == Some(0),
+        );
+        self.draw_button(
+            "コントラスト",
+            &format!("{:.0}%", adj.contrast * 100.0),
+            40.0,
+            250.0,
+            160.0,
+            30.0,
+            adj.contrast != 1.0,
+            focus_idx == Some(1),
+        );
+        self.draw_button(
+            "ガンマ",
+            &format!("{:.2}", adj.gamma),
+            40.0,
+            290.0,
+            160.0,
+            30.0,
+            adj.gamma != 1.0,
+            focus_idx == Some(2),
+        );
+        self.draw_button(
+            "色調整リセット",
+            "表示中のソースの色調整を既定値に戻す",
+            40.0,
+            330.0,
+            160.0,
+            30.0,
+            false,
+            focus_idx == Some(3),
+        );
+        let night_mode_text = if !settings.invert_colors {
+            "オフ"
+        } else if settings.smart_invert {
+            "色反転 (暗いページは除外)"
+        } else {
+            "色反転 (全ページ)"
+        };
+        self.draw_button(
+            "夜間モード",
+            night_mode_text,
+            40.0,
+            370.0,
+            160.0,
+            30.0,
+            settings.invert_colors,
+            focus_idx == Some(4),
+        );
+        self.draw_button(
+            "ブルーライト軽減",
+            &if settings.blue_light_strength > 0.0 {
+                format!("{:.0}%", settings.blue_light_strength * 100.0)
+            } else {
+                "オフ".to_string()
+            },
+            40.0,
+            410.0,
+            160.0,
+            30.0,
+            settings.blue_light_strength > 0.0,
+            focus_idx == Some(5),
+        );
+        self.draw_button(
+            "時間指定",
+            &if settings.blue_light_schedule {
+                format!("{} 〜 {} のみ適用", settings.blue_light_start, settings.blue_light_end)
+            } else {
+                "常に適用".to_string()
+            },
+            40.0,
+            450.0,
+            160.0,
+            30.0,
+            settings.blue_light_schedule,
+            focus_idx == Some(6),
+        );
+        let background_text = crate::config::BACKGROUND_THEMES
+            .iter()
+            .find(|(name, _, _)| *name == settings.background_theme)
+            .map_or("カスタム (config.json)", |(_, label, _)| *label);
+        self.draw_button(
+            "背景",
+            background_text,
+            40.0,
+            490.0,
+            160.0,
+            30.0,
+            settings.background_theme != "dark",
+            focus_idx == Some(7),
+        );
+        self.draw_button(
+            "背景の明暗追従",
+            &if settings.adaptive_background > 0.0 {
+                format!("{:.0}%", settings.adaptive_background * 100.0)
+            } else {
+                "オフ".to_string()
+            },
+            40.0,
+            530.0,
+            160.0,
+            30.0,
+            settings.adaptive_background > 0.0,
+            focus_idx == Some(8),
+        );
+    }
+
+    fn draw_controls_tab(&self, settings: &Settings) {
+        let focus_idx = if !self.is_focus_on_tabs {
+            Some(self.focus_index)
+        } else {
+            None
+        };
+
+        let guide_text = "■ 操作\n\n(※ 左手モードはシークバーの向きと OSD の位置を左右反転します)\n(※ パスの省略表示はタイトルバー・ステータスバーの長いパスの中央を「…」で省略します)";
+        self.draw_debug_text(guide_text, 130.0);
+
+        self.draw_button(
+            "左手モード",
+            if settings.left_handed_mode { "有効" } else { "無効" },
+            40.0,
+            210.0,
+            160.0,
+            30.0,
+            settings.left_handed_mode,
+            focus_idx == Some(0),
+        );
+
+        self.draw_button(
+            "パスの省略表示",
+            &if settings.path_display_max_chars == 0 {
+                "省略しない".to_string()
+            } else {
+                format!("{}文字まで", settings.path_display_max_chars)
+            },
+            40.0,
+            250.0,
+            160.0,
+            30.0,
+            settings.path_display_max_chars > 0,
+            focus_idx == Some(1),
+        );
+
+        let transform = self.page_transform;
+        self.draw_button(
+            "ページの回転",
+            &format!("{}°", transform.rotation),
+            40.0,
+            290.0,
+            160.0,
+            30.0,
+            transform.rotation != 0,
+            focus_idx == Some(2),
+        );
+        self.draw_button(
+            "左右反転",
+            if transform.flip_h { "有効" } else { "無効" },
+            40.0,
+            330.0,
+            160.0,
+            30.0,
+            transform.flip_h,
+            focus_idx == Some(3),
+        );
+        self.draw_button(
+            "上下反転",
+            if transform.flip_v { "有効" } else { "無効" },
+            40.0,
+            370.0,
+            160.0,
+            30.0,
+            transform.flip_v,
+            focus_idx == Some(4),
+        );
+        self.draw_button(
+            "瞬時切り替え",
+            if settings.instant_flip { "有効" } else { "無効" },
+            40.0,
+            410.0,
+            160.0,
+            30.0,
+            settings.instant_flip,
+            focus_idx == Some(5),
+        );
+        self.draw_button(
+            "色差の補間",
+            match settings.chroma_upsampling.as_str() {
+                "CatmullRom" => "Catmull-Rom (くっきり)",
+                _ => "Bilinear (双線形補間)",
+            },
+            40.0,
+            450.0,
+            160.0,
+            30.0,
+            settings.chroma_upsampling == "CatmullRom",
+            focus_idx == Some(6),
+        );
+        self.draw_button(
+            "ページめくり",
+            match settings.page_turn_animation_type.as_str() {
+                "slide" => "スライド",
+                "curl" => "めくる (D3D11 / OpenGL)",
+                _ => "なし",
+            },
+            40.0,
+            490.0,
+            160.0,
+            30.0,
+            matches!(settings.page_turn_animation_type.as_str(), "slide" | "curl"),
+            focus_idx == Some(7),
+        );
+        self.draw_button(
+            "サイズ変更中の描画",
+            if settings.freeze_frame_on_resize { "直前の画面を縮小" } else { "毎回配置し直す" },
+            40.0,
+            530.0,
+            160.0,
+            30.0,
+            settings.freeze_frame_on_resize,
+            focus_idx == Some(8),
+        );
+    }
+
+    fn draw_format_tab(&self, settings: &Settings) {
+        let focus_idx = if !self.is_focus_on_tabs {
+            Some(self.focus_index)
+        } else {
+            None
+        };
+
+        let guide_text = "■ 書式\n(※ Enter で編集・確定、Esc で取り消し。空にすると既定)";
+        self.draw_debug_text(guide_text, 120.0);
+
+        let formats = [
+            ("タイトルバー", &settings.title_format, crate::template::DEFAULT_TITLE_FORMAT),
+            ("ステータスバー", &settings.statusbar_format, crate::template::DEFAULT_STATUSBAR_FORMAT),
+        ];
+        for (idx, (label, format, default)) in formats.into_iter().enumerate() {
+            let top = FORMAT_ITEM_TOPS[idx];
+            let is_editing = self.editing.is_some() && self.focus_index == idx;
+            self.draw_button(
+                label,
+                if is_editing {
+                    "編集中"
+                } else if format == default {
+                    "既定"
+                } else {
+                    "カスタム"
+                },
+                40.0,
+                top,
+                160.0,
+                30.0,
+                is_editing,
+                focus_idx == Some(idx),
+            );
+            let text = match self.editing {
+                Some(ref editing) if is_editing => format!("{}|", editing),
+                _ => format.clone(),
+            };
+            self.draw_small_text(&text, 40.0, top + 34.0, 460.0, top + 72.0);
+        }
+
+        self.draw_button(
+            "既定に戻す",
+            "",
+            40.0,
+            FORMAT_ITEM_TOPS[2],
+            160.0,
+            30.0,
+            false,
+            focus_idx == Some(2),
+        );
+
+        let placeholders: Vec<String> = crate::template::PLACEHOLDERS
+            .iter()
+            .map(|(name, description)| format!("{{{}}}  {}", name, description))
+            .collect();
+        self.draw_small_text(&placeholders.join("\n"), 40.0, 365.0, 460.0, 575.0);
+    }
+
+    fn draw_small_text(&self, text: &str, left: f32, top: f32, right: f32, bottom: f32) {
+        unsafe {
+            self.brush.SetColor(&D2D1_COLOR_F {
+                r: 0.8,
+                g: 0.8,
+                b: 0.8,
+                a: 1.0,
+            });
+            let wide_text: Vec<u16> = text.encode_utf16().collect();
+            self.context.DrawText(
+                &wide_text,
+                &self.text_format_small,
+                &D2D_RECT_F {
+                    left,
+                    top,
+                    right,
+                    bottom,
+                },
+                &self.brush,
+                D2D1_DRAW_TEXT_OPTIONS_CLIP,
+                DWRITE_MEASURING_MODE_NATURAL,
+            );
+        }
+    }
+
+    fn draw_stats_tab(&self) {
+        let summary = &self.stats_summary;
+        let line = |label: &str, stats: &crate::state::stats::DayStats| {
+            format!(
+                "{}: {} ページ / {} / {} 回{}",
+                label,
+                stats.pages,
+                format_reading_time(stats.seconds),
+                stats.sessions,
+                stats
+                    .seconds_per_page()
+                    .map(|s| format!(" ({:.1} 秒/ページ)", s))
+                    .unwrap_or_default()
+            )
+        };
+        let text = format!(
+            "■ 読書の統計\n\n{}\n{}\n{}\n\n■ 最近読んだもの",
+            line("今日", &summary.today),
+            line("今週", &summary.week),
+            line("累計", &summary.total),
+        );
+        self.draw_debug_text(&text, 120.0);
+
+        if summary.recent_sources.is_empty() {
+            self.draw_small_text("まだ記録がありません", 40.0, 280.0, 460.0, 310.0);
+            return;
+        }
+        for (i, (path, stats)) in summary.recent_sources.iter().enumerate() {
+            let name = std::path::Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            let top = 280.0 + i as f32 * 44.0;
+            self.draw_small_text(
+                &format!("{}\n  {} ページ / {} / {} 回", name, stats.pages, format_reading_time(stats.seconds), stats.sessions),
+                40.0,
+                top,
+                460.0,
+                top + 42.0,
+            );
+        }
     }
 
     fn draw_about_tab(&self, settings: &Settings) {
@@ -840,7 +1438,7 @@ impl ModernSettingsWindow {
                 ("Renderer", &settings.rendering_backend),
                 (
                     "Parallel Workers",
-                    &settings.parallel_decoding_workers.to_string(),
+                    &crate::image::decoder::effective_workers(settings.parallel_decoding_workers).to_string(),
                 ),
                 (
                     "CPU Resampling",
@@ -920,6 +1518,35 @@ impl ModernSettingsWindow {
                 );
             }
 
+        }
+
+        // 更新確認
+        let focus_idx = if !self.is_focus_on_tabs {
+            Some(self.focus_index)
+        } else {
+            None
+        };
+        let update_text = if !settings.check_for_updates {
+            "無効".to_string()
+        } else if !settings.latest_version.is_empty()
+            && crate::platform::update::is_newer(&settings.latest_version, version)
+        {
+            format!("新しいバージョン v{} があります", settings.latest_version)
+        } else {
+            "有効 (週1回確認)".to_string()
+        };
+        self.draw_button(
+            "更新確認",
+            &update_text,
+            40.0,
+            505.0,
+            160.0,
+            30.0,
+            settings.check_for_updates,
+            focus_idx == Some(0),
+        );
+
+        unsafe {
             // 4. フッタークレジット
             let footer_text = "© 2024 Tatsumaki Ishino. All rights reserved.";
             let footer_rect = D2D_RECT_F {
@@ -984,13 +1611,18 @@ impl ModernSettingsWindow {
 
     fn get_item_count(&self) -> usize {
         match self.selected_tab {
-            0 => 8, // 全般: 表示モード, 先頭単一, ステータスバー, ルーペ倍率, 履歴件数, キャッシュ, CPU先読み, GPU先読み
-            1 => 4, // レンダリング: エンジン, CPUサンプリング, GPUサンプリング, CPU色変換
+            0 => 11, // 全般: 表示モード, 先頭単一, ステータスバー, ルーペ倍率, 履歴件数, キャッシュ, CPU先読み, GPU先読み, 章頭単一, 横長ページ単独, 前回のソースを開く
+            1 => 10, // レンダリング: エンジン, CPUサンプリング, GPUサンプリング, CPU色変換, デコードスレッド数, ズームアニメーション, 事前回転, 先読み, GPUキャッシュ, 自動レベル補正
+            2 => 9, // 画質: 明るさ, コントラスト, ガンマ, 色調整リセット, 夜間モード, ブルーライト軽減, 時間指定, 背景, 背景の明暗追従
+            3 => 9, // 操作: 左手モード, パスの省略表示, ページの回転, 左右反転, 上下反転, 瞬時切り替え, 色差の補間, ページめくり, サイズ変更中の描画
+            4 => 3, // 書式: タイトルバー, ステータスバー, 既定に戻す
+            5 => 1, // 情報: 更新確認
+            6 => 0, // 統計: 表示のみ
             _ => 0,
         }
     }
 
-    fn handle_action_at(&self, index: usize, settings: &Settings, direction: isize) {
+    fn handle_action_at(&mut self, index: usize, settings: &Settings, direction: isize) {
         if self.selected_tab == 0 {
             match index {
                 0 => {
@@ -1063,6 +1695,21 @@ impl ModernSettingsWindow {
                         .event_proxy
                         .send_event(crate::image::loader::UserEvent::SetGpuPrefetchPages(pages));
                 }
+                8 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleChapterStartSingle);
+                }
+                9 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleWidePageSingle);
+                }
+                10 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleReopenLastOnStartup);
+                }
                 _ => {}
             }
         } else if self.selected_tab == 1 {
@@ -1087,8 +1734,192 @@ impl ModernSettingsWindow {
                         .event_proxy
                         .send_event(crate::image::loader::UserEvent::ToggleCpuColorConversion);
                 }
+                4 => {
+                    // 自動 -> 1 -> 2 -> ... -> 論理 CPU 数の2倍 -> 自動
+                    let max = crate::image::decoder::effective_workers(0) * 2;
+                    let current = settings.parallel_decoding_workers.min(max) as isize;
+                    let workers = (current + direction).rem_euclid(max as isize + 1) as usize;
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::SetDecodeWorkers(workers));
+                }
+                5 => {
+                    // オフ -> 100ms -> 150ms -> 250ms
+                    const STEPS: [u32; 4] = [0, 100, 150, 250];
+                    let current = STEPS.iter().position(|&ms| ms == settings.view_animation_ms).unwrap_or(2) as isize;
+                    let next = STEPS[(current + direction).rem_euclid(STEPS.len() as isize) as usize];
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::SetViewAnimation(next));
+                }
+                6 => {
+                    let rotation = (settings.effective_pre_rotation() as isize + 90 * direction).rem_euclid(360) as u32;
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::SetPreRotation(rotation));
+                }
+                7 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::TogglePrefetchPaused);
+                }
+                8 => {
+                    // 制限なし -> 256MB -> 512MB -> ... -> 4096MB -> 制限なし
+                    let mut size = settings.gpu_max_cache_mb;
+                    if direction > 0 {
+                        size = if size >= 4096 { 0 } else { (size + 256) / 256 * 256 };
+                    } else {
+                        size = if size == 0 { 4096 } else { size.saturating_sub(256) };
+                    }
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::SetGpuCacheSize(size));
+                }
+                9 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleAutoLevels);
+                }
                 _ => {}
             }
+        } else if self.selected_tab == 2 {
+            match index {
+                0 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::StepBrightness(direction));
+                }
+                1 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::StepContrast(direction));
+                }
+                2 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::StepGamma(direction));
+                }
+                3 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ResetColorAdjustment);
+                }
+                4 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::RotateNightMode(direction));
+                }
+                5 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::StepBlueLight(direction));
+                }
+                6 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleBlueLightSchedule);
+                }
+                7 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::RotateBackgroundTheme(direction));
+                }
+                8 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::StepAdaptiveBackground(direction));
+                }
+                _ => {}
+            }
+        } else if self.selected_tab == 3 {
+            match index {
+                0 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleLeftHandedMode);
+                }
+                1 => {
+                    // 省略しない -> 40 -> 60 -> 80 -> 120 文字
+                    const STEPS: [usize; 5] = [0, 40, 60, 80, 120];
+                    let current = STEPS.iter().position(|&n| n == settings.path_display_max_chars).unwrap_or(2) as isize;
+                    let next = STEPS[(current + direction).rem_euclid(STEPS.len() as isize) as usize];
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::SetPathDisplayLimit(next));
+                }
+                2 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::RotatePage(direction));
+                }
+                3 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::FlipPage(true));
+                }
+                4 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::FlipPage(false));
+                }
+                5 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleInstantFlip);
+                }
+                6 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleChromaUpsampling);
+                }
+                7 => {
+                    // なし -> スライド -> めくる
+                    const KINDS: [&str; 3] = ["none", "slide", "curl"];
+                    let current = KINDS.iter().position(|&k| k == settings.page_turn_animation_type).unwrap_or(0) as isize;
+                    let next = KINDS[(current + direction).rem_euclid(KINDS.len() as isize) as usize];
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::SetPageTurnAnimation(next.to_string()));
+                }
+                8 => {
+                    let _ = self
+                        .event_proxy
+                        .send_event(crate::image::loader::UserEvent::ToggleFreezeFrameOnResize);
+                }
+                _ => {}
+            }
+        } else if self.selected_tab == 4 {
+            match index {
+                0 => self.editing = Some(settings.title_format.clone()),
+                1 => self.editing = Some(settings.statusbar_format.clone()),
+                2 => {
+                    let _ = self.event_proxy.send_event(crate::image::loader::UserEvent::SetTitleFormat(
+                        crate::template::DEFAULT_TITLE_FORMAT.to_string(),
+                    ));
+                    let _ = self.event_proxy.send_event(crate::image::loader::UserEvent::SetStatusbarFormat(
+                        crate::template::DEFAULT_STATUSBAR_FORMAT.to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        } else if self.selected_tab == 5 {
+            if index == 0 {
+                let _ = self
+                    .event_proxy
+                    .send_event(crate::image::loader::UserEvent::ToggleUpdateCheck);
+            }
         }
     }
 }
+
+/// 読書時間の表示 (1 時間 5 分 / 12 分 / 1 分未満)
+fn format_reading_time(seconds: f64) -> String {
+    let minutes = (seconds / 60.0) as u64;
+    if minutes >= 60 {
+        format!("{} 時間 {} 分", minutes / 60, minutes % 60)
+    } else if minutes > 0 {
+        format!("{} 分", minutes)
+    } else {
+        "1 分未満".to_string()
+    }
+}
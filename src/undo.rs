@@ -0,0 +1,66 @@
+use crate::image::postprocess::CropMargins;
+use crate::render::PageTransform;
+use crate::state::BindingDirection;
+use crate::triage::TriageRecord;
+use std::collections::HashMap;
+
+/// 取り消し履歴の上限
+const MAX_UNDO: usize = 100;
+
+/// 操作前の表示状態 (ページ位置・表示モード・回転/反転・手動トリミング)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewSnapshot {
+    pub path_key: String,
+    pub page_index: usize,
+    pub is_spread_view: bool,
+    pub is_webtoon_view: bool,
+    pub binding_direction: BindingDirection,
+    pub page_transforms: HashMap<usize, PageTransform>,
+    pub manual_crop: Option<CropMargins>,
+}
+
+/// Ctrl + Z で取り消せる操作
+#[derive(Debug, Clone)]
+pub enum UndoEntry {
+    /// ページジャンプ・表示モードの切り替え・回転など (label は取り消したときの表示用)
+    View { label: &'static str, snapshot: ViewSnapshot },
+    /// 仕分けによる移動/コピー
+    Triage(TriageRecord),
+}
+
+/// このセッションで行った操作の取り消し履歴 (新しいものが末尾)
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    entries: Vec<UndoEntry>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, entry: UndoEntry) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_UNDO {
+            self.entries.remove(0);
+        }
+    }
+
+    /// 操作前の表示状態を積む。直前に積んだ状態と同じなら積まない (シークバーの連続クリックなど)
+    pub fn push_view(&mut self, label: &'static str, snapshot: ViewSnapshot) {
+        if let Some(UndoEntry::View { snapshot: last, .. }) = self.entries.last() {
+            if *last == snapshot {
+                return;
+            }
+        }
+        self.push(UndoEntry::View { label, snapshot });
+    }
+
+    pub fn pop(&mut self) -> Option<UndoEntry> {
+        self.entries.pop()
+    }
+
+    /// ソースを切り替えたとき、他のソースの表示状態の記録を捨てる (ファイル操作である仕分けの記録は残す)
+    pub fn retain_source(&mut self, path_key: &str) {
+        self.entries.retain(|entry| match entry {
+            UndoEntry::View { snapshot, .. } => snapshot.path_key == path_key,
+            UndoEntry::Triage(_) => true,
+        });
+    }
+}